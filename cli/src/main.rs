@@ -1,7 +1,8 @@
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use anyhow::Result;
 use std::path::Path;
-use draco_oxide::prelude::ConfigType;
+use draco_oxide::encode::{self, PositionPrediction};
+use draco_oxide::prelude::{ConfigType, Mesh};
 
 #[derive(Parser)]
 #[command(name = "draco-cli")]
@@ -18,19 +19,79 @@ struct Cli {
     /// Transcode mode for glTF/GLB files (compress with Draco)
     #[arg(long)]
     transcode: bool,
+
+    /// Number of quantization bits of every quantized attribute (conversion mode only)
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=30))]
+    quantization_bits: Option<u8>,
+
+    /// Prediction of the positions; overrides the one chosen by the compression level (conversion mode only)
+    #[arg(long, value_enum)]
+    prediction: Option<Prediction>,
+
+    /// Compression level from 0 (fastest) to 10 (smallest): 0-2 predict the positions by delta,
+    /// 3-7 by parallelogram (the default), and 8-10 by multi-parallelogram; a point cloud only takes 0-2 (conversion mode only)
+    #[arg(long, value_parser = clap::value_parser!(u8).range(0..=10))]
+    compression_level: Option<u8>,
+}
+
+#[derive(Clone, Copy, Debug, clap::ValueEnum)]
+enum Prediction {
+    Delta,
+    Parallelogram,
+    MultiParallelogram,
+}
+
+impl From<Prediction> for PositionPrediction {
+    fn from(prediction: Prediction) -> Self {
+        match prediction {
+            Prediction::Delta => PositionPrediction::Delta,
+            Prediction::Parallelogram => PositionPrediction::Parallelogram,
+            Prediction::MultiParallelogram => PositionPrediction::MultiParallelogram,
+        }
+    }
 }
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
 
     if cli.transcode {
+        if cli.quantization_bits.is_some() || cli.prediction.is_some() || cli.compression_level.is_some() {
+            anyhow::bail!("--quantization-bits, --prediction, and --compression-level only apply to conversion mode");
+        }
         transcode_gltf(&cli.input, &cli.output)
     } else {
-        convert_obj_to_drc(&cli.input, &cli.output)
+        convert_obj_to_drc(&cli)
+    }
+}
+
+/// Maps the command line options onto the encoder configuration, rejecting the ones that do not apply to the mesh.
+fn encode_config(cli: &Cli, mesh: &Mesh) -> Result<encode::Config> {
+    let mut config = encode::Config::default();
+    config.quantization_bits = cli.quantization_bits;
+
+    let is_point_cloud = mesh.get_faces().is_empty();
+    if let Some(prediction) = cli.prediction {
+        if is_point_cloud && !matches!(prediction, Prediction::Delta) {
+            let name = prediction.to_possible_value().map(|v| v.get_name().to_string()).unwrap_or_default();
+            anyhow::bail!("--prediction {} needs the faces of a mesh, but the input is a point cloud", name);
+        }
+        config.position_prediction = prediction.into();
+    } else if let Some(level) = cli.compression_level {
+        let prediction = match level {
+            0..=2 => PositionPrediction::Delta,
+            3..=7 => PositionPrediction::Parallelogram,
+            _ => PositionPrediction::MultiParallelogram,
+        };
+        if is_point_cloud && !matches!(prediction, PositionPrediction::Delta) {
+            anyhow::bail!("--compression-level {} needs the faces of a mesh, but the input is a point cloud", level);
+        }
+        config.position_prediction = prediction;
     }
+    Ok(config)
 }
 
-fn convert_obj_to_drc(input_path: &str, output_path: &str) -> Result<()> {
+fn convert_obj_to_drc(cli: &Cli) -> Result<()> {
+    let (input_path, output_path) = (cli.input.as_str(), cli.output.as_str());
     
     // Check input file extension
     let input_ext = Path::new(input_path)
@@ -58,11 +119,10 @@ fn convert_obj_to_drc(input_path: &str, output_path: &str) -> Result<()> {
 
 
     // Configure compression settings
-    let config = draco_oxide::encode::Config::default();
+    let config = encode_config(cli, &mesh)?;
 
     // Encode the mesh to a buffer
-    let mut buffer = Vec::new();
-    draco_oxide::encode::encode(mesh, &mut buffer, config)
+    let buffer = draco_oxide::encode::encode_to_vec(mesh, config)
         .map_err(|e| anyhow::anyhow!("Failed to encode mesh: {:?}", e))?;
 
     // Write to output file
@@ -109,4 +169,36 @@ fn transcode_gltf(input_path: &str, output_path: &str) -> Result<()> {
         .map_err(|e| anyhow::anyhow!("Failed to transcode: {:?}", e))?;
 
     Ok(())
-}
\ No newline at end of file
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Converts the sphere with the extra arguments, and returns the size of the .drc file.
+    fn convert_sphere(name: &str, args: &[&str]) -> usize {
+        let output = std::env::temp_dir().join(format!("draco_cli_{}_{}.drc", name, std::process::id()));
+        let output = output.to_str().unwrap();
+        let cli = Cli::parse_from(["draco-cli", "-i", "../draco-oxide/tests/data/sphere.obj", "-o", output].iter().chain(args));
+        convert_obj_to_drc(&cli).unwrap();
+        let size = std::fs::metadata(output).unwrap().len() as usize;
+        std::fs::remove_file(output).unwrap();
+        size
+    }
+
+    #[test]
+    fn test_quantization_bits() {
+        let default = convert_sphere("default", &[]);
+        let coarse = convert_sphere("coarse", &["--quantization-bits", "8"]);
+        assert!(coarse < default, "8 bits: {} bytes, default: {} bytes", coarse, default);
+    }
+
+    #[test]
+    fn test_compression_level_of_point_cloud() {
+        let point_cloud = Mesh::new();
+        let cli = |level: &str| Cli::parse_from(["draco-cli", "-i", "in.obj", "-o", "out.drc", "--compression-level", level]);
+        assert!(matches!(encode_config(&cli("1"), &point_cloud).unwrap().position_prediction, PositionPrediction::Delta));
+        assert!(encode_config(&cli("5"), &point_cloud).is_err());
+    }
+}
@@ -30,6 +30,30 @@ pub fn impl_ndvector_ops_for_dim(input: TokenStream) -> TokenStream {
     let indices_elem_div = (0..n).map(|i| {
         quote! { *self.data.get_unchecked_mut(#i) /= *rhs.data.get_unchecked(#i); }
     });
+
+    // The vectors of three components, the hottest case, go through 'DataValue' so that the floats
+    // can be computed with SIMD.
+    let dot_body = if n == 3 {
+        quote! { Data::dot3(self.data, rhs.data) }
+    } else {
+        quote! {
+            let mut result = Data::zero();
+            unsafe {
+                #(#indices_dot)*
+            };
+            result
+        }
+    };
+    let elem_mul_body = if n == 3 {
+        quote! { self.data = Data::elem_mul3(self.data, rhs.data); }
+    } else {
+        quote! { unsafe { #(#indices_elem_mul)* } }
+    };
+    let elem_div_body = if n == 3 {
+        quote! { self.data = Data::elem_div3(self.data, rhs.data); }
+    } else {
+        quote! { unsafe { #(#indices_elem_div)* } }
+    };
     let indices_partial_eq = (0..n).map(|i| {
         quote! { result &= self.data.get_unchecked(#i).eq(rhs.data.get_unchecked(#i)); }
     });
@@ -134,11 +158,7 @@ pub fn impl_ndvector_ops_for_dim(input: TokenStream) -> TokenStream {
         {
             type Product = Data;
             fn dot(self, rhs: Self) -> Self::Product {
-                let mut result = Data::zero();
-                unsafe {
-                    #(#indices_dot)*
-                };
-                result
+                #dot_body
             }
         }
 
@@ -149,7 +169,7 @@ pub fn impl_ndvector_ops_for_dim(input: TokenStream) -> TokenStream {
         {
             type Output = Self;
             fn elem_mul(mut self, rhs: Self) -> Self::Output {
-                unsafe { #(#indices_elem_mul)* }
+                #elem_mul_body
                 self
             }
         }
@@ -159,7 +179,7 @@ pub fn impl_ndvector_ops_for_dim(input: TokenStream) -> TokenStream {
         {
             type Output = Self;
             fn elem_div(mut self, rhs: Self) -> Self::Output {
-                unsafe { #(#indices_elem_div)* }
+                #elem_div_body
                 self
             }
         }
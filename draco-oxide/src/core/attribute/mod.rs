@@ -1,10 +1,41 @@
 use serde::Serialize;
 
-use crate::core::shared::{AttributeValueIdx, PointIdx, VecPointIdx, Vector};
+use crate::core::shared::{AttributeValueIdx, NdVector, PointIdx, VecPointIdx, Vector};
 use crate::prelude::{ByteReader, ByteWriter};
 use super::{buffer, shared::DataValue};
 
 
+/// Evaluates 'body' with 'Data' and 'N' defined as the value type and the number of components of the attribute
+/// of the given component type and number of components, i.e. the scalar for one component and 'NdVector' of
+/// the scalars for two to four components. It panics on other numbers of components.
+macro_rules! with_value_type {
+	($component_type:expr, $num_components:expr, |$data:ident, $n:ident| $body:expr) => {
+		match $component_type {
+			ComponentDataType::I8 => with_value_type!(@components i8, $num_components, |$data, $n| $body),
+			ComponentDataType::U8 => with_value_type!(@components u8, $num_components, |$data, $n| $body),
+			ComponentDataType::I16 => with_value_type!(@components i16, $num_components, |$data, $n| $body),
+			ComponentDataType::U16 => with_value_type!(@components u16, $num_components, |$data, $n| $body),
+			ComponentDataType::I32 => with_value_type!(@components i32, $num_components, |$data, $n| $body),
+			ComponentDataType::U32 => with_value_type!(@components u32, $num_components, |$data, $n| $body),
+			ComponentDataType::I64 => with_value_type!(@components i64, $num_components, |$data, $n| $body),
+			ComponentDataType::U64 => with_value_type!(@components u64, $num_components, |$data, $n| $body),
+			ComponentDataType::F16 => with_value_type!(@components half::f16, $num_components, |$data, $n| $body),
+			ComponentDataType::F32 => with_value_type!(@components f32, $num_components, |$data, $n| $body),
+			ComponentDataType::F64 => with_value_type!(@components f64, $num_components, |$data, $n| $body),
+			ComponentDataType::Invalid => panic!("The attribute has an invalid component type"),
+		}
+	};
+	(@components $scalar:ty, $num_components:expr, |$data:ident, $n:ident| $body:expr) => {
+		match $num_components {
+			1 => { type $data = $scalar; const $n: usize = 1; $body }
+			2 => { type $data = NdVector<2, $scalar>; const $n: usize = 2; $body }
+			3 => { type $data = NdVector<3, $scalar>; const $n: usize = 3; $body }
+			4 => { type $data = NdVector<4, $scalar>; const $n: usize = 4; $body }
+			n => panic!("Unsupported number of components: {}", n),
+		}
+	};
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum Err {
 	/// Invalid attribute domain id
@@ -15,6 +46,9 @@ pub enum Err {
 	ReaderError(#[from] crate::core::bit_coder::ReaderErr),
 	#[error("Invalid DataTypeId: {0}")]
 	InvalidDataTypeId(u8),
+	/// The requested value type does not match the attribute
+	#[error("Type mismatch: {0}")]
+	TypeMismatch(String),
 }
 
 /// Represents an attribute in a mesh. An attribute can be an array of values representing potisions
@@ -46,6 +80,10 @@ pub struct Attribute {
 
 	/// name of the attribute, if any
 	name: Option<String>,
+
+	/// The minimum and maximum of each component given by the user, if any.
+	/// The quantization uses them instead of scanning the values.
+	value_bounds: Option<(Vec<f64>, Vec<f64>)>,
 }
 
 impl Attribute {
@@ -63,6 +101,7 @@ impl Attribute {
 			domain,
 			point_to_att_val_map: None,
 			name: None,
+			value_bounds: None,
 		};
 		out.remove_duplicate_values::<Data, N>();
 		out
@@ -81,6 +120,7 @@ impl Attribute {
 			domain,
 			point_to_att_val_map: None,
 			name: None,
+			value_bounds: None,
 		}
 	}
 
@@ -97,6 +137,7 @@ impl Attribute {
 			domain,
 			point_to_att_val_map: None,
 			name: None,
+			value_bounds: None,
 		};
 		out.remove_duplicate_values::<Data, N>();
 		out
@@ -115,10 +156,142 @@ impl Attribute {
 			domain,
 			point_to_att_val_map: None,
 			name: None,
+			value_bounds: None,
 		};
 		out
 	}
 
+	/// Creates an attribute of half-float values. Half-floats are not arithmetic types in draco-oxide, so
+	/// the values are stored as their bit patterns and are only converted to f32 when the mesh is encoded.
+	pub fn from_f16<const N: usize>(data: Vec<[half::f16; N]>, att_type: AttributeType, domain: AttributeDomain, parents: Vec<AttributeId>) -> Self 
+		where NdVector<N, u16>: Vector<N, Component = u16>,
+	{
+		let bits = data.into_iter()
+			.map(|v| NdVector::from(v.map(|x| x.to_bits())))
+			.collect::<Vec<_>>();
+		let mut out = Self::new(bits, att_type, domain, parents);
+		out.set_component_type(ComponentDataType::F16);
+		out
+	}
+
+	/// Creates a [AttributeType::Custom] attribute of 16 components of f32 from 4x4 matrices, e.g. the inverse
+	/// bind matrices of the joints of a skin, with one matrix per point. The components are stored in the
+	/// row-major order. Attributes of more than 4 components are encoded losslessly with [crate::encode::encode],
+	/// so the matrices are restored exactly by [Attribute::to_row_major_matrices].
+	pub fn from_row_major_matrices(id: AttributeId, matrices: &[[[f32; 4]; 4]], domain: AttributeDomain) -> Self {
+		let bytes = matrices.iter()
+			.flat_map(|m| m.iter().flatten().flat_map(|c| c.to_le_bytes()))
+			.collect::<Vec<_>>();
+		Self::from_bytes(id, bytes, ComponentDataType::F32, 16, AttributeType::Custom, domain)
+	}
+
+	/// Creates an attribute of any number of components from the little-endian bytes of its values,
+	/// one value per point, e.g. for the values that have no vector type.
+	pub(crate) fn from_bytes(
+		id: AttributeId,
+		bytes: Vec<u8>,
+		component_type: ComponentDataType,
+		num_components: usize,
+		att_type: AttributeType,
+		domain: AttributeDomain,
+	) -> Self {
+		Self {
+			id,
+			buffer: buffer::attribute::AttributeBuffer::from_bytes(bytes, component_type, num_components),
+			parents: Vec::new(),
+			att_type,
+			domain,
+			point_to_att_val_map: None,
+			name: None,
+			value_bounds: None,
+		}
+	}
+
+	/// Returns the 4x4 matrix of each point of an attribute created by [Attribute::from_row_major_matrices],
+	/// or 'None' if the attribute does not consist of 16 components of f32.
+	pub fn to_row_major_matrices(&self) -> Option<Vec<[[f32; 4]; 4]>> {
+		if self.get_component_type() != ComponentDataType::F32 || self.get_num_components() != 16 {
+			return None;
+		}
+		let matrices = (0..self.len())
+			.map(|p| {
+				let bytes = self.get_as_bytes(usize::from(self.get_unique_val_idx(PointIdx::from(p))));
+				let c = |i: usize| f32::from_le_bytes(bytes[i * 4..(i + 1) * 4].try_into().unwrap());
+				[0, 1, 2, 3].map(|r| [0, 1, 2, 3].map(|col| c(r * 4 + col)))
+			})
+			.collect();
+		Some(matrices)
+	}
+
+	/// Converts the attribute of half-floats into the attribute of f32. The conversion is exact.
+	pub(crate) fn widen_f16_to_f32(self) -> Self {
+		assert_eq!(self.get_component_type(), ComponentDataType::F16, "The attribute must be of half-floats.");
+		match self.get_num_components() {
+			1 => self.widen_f16_to_f32_impl::<1>(),
+			2 => self.widen_f16_to_f32_impl::<2>(),
+			3 => self.widen_f16_to_f32_impl::<3>(),
+			4 => self.widen_f16_to_f32_impl::<4>(),
+			n => panic!("Unsupported number of components: {}", n),
+		}
+	}
+
+	fn widen_f16_to_f32_impl<const N: usize>(self) -> Self 
+		where NdVector<N, f32>: Vector<N, Component = f32>,
+	{
+		let values = self.get_data_as_bytes()
+			.chunks(2 * N)
+			.map(|v| {
+				let mut out = NdVector::<N, f32>::zero();
+				for i in 0..N {
+					*out.get_mut(i) = half::f16::from_le_bytes([v[2 * i], v[2 * i + 1]]).to_f32();
+				}
+				out
+			})
+			.collect::<Vec<_>>();
+		let Self { id, att_type, domain, parents, point_to_att_val_map, name, .. } = self;
+		let mut out = Self::from_without_removing_duplicates(id, values, att_type, domain, parents);
+		out.point_to_att_val_map = point_to_att_val_map;
+		out.name = name;
+		out
+	}
+
+	/// Converts the attribute of floats whose components are all integers, e.g. positions snapped to an integer grid,
+	/// into the attribute of i32. The conversion is exact; 'None' is returned if any component is not an integer
+	/// that fits in i32.
+	pub(crate) fn narrow_to_i32(&self) -> Option<Self> {
+		if !matches!(self.get_component_type(), ComponentDataType::F32 | ComponentDataType::F64) {
+			return None;
+		}
+		match self.get_num_components() {
+			1 => self.narrow_to_i32_impl::<1>(),
+			2 => self.narrow_to_i32_impl::<2>(),
+			3 => self.narrow_to_i32_impl::<3>(),
+			4 => self.narrow_to_i32_impl::<4>(),
+			_ => None,
+		}
+	}
+
+	fn narrow_to_i32_impl<const N: usize>(&self) -> Option<Self> 
+		where NdVector<N, i32>: Vector<N, Component = i32>,
+	{
+		let values = (0..self.num_unique_values())
+			.map(|i| {
+				let mut out = NdVector::<N, i32>::zero();
+				for (j, c) in crate::core::mesh::diff::unique_value_as_f64(self, i).into_iter().enumerate() {
+					if c.fract() != 0.0 || c < i32::MIN as f64 || c > i32::MAX as f64 {
+						return None;
+					}
+					*out.get_mut(j) = c as i32;
+				}
+				Some(out)
+			})
+			.collect::<Option<Vec<_>>>()?;
+		let mut out = Self::from_without_removing_duplicates(self.id, values, self.att_type, self.domain, self.parents.clone());
+		out.point_to_att_val_map = self.point_to_att_val_map.clone();
+		out.name = self.name.clone();
+		Some(out)
+	}
+
 	pub fn get<Data, const N: usize>(&self, p_idx: PointIdx) -> Data 
 		where 
 			Data: Vector<N>,
@@ -127,6 +300,18 @@ impl Attribute {
 		self.buffer.get(self.get_unique_val_idx(p_idx))
 	}
 
+	/// Same as [Attribute::get], but a mismatch of the number of components or of the component type is returned
+	/// as an error instead of a panic, e.g. for the attributes whose type is only known at runtime.
+	/// Panics if the point is out of range, as [Attribute::get] does.
+	pub fn try_get<Data, const N: usize>(&self, p_idx: PointIdx) -> Result<Data, Err> 
+		where 
+			Data: Vector<N>,
+			Data::Component: DataValue
+	{
+		self.check_layout::<Data, N>()?;
+		Ok(self.get(p_idx))
+	}
+
 	pub fn get_unique_val<Data, const N: usize>(&self, val_idx: AttributeValueIdx) -> Data 
 		where 
 			Data: Vector<N>,
@@ -135,6 +320,22 @@ impl Attribute {
 		self.buffer.get(val_idx)
 	}
 
+	/// Iterates over the values of the points in their order, i.e. the unique values are repeated as the point-to-value
+	/// map refers to them, unlike [Attribute::unique_vals_as_slice].
+	/// Panics if the values cannot be read as 'Data' (see [Attribute::validate_layout]).
+	pub fn iter_values<Data, const N: usize>(&self) -> impl Iterator<Item = Data> + '_
+		where 
+			Data: Vector<N>,
+			Data::Component: DataValue
+	{
+		assert!(
+			self.validate_layout::<Data, N>(),
+			"The attribute of {} components of {:?} is read as {} components of {:?}.",
+			self.get_num_components(), self.get_component_type(), N, Data::Component::get_dyn()
+		);
+		(0..self.len()).map(move |p| self.get(PointIdx::from(p)))
+	}
+
 	pub fn get_component_type(&self) -> ComponentDataType {
 		self.buffer.get_component_type()
 	}
@@ -142,12 +343,14 @@ impl Attribute {
 	#[inline]
 	#[allow(unused)]
 	pub(crate) fn set_component_type(&mut self, component_type: ComponentDataType) {
+		self.value_bounds = None;
 		self.buffer.set_component_type(component_type);
 	}
 
 	#[inline]
 	#[allow(unused)]
 	pub(crate) fn set_num_components(&mut self, num_components: usize) {
+		self.value_bounds = None;
 		self.buffer.set_num_components(num_components);
 	}
 
@@ -156,7 +359,6 @@ impl Attribute {
 	}
 
 	#[inline]
-	#[allow(unused)]
 	pub(crate) fn get_as_bytes(&self, i: usize) -> &[u8] {
 		&self.buffer.as_slice_u8()[
 			i * self.buffer.get_num_components() * self.buffer.get_component_type().size()..
@@ -164,6 +366,29 @@ impl Attribute {
 		]
 	}
 
+	/// Returns the values of all points as bytes in the given layout, i.e. 'xyzxyz...' for
+	/// [AttributeLayout::Interleaved] and 'xx...yy...zz...' for [AttributeLayout::Planar].
+	pub fn to_bytes_with_layout(&self, layout: AttributeLayout) -> Vec<u8> {
+		let num_components = self.get_num_components();
+		let component_size = self.get_component_type().size();
+		let value_size = num_components * component_size;
+		let mut out = vec![0_u8; self.len() * value_size];
+		for p in 0..self.len() {
+			let val_idx = usize::from(self.get_unique_val_idx(PointIdx::from(p)));
+			let value = self.get_as_bytes(val_idx);
+			match layout {
+				AttributeLayout::Interleaved => out[p * value_size..(p + 1) * value_size].copy_from_slice(value),
+				AttributeLayout::Planar => {
+					for c in 0..num_components {
+						let dst = (c * self.len() + p) * component_size;
+						out[dst..dst + component_size].copy_from_slice(&value[c * component_size..(c + 1) * component_size]);
+					}
+				}
+			}
+		}
+		out
+	}
+
 	pub(crate) fn set_point_to_att_val_map(&mut self, point_to_att_val_map: Option<VecPointIdx<AttributeValueIdx>>) {
 		self.point_to_att_val_map = point_to_att_val_map;
 	}
@@ -177,6 +402,10 @@ impl Attribute {
 		self.id
 	}
 
+	pub(crate) fn set_id(&mut self, id: AttributeId) {
+		self.id = id;
+	}
+
 	#[inline]
 	pub fn get_num_components(&self) -> usize {
 		self.buffer.get_num_components()
@@ -197,6 +426,11 @@ impl Attribute {
 		self.parents.as_ref()
 	}
 
+	#[inline]
+	pub(crate) fn set_parents(&mut self, parents: Vec<AttributeId>) {
+		self.parents = parents;
+	}
+
 	/// The number of values of the attribute.
 	#[inline(always)]
 	pub fn len(&self) -> usize {
@@ -229,6 +463,11 @@ impl Attribute {
 		}
 	}
 
+	#[inline]
+	pub(crate) fn set_attribute_type(&mut self, att_type: AttributeType) {
+		self.att_type = att_type;
+	}
+
 	#[inline]
 	pub fn set_name(&mut self, name: String) {
 		self.name = Some(name);
@@ -239,12 +478,39 @@ impl Attribute {
 		self.name.as_ref()
 	}
 
+	/// Sets the minimum and maximum of each component of the values, e.g. when they are already known from
+	/// procedural generation. The quantization then uses them instead of scanning the values for the bounding box.
+	/// All the values must lie within the bounds; this is checked in debug builds when the attribute is quantized.
+	pub fn set_value_bounds(&mut self, min: &[f64], max: &[f64]) {
+		assert_eq!(min.len(), self.get_num_components(), "The bounds must have one entry per component.");
+		assert_eq!(max.len(), self.get_num_components(), "The bounds must have one entry per component.");
+		assert!(min.iter().zip(max).all(|(a, b)| a <= b), "The minimum must not exceed the maximum.");
+		self.value_bounds = Some((min.to_vec(), max.to_vec()));
+	}
+
+	/// Returns the bounds set by [Attribute::set_value_bounds], if any.
+	#[inline]
+	pub fn get_value_bounds(&self) -> Option<(&[f64], &[f64])> {
+		self.value_bounds.as_ref().map(|(min, max)| (min.as_slice(), max.as_slice()))
+	}
+
+	/// Forgets the bounds set by [Attribute::set_value_bounds]. They are already cleared when the values are
+	/// modified through the attribute, e.g. by [Attribute::unique_vals_as_slice_mut].
+	#[inline]
+	pub fn clear_value_bounds(&mut self) {
+		self.value_bounds = None;
+	}
+
 	/// returns the data values as a slice of values casted to the given type.
+	/// Panics if the attribute does not have 'N' components of the component type of 'Data'.
 	#[inline]
-	pub fn unique_vals_as_slice<Data>(&self) -> &[Data] {
-		assert_eq!(
-			self.buffer.get_num_components() * self.buffer.get_component_type().size(),
-			std::mem::size_of::<Data>(),
+	pub fn unique_vals_as_slice<Data, const N: usize>(&self) -> &[Data]
+		where Data: Vector<N>,
+	{
+		assert!(
+			self.validate_layout::<Data, N>(),
+			"The attribute has {} components of {:?}, but it is read as {} components of {:?}",
+			self.get_num_components(), self.get_component_type(), N, Data::Component::get_dyn()
 		);
 		unsafe {
 			self.buffer.as_slice::<Data>()
@@ -252,33 +518,81 @@ impl Attribute {
 	}
 
 	/// returns the data values as a mutable slice of values casted to the given type.
+	/// Panics if the attribute does not have 'N' components of the component type of 'Data'.
+	/// The values may be modified through the slice, so the bounds set by [Attribute::set_value_bounds] are cleared.
 	#[inline]
-	pub fn unique_vals_as_slice_mut<Data>(&mut self) -> &mut [Data] {
-		assert_eq!(
-			self.buffer.get_num_components() * self.buffer.get_component_type().size(),
-			std::mem::size_of::<Data>(),
+	pub fn unique_vals_as_slice_mut<Data, const N: usize>(&mut self) -> &mut [Data]
+		where Data: Vector<N>,
+	{
+		assert!(
+			self.validate_layout::<Data, N>(),
+			"The attribute has {} components of {:?}, but it is read as {} components of {:?}",
+			self.get_num_components(), self.get_component_type(), N, Data::Component::get_dyn()
 		);
+		self.value_bounds = None;
 		unsafe {
 			self.buffer.as_slice_mut::<Data>()
 		}
 	}
 
+	/// Returns true if the values of the attribute can be read as 'Data', i.e. the attribute has 'N' components
+	/// of the component type of 'Data'. The unchecked casts of the buffer are only sound if this holds.
+	#[inline]
+	pub fn validate_layout<Data, const N: usize>(&self) -> bool
+		where Data: Vector<N>,
+	{
+		self.get_num_components() == N && self.get_component_type() == Data::Component::get_dyn()
+	}
+
+	/// Same as [Attribute::validate_layout], but returns the mismatch as an error.
+	fn check_layout<Data, const N: usize>(&self) -> Result<(), Err>
+		where Data: Vector<N>,
+	{
+		if !self.validate_layout::<Data, N>() {
+			return Err(Err::TypeMismatch(format!(
+				"the attribute has {} components of {:?}, but {} components of {:?} are requested",
+				self.get_num_components(), self.get_component_type(), N, Data::Component::get_dyn()
+			)));
+		}
+		Ok(())
+	}
+
 	/// returns the data values as a slice of values casted to the given type.
-	/// # Safety:
-	/// This function assumes that the buffer's data is properly aligned and matches the type `Data`.
+	///
+	/// # Safety
+	/// The attribute must have 'N' components of the component type of `Data`, i.e. [Attribute::validate_layout]
+	/// must return true for `Data`. Otherwise the buffer is read with the wrong size and alignment of the values.
+	/// This is only asserted in debug builds.
 	#[inline]
-	pub unsafe fn unique_vals_as_slice_unchecked<Data>(&self) -> &[Data]
+	pub unsafe fn unique_vals_as_slice_unchecked<Data, const N: usize>(&self) -> &[Data]
+		where Data: Vector<N>,
 	{
+		debug_assert!(
+			self.validate_layout::<Data, N>(),
+			"The attribute has {} components of {:?}, but it is read as {} components of {:?}",
+			self.get_num_components(), self.get_component_type(), N, Data::Component::get_dyn()
+		);
 		// Safety: upheld
 		self.buffer.as_slice::<Data>()
 	}
 
 	/// returns the data values as a mutable slice of values casted to the given type.
-	/// # Safety:
-	/// This function assumes that the buffer's data is properly aligned and matches the type `Data`.
+	///
+	/// # Safety
+	/// The attribute must have 'N' components of the component type of `Data`, i.e. [Attribute::validate_layout]
+	/// must return true for `Data`. Otherwise the buffer is read with the wrong size and alignment of the values.
+	/// This is only asserted in debug builds. The bounds set by [Attribute::set_value_bounds] are cleared as
+	/// [Attribute::unique_vals_as_slice_mut] does.
     #[inline]
-	pub unsafe fn unique_vals_as_slice_unchecked_mut<Data>(&mut self) -> &mut [Data]
+	pub unsafe fn unique_vals_as_slice_unchecked_mut<Data, const N: usize>(&mut self) -> &mut [Data]
+		where Data: Vector<N>,
 	{
+		debug_assert!(
+			self.validate_layout::<Data, N>(),
+			"The attribute has {} components of {:?}, but it is read as {} components of {:?}",
+			self.get_num_components(), self.get_component_type(), N, Data::Component::get_dyn()
+		);
+		self.value_bounds = None;
 		// Safety: upheld
 		self.buffer.as_slice_mut::<Data>()
 	}
@@ -352,6 +666,30 @@ impl Attribute {
 		}
 	}
 
+	/// Same as [Attribute::take_values], but a mismatch of the number of components or of the component type
+	/// is returned as an error instead of a panic.
+	pub fn try_take_values<Data, const N: usize>(self) -> Result<Vec<Data>, Err>
+		where Data: Vector<N>,
+	{
+		self.check_layout::<Data, N>()?;
+		Ok(self.take_values())
+	}
+
+	/// Returns the value of each point, e.g. the positions of a decoded mesh as 'Vec<NdVector<3, f32>>'.
+	/// This is the canonical way to extract the data of an attribute; unlike [Attribute::take_values],
+	/// the values shared by several points are repeated, and a mismatch of the number of components or
+	/// of the component type is returned as an error instead of a panic.
+	pub fn into_vec<Data, const N: usize>(mut self) -> Result<Vec<Data>, Err>
+		where Data: Vector<N>,
+	{
+		self.check_layout::<Data, N>()?;
+		let point_to_att_val_map = self.point_to_att_val_map.take();
+		let values = self.take_values::<Data, N>();
+		Ok(match point_to_att_val_map {
+			Some(map) => map.iter().map(|&i| values[usize::from(i)]).collect(),
+			None => values,
+		})
+	}
 
 	pub fn into_parts<Data, const N: usize>(mut self) -> (Vec<Data>, Option<VecPointIdx<AttributeValueIdx>>, Self)
 		where Data: Vector<N>,
@@ -389,16 +727,95 @@ impl Attribute {
 		);
 		assert_eq!( self.len(), 0 );
 		self.buffer = buffer::attribute::AttributeBuffer::from_vec(data);
+		self.value_bounds = None;
+	}
+
+	/// Appends the values of 'other' to the attribute, e.g. to merge the attributes of two meshes. The points of 'other'
+	/// follow the points of 'self', and they refer to the appended values, i.e. their value indices are offset by the
+	/// number of the unique values of 'self'. Values shared by the two attributes are not deduplicated.
+	/// Both attributes must have the same type, domain, component type, and number of components.
+	pub fn concat(&mut self, other: Attribute) {
+		assert_eq!(self.att_type, other.att_type, "Cannot concatenate attributes of different types.");
+		assert_eq!(self.domain, other.domain, "Cannot concatenate attributes of different domains.");
+		assert_eq!(self.get_component_type(), other.get_component_type(), "Cannot concatenate attributes of different component types.");
+		assert_eq!(self.get_num_components(), other.get_num_components(), "Cannot concatenate attributes with different numbers of components.");
+
+		let offset = self.num_unique_values();
+		if self.point_to_att_val_map.is_some() || other.point_to_att_val_map.is_some() {
+			let mut map = self.point_to_att_val_map.take()
+				.unwrap_or_else(|| VecPointIdx::from((0..self.len()).map(AttributeValueIdx::from).collect::<Vec<_>>()));
+			for p in 0..other.len() {
+				let idx = other.get_unique_val_idx(PointIdx::from(p));
+				map.push(AttributeValueIdx::from(usize::from(idx) + offset));
+			}
+			self.point_to_att_val_map = Some(map);
+		}
+		self.buffer.append(&other.buffer);
+
+		self.value_bounds = match (self.value_bounds.take(), other.value_bounds) {
+			(Some((min, max)), Some((other_min, other_max))) => Some((
+				min.iter().zip(&other_min).map(|(a, b)| a.min(*b)).collect(),
+				max.iter().zip(&other_max).map(|(a, b)| a.max(*b)).collect(),
+			)),
+			_ => None,
+		};
+	}
+
+	/// Merges the unique values of the same bytes into the first of them, e.g. after [Attribute::concat].
+	/// Unlike [Attribute::remove_duplicate_values], it works for any type of the values and keeps the values
+	/// that are already shared by several points.
+	pub(crate) fn merge_duplicate_values(&mut self) {
+		let mut first_of = std::collections::HashMap::new();
+		let mut kept = Vec::new();
+		let old_to_new = (0..self.num_unique_values())
+			.map(|i| *first_of.entry(self.get_as_bytes(i)).or_insert_with(|| {
+				kept.push(i);
+				kept.len() - 1
+			}))
+			.collect::<Vec<_>>();
+		if kept.len() == self.num_unique_values() {
+			return;
+		}
+		let map = (0..self.len())
+			.map(|p| AttributeValueIdx::from(old_to_new[usize::from(self.get_unique_val_idx(PointIdx::from(p)))]))
+			.collect::<Vec<_>>();
+		let bytes = kept.iter()
+			.flat_map(|&i| self.get_as_bytes(i).to_vec())
+			.collect();
+		self.buffer = buffer::attribute::AttributeBuffer::from_bytes(bytes, self.get_component_type(), self.get_num_components());
+		self.point_to_att_val_map = Some(VecPointIdx::from(map));
+	}
+
+	/// Returns the attribute whose 'i'th point has the value of the point 'points[i]' of this attribute.
+	/// The values are stored one per point, in the order of the points.
+	pub(crate) fn select_points(&self, points: &[PointIdx]) -> Self {
+		let indices = points.iter()
+			.map(|&p| usize::from(self.get_unique_val_idx(p)))
+			.collect::<Vec<_>>();
+		Self {
+			id: self.id,
+			buffer: self.buffer.select(&indices),
+			parents: self.parents.clone(),
+			att_type: self.att_type,
+			domain: self.domain,
+			point_to_att_val_map: None,
+			name: self.name.clone(),
+			value_bounds: self.value_bounds.clone(),
+		}
 	}
 
 	pub fn remove_duplicate_values<Data, const N: usize>(&mut self) 
 		where Data: Vector<N>,
 	{
+		if self.len() < 2 {
+			// no value can be a duplicate.
+			return;
+		}
 		let mut duplicate_indeces = Vec::new();
 		// start with identity mapping
 		let mut point_to_att_val_map = VecPointIdx::<_>::from( (0..self.len()).map(|i| i.into()).collect::<Vec<AttributeValueIdx>>());
-		for (i, val) in self.unique_vals_as_slice::<Data>().iter().enumerate() {
-			if i == self.len() - 1 {
+		for (i, val) in self.unique_vals_as_slice::<Data, N>().iter().enumerate() {
+			if i + 1 == self.len() {
 				// last element, no need to check for duplicates
 				break;
 			}
@@ -407,7 +824,7 @@ impl Attribute {
 				continue;
 			}
 			let mut local_duplicate_indeces = Vec::new();
-			for (j, other_val) in self.unique_vals_as_slice::<Data>()[i+1..].iter().enumerate() {
+			for (j, other_val) in self.unique_vals_as_slice::<Data, N>()[i+1..].iter().enumerate() {
 				if val == other_val {
 					local_duplicate_indeces.push(i+1 + j);
 				}
@@ -483,17 +900,7 @@ impl Attribute {
 
 	pub(crate) fn remove_dyn(&mut self, p_idx: PointIdx) {
 		assert!(usize::from(p_idx) < self.len(), "Point index out of bounds: {}", usize::from(p_idx));
-		match self.get_component_type().size() * self.get_num_components() {
-			1 => self.remove::<u8, 1>(p_idx),
-			2 => self.remove::<u16, 1>(p_idx),
-			4 => self.remove::<u32, 1>(p_idx),
-			6 => self.remove::<u16, 3>(p_idx),
-			8 => self.remove::<u64, 1>(p_idx),
-			12 => self.remove::<u32, 3>(p_idx),
-			16 => self.remove::<u64, 2>(p_idx),
-			18 => self.remove::<u64, 3>(p_idx),
-			_ => panic!("Unsupported component size: {}", self.get_component_type().size()),
-		}
+		with_value_type!(self.get_component_type(), self.get_num_components(), |Data, N| self.remove::<Data, N>(p_idx))
 	}
 
 	pub(crate) fn remove_unique_val<Data, const N: usize>(&mut self, val_idx: AttributeValueIdx) 
@@ -509,17 +916,7 @@ impl Attribute {
 	pub fn remove_unique_val_dyn(&mut self, val_idx: usize) 
 	{
 		assert!(val_idx < self.num_unique_values(), "Attribute value index out of bounds: {}", val_idx);
-		match self.get_component_type().size() * self.get_num_components() {
-			1 => self.buffer.remove::<u8, 1>(val_idx),
-			2 => self.buffer.remove::<u16, 1>(val_idx),
-			4 => self.buffer.remove::<u32, 1>(val_idx),
-			6 => self.buffer.remove::<u16, 3>(val_idx),
-			8 => self.buffer.remove::<u64, 1>(val_idx),
-			12 => self.buffer.remove::<u32, 3>(val_idx),
-			16 => self.buffer.remove::<u64, 2>(val_idx),
-			18 => self.buffer.remove::<u64, 3>(val_idx),
-			_ => panic!("Unsupported component size: {}", self.get_component_type().size()),
-		}
+		with_value_type!(self.get_component_type(), self.get_num_components(), |Data, N| self.buffer.remove::<Data, N>(val_idx))
 	}
 }
 
@@ -534,6 +931,7 @@ pub enum ComponentDataType {
 	U32,
 	I64,
 	U64,
+	F16,
 	F32,
 	F64,
 	Invalid,
@@ -544,6 +942,7 @@ impl ComponentDataType {
 	#[inline]
 	pub fn size(self) -> usize {
         match self {
+            ComponentDataType::F16 => 2,
             ComponentDataType::F32 => 4,
             ComponentDataType::F64 => 8,
             ComponentDataType::U8 => 1,
@@ -560,7 +959,7 @@ impl ComponentDataType {
 
 	#[inline]
 	pub fn is_float(self) -> bool {
-		matches!(self, ComponentDataType::F32 | ComponentDataType::F64)
+		matches!(self, ComponentDataType::F16 | ComponentDataType::F32 | ComponentDataType::F64)
 	}
 	
 	/// returns unique id for the data type.
@@ -577,6 +976,9 @@ impl ComponentDataType {
 			ComponentDataType::I64 => 8,
             ComponentDataType::F32 => 9,
             ComponentDataType::F64 => 10,
+			// Draco has no half-float type; 11 is taken by its boolean type.
+			// Half-floats are converted to f32 before encoding, so this id is not written by the encoder.
+			ComponentDataType::F16 => 12,
 			ComponentDataType::Invalid => u8::MAX, // Invalid type
         }
 	}
@@ -591,16 +993,17 @@ impl ComponentDataType {
 	#[inline]
 	pub fn from_id(id: usize) -> Result<Self, ()> {
 		match id {
-			1 => Ok(ComponentDataType::I8),
-			2 => Ok(ComponentDataType::U8),
-			3 => Ok(ComponentDataType::I16),
-			4 => Ok(ComponentDataType::U16),
-			5 => Ok(ComponentDataType::I32),
-			6 => Ok(ComponentDataType::U32),
-			7 => Ok(ComponentDataType::I64),
-			8 => Ok(ComponentDataType::U64),
+			1 => Ok(ComponentDataType::U8),
+			2 => Ok(ComponentDataType::I8),
+			3 => Ok(ComponentDataType::U16),
+			4 => Ok(ComponentDataType::I16),
+			5 => Ok(ComponentDataType::U32),
+			6 => Ok(ComponentDataType::I32),
+			7 => Ok(ComponentDataType::U64),
+			8 => Ok(ComponentDataType::I64),
 			9 => Ok(ComponentDataType::F32),
 			10 => Ok(ComponentDataType::F64),
+			12 => Ok(ComponentDataType::F16),
 			_ => Err(()),
 		}
 	}
@@ -627,6 +1030,19 @@ pub enum AttributeType {
 	Invalid,
 }
 
+crate::utils::enum_str::impl_enum_str!(AttributeType, "attribute type", {
+	Position => ["position"],
+	Normal => ["normal"],
+	Color => ["color", "colour"],
+	TextureCoordinate => ["texcoord", "texture_coordinate", "uv"],
+	Custom => ["custom", "generic"],
+	Tangent => ["tangent"],
+	Material => ["material"],
+	Joint => ["joint"],
+	Weight => ["weight"],
+	Invalid => ["invalid"],
+});
+
 impl AttributeType {
 	pub fn get_minimum_dependency(&self) -> Vec<Self> {
 		match self {
@@ -720,6 +1136,16 @@ impl AttributeDomain {
 	}
 }
 
+/// The byte layout of the attribute values of a mesh.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum AttributeLayout {
+	/// Array of structures, i.e. the components of each value are contiguous ('xyzxyz...').
+	#[default]
+	Interleaved,
+	/// Structure of arrays, i.e. each component is stored contiguously for all values ('xx...yy...zz...').
+	Planar,
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
 pub struct AttributeId(usize);
 
@@ -756,6 +1182,207 @@ mod tests {
 		assert_eq!(att.get_attribute_type(), super::AttributeType::Position);
 	}
 
+	#[test]
+	fn test_concat() {
+		let first = vec![
+			NdVector::from([1.0f32, 2.0, 3.0]),
+			NdVector::from([4.0f32, 5.0, 6.0]),
+			NdVector::from([1.0f32, 2.0, 3.0]),
+		];
+		let second = vec![
+			NdVector::from([7.0f32, 8.0, 9.0]),
+			NdVector::from([7.0f32, 8.0, 9.0]),
+			NdVector::from([1.0f32, 2.0, 3.0]),
+			NdVector::from([0.0f32, 0.5, 1.0]),
+		];
+		// 'first' has a point-to-value map as its duplicates are removed.
+		let mut att = super::Attribute::from(AttributeId::new(0), first.clone(), super::AttributeType::Position, super::AttributeDomain::Position, Vec::new());
+		let other = super::Attribute::from(AttributeId::new(1), second.clone(), super::AttributeType::Position, super::AttributeDomain::Position, Vec::new());
+		let num_unique = att.num_unique_values() + other.num_unique_values();
+		att.concat(other);
+
+		assert_eq!(att.len(), first.len() + second.len());
+		assert_eq!(att.num_unique_values(), num_unique);
+		for (p, v) in first.iter().chain(&second).enumerate() {
+			assert_eq!(att.get::<NdVector<3, f32>, 3>(p.into()), *v);
+		}
+
+		// attributes without the map are concatenated as well.
+		let mut att = super::Attribute::from_without_removing_duplicates(AttributeId::new(0), first.clone(), super::AttributeType::Position, super::AttributeDomain::Position, Vec::new());
+		let other = super::Attribute::from_without_removing_duplicates(AttributeId::new(1), second.clone(), super::AttributeType::Position, super::AttributeDomain::Position, Vec::new());
+		att.concat(other);
+		assert_eq!(att.len(), first.len() + second.len());
+		for (p, v) in first.iter().chain(&second).enumerate() {
+			assert_eq!(att.get::<NdVector<3, f32>, 3>(p.into()), *v);
+		}
+	}
+
+	#[test]
+	fn test_value_bounds_are_cleared_on_mutation() {
+		let data = vec![NdVector::from([1.0f32, 2.0, 3.0]), NdVector::from([4.0f32, 5.0, 6.0])];
+		let mut att = super::Attribute::from(AttributeId::new(0), data, super::AttributeType::Position, super::AttributeDomain::Position, Vec::new());
+		att.set_value_bounds(&[1.0, 2.0, 3.0], &[4.0, 5.0, 6.0]);
+		// reading the values keeps the bounds.
+		assert_eq!(att.unique_vals_as_slice::<NdVector<3, f32>, 3>().len(), 2);
+		assert!(att.get_value_bounds().is_some());
+
+		// a value outside of the bounds would not be quantized correctly with them.
+		*att.unique_vals_as_slice_mut::<NdVector<3, f32>, 3>()[0].get_mut(0) = -10.0;
+		assert!(att.get_value_bounds().is_none());
+
+		att.set_value_bounds(&[-10.0, 2.0, 3.0], &[4.0, 5.0, 6.0]);
+		unsafe { att.unique_vals_as_slice_unchecked_mut::<NdVector<3, f32>, 3>()[1] = NdVector::from([0.0, 0.0, 0.0]); }
+		assert!(att.get_value_bounds().is_none());
+	}
+
+	#[test]
+	#[cfg(not(feature = "evaluation"))]
+	fn test_into_vec() {
+		use crate::core::shared::ConfigType;
+		let mesh = crate::io::obj::load_obj("tests/data/cube_quads.obj").unwrap();
+		let mut buffer = Vec::new();
+		crate::encode::encode(mesh, &mut buffer, crate::encode::Config::default()).unwrap();
+		// the corners of the cube are split by the texture coordinates and the normals, and the decoded positions
+		// keep their own values, which the points refer to.
+		let mut cfg = crate::decode::Config::default();
+		cfg.emit_attribute_indices = true;
+		let decoded = crate::decode::decode(&mut crate::prelude::SliceReader::new(&buffer), cfg).unwrap();
+		let pos = decoded.get_attributes().iter()
+			.find(|att| att.get_attribute_type() == AttributeType::Position)
+			.unwrap()
+			.clone();
+		assert!(pos.num_unique_values() < pos.len());
+		let expected = (0..pos.len())
+			.map(|p| pos.get::<NdVector<3, f32>, 3>(PointIdx::from(p)))
+			.collect::<Vec<_>>();
+
+		let positions: Vec<NdVector<3, f32>> = pos.clone().into_vec().unwrap();
+		assert_eq!(positions, expected);
+
+		assert!(matches!(pos.clone().into_vec::<NdVector<2, f32>, 2>(), Err(Err::TypeMismatch(_))));
+		assert!(matches!(pos.into_vec::<NdVector<3, f64>, 3>(), Err(Err::TypeMismatch(_))));
+	}
+
+	#[test]
+	fn test_try_get() {
+		let data = vec![NdVector::from([1.0f32, 2.0, 3.0]), NdVector::from([4.0f32, 5.0, 6.0])];
+		let att = super::Attribute::from(AttributeId::new(0), data.clone(), super::AttributeType::Position, super::AttributeDomain::Position, Vec::new());
+		assert_eq!(att.try_get::<NdVector<3, f32>, 3>(PointIdx::from(1)).unwrap(), data[1]);
+		assert!(matches!(att.try_get::<NdVector<2, f32>, 2>(PointIdx::from(1)), Err(Err::TypeMismatch(_))));
+		assert!(matches!(att.try_get::<NdVector<3, i32>, 3>(PointIdx::from(1)), Err(Err::TypeMismatch(_))));
+
+		assert!(matches!(att.clone().try_take_values::<NdVector<4, f32>, 4>(), Err(Err::TypeMismatch(_))));
+		let err = att.clone().try_take_values::<NdVector<3, u16>, 3>().unwrap_err();
+		assert!(err.to_string().contains("3 components of U16"), "{}", err);
+		assert_eq!(att.try_take_values::<NdVector<3, f32>, 3>().unwrap(), data);
+	}
+
+	#[test]
+	fn test_validate_layout() {
+		let data = vec![NdVector::from([1.0f32, 2.0, 3.0]), NdVector::from([4.0f32, 5.0, 6.0])];
+		let att = super::Attribute::from(AttributeId::new(0), data, super::AttributeType::Position, super::AttributeDomain::Position, Vec::new());
+		assert!(att.validate_layout::<NdVector<3, f32>, 3>());
+		assert!(!att.validate_layout::<NdVector<2, f32>, 2>());
+		// 'i32' has the same size as 'f32', so only the component type tells the two apart.
+		assert!(!att.validate_layout::<NdVector<3, i32>, 3>());
+	}
+
+	#[test]
+	#[cfg(debug_assertions)]
+	#[should_panic(expected = "but it is read as 3 components of I32")]
+	fn test_unchecked_access_with_wrong_type() {
+		let data = vec![NdVector::from([1.0f32, 2.0, 3.0])];
+		let att = super::Attribute::from(AttributeId::new(0), data, super::AttributeType::Position, super::AttributeDomain::Position, Vec::new());
+		let _ = unsafe { att.unique_vals_as_slice_unchecked::<NdVector<3, i32>, 3>() };
+	}
+
+	#[test]
+	#[should_panic(expected = "but it is read as 3 components of I32")]
+	fn test_slice_access_with_wrong_type() {
+		// the sizes of the values agree, but the component types do not.
+		let data = vec![NdVector::from([1.0f32, 2.0, 3.0])];
+		let att = super::Attribute::from(AttributeId::new(0), data, super::AttributeType::Position, super::AttributeDomain::Position, Vec::new());
+		let _ = att.unique_vals_as_slice::<NdVector<3, i32>, 3>();
+	}
+
+	#[test]
+	fn test_empty_attribute() {
+		use crate::core::shared::ConfigType;
+		let att = super::Attribute::from(AttributeId::new(2), Vec::<NdVector<3, f32>>::new(), super::AttributeType::Custom, super::AttributeDomain::Position, Vec::new());
+		assert_eq!(att.len(), 0);
+		assert_eq!(att.num_unique_values(), 0);
+		assert!(att.clone().into_vec::<NdVector<3, f32>, 3>().unwrap().is_empty());
+
+		// the encoder rejects the attribute instead of quantizing no values.
+		let mut mesh = crate::io::obj::load_obj("tests/data/sphere.obj").unwrap();
+		mesh.attributes.push(att);
+		let err = crate::encode::encode(mesh, &mut Vec::new(), crate::encode::Config::default()).unwrap_err();
+		assert!(matches!(err, crate::encode::Err::EmptyAttribute(id) if id == AttributeId::new(2)), "{:?}", err);
+	}
+
+	#[test]
+	fn test_interleaved_and_planar_layouts_correspond() {
+		let data = vec![
+			NdVector::from([1.0f32, 2.0, 3.0]),
+			NdVector::from([4.0f32, 5.0, 6.0]),
+			NdVector::from([1.0f32, 2.0, 3.0]),
+			NdVector::from([7.0f32, 8.0, 9.0]),
+		];
+		// duplicates are removed, so the values are read through the point-to-value map.
+		let att = super::Attribute::from(AttributeId::new(0), data.clone(), super::AttributeType::Position, super::AttributeDomain::Position, Vec::new());
+		let to_f32 = |bytes: Vec<u8>| bytes.chunks(4).map(|b| f32::from_le_bytes(b.try_into().unwrap())).collect::<Vec<_>>();
+		let aos = to_f32(att.to_bytes_with_layout(AttributeLayout::Interleaved));
+		let soa = to_f32(att.to_bytes_with_layout(AttributeLayout::Planar));
+		assert_eq!(aos.len(), soa.len());
+		for (p, v) in data.iter().enumerate() {
+			for c in 0..3 {
+				assert_eq!(aos[p * 3 + c], *v.get(c));
+				assert_eq!(soa[c * data.len() + p], *v.get(c));
+			}
+		}
+	}
+
+	#[test]
+	fn test_encode_f16_positions() {
+		use crate::core::shared::ConfigType;
+		let mut mesh = crate::io::obj::load_obj("tests/data/sphere.obj").unwrap();
+		let pos = &mesh.get_attributes()[0];
+		assert_eq!(pos.get_attribute_type(), AttributeType::Position);
+		let original = (0..pos.len())
+			.map(|p| pos.get::<NdVector<3, f32>, 3>(PointIdx::from(p)))
+			.collect::<Vec<_>>();
+		let f16_att = Attribute::from_f16(
+			original.iter().map(|v| [0,1,2].map(|i| half::f16::from_f32(*v.get(i)))).collect(),
+			AttributeType::Position,
+			AttributeDomain::Position,
+			Vec::new()
+		);
+		assert_eq!(f16_att.get_component_type(), ComponentDataType::F16);
+		assert_eq!(f16_att.get_component_type().size(), 2);
+		assert_eq!(ComponentDataType::from_id(ComponentDataType::F16.get_id() as usize), Ok(ComponentDataType::F16));
+		mesh.get_attributes_mut()[0] = f16_att;
+
+		// f32 represents every half-float exactly, so the widened values are within half-float precision of the original.
+		let widened = mesh.get_attributes()[0].clone().widen_f16_to_f32();
+		assert_eq!(widened.get_component_type(), ComponentDataType::F32);
+		for (p, v) in original.iter().enumerate() {
+			let w = widened.get::<NdVector<3, f32>, 3>(PointIdx::from(p));
+			for i in 0..3 {
+				let tolerance = v.get(i).abs() * half::f16::EPSILON.to_f32() + half::f16::MIN_POSITIVE_SUBNORMAL.to_f32();
+				assert!((w.get(i) - v.get(i)).abs() <= tolerance, "{} is not within half-float precision of {}", w.get(i), v.get(i));
+			}
+		}
+
+		// Half-floats are encoded as the widened f32 values.
+		let mut widened_mesh = mesh.clone();
+		widened_mesh.get_attributes_mut()[0] = widened;
+		let mut buffer = Vec::new();
+		crate::encode::encode(mesh, &mut buffer, crate::encode::Config::default()).unwrap();
+		let mut widened_buffer = Vec::new();
+		crate::encode::encode(widened_mesh, &mut widened_buffer, crate::encode::Config::default()).unwrap();
+		assert_eq!(buffer, widened_buffer);
+	}
+
 	#[test]
 	fn test_attribute_remap() {
 	    let positions = vec![
@@ -768,12 +1395,16 @@ mod tests {
         ];
         
         let att = Attribute::new(
-            positions,
+            positions.clone(),
             AttributeType::Position,
             AttributeDomain::Position,
             vec![],
         );
 
+		// the duplicates removed from the unique values are restored by the iterator.
+		assert_eq!(att.num_unique_values(), 4);
+		assert_eq!(att.iter_values::<NdVector<3, f32>, 3>().collect::<Vec<_>>(), positions);
+
 		assert_eq!(
 			att.point_to_att_val_map.unwrap()
 				.into_iter()
@@ -837,4 +1468,29 @@ mod tests {
 			&vec![0, 2, 1, 3]
 		);
 	}
-}
\ No newline at end of file
+
+	#[test]
+	fn test_remove_dyn() {
+		// tangents of 4 components of f32.
+		let tangents = (0..4).map(|i| NdVector::from([i as f32, 0.0, 1.0, if i % 2 == 0 { 1.0 } else { -1.0 }])).collect::<Vec<_>>();
+		let mut att = Attribute::new(tangents.clone(), AttributeType::Tangent, AttributeDomain::Position, vec![]);
+		att.remove_dyn(PointIdx::from(1));
+		assert_eq!(att.len(), 3);
+		assert_eq!(att.iter_values::<NdVector<4, f32>, 4>().collect::<Vec<_>>(), vec![tangents[0], tangents[2], tangents[3]]);
+
+		// texture coordinates of 2 components of f64, of the same size as the tangents.
+		let uvs = (0..4).map(|i| NdVector::from([i as f64 * 0.25, 1.0 - i as f64 * 0.25])).collect::<Vec<_>>();
+		let mut att = Attribute::new(uvs.clone(), AttributeType::TextureCoordinate, AttributeDomain::Corner, vec![]);
+		att.remove_dyn(PointIdx::from(3));
+		att.remove_dyn(PointIdx::from(0));
+		assert_eq!(att.iter_values::<NdVector<2, f64>, 2>().collect::<Vec<_>>(), vec![uvs[1], uvs[2]]);
+		att.remove_unique_val_dyn(0);
+		assert_eq!(att.iter_values::<NdVector<2, f64>, 2>().collect::<Vec<_>>(), vec![uvs[2]]);
+
+		// tangents of 4 components of f64, which have 32 bytes.
+		let tangents = tangents.iter().map(|t| NdVector::from([0, 1, 2, 3].map(|i| *t.get(i) as f64))).collect::<Vec<_>>();
+		let mut att = Attribute::new(tangents.clone(), AttributeType::Tangent, AttributeDomain::Position, vec![]);
+		att.remove_dyn(PointIdx::from(0));
+		assert_eq!(att.iter_values::<NdVector<4, f64>, 4>().collect::<Vec<_>>(), tangents[1..].to_vec());
+	}
+}
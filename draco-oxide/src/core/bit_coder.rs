@@ -1,3 +1,4 @@
+use std::io::{self, BufReader, BufWriter, Read, Write};
 use std::{iter::Rev, vec};
 
 use super::buffer::{MsbFirst, OrderConfig};
@@ -47,6 +48,31 @@ impl ByteWriter for Vec<u8> {
     }
 }
 
+/// A byte writer that passes the bytes to the inner writer and counts them, so that the encoder can tell
+/// how many bytes each part of the stream takes.
+pub(crate) struct CountingWriter<'a, W> {
+    writer: &'a mut W,
+    count: usize,
+}
+
+impl<'a, W: ByteWriter> CountingWriter<'a, W> {
+    pub(crate) fn new(writer: &'a mut W) -> Self {
+        Self { writer, count: 0 }
+    }
+
+    /// The number of bytes written so far.
+    pub(crate) fn count(&self) -> usize {
+        self.count
+    }
+}
+
+impl<W: ByteWriter> ByteWriter for CountingWriter<'_, W> {
+    fn write_u8(&mut self, value: u8) {
+        self.count += 1;
+        self.writer.write_u8(value);
+    }
+}
+
 /// A special byte writer that is provided by draco-oxide that allows users to build
 /// custom byte writers using a cloure.
 pub struct FunctionalByteWriter<R> {
@@ -87,6 +113,61 @@ impl <R: FnMut(u8)> FunctionalByteWriter<R> {
     }
 }
 
+/// A byte writer over any [std::io::Write], e.g. a file or a socket, so that the output is streamed instead of
+/// being collected in memory. The bytes are buffered and written in chunks, where a short write is retried until
+/// everything is written. Since [ByteWriter] cannot fail, the first error is kept and the following bytes are dropped;
+/// call [IoByteWriter::finish] to flush the buffer and get the error, if any.
+pub struct IoByteWriter<W: Write> {
+    inner: BufWriter<W>,
+    err: Option<io::Error>,
+}
+
+impl<W: Write> IoByteWriter<W> {
+    pub fn new(inner: W) -> Self {
+        IoByteWriter { inner: BufWriter::new(inner), err: None }
+    }
+
+    /// Flushes the buffered bytes and returns the inner writer, or the first error that occurred while writing.
+    /// The bytes that are not flushed when the writer is dropped without this are written on the best effort.
+    pub fn finish(mut self) -> io::Result<W> {
+        if let Some(err) = self.err.take() {
+            return Err(err);
+        }
+        self.inner.flush()?;
+        self.inner.into_inner().map_err(|e| e.into_error())
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) {
+        if self.err.is_none() {
+            if let Err(err) = self.inner.write_all(bytes) {
+                self.err = Some(err);
+            }
+        }
+    }
+}
+
+impl<W: Write> ByteWriter for IoByteWriter<W> {
+    fn write_u8(&mut self, value: u8) {
+        self.write_bytes(&[value]);
+    }
+
+    fn write_u16(&mut self, value: u16) {
+        self.write_bytes(&value.to_le_bytes());
+    }
+
+    fn write_u24(&mut self, value: u32) {
+        self.write_bytes(&value.to_le_bytes()[..3]);
+    }
+
+    fn write_u32(&mut self, value: u32) {
+        self.write_bytes(&value.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, value: u64) {
+        self.write_bytes(&value.to_le_bytes());
+    }
+}
+
 pub struct BitWriter<'buffer, Buffer: ByteWriter, Order: OrderConfig = MsbFirst> {
     buffer: &'buffer mut Buffer,
 
@@ -343,6 +424,121 @@ impl<R: FnMut() -> Result<u8, ReaderErr>> FunctionalByteReader<R> {
     }
 }
 
+/// A byte reader over a byte slice, which is the simplest way to read an encoded buffer, e.g.
+/// `decode(&mut SliceReader::new(&bytes), cfg)`. The slice is borrowed and never copied.
+#[derive(Clone, Debug)]
+pub struct SliceReader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> SliceReader<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        SliceReader { data }
+    }
+
+    /// Returns the next byte without consuming it.
+    pub fn peek(&self) -> Result<u8, ReaderErr> {
+        self.data.first().copied().ok_or(ReaderErr::NotEnoughData)
+    }
+
+    /// Returns the bytes that are not read yet.
+    pub fn remaining(&self) -> &'a [u8] {
+        self.data
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], ReaderErr> {
+        if self.data.len() < N {
+            return Err(ReaderErr::NotEnoughData);
+        }
+        let (out, rest) = self.data.split_at(N);
+        self.data = rest;
+        Ok(out.try_into().unwrap())
+    }
+}
+
+impl<'a> ByteReader for SliceReader<'a> {
+    fn read_u8(&mut self) -> Result<u8, ReaderErr> {
+        Ok(self.read_array::<1>()?[0])
+    }
+
+    fn read_u16(&mut self) -> Result<u16, ReaderErr> {
+        Ok(u16::from_le_bytes(self.read_array()?))
+    }
+
+    fn read_u32(&mut self) -> Result<u32, ReaderErr> {
+        Ok(u32::from_le_bytes(self.read_array()?))
+    }
+
+    fn read_u64(&mut self) -> Result<u64, ReaderErr> {
+        Ok(u64::from_le_bytes(self.read_array()?))
+    }
+
+    type Rev = Rev<std::iter::Copied<std::slice::Iter<'a, u8>>>;
+
+    fn spown_reverse_reader_at(&mut self, offset: usize) -> Result<Self::Rev, ReaderErr> {
+        if offset > self.data.len() {
+            return Err(ReaderErr::NotEnoughData);
+        }
+        let (front, rest) = self.data.split_at(offset);
+        self.data = rest;
+        Ok(front.iter().copied().rev())
+    }
+}
+
+/// A byte reader over any [std::io::Read], e.g. a file or a socket, so that the input is decoded while it is
+/// streamed instead of being read into memory first. The reads are buffered, and a short read is continued until
+/// the requested bytes are read; the end of the stream before them is [ReaderErr::NotEnoughData].
+pub struct IoByteReader<R: Read> {
+    inner: BufReader<R>,
+}
+
+impl<R: Read> IoByteReader<R> {
+    pub fn new(inner: R) -> Self {
+        IoByteReader { inner: BufReader::new(inner) }
+    }
+
+    /// Returns the inner reader. The bytes buffered but not read yet are lost.
+    pub fn into_inner(self) -> R {
+        self.inner.into_inner()
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], ReaderErr> {
+        let mut out = [0; N];
+        self.inner.read_exact(&mut out).map_err(ReaderErr::from)?;
+        Ok(out)
+    }
+}
+
+impl<R: Read> ByteReader for IoByteReader<R> {
+    fn read_u8(&mut self) -> Result<u8, ReaderErr> {
+        Ok(self.read_array::<1>()?[0])
+    }
+
+    fn read_u16(&mut self) -> Result<u16, ReaderErr> {
+        Ok(u16::from_le_bytes(self.read_array()?))
+    }
+
+    fn read_u32(&mut self) -> Result<u32, ReaderErr> {
+        Ok(u32::from_le_bytes(self.read_array()?))
+    }
+
+    fn read_u64(&mut self) -> Result<u64, ReaderErr> {
+        Ok(u64::from_le_bytes(self.read_array()?))
+    }
+
+    type Rev = Rev<vec::IntoIter<u8>>;
+
+    fn spown_reverse_reader_at(&mut self, offset: usize) -> Result<Self::Rev, ReaderErr> {
+        // The buffer grows with the bytes actually read, so that a corrupt offset does not allocate at once.
+        let mut front = Vec::new();
+        (&mut self.inner).take(offset as u64).read_to_end(&mut front)?;
+        if front.len() < offset {
+            return Err(ReaderErr::NotEnoughData);
+        }
+        Ok(front.into_iter().rev())
+    }
+}
+
 #[allow(unused)] // will be used in the decoder
 pub struct BitReader<'buffer, Buffer, Order: OrderConfig = MsbFirst> {
     buffer: &'buffer mut Buffer,
@@ -446,10 +642,21 @@ impl<'buffer, Buffer: ByteReader, Order: OrderConfig> BitReader<'buffer, Buffer,
 
 #[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ReaderErr {
+    #[error("I/O error while reading: {0}")]
+    Io(io::ErrorKind),
     #[error("Not enough data to read")]
     NotEnoughData,
 }
 
+impl From<io::Error> for ReaderErr {
+    fn from(err: io::Error) -> Self {
+        match err.kind() {
+            io::ErrorKind::UnexpectedEof => ReaderErr::NotEnoughData,
+            kind => ReaderErr::Io(kind),
+        }
+    }
+}
+
 
 #[allow(unused)] // will be used in the decoder
 pub trait ReverseByteReader {
@@ -660,4 +867,160 @@ mod tests {
         assert_eq!(reverse_reader.read_u8_back(), Err(NotEnoughData));
         assert!(reader.next().is_none());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_slice_reader() {
+        use crate::prelude::{ByteWriter, SliceReader};
+
+        let mut buffer = Vec::new();
+        buffer.write_u8(7);
+        buffer.write_u16(0x1234);
+        buffer.write_u32(0xDEADBEEF);
+        buffer.write_u64(u64::MAX - 1);
+        buffer.write_u8(1);
+        buffer.write_u8(2);
+        buffer.write_u8(3);
+
+        let mut reader = SliceReader::new(&buffer);
+        assert_eq!(reader.peek().unwrap(), 7);
+        assert_eq!(reader.read_u8().unwrap(), 7);
+        assert_eq!(reader.read_u16().unwrap(), 0x1234);
+        assert_eq!(reader.read_u32().unwrap(), 0xDEADBEEF);
+        assert_eq!(reader.read_u64().unwrap(), u64::MAX - 1);
+
+        // the same bytes are read as with the reader over a vector.
+        let mut vec_reader = buffer.clone().into_iter();
+        vec_reader.read_u8().unwrap();
+        vec_reader.read_u16().unwrap();
+        vec_reader.read_u32().unwrap();
+        vec_reader.read_u64().unwrap();
+        let mut rev = reader.spown_reverse_reader_at(2).unwrap();
+        let mut vec_rev = vec_reader.spown_reverse_reader_at(2).unwrap();
+        assert_eq!(rev.read_u8_back().unwrap(), vec_rev.read_u8_back().unwrap());
+        assert_eq!(rev.read_u8_back().unwrap(), 1);
+        assert!(rev.read_u8_back().is_err());
+
+        assert_eq!(reader.remaining(), &[3]);
+        assert_eq!(reader.read_u8().unwrap(), 3);
+        assert_eq!(reader.peek(), Err(NotEnoughData));
+        assert_eq!(reader.read_u16(), Err(NotEnoughData));
+    }
+
+    /// Reads and writes at most one byte per call, and fails every other call with 'Interrupted'.
+    struct Trickle<T> {
+        inner: T,
+        interrupt: bool,
+    }
+
+    impl<T: std::io::Read> std::io::Read for Trickle<T> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.interrupt = !self.interrupt;
+            if self.interrupt {
+                return Err(std::io::ErrorKind::Interrupted.into());
+            }
+            let len = buf.len().min(1);
+            self.inner.read(&mut buf[..len])
+        }
+    }
+
+    impl<T: std::io::Write> std::io::Write for Trickle<T> {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.interrupt = !self.interrupt;
+            if self.interrupt {
+                return Err(std::io::ErrorKind::Interrupted.into());
+            }
+            let len = buf.len().min(1);
+            self.inner.write(&buf[..len])
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            self.inner.flush()
+        }
+    }
+
+    #[test]
+    fn test_io_reader_and_writer_with_short_reads_and_writes() {
+        use crate::prelude::{IoByteReader, IoByteWriter};
+
+        let mut writer = IoByteWriter::new(Trickle { inner: Vec::new(), interrupt: false });
+        writer.write_u8(7);
+        writer.write_u16(0x1234);
+        writer.write_u24(0x56789A);
+        writer.write_u32(0xDEADBEEF);
+        writer.write_u64(u64::MAX - 1);
+        writer.write_u8(1);
+        writer.write_u8(2);
+        let buffer = writer.finish().unwrap().inner;
+        let mut expected = Vec::new();
+        expected.write_u8(7);
+        expected.write_u16(0x1234);
+        expected.write_u24(0x56789A);
+        expected.write_u32(0xDEADBEEF);
+        expected.write_u64(u64::MAX - 1);
+        expected.write_u8(1);
+        expected.write_u8(2);
+        assert_eq!(buffer, expected);
+
+        let mut reader = IoByteReader::new(Trickle { inner: buffer.as_slice(), interrupt: false });
+        assert_eq!(reader.read_u8().unwrap(), 7);
+        assert_eq!(reader.read_u16().unwrap(), 0x1234);
+        assert_eq!(reader.read_u24().unwrap(), 0x56789A);
+        assert_eq!(reader.read_u32().unwrap(), 0xDEADBEEF);
+        assert_eq!(reader.read_u64().unwrap(), u64::MAX - 1);
+        assert!(reader.spown_reverse_reader_at(3).is_err());
+
+        let mut reader = IoByteReader::new(&[1_u8, 2, 3][..]);
+        let mut rev = reader.spown_reverse_reader_at(2).unwrap();
+        assert_eq!(rev.read_u16_back().unwrap(), 0x0201);
+        assert_eq!(reader.read_u8().unwrap(), 3);
+        assert_eq!(reader.read_u16(), Err(NotEnoughData));
+    }
+
+    #[test]
+    fn test_io_writer_keeps_the_first_error() {
+        use crate::prelude::IoByteWriter;
+
+        struct Full;
+        impl std::io::Write for Full {
+            fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+                Ok(0)
+            }
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+        let mut writer = IoByteWriter::new(Full);
+        // more than the buffer holds, so that the bytes reach the inner writer before 'finish'.
+        for i in 0..100_000 {
+            writer.write_u32(i);
+        }
+        let err = writer.finish().err().unwrap();
+        assert_eq!(err.kind(), std::io::ErrorKind::WriteZero);
+    }
+
+    #[test]
+    fn test_encode_into_file() {
+        use crate::prelude::{ConfigType, IoByteWriter};
+
+        let mesh = crate::io::obj::load_obj("tests/data/sphere.obj").unwrap();
+        let mut expected = Vec::new();
+        crate::encode::encode(mesh.clone(), &mut expected, crate::encode::Config::default()).unwrap();
+
+        let path = std::env::temp_dir().join(format!("draco_oxide_encode_into_file_{}.drc", std::process::id()));
+        let mut writer = IoByteWriter::new(std::fs::File::create(&path).unwrap());
+        crate::encode::encode(mesh, &mut writer, crate::encode::Config::default()).unwrap();
+        writer.finish().unwrap();
+        let written = std::fs::read(&path).unwrap();
+
+        #[cfg(not(feature = "evaluation"))]
+        {
+            use crate::prelude::IoByteReader;
+            let mut reader = IoByteReader::new(std::fs::File::open(&path).unwrap());
+            let decoded = crate::decode::decode(&mut reader, crate::decode::Config::default()).unwrap();
+            let from_memory = crate::decode::decode(&mut crate::prelude::SliceReader::new(&expected), crate::decode::Config::default()).unwrap();
+            assert_eq!(decoded.get_faces(), from_memory.get_faces());
+        }
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(written, expected);
+    }
+}
@@ -25,6 +25,11 @@ pub(crate) struct AttributeBuffer {
     num_components: usize,
 }
 
+// Safety: the buffer owns its allocation, which 'last' points into, and it is only written through '&mut self',
+// as in 'Vec<u8>'.
+unsafe impl Send for AttributeBuffer {}
+unsafe impl Sync for AttributeBuffer {}
+
 
 impl AttributeBuffer {
     pub(crate) fn new(component_type: ComponentDataType, num_components: usize) -> Self {
@@ -169,7 +174,10 @@ impl AttributeBuffer {
             self.component_type.size(),
         );
 
-        
+        // the pointer of an empty buffer may not be aligned for 'Data'.
+        if self.len == 0 {
+            return &[];
+        }
         std::slice::from_raw_parts(
             self.as_ptr() as *const Data,
             self.len,
@@ -190,7 +198,10 @@ impl AttributeBuffer {
             self.component_type.size(),
         );
 
-        
+        // the pointer of an empty buffer may not be aligned for 'Data'.
+        if self.len == 0 {
+            return &mut [];
+        }
         std::slice::from_raw_parts_mut(
             self.as_ptr() as *mut Data,
             self.len,
@@ -229,6 +240,9 @@ impl AttributeBuffer {
             N, self.num_components
         );
 
+        if self.len == 0 {
+            return Vec::new();
+        }
         unsafe {
             let slice = self.as_slice::<Data>();
             Vec::from_raw_parts(slice.as_ptr() as *mut Data, self.len, self.len)
@@ -313,6 +327,62 @@ impl AttributeBuffer {
         }
     }
 
+    /// Appends the values of 'other' to the buffer. Both buffers must have the same component type and number of components.
+    pub(crate) fn append(&mut self, other: &AttributeBuffer) {
+        assert_eq!(self.component_type, other.component_type, "Cannot append a buffer of a different component type");
+        assert_eq!(self.num_components, other.num_components, "Cannot append a buffer with a different number of components");
+        let mut data = self.as_slice_u8().to_vec();
+        data.extend_from_slice(other.as_slice_u8());
+        let len = self.len + other.len;
+        let buffer = RawBuffer::from_vec(data);
+        self.last = unsafe {
+            buffer.as_ptr().add(len * self.num_components * self.component_type.size())
+        };
+        self.data = buffer;
+        self.len = len;
+    }
+
+    /// Creates a buffer from the raw little-endian bytes of the values, e.g. of values of more components
+    /// than [Vector] is implemented for.
+    pub(crate) fn from_bytes(bytes: Vec<u8>, component_type: ComponentDataType, num_components: usize) -> Self {
+        let elem_size = num_components * component_type.size();
+        assert_eq!(bytes.len() % elem_size, 0, "The bytes must consist of whole values.");
+        let len = bytes.len() / elem_size;
+        let buffer = RawBuffer::from_vec(bytes);
+        let last = unsafe {
+            buffer.as_ptr().add(len * elem_size)
+        };
+        Self {
+            data: buffer,
+            len,
+            last,
+            component_type,
+            num_components,
+        }
+    }
+
+    /// Returns a buffer of the values at 'indices', in that order. An index may appear more than once.
+    pub(crate) fn select(&self, indices: &[usize]) -> Self {
+        let elem_size = self.num_components * self.component_type.size();
+        let bytes = self.as_slice_u8();
+        let mut data = Vec::with_capacity(indices.len() * elem_size);
+        for &i in indices {
+            data.extend_from_slice(&bytes[i * elem_size..(i + 1) * elem_size]);
+        }
+        let len = indices.len();
+        let buffer = RawBuffer::from_vec(data);
+        let last = unsafe {
+            buffer.as_ptr().add(len * elem_size)
+        };
+        Self {
+            data: buffer,
+            len,
+            last,
+            component_type: self.component_type,
+            num_components: self.num_components,
+        }
+    }
+
     pub fn remove<Data, const N: usize>(&mut self, i: usize) {
         assert!(i < self.len, "Index out of bounds: The index {} is out of bounds for the attribute buffer with length {}", i, self.len);
         let elem_size = self.num_components * self.component_type.size();
@@ -458,6 +528,15 @@ impl std::fmt::Debug for AttributeBuffer {
                     _ => panic!("Unsupported number of components: {}", self.num_components),
                 }
             },
+            ComponentDataType::F16 => {
+                match self.num_components {
+                    1 => format!("{:?}", unsafe{ self.as_slice::<[half::f16;1]>() }),
+                    2 => format!("{:?}", unsafe{ self.as_slice::<[half::f16;2]>() }),
+                    3 => format!("{:?}", unsafe{ self.as_slice::<[half::f16;3]>() }),
+                    4 => format!("{:?}", unsafe{ self.as_slice::<[half::f16;4]>() }),
+                    _ => panic!("Unsupported number of components: {}", self.num_components),
+                }
+            },
             ComponentDataType::F32 => {
                 match self.num_components {
                     1 => format!("{:?}", unsafe{ self.as_slice::<[f32;1]>() }),
@@ -1,5 +1,8 @@
 pub(crate) mod attribute_corner_table;
 pub(crate) mod all_inclusive_corner_table;
+pub(crate) mod point_cloud_corner_table;
+#[cfg(any(test, feature = "validation"))]
+pub(crate) mod validation;
 
 use std::{collections::BTreeMap};
 
@@ -51,6 +54,13 @@ pub(crate) trait GenericCornerTable {
     }
 }
 
+// Counts the number of corner tables built on the current thread.
+// Used by the tests to make sure that the position corner table is built once and shared among attributes.
+#[cfg(test)]
+thread_local! {
+    pub(crate) static NUM_CORNER_TABLE_BUILDS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct CornerTable<'mesh> {
     /// Records the opposite corner for each corner.
@@ -71,6 +81,7 @@ pub(crate) struct CornerTable<'mesh> {
     num_vertices: usize,
 
     /// Stores the left most corner for each vertex.
+    /// The vertices that no face refers to are isolated, and their value is 'CornerIdx::from(usize::MAX)'.
     left_most_corners: VecVertexIdx<CornerIdx>,
 
     /// corner to vertex map.
@@ -78,10 +89,16 @@ pub(crate) struct CornerTable<'mesh> {
 
     /// Stores the parents of non-manifold vertices.
     non_manifold_vertex_parents: Vec<VertexIdx>,
+
+    /// The valence of each vertex, computed once when the table is built. Isolated vertices have valence 0.
+    valences: VecVertexIdx<usize>,
 }
 
 impl<'mesh> CornerTable<'mesh> {
     pub(crate) fn new(mesh_faces: &'mesh [[PointIdx;3]], pos_att: &Attribute) -> Self {
+        #[cfg(test)]
+        NUM_CORNER_TABLE_BUILDS.with(|n| n.set(n.get() + 1));
+
         let conn_faces = mesh_faces.iter()
             .map(|f| 
                 [
@@ -100,19 +117,18 @@ impl<'mesh> CornerTable<'mesh> {
             left_most_corners: VecVertexIdx::new(), // will be computed later
             corner_to_vertex: BTreeMap::new(), // will be computed later
             non_manifold_vertex_parents: Vec::new(), // will be computed later
+            valences: VecVertexIdx::new(), // will be computed later
         };
 
-        let unused_vertices = Self::get_unused_vertices(&out.conn_faces);
-        if !unused_vertices.is_empty() {
-            panic!("Mesh contains unused vertices: {:?}. This is not supported by the corner table.", unused_vertices);
-        }
-
-
         out.compute_table();
         if Self::contains_non_manifold_edges(&out.conn_faces) {
             out.handle_no_manifold_edges();
         }
         out.compute_left_most_corners();
+        out.valences = (0..out.num_vertices())
+            .map(|v| out.vertex_valence(VertexIdx::from(v)))
+            .collect::<Vec<_>>()
+            .into();
 
         out
     }
@@ -233,22 +249,6 @@ impl<'mesh> CornerTable<'mesh> {
         }
     }
 
-    fn get_unused_vertices(faces: &[[VertexIdx;3]]) -> Vec<usize> {
-        let num_vertices = {
-            let num_vertices_minus_1 = *faces.iter().flatten().max().unwrap_or(&VertexIdx::from(0));
-            usize::from(num_vertices_minus_1) + 1
-        };
-        let mut used_vertices = vec![false; num_vertices];
-        for f in faces {
-            for &v in f {
-                used_vertices[usize::from(v)] = true;
-            }
-        }
-        used_vertices.iter().enumerate()
-            .filter_map(|(idx, &used)| if !used { Some(idx) } else { None })
-            .collect()
-    }
-
     fn compute_table(&mut self) {
         let default_opposite = CornerIdx::from(usize::MAX);
         let default_vertex = VertexIdx::from(usize::MAX);
@@ -286,12 +286,10 @@ impl<'mesh> CornerTable<'mesh> {
             let source_v = self.vertex_idx(self.next(c));
             let sink_v = self.vertex_idx(self.previous(c));
 
-            let f_idx = self.face_idx_containing(c);
-            if c == Self::first_corner(f_idx) {
-                let v0 = self.vertex_idx(c);
-                if v0 == source_v || v0 == sink_v || source_v == sink_v {
-                    continue; // skip degenerate corners
-                }
+            // A degenerate face, i.e. one with a repeated vertex, has no opposite corners, and its edges are not
+            // matched with those of the other faces, whichever of its corners comes first.
+            if tip_v == source_v || tip_v == sink_v || source_v == sink_v {
+                continue;
             }
 
             let mut opposite_c = default_opposite;
@@ -413,27 +411,60 @@ impl<'mesh> CornerTable<'mesh> {
                 }
             }
         }
+        // Every vertex referred to by a face has got its corner above, so the remaining ones keep
+        // 'default_corner', which marks them as isolated.
+    }
+
+    /// The vertices whose faces form more than one fan, i.e. the positions split into several vertices
+    /// since they violate the manifold condition. They are sorted and listed once each.
+    pub(crate) fn non_manifold_vertices(&self) -> Vec<VertexIdx> {
+        let mut vertices = self.non_manifold_vertex_parents.clone();
+        vertices.sort();
+        vertices.dedup();
+        vertices
     }
 
+    /// The faces of the position attribute, i.e. the faces on the unique positions before any vertex is split.
+    pub(crate) fn conn_faces(&self) -> &[[VertexIdx; 3]] {
+        &self.conn_faces
+    }
+
+    /// The vertices that no face refers to, i.e. the unused values of the position attribute.
+    /// They have no corner, so they must not be passed to [GenericCornerTable::left_most_corner].
+    pub(crate) fn isolated_vertices(&self) -> Vec<VertexIdx> {
+        (0..self.num_vertices())
+            .map(VertexIdx::from)
+            .filter(|&v| self.left_most_corners[v] == CornerIdx::from(usize::MAX))
+            .collect()
+    }
+
+    /// Returns the valence of the vertex, i.e. the number of the edges incident to it, as cached when the table was built.
     #[inline]
+    pub(crate) fn valence(&self, v: VertexIdx) -> usize {
+        self.valences[v]
+    }
+
+    /// Computes the valence of the vertex by swinging around it. This is linear in the valence,
+    /// so [CornerTable::valence] should be preferred once the table is built.
     pub(crate) fn vertex_valence(&self, v: VertexIdx) -> usize {
-        let c = self.left_most_corner(v);
+        let first = self.left_most_corners[v];
+        if first == CornerIdx::from(usize::MAX) {
+            // isolated vertex
+            return 0;
+        }
+        let mut c = first;
         let mut count = 2;
         while let Some(next_c) = self.swing_right(c) {
-            if next_c == c {
+            if next_c == first {
                 count -= 1;
                 break; // we have reached back to the initial corner
             }
             count += 1;
+            c = next_c;
         }
         count
     }
 
-    #[inline]
-    pub(crate) fn first_corner(face_idx: FaceIdx) -> CornerIdx {
-        CornerIdx::from(usize::from(face_idx) * 3)
-    }
-
     #[inline]
     pub(crate) fn get_mesh_faces(&self) -> &[[PointIdx; 3]] {
         self.mesh_faces
@@ -524,7 +555,9 @@ impl<'mesh> GenericCornerTable for CornerTable<'mesh> {
 
     #[inline]
     fn left_most_corner(&self, vertex: VertexIdx) -> CornerIdx {
-        self.left_most_corners[vertex]
+        let c = self.left_most_corners[vertex];
+        assert!(c != CornerIdx::from(usize::MAX), "The vertex {} is isolated; no face refers to it.", usize::from(vertex));
+        c
     }
 }
 
@@ -578,6 +611,41 @@ mod tests {
         assert_eq!(corner_table.next(CornerIdx::from(2)), CornerIdx::from(0));
     }
 
+    #[test]
+    fn test_degenerate_face() {
+        // the point 4 is on the position of the point 1, so that the first face is of zero area with a repeated vertex.
+        let att = Attribute::new(
+            vec![
+                NdVector::from([0_f32, 0.0]), 
+                NdVector::from([1_f32, 0.0]), 
+                NdVector::from([0_f32, 1.0]), 
+                NdVector::from([1_f32, 1.0]),
+                NdVector::from([1_f32, 0.0]),
+            ],
+            AttributeType::Position,
+            AttributeDomain::Position,
+            vec![],
+        );
+        // whichever corner of the degenerate face comes first, none of its corners has an opposite,
+        // and the edge it shares with the other faces stays between them.
+        for rotation in 0..3 {
+            let mut degenerate = [PointIdx::from(1), PointIdx::from(2), PointIdx::from(4)];
+            degenerate.rotate_left(rotation);
+            let faces = vec![
+                degenerate,
+                [PointIdx::from(0), PointIdx::from(1), PointIdx::from(2)], 
+                [PointIdx::from(2), PointIdx::from(1), PointIdx::from(3)],
+            ];
+            let corner_table = CornerTable::new(&faces, &att);
+            assert_eq!(corner_table.num_faces(), 3);
+            assert_eq!(corner_table.opposite(CornerIdx::from(3)), Some(CornerIdx::from(8)), "rotation {}", rotation);
+            assert_eq!(corner_table.opposite(CornerIdx::from(8)), Some(CornerIdx::from(3)), "rotation {}", rotation);
+            for c in 0..3 {
+                assert_eq!(corner_table.opposite(CornerIdx::from(c)), None, "rotation {}", rotation);
+            }
+        }
+    }
+
     #[test]
     fn test_no_att_seam() {
         let faces = vec![
@@ -670,5 +738,87 @@ mod tests {
         );
     }
 
-    // ToDo: Add tests for non-manifold vertices cases.
-}
\ No newline at end of file
+    #[test]
+    fn test_left_most_corners_of_non_manifold_and_isolated_vertices() {
+        // The vertex 0 has three fans, the edge (7, 8) is shared by three faces, and no face refers to the vertex 12.
+        let faces = [[0, 1, 2], [2, 1, 13], [0, 3, 4], [0, 5, 6], [7, 8, 9], [8, 7, 10], [7, 8, 11]]
+            .map(|f| f.map(PointIdx::from));
+        let att = Attribute::new(
+            (0..14).map(|i| NdVector::from([i as f32, (i * i) as f32])).collect(),
+            AttributeType::Position,
+            AttributeDomain::Position,
+            vec![],
+        );
+
+        let corner_table = CornerTable::new(&faces, &att);
+        assert_eq!(corner_table.isolated_vertices(), vec![VertexIdx::from(12)]);
+        for v in (0..corner_table.num_vertices()).map(VertexIdx::from) {
+            if v == VertexIdx::from(12) {
+                continue;
+            }
+            let c = corner_table.left_most_corner(v);
+            assert!(usize::from(c) < corner_table.num_corners(), "The vertex {} has no corner.", usize::from(v));
+            assert_eq!(corner_table.vertex_idx(c), v);
+        }
+        // the second and the third fans of the vertex 0 are split into new vertices.
+        assert_eq!(corner_table.non_manifold_vertex_parents.iter().filter(|&&p| p == VertexIdx::from(0)).count(), 2);
+
+        let isolated = std::panic::catch_unwind(|| corner_table.left_most_corner(VertexIdx::from(12)));
+        assert!(isolated.is_err());
+    }
+
+    #[test]
+    fn test_cached_valences() {
+        // The center of the disk has six neighbors, and each rim vertex has the center and two rim vertices.
+        let mesh = crate::io::obj::load_obj("tests/data/disk.obj").unwrap();
+        let pos_att = &mesh.get_attributes()[0];
+        let corner_table = CornerTable::new(mesh.get_faces(), pos_att);
+        let mut valences = (0..corner_table.num_vertices())
+            .map(|v| corner_table.valence(VertexIdx::from(v)))
+            .collect::<Vec<_>>();
+        for v in (0..corner_table.num_vertices()).map(VertexIdx::from) {
+            assert_eq!(corner_table.valence(v), corner_table.vertex_valence(v));
+        }
+        valences.sort();
+        assert_eq!(valences, vec![3, 3, 3, 3, 3, 3, 6]);
+
+        // A fan of many faces around a single interior vertex.
+        let n = 1000;
+        let faces = (0..n)
+            .map(|i| [0, 1 + i, 1 + (i + 1) % n].map(PointIdx::from))
+            .collect::<Vec<_>>();
+        let att = Attribute::new(
+            std::iter::once(NdVector::from([0_f32, 0.0]))
+                .chain((0..n).map(|i| {
+                    let t = i as f32 / n as f32 * std::f32::consts::TAU;
+                    NdVector::from([t.cos(), t.sin()])
+                }))
+                .collect(),
+            AttributeType::Position,
+            AttributeDomain::Position,
+            vec![],
+        );
+        let corner_table = CornerTable::new(&faces, &att);
+        for v in (0..corner_table.num_vertices()).map(VertexIdx::from) {
+            assert_eq!(corner_table.valence(v), corner_table.vertex_valence(v));
+        }
+        let center = corner_table.vertex_idx(CornerIdx::from(0));
+        assert_eq!(corner_table.valence(center), n);
+        assert_eq!(corner_table.valence(corner_table.vertex_idx(CornerIdx::from(1))), 3);
+    }
+
+    #[test]
+    fn test_corner_table_built_once_for_multiple_attributes() {
+        use crate::core::shared::ConfigType;
+        // tetrahedron.obj has position, normal, and texture coordinate attributes.
+        let mesh = crate::io::obj::load_obj("tests/data/tetrahedron.obj").unwrap();
+        assert!(mesh.get_attributes().len() > 1);
+
+        NUM_CORNER_TABLE_BUILDS.with(|n| n.set(0));
+        crate::encode::encode(mesh, &mut Vec::new(), crate::encode::Config::default()).unwrap();
+        assert_eq!(
+            NUM_CORNER_TABLE_BUILDS.with(|n| n.get()), 1, 
+            "The position corner table must be built once and shared among all attributes."
+        );
+    }
+}
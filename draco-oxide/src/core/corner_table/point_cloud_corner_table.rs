@@ -0,0 +1,71 @@
+use crate::core::{
+    corner_table::GenericCornerTable,
+    shared::{CornerIdx, FaceIdx, PointIdx, VertexIdx}
+};
+
+
+/// Corner table of a point cloud, i.e. of the points without faces.
+/// Each point is a corner and a vertex of its own with no neighbors, so that the attributes of a point cloud
+/// are encoded by the same prediction schemes as those of a mesh, in the order of the points.
+#[derive(Debug, Clone)]
+pub(crate) struct PointCloudCornerTable {
+    num_points: usize,
+}
+
+impl PointCloudCornerTable {
+    pub fn new(num_points: usize) -> Self {
+        Self { num_points }
+    }
+}
+
+impl GenericCornerTable for PointCloudCornerTable {
+    #[inline]
+    fn face_idx_containing(&self, _corner: CornerIdx) -> FaceIdx {
+        unreachable!("A point cloud has no faces.")
+    }
+
+    #[inline]
+    fn num_faces(&self) -> usize {
+        0
+    }
+
+    #[inline]
+    fn num_corners(&self) -> usize {
+        self.num_points
+    }
+
+    #[inline]
+    fn num_vertices(&self) -> usize {
+        self.num_points
+    }
+
+    #[inline]
+    fn point_idx(&self, corner: CornerIdx) -> PointIdx {
+        PointIdx::from(usize::from(corner))
+    }
+
+    #[inline]
+    fn vertex_idx(&self, corner: CornerIdx) -> VertexIdx {
+        VertexIdx::from(usize::from(corner))
+    }
+
+    #[inline]
+    fn opposite(&self, _corner: CornerIdx) -> Option<CornerIdx> {
+        None
+    }
+
+    #[inline]
+    fn previous(&self, corner: CornerIdx) -> CornerIdx {
+        corner
+    }
+
+    #[inline]
+    fn next(&self, corner: CornerIdx) -> CornerIdx {
+        corner
+    }
+
+    #[inline]
+    fn left_most_corner(&self, vertex: VertexIdx) -> CornerIdx {
+        CornerIdx::from(usize::from(vertex))
+    }
+}
@@ -0,0 +1,180 @@
+use crate::core::mesh::diff::unique_value_as_f64;
+use crate::core::shared::{CornerIdx, PointIdx, VertexIdx};
+use crate::prelude::Attribute;
+use super::{CornerTable, GenericCornerTable};
+
+/// Checks the corner table produced by the connectivity decoder against the table rebuilt from the decoded
+/// faces. The attributes are attached to the corners of the decoded table, so both tables must agree on the
+/// vertices of the corners, including the vertices split at non-manifold points, on the opposite corners, and
+/// on the left-most corners. The opposite corners of the decoded table are also checked to be symmetric and
+/// to share their edges.
+/// The encoder splits the vertices that a face of zero area shares with the other faces, which may leave open
+/// an edge between two other faces that the rebuilt table closes. Such an edge, open from both sides in the
+/// decoded table, is returned as its corners to be checked by [validate_cut_edges] once the positions are decoded,
+/// and the left-most corners of its vertices are only checked to be on the boundary.
+pub(crate) fn validate_corner_table<C>(decoded: &C, faces: &[[PointIdx; 3]], pos_att: &Attribute) -> Result<Vec<CornerIdx>, Err>
+    where C: GenericCornerTable,
+{
+    let rebuilt = CornerTable::new(faces, pos_att);
+    if decoded.num_corners() != rebuilt.num_corners() || decoded.num_vertices() != rebuilt.num_vertices() {
+        return Err(Err::SizeMismatch(format!(
+            "the decoded table has {} corners and {} vertices, but the rebuilt table has {} corners and {} vertices",
+            decoded.num_corners(), decoded.num_vertices(), rebuilt.num_corners(), rebuilt.num_vertices()
+        )));
+    }
+
+    let mut cut_edges = Vec::new();
+    let mut on_cut_edges = vec![false; decoded.num_vertices()];
+    for c in (0..decoded.num_corners()).map(CornerIdx::from) {
+        if decoded.vertex_idx(c) != rebuilt.vertex_idx(c) {
+            return Err(Err::VertexMismatch {
+                corner: usize::from(c),
+                decoded: usize::from(decoded.vertex_idx(c)),
+                rebuilt: usize::from(rebuilt.vertex_idx(c)),
+            });
+        }
+        let is_cut = decoded.opposite(c).is_none() && rebuilt.opposite(c).is_some_and(|o| decoded.opposite(o).is_none());
+        if is_cut {
+            cut_edges.push(c);
+            on_cut_edges[usize::from(decoded.vertex_idx(decoded.next(c)))] = true;
+            on_cut_edges[usize::from(decoded.vertex_idx(decoded.previous(c)))] = true;
+        } else if decoded.opposite(c) != rebuilt.opposite(c) {
+            return Err(Err::OppositeCornerMismatch {
+                corner: usize::from(c),
+                decoded: decoded.opposite(c).map(usize::from),
+                rebuilt: rebuilt.opposite(c).map(usize::from),
+            });
+        }
+        if let Some(o) = decoded.opposite(c) {
+            // the opposite corners face the same edge from both sides.
+            let shares_edge = decoded.vertex_idx(decoded.next(c)) == decoded.vertex_idx(decoded.previous(o))
+                && decoded.vertex_idx(decoded.previous(c)) == decoded.vertex_idx(decoded.next(o));
+            if decoded.opposite(o) != Some(c) || !shares_edge {
+                return Err(Err::BrokenInvariant(format!("the corners {} and {} are not opposite to each other", usize::from(c), usize::from(o))));
+            }
+        }
+    }
+
+    for v in (0..decoded.num_vertices()).map(VertexIdx::from) {
+        let c = decoded.left_most_corner(v);
+        if on_cut_edges[usize::from(v)] {
+            if decoded.vertex_idx(c) != v || decoded.swing_left(c).is_some() {
+                return Err(Err::BrokenInvariant(format!("the left-most corner {} of the vertex {} is not on the boundary", usize::from(c), usize::from(v))));
+            }
+        } else if c != rebuilt.left_most_corner(v) {
+            return Err(Err::LeftMostCornerMismatch {
+                vertex: usize::from(v),
+                decoded: usize::from(decoded.left_most_corner(v)),
+                rebuilt: usize::from(rebuilt.left_most_corner(v)),
+            });
+        }
+    }
+    Ok(cut_edges)
+}
+
+/// Checks that each edge returned by [validate_corner_table] is next to a face of zero area, i.e. that both of
+/// its end points are on such a face of the decoded mesh, so that the decoder does not accept any other edge
+/// left open. 'faces' are those of the decoded mesh, whose corners are those of the decoded table.
+pub(crate) fn validate_cut_edges(cut_edges: &[CornerIdx], faces: &[[PointIdx; 3]], pos_att: &Attribute) -> Result<(), Err> {
+    let position = |p: PointIdx| unique_value_as_f64(pos_att, usize::from(pos_att.get_unique_val_idx(p)));
+    let zero_area_faces = faces.iter()
+        .map(|f| f.map(position))
+        .filter(|[a, b, c]| {
+            let u = b.iter().zip(a).map(|(b, a)| b - a).collect::<Vec<_>>();
+            let v = c.iter().zip(a).map(|(c, a)| c - a).collect::<Vec<_>>();
+            // The area is zero if and only if every component of the wedge product of the edges is zero.
+            (0..u.len()).all(|i| (0..i).all(|j| u[i] * v[j] == u[j] * v[i]))
+        })
+        .collect::<Vec<_>>();
+
+    for &c in cut_edges {
+        let face = faces.get(usize::from(c) / 3)
+            .ok_or_else(|| Err::SizeMismatch(format!("the corner {} of a cut edge is not on any face", usize::from(c))))?;
+        let i = usize::from(c) % 3;
+        let ends = [face[(i + 1) % 3], face[(i + 2) % 3]].map(position);
+        if !zero_area_faces.iter().any(|f| ends.iter().all(|e| f.contains(e))) {
+            return Err(Err::UnexpectedOpenEdge(usize::from(c)));
+        }
+    }
+    Ok(())
+}
+
+#[remain::sorted]
+#[derive(thiserror::Error, Debug)]
+pub enum Err {
+    #[error("Broken invariant of the decoded corner table: {0}")]
+    BrokenInvariant(String),
+    #[error("The left-most corner of the vertex {vertex} is {decoded}, but it is {rebuilt} in the rebuilt table")]
+    LeftMostCornerMismatch { vertex: usize, decoded: usize, rebuilt: usize },
+    #[error("The opposite corner of the corner {corner} is {decoded:?}, but it is {rebuilt:?} in the rebuilt table")]
+    OppositeCornerMismatch { corner: usize, decoded: Option<usize>, rebuilt: Option<usize> },
+    #[error("Size mismatch: {0}")]
+    SizeMismatch(String),
+    #[error("The edge facing the corner {0} is left open, but it is not next to any face of zero area")]
+    UnexpectedOpenEdge(usize),
+    #[error("The vertex of the corner {corner} is {decoded}, but it is {rebuilt} in the rebuilt table")]
+    VertexMismatch { corner: usize, decoded: usize, rebuilt: usize },
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validation_catches_corrupted_connectivity() {
+        // The decoder is not complete yet, so the decoded table is stood in by the table of the original faces.
+        let mesh = crate::io::obj::load_obj("tests/data/torus.obj").unwrap();
+        let pos_att = &mesh.get_attributes()[0];
+        let faces = mesh.get_faces().to_vec();
+        let decoded = CornerTable::new(&faces, pos_att);
+        assert!(validate_corner_table(&decoded, &faces, pos_att).is_ok());
+
+        // A corrupted symbol attaches a face with the wrong orientation. Its edges lose their opposite corners,
+        // which breaks the fans of its vertices, so the rebuilt table splits them.
+        let mut corrupted = faces.clone();
+        corrupted[100].swap(1, 2);
+        let err = validate_corner_table(&decoded, &corrupted, pos_att).unwrap_err();
+        assert!(matches!(err, Err::SizeMismatch(_)), "{}", err);
+
+        // The vertices split at non-manifold points are compared as well, and faces attached in another order are caught.
+        let bowtie = [[0, 1, 2], [0, 3, 4]].map(|f| f.map(PointIdx::from));
+        let bowtie_att = Attribute::from_without_removing_duplicates(
+            crate::core::attribute::AttributeId::new(0),
+            (0..5).map(|i| crate::prelude::NdVector::from([i as f32, 0.0])).collect(),
+            crate::prelude::AttributeType::Position,
+            crate::core::attribute::AttributeDomain::Position,
+            Vec::new(),
+        );
+        let decoded = CornerTable::new(&bowtie, &bowtie_att);
+        assert!(validate_corner_table(&decoded, &bowtie, &bowtie_att).is_ok());
+        let err = validate_corner_table(&decoded, &[bowtie[1], bowtie[0]], &bowtie_att).unwrap_err();
+        assert!(matches!(err, Err::VertexMismatch { .. }), "{}", err);
+    }
+
+    #[test]
+    fn test_cut_edges_next_to_zero_area_faces() {
+        // Two faces on the edge from the point 1 to the point 2, and a face of zero area on the edge whose point 3
+        // is at the position of the point 1.
+        let faces = [[0, 1, 2], [2, 1, 4], [3, 1, 2]].map(|f| f.map(PointIdx::from));
+        let positions = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [1.0, 0.0, 0.0], [1.0, 1.0, 1.0]];
+        let pos_att = Attribute::from_without_removing_duplicates(
+            crate::core::attribute::AttributeId::new(0),
+            positions.into_iter().map(crate::prelude::NdVector::from).collect(),
+            crate::prelude::AttributeType::Position,
+            crate::core::attribute::AttributeDomain::Position,
+            Vec::new(),
+        );
+        // The edge facing the corner 0 is next to the face of zero area.
+        let cut_edges = [CornerIdx::from(0), CornerIdx::from(5)];
+        assert!(validate_cut_edges(&cut_edges, &faces, &pos_att).is_ok());
+
+        // The edge from the point 0 to the point 1 is next to the faces of non-zero area alone.
+        let err = validate_cut_edges(&[CornerIdx::from(2)], &faces, &pos_att).unwrap_err();
+        assert!(matches!(err, Err::UnexpectedOpenEdge(2)), "{}", err);
+
+        // Without the face of zero area, no edge may be left open.
+        let err = validate_cut_edges(&cut_edges, &faces[..2], &pos_att).unwrap_err();
+        assert!(matches!(err, Err::UnexpectedOpenEdge(0)), "{}", err);
+    }
+}
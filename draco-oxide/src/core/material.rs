@@ -3,11 +3,17 @@ use std::collections::HashMap;
 use crate::{core::{texture::{self, TextureLibrary, TextureMap}}, prelude::NdVector};
 
 #[derive(Clone, Debug)]
-pub(crate) struct MaterialLibrary {
+pub struct MaterialLibrary {
     materials: Vec<Material>,
     texture_library: TextureLibrary,
 }
 
+impl Default for MaterialLibrary {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl MaterialLibrary {
     pub fn new() -> Self {
         Self {
@@ -31,11 +37,11 @@ impl MaterialLibrary {
         self.materials.get_mut(index)
     }
 
-    pub fn get_texture_library(&self) -> &TextureLibrary {
+    pub(crate) fn get_texture_library(&self) -> &TextureLibrary {
         &self.texture_library
     }
 
-    pub fn get_texture_library_mut(&mut self) -> &mut TextureLibrary {
+    pub(crate) fn get_texture_library_mut(&mut self) -> &mut TextureLibrary {
         &mut self.texture_library
     }
 
@@ -48,7 +54,7 @@ impl MaterialLibrary {
 
 
 #[derive(Clone, Debug)]
-pub(crate) struct Material {
+pub struct Material {
     name: String,
     color_factor: NdVector<4, f32>,
     metallic_factor: f32,
@@ -100,6 +106,12 @@ pub enum TransparencyMode {
     Blend,
 }
 
+impl Default for Material {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Material {
     pub fn new() -> Self {
         Self {
@@ -139,11 +151,11 @@ impl Material {
         self.unlit
     }
 
-    pub fn get_texture_map_by_index(&self, index: usize) -> Option<&TextureMap> {
+    pub(crate) fn get_texture_map_by_index(&self, index: usize) -> Option<&TextureMap> {
         self.texture_maps.get(index).map(|tm| tm.as_ref())
     }
 
-    pub fn get_texture_map_by_type(&self, texture_type: texture::Type) -> Option<&TextureMap> {
+    pub(crate) fn get_texture_map_by_type(&self, texture_type: texture::Type) -> Option<&TextureMap> {
         self.texture_map_type_to_index_map.get(&texture_type)
             .and_then(|&idx| self.get_texture_map_by_index(idx))
     }
@@ -225,7 +237,7 @@ impl Material {
 
     /// Adds a texture map to the material and returns its index.
     /// If a texture map with the same type already exists, it will be replaced.
-    pub fn set_texture_map(&mut self, texture_type: texture::Type, texture_map: TextureMap) {
+    pub(crate) fn set_texture_map(&mut self, texture_type: texture::Type, texture_map: TextureMap) {
         // Check if we already have a texture map of this type
         if let Some(&existing_index) = self.texture_map_type_to_index_map.get(&texture_type) {
             // Replace existing texture map
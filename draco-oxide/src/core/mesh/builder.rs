@@ -15,6 +15,8 @@ use super::Mesh;
 pub struct MeshBuilder {
     pub attributes: Vec<Attribute>,
     faces: Vec<[usize; 3]>,
+    polygons: Vec<Vec<usize>>,
+    triangulate: bool,
     current_id: usize,
 }
 
@@ -24,6 +26,8 @@ impl MeshBuilder {
             attributes: Vec::new(),
             current_id: 0,
             faces: Vec::new(),
+            polygons: Vec::new(),
+            triangulate: false,
         }
     }
 
@@ -59,10 +63,77 @@ impl MeshBuilder {
         self.faces = data;
     }
 
+    /// Sets the connectivity as a list of polygons, each of which is a list of point indices.
+    /// [Mesh] only stores triangles, so [MeshBuilder::build] fails with [Err::NonTriangularFace]
+    /// if any polygon is not a triangle, unless triangulation is enabled by [MeshBuilder::set_triangulate].
+    pub fn set_polygon_connectivity(&mut self, polygons: Vec<Vec<usize>>) {
+        self.polygons = polygons;
+    }
+
+    /// If set to true, polygons given by [MeshBuilder::set_polygon_connectivity] are fan-triangulated
+    /// when the mesh is built. Defaults to false.
+    pub fn set_triangulate(&mut self, triangulate: bool) {
+        self.triangulate = triangulate;
+    }
+
+    /// Checks the faces against the position attribute without building the mesh, so that malformed input
+    /// is reported instead of failing later in the pipeline. [MeshBuilder::build] silently drops the degenerate
+    /// faces and the unused vertices, whereas this returns the first kind of problem found, with every offending
+    /// index: [Err::FaceIndicesOutOfRange], [Err::DegenerateFaces], and then [Err::UnusedVertices].
+    /// The faces are numbered in the order of [MeshBuilder::set_connectivity_attribute] followed by
+    /// [MeshBuilder::set_polygon_connectivity]. Without faces, the points are those of a point cloud and none is unused.
+    pub fn validate(&self) -> Result<(), Err> {
+        let Some(pos) = self.attributes.iter().find(|att| att.get_attribute_type() == AttributeType::Position) else {
+            return Ok(());
+        };
+        let num_points = pos.len();
+        let faces = self.faces.iter()
+            .map(|f| f.as_slice())
+            .chain(self.polygons.iter().map(|p| p.as_slice()))
+            .collect::<Vec<_>>();
+
+        let out_of_range = faces.iter()
+            .enumerate()
+            .flat_map(|(i, f)| f.iter().filter(|&&p| p >= num_points).map(move |&p| (i, p)))
+            .collect::<Vec<_>>();
+        if !out_of_range.is_empty() {
+            return Err(Err::FaceIndicesOutOfRange(out_of_range, num_points));
+        }
+
+        let degenerate = faces.iter()
+            .enumerate()
+            .filter(|(_, f)| (0..f.len()).any(|j| f[j+1..].contains(&f[j])))
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>();
+        if !degenerate.is_empty() {
+            return Err(Err::DegenerateFaces(degenerate));
+        }
+
+        if faces.is_empty() {
+            return Ok(());
+        }
+        let mut used = vec![false; num_points];
+        for &p in faces.iter().flat_map(|f| f.iter()) {
+            used[p] = true;
+        }
+        let unused = (0..num_points).filter(|&p| !used[p]).collect::<Vec<_>>();
+        if !unused.is_empty() {
+            return Err(Err::UnusedVertices(unused));
+        }
+        Ok(())
+    }
+
     pub fn build(self) -> Result<Mesh, Err> {
         self.dependency_check()?;
 
-        let Self { attributes, faces, .. } = self;
+        let Self { attributes, mut faces, polygons, triangulate, .. } = self;
+
+        for (i, polygon) in polygons.into_iter().enumerate() {
+            if polygon.len() < 3 || (polygon.len() > 3 && !triangulate) {
+                return Err(Err::NonTriangularFace(i, polygon.len()));
+            }
+            faces.extend((1..polygon.len()-1).map(|j| [polygon[0], polygon[j], polygon[j+1]]));
+        }
 
         let attributes = Self::get_sorted_attributes(attributes);
 
@@ -386,15 +457,26 @@ pub enum Err {
     #[error("Failed to deduplicate vertices: {0}")]
     DeduplicationError(String),
 
+    #[error("The faces {0:?} refer to the same point more than once.")]
+    DegenerateFaces(Vec<usize>),
+
     #[error("Duplicate attribute ID: {0:?}")]
     DuplicateAttributeId(AttributeId),
 
+    #[error("The faces refer to points out of the {1} points of the position attribute, as (face, point): {0:?}")]
+    FaceIndicesOutOfRange(Vec<(usize, usize)>, usize),
+
     #[error("One of the attributes does not meet the minimum dependency; {:?} must depend on {:?}.", .0, .1)]
     MinimumDependencyError(AttributeType, AttributeType),
+
+    #[error("The face {0} has {1} vertices, but only triangles are supported; enable triangulation with `MeshBuilder::set_triangulate`.")]
+    NonTriangularFace(usize, usize),
     
     #[error("The connectivity attribute and the position attribute are not compatible; the connectivity attribute has a maximum index of {0} and the position attribute has a length of {1}.")]
     PositionAndConnectivityNotCompatible(usize, usize),
 
+    #[error("The points {0:?} are not used by any face.")]
+    UnusedVertices(Vec<usize>),
 }
 
 #[cfg(test)]
@@ -435,4 +517,59 @@ mod tests {
         assert_eq!(mesh.get_attributes().len(), 1, "Mesh should have 1 attribute");
         assert_eq!(mesh.get_attributes()[0].len(), 4, "Position attribute should have 4 vertices as duplicates are merged");
     }
-}
\ No newline at end of file
+
+    fn quad_builder() -> MeshBuilder {
+        let pos = vec![
+            NdVector::from([0.0f32, 0.0, 0.0]),
+            NdVector::from([1.0f32, 0.0, 0.0]),
+            NdVector::from([1.0f32, 1.0, 0.0]),
+            NdVector::from([0.0f32, 1.0, 0.0]),
+        ];
+        let mut builder = MeshBuilder::new();
+        builder.set_polygon_connectivity(vec![vec![0, 1, 2, 3]]);
+        builder.add_attribute(
+            pos,
+            AttributeType::Position,
+            AttributeDomain::Position,
+            vec![],
+        );
+        builder
+    }
+
+    #[test]
+    fn test_quad_without_triangulation_is_rejected() {
+        let err = quad_builder().build().expect_err("A quad must not be accepted without triangulation");
+        assert!(matches!(err, Err::NonTriangularFace(0, 4)));
+        assert!(err.to_string().contains("set_triangulate"));
+    }
+
+    #[test]
+    fn test_quad_with_triangulation() {
+        let mut builder = quad_builder();
+        builder.set_triangulate(true);
+        let mesh = builder.build().expect("Failed to build mesh");
+        assert_eq!(mesh.get_faces().len(), 2, "The quad should be split into 2 triangles");
+        mesh.ensure_triangulated().unwrap();
+    }
+
+    #[test]
+    fn test_validate() {
+        quad_builder().validate().unwrap();
+
+        // the face index out of the four positions is reported with its face.
+        let mut builder = quad_builder();
+        builder.set_connectivity_attribute(vec![[0, 1, 2], [0, 2, 4]]);
+        builder.set_polygon_connectivity(Vec::new());
+        assert!(matches!(builder.validate(), Err(Err::FaceIndicesOutOfRange(ref idx, 4)) if idx == &vec![(1, 4)]));
+
+        // the point 3 is used by no face.
+        let mut builder = quad_builder();
+        builder.set_connectivity_attribute(vec![[0, 1, 2]]);
+        builder.set_polygon_connectivity(Vec::new());
+        assert!(matches!(builder.validate(), Err(Err::UnusedVertices(ref idx)) if idx == &vec![3]));
+
+        let mut builder = quad_builder();
+        builder.set_connectivity_attribute(vec![[0, 1, 1]]);
+        assert!(matches!(builder.validate(), Err(Err::DegenerateFaces(ref idx)) if idx == &vec![0]));
+    }
+}
@@ -0,0 +1,371 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::core::attribute::{Attribute, AttributeId, AttributeType, ComponentDataType};
+use crate::core::shared::PointIdx;
+use super::Mesh;
+
+/// How the error between two attribute values is measured.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiffMetric {
+    /// Euclidean distance between the values.
+    Distance,
+    /// Angle between the values in radians. Used for normals.
+    Angle,
+}
+
+/// Error between an attribute of a mesh and the matching attribute of another mesh.
+#[derive(Clone, Debug)]
+pub struct AttributeDiff {
+    pub id: AttributeId,
+    pub attribute_type: AttributeType,
+    pub metric: DiffMetric,
+    pub max_error: f64,
+    pub mean_error: f64,
+}
+
+/// Per-attribute error report returned by [Mesh::assert_close].
+#[derive(Clone, Debug)]
+pub struct DiffReport {
+    pub tolerance: f64,
+    pub attributes: Vec<AttributeDiff>,
+}
+
+impl DiffReport {
+    /// Returns true if the maximum error of every attribute is within the tolerance.
+    pub fn is_within_tolerance(&self) -> bool {
+        self.attributes.iter().all(|d| d.max_error <= self.tolerance)
+    }
+}
+
+impl fmt::Display for DiffReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "tolerance: {}", self.tolerance)?;
+        for d in &self.attributes {
+            writeln!(
+                f,
+                "{:?} (id {}): max {:?} error {}, mean {}{}",
+                d.attribute_type,
+                d.id.as_usize(),
+                d.metric,
+                d.max_error,
+                d.mean_error,
+                if d.max_error > self.tolerance { " <- exceeds the tolerance" } else { "" }
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[remain::sorted]
+#[derive(thiserror::Error, Debug, Clone)]
+pub enum Err {
+    #[error("The meshes have different attributes: {0}")]
+    AttributeMismatch(String),
+
+    #[error("The meshes are not close:\n{0}")]
+    NotClose(DiffReport),
+}
+
+impl Mesh {
+    /// Compares the attributes of the mesh with the matching attributes of `other`, e.g. the mesh
+    /// before encoding and the decoded one. The points of the two meshes are matched by their
+    /// closest positions, found through a grid of the positions of `other`, as the encoder may reorder
+    /// them. Normals are compared by angle (in radians) and the other attributes by distance. Returns the per-attribute report, or [Err::NotClose] with
+    /// the same report if any error exceeds `tolerance`.
+    pub fn assert_close(&self, other: &Mesh, tolerance: f64) -> Result<DiffReport, Err> {
+        let pairs = matching_attributes(self, other)?;
+
+        let pos = pairs.iter()
+            .find(|(att, _)| att.get_attribute_type() == AttributeType::Position)
+            .ok_or_else(|| Err::AttributeMismatch("no position attribute".to_string()))?;
+        let other_positions = (0..pos.1.len())
+            .map(|q| value_as_f64(pos.1, PointIdx::from(q)))
+            .collect::<Vec<_>>();
+        // the point of 'other' that is the closest to each point of 'self'.
+        let grid = PointGrid::new(&other_positions);
+        let matches = (0..pos.0.len())
+            .map(|p| {
+                grid.closest(&value_as_f64(pos.0, PointIdx::from(p)))
+                    .map(PointIdx::from)
+                    .ok_or_else(|| Err::AttributeMismatch("the other mesh has no points".to_string()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let attributes = pairs.iter()
+            .map(|(att, other_att)| {
+                let metric = if att.get_attribute_type() == AttributeType::Normal {
+                    DiffMetric::Angle
+                } else {
+                    DiffMetric::Distance
+                };
+                let errors = matches.iter()
+                    .enumerate()
+                    .map(|(p, &q)| {
+                        let a = value_as_f64(att, PointIdx::from(p));
+                        let b = value_as_f64(other_att, q);
+                        match metric {
+                            DiffMetric::Distance => distance(&a, &b),
+                            DiffMetric::Angle => angle(&a, &b),
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                AttributeDiff {
+                    id: att.get_id(),
+                    attribute_type: att.get_attribute_type(),
+                    metric,
+                    max_error: errors.iter().cloned().fold(0.0, f64::max),
+                    mean_error: errors.iter().sum::<f64>() / errors.len().max(1) as f64,
+                }
+            })
+            .collect();
+
+        let report = DiffReport { tolerance, attributes };
+        if report.is_within_tolerance() {
+            Ok(report)
+        } else {
+            Err(Err::NotClose(report))
+        }
+    }
+}
+
+/// The points hashed into the cells of a uniform grid, so that the closest point is searched among the nearby
+/// cells instead of all the points.
+struct PointGrid<'a> {
+    points: &'a [Vec<f64>],
+    min: Vec<f64>,
+    cell_size: f64,
+    /// The range of the cell coordinates along each axis.
+    cell_range: Vec<(i64, i64)>,
+    cells: HashMap<Vec<i64>, Vec<usize>>,
+}
+
+impl<'a> PointGrid<'a> {
+    fn new(points: &'a [Vec<f64>]) -> Self {
+        let dim = points.first().map_or(0, |p| p.len());
+        let min = (0..dim).map(|i| points.iter().map(|p| p[i]).fold(f64::MAX, f64::min)).collect::<Vec<_>>();
+        let extent = (0..dim)
+            .map(|i| points.iter().map(|p| p[i]).fold(f64::MIN, f64::max) - min[i])
+            .fold(0.0, f64::max);
+        // about one point per cell if the points fill the bounding box.
+        let cells_per_axis = (points.len() as f64).powf(1.0 / dim.max(1) as f64).ceil();
+        let cell_size = if extent > 0.0 && extent.is_finite() { extent / cells_per_axis } else { 1.0 };
+        let mut grid = Self { points, min, cell_size, cell_range: vec![(i64::MAX, i64::MIN); dim], cells: HashMap::new() };
+        for (q, p) in points.iter().enumerate() {
+            let cell = grid.cell_of(p);
+            for (range, &c) in grid.cell_range.iter_mut().zip(&cell) {
+                *range = (range.0.min(c), range.1.max(c));
+            }
+            grid.cells.entry(cell).or_default().push(q);
+        }
+        grid
+    }
+
+    fn cell_of(&self, p: &[f64]) -> Vec<i64> {
+        p.iter().zip(&self.min).map(|(x, min)| ((x - min) / self.cell_size).floor() as i64).collect()
+    }
+
+    /// The index of the point closest to 'p', the smallest of the ties, or 'None' if there are no points.
+    /// The cells are visited in rings around the cell of 'p' until no farther ring can hold a closer point,
+    /// or all the points are compared once a ring has more cells than there are occupied, e.g. for 'p' far away.
+    fn closest(&self, p: &[f64]) -> Option<usize> {
+        let center = self.cell_of(p);
+        // the ring beyond which there are no cells.
+        let max_ring = center.iter()
+            .zip(&self.cell_range)
+            .map(|(&c, &(lo, hi))| c.saturating_sub(lo).abs().max(hi.saturating_sub(c).abs()))
+            .max()?;
+        let closer = |d: f64, q: usize, best: Option<(f64, usize)>| {
+            best.is_none_or(|(best_d, best_q)| d.total_cmp(&best_d).then(q.cmp(&best_q)).is_lt())
+        };
+        let mut best = None;
+        for ring in 0..=max_ring {
+            if (2.0 * ring as f64 + 1.0).powi(center.len() as i32) > self.cells.len() as f64 {
+                for (q, other) in self.points.iter().enumerate() {
+                    let d = distance(p, other);
+                    if closer(d, q, best) {
+                        best = Some((d, q));
+                    }
+                }
+                break;
+            }
+            for offset in ring_offsets(center.len(), ring) {
+                let cell = center.iter().zip(&offset).map(|(c, o)| c.saturating_add(*o)).collect::<Vec<_>>();
+                for &q in self.cells.get(&cell).into_iter().flatten() {
+                    let d = distance(p, &self.points[q]);
+                    if closer(d, q, best) {
+                        best = Some((d, q));
+                    }
+                }
+            }
+            // the points of the farther rings are at least 'ring' cells away.
+            if best.is_some_and(|(d, _)| d < ring as f64 * self.cell_size) {
+                break;
+            }
+        }
+        best.map(|(_, q)| q)
+    }
+}
+
+/// The offsets of the cells at the Chebyshev distance 'ring' from a cell of 'dim' coordinates.
+fn ring_offsets(dim: usize, ring: i64) -> Vec<Vec<i64>> {
+    let mut out = vec![Vec::new()];
+    for _ in 0..dim {
+        out = out.into_iter()
+            .flat_map(|o| (-ring..=ring).map(move |c| o.iter().copied().chain([c]).collect::<Vec<_>>()))
+            .collect();
+    }
+    out.retain(|o| o.iter().any(|c| c.abs() == ring));
+    out
+}
+
+/// Pairs the i'th attribute of each type in 'mesh' with the i'th attribute of the same type in 'other'.
+fn matching_attributes<'a>(mesh: &'a Mesh, other: &'a Mesh) -> Result<Vec<(&'a Attribute, &'a Attribute)>, Err> {
+    let mut out = Vec::new();
+    for (i, att) in mesh.get_attributes().iter().enumerate() {
+        let ty = att.get_attribute_type();
+        let nth = mesh.get_attributes()[..i].iter().filter(|a| a.get_attribute_type() == ty).count();
+        let other_att = other.get_attributes().iter()
+            .filter(|a| a.get_attribute_type() == ty)
+            .nth(nth)
+            .ok_or_else(|| Err::AttributeMismatch(format!("the other mesh has no {:?} attribute matching the attribute {}", ty, att.get_id().as_usize())))?;
+        if att.get_num_components() != other_att.get_num_components() {
+            return Err(Err::AttributeMismatch(format!(
+                "{:?} attribute has {} components, but the other has {}",
+                ty, att.get_num_components(), other_att.get_num_components()
+            )));
+        }
+        out.push((att, other_att));
+    }
+    Ok(out)
+}
+
+fn value_as_f64(att: &Attribute, p: PointIdx) -> Vec<f64> {
+    unique_value_as_f64(att, usize::from(att.get_unique_val_idx(p)))
+}
+
+/// Returns the components of the 'idx'th unique value of 'att' as f64.
+pub(crate) fn unique_value_as_f64(att: &Attribute, idx: usize) -> Vec<f64> {
+    let bytes = att.get_as_bytes(idx);
+    let size = att.get_component_type().size();
+    bytes.chunks(size)
+        .map(|b| match att.get_component_type() {
+            ComponentDataType::I8 => b[0] as i8 as f64,
+            ComponentDataType::U8 => b[0] as f64,
+            ComponentDataType::I16 => i16::from_le_bytes([b[0], b[1]]) as f64,
+            ComponentDataType::U16 => u16::from_le_bytes([b[0], b[1]]) as f64,
+            ComponentDataType::I32 => i32::from_le_bytes(b.try_into().unwrap()) as f64,
+            ComponentDataType::U32 => u32::from_le_bytes(b.try_into().unwrap()) as f64,
+            ComponentDataType::I64 => i64::from_le_bytes(b.try_into().unwrap()) as f64,
+            ComponentDataType::U64 => u64::from_le_bytes(b.try_into().unwrap()) as f64,
+            ComponentDataType::F16 => half::f16::from_le_bytes([b[0], b[1]]).to_f64(),
+            ComponentDataType::F32 => f32::from_le_bytes(b.try_into().unwrap()) as f64,
+            ComponentDataType::F64 => f64::from_le_bytes(b.try_into().unwrap()),
+            ComponentDataType::Invalid => panic!("Attribute has an invalid component type"),
+        })
+        .collect()
+}
+
+fn distance(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| (x - y) * (x - y)).sum::<f64>().sqrt()
+}
+
+fn angle(a: &[f64], b: &[f64]) -> f64 {
+    let dot = a.iter().zip(b).map(|(x, y)| x * y).sum::<f64>();
+    let norm = |v: &[f64]| v.iter().map(|x| x * x).sum::<f64>().sqrt();
+    let norms = norm(a) * norm(b);
+    if norms == 0.0 {
+        return if norm(a) == norm(b) { 0.0 } else { std::f64::consts::PI };
+    }
+    (dot / norms).clamp(-1.0, 1.0).acos()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::shared::{NdVector, Vector};
+
+    /// Simulates the lossy round trip of the encoder by snapping every component to a grid of 'bits' bits.
+    fn quantized(mesh: &Mesh, bits: u32) -> Mesh {
+        let mut out = mesh.clone();
+        for att in out.get_attributes_mut() {
+            match (att.get_component_type(), att.get_num_components()) {
+                (ComponentDataType::F32, 2) => snap::<2>(att, bits),
+                (ComponentDataType::F32, 3) => snap::<3>(att, bits),
+                _ => {}
+            }
+        }
+        out
+    }
+
+    fn snap<const N: usize>(att: &mut Attribute, bits: u32)
+        where NdVector<N, f32>: Vector<N, Component = f32>
+    {
+        let values = att.unique_vals_as_slice_mut::<NdVector<N, f32>, N>();
+        let components = || values.iter().flat_map(|v| (0..N).map(|i| *v.get(i)));
+        let min = components().fold(f32::MAX, f32::min);
+        let max = components().fold(f32::MIN, f32::max);
+        let step = (max - min) / ((1 << bits) - 1) as f32;
+        for v in values.iter_mut() {
+            for i in 0..N {
+                *v.get_mut(i) = min + ((*v.get(i) - min) / step).round() * step;
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature = "evaluation"))]
+    fn test_round_trip_within_tolerance() {
+        use crate::core::shared::ConfigType;
+        let mesh = crate::io::obj::load_obj("tests/data/sphere.obj").unwrap();
+        let encoded = crate::encode::encode_to_vec(mesh.clone(), crate::encode::Config::default()).unwrap();
+        let round_tripped = crate::decode::decode(
+            &mut crate::prelude::SliceReader::new(&encoded), crate::decode::Config::default()
+        ).unwrap();
+
+        let report = mesh.assert_close(&round_tripped, 1e-2).unwrap();
+        assert_eq!(report.attributes.len(), mesh.get_attributes().len());
+        let normal = report.attributes.iter()
+            .find(|d| d.attribute_type == AttributeType::Normal)
+            .unwrap();
+        assert_eq!(normal.metric, DiffMetric::Angle);
+        assert!(normal.max_error > 0.0);
+    }
+
+    #[test]
+    fn test_coarse_round_trip_is_reported() {
+        let mesh = crate::io::obj::load_obj("tests/data/sphere.obj").unwrap();
+        // a coarse round trip is reported with the offending attribute.
+        let coarse = quantized(&mesh, 3);
+        match mesh.assert_close(&coarse, 1e-2) {
+            Err(Err::NotClose(report)) => {
+                assert!(!report.is_within_tolerance());
+                assert!(report.to_string().contains("exceeds the tolerance"));
+            },
+            other => panic!("expected the meshes not to be close, but got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_grid_finds_the_closest_point() {
+        // the points of a lattice, each twice so that the ties go to the smaller index, and one far away.
+        let mut points = (0..125)
+            .map(|i| vec![(i % 5) as f64 * 0.3, (i / 5 % 5) as f64 * 0.7, (i / 25) as f64 * 1.1])
+            .collect::<Vec<_>>();
+        points.extend(points.clone());
+        points.push(vec![1e6, 0.0, 0.0]);
+        let grid = PointGrid::new(&points);
+        let brute_force = |p: &[f64]| (0..points.len())
+            .min_by(|&a, &b| distance(p, &points[a]).total_cmp(&distance(p, &points[b])));
+        for i in 0..200 {
+            let t = i as f64;
+            let p = vec![(t * 0.37).sin() * 2.0, (t * 0.11).cos() * 3.0, t * 0.03 - 1.0];
+            assert_eq!(grid.closest(&p), brute_force(&p), "{:?}", p);
+        }
+        for p in [vec![0.6, 1.4, 2.2], vec![-1e9, 5.0, 5.0], vec![2e6, 1.0, 0.0]] {
+            assert_eq!(grid.closest(&p), brute_force(&p), "{:?}", p);
+        }
+        assert_eq!(PointGrid::new(&[]).closest(&[0.0, 0.0, 0.0]), None);
+    }
+}
@@ -0,0 +1,55 @@
+use crate::core::attribute::AttributeType;
+use crate::core::corner_table::{CornerTable, GenericCornerTable};
+use crate::core::shared::{CornerIdx, VertexIdx};
+use super::Mesh;
+
+impl Mesh {
+    /// Iterates over the unique undirected edges of the mesh, as the pairs of the vertices of the corner table.
+    /// Each corner faces one edge of its face; an edge shared by two faces is faced by a pair of opposite
+    /// corners and is yielded only for the smaller one. The mesh without a position attribute has no edges.
+    pub fn iter_edges(&self) -> impl Iterator<Item = (VertexIdx, VertexIdx)> {
+        let Some(pos_att) = self.attributes.iter()
+            .find(|att| att.get_attribute_type() == AttributeType::Position)
+        else {
+            return Vec::new().into_iter();
+        };
+
+        let corner_table = CornerTable::new(&self.faces, pos_att);
+        (0..corner_table.num_corners())
+            .map(CornerIdx::from)
+            .filter(|&c| corner_table.opposite(c).is_none_or(|o| c < o))
+            .map(|c| (corner_table.vertex_idx(corner_table.next(c)), corner_table.vertex_idx(corner_table.previous(c))))
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::core::mesh::mesh_from;
+    use crate::core::shared::NdVector;
+
+    #[test]
+    fn test_iter_edges() {
+        let positions = vec![
+            NdVector::from([0.0_f32, 0.0, 0.0]),
+            NdVector::from([1.0, 0.0, 0.0]),
+            NdVector::from([0.0, 1.0, 0.0]),
+            NdVector::from([1.0, 1.0, 0.0]),
+        ];
+        let triangle = mesh_from(positions.clone(), vec![[0, 1, 2]]);
+        assert_eq!(triangle.iter_edges().count(), 3);
+
+        // the shared edge is yielded once.
+        let quad = mesh_from(positions, vec![[0, 1, 2], [2, 1, 3]]);
+        let mut edges = quad.iter_edges()
+            .map(|(a, b)| {
+                let (a, b) = (usize::from(a), usize::from(b));
+                (a.min(b), a.max(b))
+            })
+            .collect::<Vec<_>>();
+        edges.sort();
+        assert_eq!(edges, vec![(0, 1), (0, 2), (1, 2), (1, 3), (2, 3)]);
+    }
+}
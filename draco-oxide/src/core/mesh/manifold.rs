@@ -0,0 +1,102 @@
+use crate::core::attribute::AttributeType;
+use crate::core::corner_table::CornerTable;
+use crate::core::shared::VertexIdx;
+use super::Mesh;
+
+/// The result of [Mesh::validate_manifold].
+/// The vertices are the unique positions of the mesh, as in the corner table built by the encoder.
+/// The edges are the pairs of their vertices with the smaller one first, sorted and listed once each.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct ManifoldReport {
+    /// The edges shared by more than two faces. The encoder breaks the connectivity at them.
+    pub non_manifold_edges: Vec<(VertexIdx, VertexIdx)>,
+    /// The vertices whose faces form more than one fan. The encoder splits each of them into several vertices.
+    pub non_manifold_vertices: Vec<VertexIdx>,
+    /// The edges of only one face.
+    pub boundary_edges: Vec<(VertexIdx, VertexIdx)>,
+}
+
+impl ManifoldReport {
+    /// Whether the mesh has neither non-manifold edges nor non-manifold vertices, so that the edgebreaker
+    /// encodes it without splitting. Boundary edges are allowed.
+    pub fn is_manifold(&self) -> bool {
+        self.non_manifold_edges.is_empty() && self.non_manifold_vertices.is_empty()
+    }
+
+    /// Whether the mesh is manifold and has no boundary, i.e. it encloses a volume.
+    pub fn is_closed(&self) -> bool {
+        self.is_manifold() && self.boundary_edges.is_empty()
+    }
+}
+
+impl Mesh {
+    /// Checks the manifoldness of the connectivity of the positions, so that the user can tell whether
+    /// the edgebreaker will split the mesh before encoding it.
+    /// The mesh without a position attribute has no edges, and its report is empty.
+    pub fn validate_manifold(&self) -> ManifoldReport {
+        let Some(pos_att) = self.attributes.iter()
+            .find(|att| att.get_attribute_type() == AttributeType::Position)
+        else {
+            return ManifoldReport::default();
+        };
+
+        let corner_table = CornerTable::new(&self.faces, pos_att);
+        let mut edges = corner_table.conn_faces().iter()
+            .flat_map(|&[v0, v1, v2]| [(v0, v1), (v1, v2), (v2, v0)])
+            .map(|(a, b)| (a.min(b), a.max(b)))
+            .collect::<Vec<_>>();
+        edges.sort();
+
+        let mut report = ManifoldReport {
+            non_manifold_vertices: corner_table.non_manifold_vertices(),
+            ..ManifoldReport::default()
+        };
+        for run in edges.chunk_by(|a, b| a == b) {
+            match run.len() {
+                1 => report.boundary_edges.push(run[0]),
+                2 => {},
+                _ => report.non_manifold_edges.push(run[0]),
+            }
+        }
+        report
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::core::mesh::mesh_from;
+    use crate::core::shared::{NdVector, VertexIdx};
+
+    /// The positions of the points, which are distinct.
+    fn positions(num_points: usize) -> Vec<NdVector<3, f32>> {
+        (0..num_points)
+            .map(|i| NdVector::from([i as f32, (i * i) as f32, 0.0]))
+            .collect()
+    }
+
+    fn edges(pairs: &[(usize, usize)]) -> Vec<(VertexIdx, VertexIdx)> {
+        pairs.iter().map(|&(a, b)| (VertexIdx::from(a), VertexIdx::from(b))).collect()
+    }
+
+    #[test]
+    fn test_validate_manifold() {
+        // the faces of 'test_non_manifold_with_seam' in the corner table, where the edge (1, 2) has three faces.
+        let mesh = mesh_from(positions(5), vec![[0, 1, 2], [1, 3, 2], [2, 1, 4]]);
+        let report = mesh.validate_manifold();
+        assert!(!report.is_manifold());
+        assert_eq!(report.non_manifold_edges, edges(&[(1, 2)]));
+        assert_eq!(report.boundary_edges, edges(&[(0, 1), (0, 2), (1, 3), (1, 4), (2, 3), (2, 4)]));
+
+        // two triangles touching at the vertex 0.
+        let report = mesh_from(positions(5), vec![[0, 1, 2], [0, 3, 4]]).validate_manifold();
+        assert!(report.non_manifold_edges.is_empty());
+        assert_eq!(report.non_manifold_vertices, vec![VertexIdx::from(0)]);
+        assert!(!report.is_manifold());
+
+        // a tetrahedron is closed.
+        let report = mesh_from(positions(4), vec![[0, 1, 2], [0, 3, 1], [1, 3, 2], [2, 3, 0]]).validate_manifold();
+        assert!(report.is_closed());
+        assert_eq!(report, Default::default());
+    }
+}
@@ -0,0 +1,108 @@
+use crate::core::shared::PointIdx;
+use super::{Err, Mesh};
+
+impl Mesh {
+    /// Appends the faces and the points of `other` to the mesh, e.g. to encode many small meshes as one.
+    /// The points of `other` come after those of the mesh, and the faces of `other` are offset accordingly.
+    ///
+    /// The attributes are paired by their types in their orders, i.e. the `k`th normal of the mesh with the `k`th
+    /// normal of `other`, and the paired attributes must have the same domain, component type and number of
+    /// components. Otherwise [Err::AttributeMismatch] is returned and the mesh is left as it is. The values shared
+    /// by the two meshes are stored once. The attributes and the material library of the mesh are kept; an empty
+    /// mesh takes everything from `other`.
+    pub fn merge(&mut self, other: &Mesh) -> Result<(), Err> {
+        if self.attributes.is_empty() && self.faces.is_empty() {
+            *self = other.clone();
+            return Ok(());
+        }
+        if self.attributes.len() != other.attributes.len() {
+            return Err(Err::AttributeMismatch(format!(
+                "the meshes have {} and {} attributes", self.attributes.len(), other.attributes.len()
+            )));
+        }
+
+        // the attribute of 'other' paired with each attribute of the mesh.
+        let mut pairs = Vec::with_capacity(self.attributes.len());
+        for (i, att) in self.attributes.iter().enumerate() {
+            let ty = att.get_attribute_type();
+            let k = self.attributes[..i].iter().filter(|a| a.get_attribute_type() == ty).count();
+            let other_att = other.attributes.iter()
+                .filter(|a| a.get_attribute_type() == ty)
+                .nth(k)
+                .ok_or_else(|| Err::AttributeMismatch(format!("the other mesh has no attribute of {:?} at {}", ty, k)))?;
+            if other_att.get_domain() != att.get_domain()
+                || other_att.get_component_type() != att.get_component_type()
+                || other_att.get_num_components() != att.get_num_components()
+            {
+                return Err(Err::AttributeMismatch(format!(
+                    "the attributes of {:?} are of {:?} with {} components of {:?} and of {:?} with {} components of {:?}",
+                    ty,
+                    att.get_domain(), att.get_num_components(), att.get_component_type(),
+                    other_att.get_domain(), other_att.get_num_components(), other_att.get_component_type(),
+                )));
+            }
+            pairs.push(other_att);
+        }
+
+        let offset = self.attributes.first().map_or(0, |att| att.len());
+        for (att, other_att) in self.attributes.iter_mut().zip(pairs) {
+            att.concat(other_att.clone());
+            att.merge_duplicate_values();
+        }
+        self.faces.extend(other.faces.iter().map(|face| face.map(|p| PointIdx::from(usize::from(p) + offset))));
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::core::attribute::{Attribute, AttributeDomain, AttributeId};
+    use crate::core::mesh::{Err, Mesh};
+    use crate::core::shared::{ConfigType, NdVector, PointIdx};
+    use crate::prelude::AttributeType;
+
+    fn triangle(positions: [[f32; 3]; 3]) -> Mesh {
+        let mut mesh = Mesh::new();
+        let positions = positions.into_iter().map(NdVector::from).collect::<Vec<_>>();
+        mesh.attributes.push(Attribute::from(AttributeId::new(0), positions, AttributeType::Position, AttributeDomain::Position, Vec::new()));
+        mesh.faces = vec![[0, 1, 2].map(PointIdx::from)];
+        mesh
+    }
+
+    #[test]
+    fn test_merge() {
+        // two triangles sharing the edge from (1, 0, 0) to (0, 1, 0).
+        let mut mesh = triangle([[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]]);
+        let other = triangle([[1.0, 0.0, 0.0], [1.0, 1.0, 0.0], [0.0, 1.0, 0.0]]);
+        mesh.merge(&other).unwrap();
+
+        assert_eq!(mesh.get_faces().len(), 2);
+        assert_eq!(mesh.get_faces()[1], [3, 4, 5].map(PointIdx::from));
+        let pos = &mesh.get_attributes()[0];
+        assert_eq!(pos.len(), 6);
+        assert_eq!(pos.num_unique_values(), 4);
+        assert_eq!(pos.get::<NdVector<3, f32>, 3>(PointIdx::from(3)), NdVector::from([1.0, 0.0, 0.0]));
+        assert_eq!(pos.get::<NdVector<3, f32>, 3>(PointIdx::from(4)), NdVector::from([1.0, 1.0, 0.0]));
+
+        let mut buffer = Vec::new();
+        crate::encode::encode(mesh, &mut buffer, crate::encode::Config::default()).unwrap();
+        assert!(!buffer.is_empty());
+    }
+
+    #[test]
+    fn test_merge_mismatched_attributes() {
+        let mut mesh = triangle([[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]]);
+        let mut other = mesh.clone();
+        let normals = vec![NdVector::from([0.0f32, 0.0, 1.0]); 3];
+        other.attributes.push(Attribute::from(AttributeId::new(1), normals, AttributeType::Normal, AttributeDomain::Corner, Vec::new()));
+        assert!(matches!(mesh.merge(&other), Err(Err::AttributeMismatch(_))));
+        // the mesh is left as it is.
+        assert_eq!(mesh.get_faces().len(), 1);
+
+        // the positions of f64 cannot be appended to those of f32.
+        let positions = vec![NdVector::from([0.0f64, 0.0, 0.0]), NdVector::from([1.0, 0.0, 0.0]), NdVector::from([0.0, 1.0, 0.0])];
+        other.attributes = vec![Attribute::from(AttributeId::new(0), positions, AttributeType::Position, AttributeDomain::Position, Vec::new())];
+        assert!(matches!(mesh.merge(&other), Err(Err::AttributeMismatch(_))));
+    }
+}
@@ -17,4 +17,8 @@ impl Metadata {
     pub fn get_entry(&self, key: &str) -> Option<&String> {
         self.entries.get(key)
     }
+
+    pub fn entries(&self) -> impl Iterator<Item = (&String, &String)> {
+        self.entries.iter()
+    }
 }
\ No newline at end of file
@@ -1,14 +1,27 @@
 pub mod builder;
+pub mod diff;
+pub mod edges;
+pub mod manifold;
+pub mod merge;
 pub mod metadata;
 pub mod meh_features;
+pub mod remap;
+pub mod size_estimate;
+pub mod smooth;
+pub mod topology;
+pub mod transform;
+pub mod vertex_cache;
+pub mod weld;
 
-use super::{attribute::{AttributeType, ComponentDataType, Attribute}, shared::{Float, Vector}};
-use crate::core::{material::MaterialLibrary, shared::{NdVector, PointIdx}};
+use super::{attribute::{AttributeId, AttributeType, ComponentDataType, Attribute}, shared::{Float, Vector}};
+use crate::core::{material::{Material, MaterialLibrary}, shared::{NdVector, PointIdx}};
 use crate::utils::geom::point_to_face_distance_3d;
 
 /// Represents a 3D mesh.
 /// It consists of a list of faces, where each face is defined by three vertex indices, 
 /// and a list of attributes ([Attribute]) that can be associated with the mesh.
+/// Faces must be triangles; polygons have to be triangulated before they are stored
+/// (see [builder::MeshBuilder::set_triangulate]), and [Mesh::ensure_triangulated] checks this.
 #[derive(Clone, Debug)]
 pub struct Mesh {
     pub(crate) faces: Vec<[PointIdx; 3]>,
@@ -35,6 +48,16 @@ impl Mesh {
         &mut self.attributes
     }
 
+    /// Returns the attribute with the given id, i.e. the identity used by the parent links of the attributes.
+    pub fn attribute(&self, id: AttributeId) -> Option<&Attribute> {
+        self.attributes.iter().find(|att| att.get_id() == id)
+    }
+
+    /// Returns the attribute with the given id mutably. See [Mesh::attribute].
+    pub fn attribute_mut(&mut self, id: AttributeId) -> Option<&mut Attribute> {
+        self.attributes.iter_mut().find(|att| att.get_id() == id)
+    }
+
     pub fn get_attributes_mut_by_indices<'a>(&'a mut self, indices: &[usize]) -> Vec<&'a mut Attribute> {
         let out = indices.iter()
             .map(|i| &mut self.attributes[*i] as *mut Attribute)
@@ -66,11 +89,73 @@ impl Mesh {
         }
     }
 
-    #[allow(unused)]
-    pub(crate) fn get_material_library(&self) -> &MaterialLibrary {
+    /// Sets the faces of the mesh, so that a mesh can be built in a chain of calls, e.g. for tests and examples.
+    /// Each face must refer to three distinct points that exist in the attributes added so far, if any.
+    /// [builder::MeshBuilder] is the one to use for polygons and attributes built piece by piece.
+    ///
+    /// ```
+    /// use draco_oxide::prelude::*;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let positions = vec![
+    ///     NdVector::from([0.0_f32, 0.0, 0.0]),
+    ///     NdVector::from([1.0, 0.0, 0.0]),
+    ///     NdVector::from([0.0, 1.0, 0.0]),
+    /// ];
+    /// let mesh = Mesh::new()
+    ///     .with_faces(vec![[0, 1, 2]])?
+    ///     .with_attribute(Attribute::new(positions, AttributeType::Position, AttributeDomain::Position, Vec::new()))?;
+    ///
+    /// let mut buffer = Vec::new();
+    /// encode(mesh, &mut buffer, encode::Config::default())?;
+    /// assert!(!buffer.is_empty());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_faces(mut self, faces: Vec<[usize; 3]>) -> Result<Self, Err> {
+        self.faces = faces.into_iter().map(|f| f.map(PointIdx::from)).collect();
+        self.ensure_triangulated_with(self.attributes.first().map(|att| att.len()))?;
+        Ok(self)
+    }
+
+    /// Adds the attribute to the mesh, so that a mesh can be built in a chain of calls (see [Mesh::with_faces]).
+    /// The attribute is given the id of its position in the mesh, as [builder::MeshBuilder::add_attribute] does,
+    /// and its parents must be the attributes added before it. It must have as many points as the attributes
+    /// added before it, and the faces must refer to its points.
+    pub fn with_attribute(mut self, mut att: Attribute) -> Result<Self, Err> {
+        if let Some(first) = self.attributes.first() {
+            if first.len() != att.len() {
+                return Err(Err::AttributeMismatch(format!(
+                    "the {:?} attribute has {} points, but the mesh has {}", att.get_attribute_type(), att.len(), first.len()
+                )));
+            }
+        }
+        if let Some(parent) = att.get_parents().iter().find(|&&id| self.attribute(id).is_none()) {
+            return Err(Err::AttributeMismatch(format!("the parent {:?} is not in the mesh", parent)));
+        }
+        self.ensure_triangulated_with(Some(att.len()))?;
+        att.set_id(AttributeId::new(self.attributes.len()));
+        self.attributes.push(att);
+        Ok(self)
+    }
+
+    /// Returns the materials of the mesh.
+    pub fn get_material_library(&self) -> &MaterialLibrary {
         &self.material_library
     }
 
+    /// Replaces the materials of the mesh. The materials are not compressed with Draco, but they are written
+    /// to the glTF output, e.g. by [crate::io::gltf::write_glb].
+    pub fn set_material_library(&mut self, material_library: MaterialLibrary) {
+        self.material_library = material_library;
+    }
+
+    /// Adds a material to the mesh and returns its index in the material library of the mesh.
+    /// The first material is the one bound to the mesh in the glTF output.
+    pub fn add_material(&mut self, material: Material) -> usize {
+        self.material_library.add_material(material)
+    }
+
     pub(crate) fn get_material_library_mut(&mut self) -> &mut MaterialLibrary {
         &mut self.material_library
     }
@@ -90,22 +175,225 @@ impl Mesh {
                 panic!("Position attribute must have 3 components, but the first mesh has {} components", pos_att.get_num_components());
             }
 
-            // Faces are now stored directly in the mesh
-            let faces = &self.faces;
-            let other_faces = &other.faces;
+            // The faces refer to the points, which refer to the unique values of the positions.
+            let faces = faces_on_unique_values(pos_att, &self.faces);
+            let other_faces = faces_on_unique_values(other_pos_att, &other.faces);
 
             num_points += pos_att.len();
             num_points += other_pos_att.len();
             sum_of_squared_dist += sum_of_squared_dist_unpack_datatype(
                 pos_att, 
-                faces,
+                &faces,
                 other_pos_att,
-                other_faces
+                &other_faces
             );
         }
 
         sum_of_squared_dist.sqrt()/ num_points as f64
     }
+
+    /// Returns the smallest unsigned component type that can hold every face index of the mesh,
+    /// i.e. one of [ComponentDataType::U8], [ComponentDataType::U16], or [ComponentDataType::U32].
+    /// This is the index type that glTF expects when writing the index accessor.
+    pub fn min_index_type(&self) -> ComponentDataType {
+        let max_idx = self.faces.iter()
+            .flatten()
+            .map(|&p| usize::from(p))
+            .max()
+            .unwrap_or(0);
+        if max_idx <= u8::MAX as usize {
+            ComponentDataType::U8
+        } else if max_idx <= u16::MAX as usize {
+            ComponentDataType::U16
+        } else {
+            ComponentDataType::U32
+        }
+    }
+
+    /// Returns the face indices converted to the integer type `T`.
+    /// Fails with [Err::IndexOutOfRange] if any index does not fit in `T`.
+    pub fn indices_as<T>(&self) -> Result<Vec<[T; 3]>, Err> 
+        where T: TryFrom<usize>
+    {
+        self.faces.iter()
+            .map(|face| {
+                let [a, b, c] = face.map(|p| T::try_from(usize::from(p)).map_err(|_| Err::IndexOutOfRange(usize::from(p), std::any::type_name::<T>())));
+                Ok([a?, b?, c?])
+            })
+            .collect()
+    }
+
+    /// Checks that every face is a proper triangle, i.e. it refers to three distinct points
+    /// that exist in the mesh. A face with a repeated index is typically a polygon that was
+    /// squeezed into the triangle list without being triangulated. The encoder does not require this,
+    /// as it encodes such a face of zero area as it is; it only checks [Mesh::ensure_points_exist].
+    pub fn ensure_triangulated(&self) -> Result<(), Err> {
+        self.ensure_triangulated_with(Some(self.num_points()))
+    }
+
+    /// Same as [Mesh::ensure_triangulated] for the given number of points, where 'None' skips the check of the range.
+    fn ensure_triangulated_with(&self, num_points: Option<usize>) -> Result<(), Err> {
+        for (i, face) in self.faces.iter().enumerate() {
+            if face[0] == face[1] || face[1] == face[2] || face[2] == face[0] {
+                return Err(Err::NotATriangle(i));
+            }
+            if let Some(num_points) = num_points {
+                ensure_face_points_exist(i, face, num_points)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that every face refers to points that exist in the mesh, which is all that the encoder requires
+    /// of the faces.
+    pub(crate) fn ensure_points_exist(&self) -> Result<(), Err> {
+        let num_points = self.num_points();
+        self.faces.iter()
+            .enumerate()
+            .try_for_each(|(i, face)| ensure_face_points_exist(i, face, num_points))
+    }
+
+    /// The number of the points, i.e. of the values of the first attribute.
+    fn num_points(&self) -> usize {
+        self.attributes.first().map(|att| att.len()).unwrap_or(0)
+    }
+
+    /// Returns the index stream of the 'att_idx'th attribute, i.e. the indices of the unique values of
+    /// the attribute referred to by the three corners of each face. On a mesh with seams, e.g. in the
+    /// texture coordinates, the index stream of an attribute differs from that of the positions, which
+    /// is what engines that keep a separate index buffer for each attribute expect.
+    pub fn attribute_index_stream(&self, att_idx: usize) -> Vec<[usize; 3]> {
+        let att = &self.attributes[att_idx];
+        self.faces.iter()
+            .map(|face| face.map(|p| usize::from(att.get_unique_val_idx(p))))
+            .collect()
+    }
+
+    /// Reports, for each attribute, how many values it has and how many of them are unique,
+    /// i.e. how much the attribute would shrink if the duplicate values were removed.
+    /// Values are compared bit-wise. The mesh is not modified.
+    pub fn dedup_report(&self) -> Vec<DedupReport> {
+        self.attributes.iter()
+            .map(|att| {
+                let unique = (0..att.len())
+                    .map(|p| att.get_as_bytes(usize::from(att.get_unique_val_idx(PointIdx::from(p)))))
+                    .collect::<std::collections::HashSet<_>>();
+                DedupReport {
+                    id: att.get_id(),
+                    attribute_type: att.get_attribute_type(),
+                    num_values: att.len(),
+                    num_unique_values: unique.len(),
+                }
+            })
+            .collect()
+    }
+
+    /// Returns a hash of the content of the mesh, i.e. of its faces and of the values of its attributes
+    /// at each point. The attributes are hashed in a canonical order, so the hash does not depend on the
+    /// order in which they were added, nor on their ids. Names and materials are not part of the content.
+    /// The hash is FNV-1a over the canonical bytes, and is therefore stable across builds and platforms.
+    pub fn content_hash(&self) -> u64 {
+        let mut hash = FNV_OFFSET_BASIS;
+        let mut feed = |bytes: &[u8]| {
+            for &b in bytes {
+                hash ^= b as u64;
+                hash = hash.wrapping_mul(FNV_PRIME);
+            }
+        };
+        feed(&(self.faces.len() as u64).to_le_bytes());
+        for p in self.faces.iter().flatten() {
+            feed(&(usize::from(*p) as u64).to_le_bytes());
+        }
+        for att in self.canonical_attribute_bytes() {
+            feed(&(att.len() as u64).to_le_bytes());
+            feed(&att);
+        }
+        hash
+    }
+
+    /// Returns true if the two meshes have the same content in the sense of [Mesh::content_hash],
+    /// i.e. the same faces and the same attribute values at each point, regardless of the order of the attributes.
+    pub fn structurally_eq(&self, other: &Self) -> bool {
+        self.faces == other.faces
+            && self.attributes.len() == other.attributes.len()
+            && self.canonical_attribute_bytes() == other.canonical_attribute_bytes()
+    }
+
+    /// Serializes each attribute into its type, domain, component type, number of components, and the values
+    /// at each point in little endian, and sorts the results so that the order does not depend on the order of the attributes.
+    fn canonical_attribute_bytes(&self) -> Vec<Vec<u8>> {
+        let mut out = self.attributes.iter()
+            .map(|att| {
+                let mut bytes = Vec::new();
+                att.get_attribute_type().write_to(&mut bytes);
+                att.get_domain().write_to(&mut bytes);
+                att.get_component_type().write_to(&mut bytes);
+                bytes.push(att.get_num_components() as u8);
+                // the components are in the native byte order in the buffer, so they are written in little endian.
+                let size = att.get_component_type().size();
+                for p in (0..att.len()).map(PointIdx::from) {
+                    for component in att.get_as_bytes(usize::from(att.get_unique_val_idx(p))).chunks(size) {
+                        if cfg!(target_endian = "big") {
+                            bytes.extend(component.iter().rev());
+                        } else {
+                            bytes.extend_from_slice(component);
+                        }
+                    }
+                }
+                bytes
+            })
+            .collect::<Vec<_>>();
+        out.sort();
+        out
+    }
+}
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// The number of values and unique values of an attribute, returned by [Mesh::dedup_report].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DedupReport {
+    pub id: AttributeId,
+    pub attribute_type: AttributeType,
+    pub num_values: usize,
+    pub num_unique_values: usize,
+}
+
+#[derive(thiserror::Error, Debug, Clone)]
+pub enum Err {
+    #[error("The attributes of the meshes do not match: {0}")]
+    AttributeMismatch(String),
+
+    #[error("Face index {0} does not fit in the index type {1}.")]
+    IndexOutOfRange(usize, &'static str),
+
+    #[error("The points of the values {0:?} of the position attribute are not used by any face.")]
+    IsolatedVertices(Vec<usize>),
+
+    #[error("The mesh has no position attribute.")]
+    NoPositionAttribute,
+
+    #[error("Face {0} is not a triangle; it refers to the same point more than once.")]
+    NotATriangle(usize),
+
+    #[error("Face {0} refers to the point {1}, but the mesh only has {2} points.")]
+    PointIndexOutOfRange(usize, usize, usize),
+}
+
+
+fn ensure_face_points_exist(i: usize, face: &[PointIdx; 3], num_points: usize) -> Result<(), Err> {
+    match face.iter().find(|&&p| usize::from(p) >= num_points) {
+        Some(&p) => Err(Err::PointIndexOutOfRange(i, usize::from(p), num_points)),
+        None => Ok(()),
+    }
+}
+
+/// The faces with their corners mapped from the points to the indices of the unique values of 'att'.
+fn faces_on_unique_values(att: &Attribute, faces: &[[PointIdx;3]]) -> Vec<[PointIdx;3]> {
+    faces.iter()
+        .map(|face| face.map(|p| PointIdx::from(usize::from(att.get_unique_val_idx(p)))))
+        .collect()
 }
 
 
@@ -124,13 +412,18 @@ fn sum_of_squared_dist_unpack_datatype(
                 position_att, 
                 faces,
                 other_position_att,
-                other_faces
+                other_faces,
+                #[cfg(all(feature = "simd", target_arch = "x86_64"))]
+                crate::core::simd::min_dist_points_to_faces_f32,
+                #[cfg(not(all(feature = "simd", target_arch = "x86_64")))]
+                min_dist_points_to_faces,
             ) as f64,
             ComponentDataType::F64 => sum_of_squared_dist_impl::<f64>(
                 position_att, 
                 faces,
                 other_position_att,
-                other_faces
+                other_faces,
+                min_dist_points_to_faces,
             ),
             _ => panic!("Position Attribute is not of type f32 or f64")
         }
@@ -142,7 +435,8 @@ unsafe fn sum_of_squared_dist_impl<F>(
     self_pos_att: &Attribute, 
     self_faces: &[[PointIdx;3]], 
     other_pos_att: &Attribute, 
-    other_faces: &[[PointIdx;3]]
+    other_faces: &[[PointIdx;3]],
+    min_dist_points_to_faces: MinDistPointsToFaces<F>,
 ) -> F
     where
         F: Float,
@@ -160,25 +454,36 @@ unsafe fn sum_of_squared_dist_impl<F>(
     }
 
     // Safety: upheld
-    let self_pos_att = self_pos_att.unique_vals_as_slice_unchecked::<NdVector<3, F>>();
+    let self_pos_att = self_pos_att.unique_vals_as_slice_unchecked::<NdVector<3, F>, 3>();
     // Satety: Just checked
-    let other_pos_att = unsafe{ other_pos_att.unique_vals_as_slice_unchecked::<NdVector<3,F>>() };
+    let other_pos_att = unsafe{ other_pos_att.unique_vals_as_slice_unchecked::<NdVector<3,F>, 3>() };
         
 
     let mut sum_of_squared_dist = F::zero();
-    for pos in self_pos_att.iter() {
-        let min_dist = min_dist_point_to_faces(*pos, other_faces, other_pos_att);
+    for min_dist in min_dist_points_to_faces(self_pos_att, other_faces, other_pos_att) {
         sum_of_squared_dist += min_dist * min_dist;
     }
-    for pos in other_pos_att.iter() {
-        let min_dist = min_dist_point_to_faces(*pos, self_faces, self_pos_att);
+    for min_dist in min_dist_points_to_faces(other_pos_att, self_faces, self_pos_att) {
         sum_of_squared_dist += min_dist * min_dist;
     };
 
     sum_of_squared_dist.sqrt()
 }
 
-fn min_dist_point_to_faces<F>(p: NdVector<3,F>, faces: &[[PointIdx;3]], pos_att: &[NdVector<3,F>]) -> F 
+/// The smallest distance from each point to the faces. When the 'simd' feature is enabled, the 'f32' positions
+/// are measured four at a time with SIMD, and the 'f64' ones one at a time with the SIMD vector ops.
+type MinDistPointsToFaces<F> = fn(&[NdVector<3,F>], &[[PointIdx;3]], &[NdVector<3,F>]) -> Vec<F>;
+
+fn min_dist_points_to_faces<F>(points: &[NdVector<3,F>], faces: &[[PointIdx;3]], pos_att: &[NdVector<3,F>]) -> Vec<F>
+    where 
+        F: Float,
+{
+    points.iter()
+        .map(|&p| min_dist_point_to_faces(p, faces, pos_att))
+        .collect()
+}
+
+pub(crate) fn min_dist_point_to_faces<F>(p: NdVector<3,F>, faces: &[[PointIdx;3]], pos_att: &[NdVector<3,F>]) -> F 
     where 
         F: Float,
 {
@@ -193,4 +498,224 @@ fn min_dist_point_to_faces<F>(p: NdVector<3,F>, faces: &[[PointIdx;3]], pos_att:
         }
     }
     min_dist
-}
\ No newline at end of file
+}
+
+/// A mesh of the given positions and faces, for the tests of the mesh operations.
+#[cfg(test)]
+pub(crate) fn mesh_from(positions: Vec<NdVector<3, f32>>, faces: Vec<[usize; 3]>) -> Mesh {
+    use crate::core::attribute::AttributeDomain;
+    let mut mesh = Mesh::new();
+    mesh.attributes.push(Attribute::from(AttributeId::new(0), positions, AttributeType::Position, AttributeDomain::Position, Vec::new()));
+    mesh.faces = faces.into_iter().map(|f| f.map(PointIdx::from)).collect();
+    mesh
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::attribute::AttributeDomain;
+    use crate::prelude::MeshBuilder;
+
+    fn strip_mesh(num_points: usize) -> Mesh {
+        let faces = (0..num_points-2).map(|i| [i, i+1, i+2]).collect::<Vec<_>>();
+        let pos = (0..num_points)
+            .map(|i| NdVector::from([i as f32, (i%2) as f32, 0.0]))
+            .collect::<Vec<_>>();
+        let mut builder = MeshBuilder::new();
+        builder.set_connectivity_attribute(faces);
+        builder.add_attribute(pos, AttributeType::Position, AttributeDomain::Position, vec![]);
+        builder.build().unwrap()
+    }
+
+    #[test]
+    fn test_diff_l2_norm_beyond_vertices() {
+        let triangle = |pos: [[f32; 3]; 3]| {
+            let mut builder = MeshBuilder::new();
+            builder.set_connectivity_attribute(vec![[0, 1, 2]]);
+            builder.add_attribute(pos.map(NdVector::from).to_vec(), AttributeType::Position, AttributeDomain::Position, vec![]);
+            builder.build().unwrap()
+        };
+        // every vertex is nearest to a vertex of the other triangle, and not to the lines of its edges.
+        let mesh = triangle([[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]]);
+        let other = triangle([[-3.0, -4.0, 0.0], [-4.0, -4.0, 0.0], [-3.0, -5.0, 0.0]]);
+        // the norm of each position attribute is summed up before the square root of the total is taken.
+        let expected = (2.0_f64 * (25.0 + 32.0 + 34.0)).sqrt().sqrt() / 6.0;
+        assert!((mesh.diff_l2_norm(&other) - expected).abs() < 1e-6, "{}", mesh.diff_l2_norm(&other));
+    }
+
+    #[test]
+    fn test_diff_l2_norm_of_shared_positions() {
+        // the corners of the cube are split by the texture coordinates and the normals, so the points of the faces
+        // are not the indices of the positions, whereas the welded cube has a point for each position.
+        let mesh = crate::io::obj::load_obj("tests/data/cube_quads.obj").unwrap();
+        assert!(mesh.get_attributes()[0].num_unique_values() < mesh.get_attributes()[0].len());
+        let mut welded = mesh.clone();
+        welded.weld_by_grid(0.1);
+        assert_eq!(welded.get_attributes()[0].len(), 8);
+        assert_eq!(mesh.diff_l2_norm(&welded), 0.0);
+        assert_eq!(welded.diff_l2_norm(&mesh), 0.0);
+    }
+
+    #[test]
+    fn test_min_index_type_u8() {
+        let mesh = strip_mesh(100);
+        assert_eq!(mesh.min_index_type(), ComponentDataType::U8);
+        let indices = mesh.indices_as::<u8>().unwrap();
+        assert_eq!(indices.len(), mesh.get_faces().len());
+        assert_eq!(indices[0], [0, 1, 2]);
+        assert_eq!(indices[97], [97, 98, 99]);
+    }
+
+    #[test]
+    fn test_min_index_type_u16_and_u32() {
+        let mesh = strip_mesh(1000);
+        assert_eq!(mesh.min_index_type(), ComponentDataType::U16);
+        assert!(mesh.indices_as::<u8>().is_err());
+        let indices = mesh.indices_as::<u16>().unwrap();
+        assert_eq!(indices[997], [997, 998, 999]);
+
+        let mesh = strip_mesh(70_000);
+        assert_eq!(mesh.min_index_type(), ComponentDataType::U32);
+        assert!(mesh.indices_as::<u16>().is_err());
+        let indices = mesh.indices_as::<u32>().unwrap();
+        assert_eq!(indices[69_997], [69_997, 69_998, 69_999]);
+    }
+
+    #[test]
+    fn test_dedup_report() {
+        // a quad given as two triangles with their own copies of the shared positions.
+        let pos = vec![
+            NdVector::from([0.0f32, 0.0, 0.0]),
+            NdVector::from([1.0f32, 0.0, 0.0]),
+            NdVector::from([0.0f32, 1.0, 0.0]),
+            NdVector::from([1.0f32, 0.0, 0.0]),
+            NdVector::from([1.0f32, 1.0, 0.0]),
+            NdVector::from([0.0f32, 1.0, 0.0]),
+        ];
+        let mut mesh = Mesh::new();
+        mesh.faces = vec![[0, 1, 2], [3, 4, 5]].into_iter().map(|f| f.map(PointIdx::from)).collect();
+        mesh.attributes.push(
+            Attribute::from_without_removing_duplicates(AttributeId::new(0), pos, AttributeType::Position, AttributeDomain::Position, Vec::new())
+        );
+
+        let report = mesh.dedup_report();
+        assert_eq!(report, vec![DedupReport {
+            id: AttributeId::new(0),
+            attribute_type: AttributeType::Position,
+            num_values: 6,
+            num_unique_values: 4,
+        }]);
+        // the mesh is not modified.
+        assert_eq!(mesh.get_attributes()[0].num_unique_values(), 6);
+    }
+
+    #[test]
+    fn test_attribute_by_id() {
+        let mut builder = MeshBuilder::new();
+        builder.set_connectivity_attribute(vec![[0, 1, 2]]);
+        let pos = vec![
+            NdVector::from([0.0f32, 0.0, 0.0]),
+            NdVector::from([1.0f32, 0.0, 0.0]),
+            NdVector::from([0.0f32, 1.0, 0.0]),
+        ];
+        let normals = vec![NdVector::from([0.0f32, 0.0, 1.0]); 3];
+        let pos_id = builder.add_attribute(pos, AttributeType::Position, AttributeDomain::Position, vec![]);
+        let normal_id = builder.add_attribute(normals, AttributeType::Normal, AttributeDomain::Position, vec![pos_id]);
+        let mut mesh = builder.build().unwrap();
+
+        let normal = mesh.attribute(normal_id).unwrap();
+        assert_eq!(normal.get_attribute_type(), AttributeType::Normal);
+        assert_eq!(normal.get_parents(), &vec![pos_id]);
+        let parent = mesh.attribute(normal.get_parents()[0]).unwrap();
+        assert_eq!(parent.get_attribute_type(), AttributeType::Position);
+        assert!(mesh.attribute(AttributeId::new(2)).is_none());
+
+        mesh.attribute_mut(normal_id).unwrap().set_name("smooth".to_string());
+        assert_eq!(mesh.attribute(normal_id).unwrap().get_name().map(String::as_str), Some("smooth"));
+    }
+
+    #[test]
+    fn test_content_hash_is_independent_of_attribute_order() {
+        let faces = [[0, 1, 2], [1, 3, 2]];
+        let pos = vec![
+            NdVector::from([0.0f32, 0.0, 0.0]),
+            NdVector::from([1.0f32, 0.0, 0.0]),
+            NdVector::from([0.0f32, 1.0, 0.0]),
+            NdVector::from([1.0f32, 1.0, 0.0]),
+        ];
+        let uv = vec![
+            NdVector::from([0.0f32, 0.0]),
+            NdVector::from([1.0f32, 0.0]),
+            NdVector::from([0.0f32, 1.0]),
+            NdVector::from([1.0f32, 1.0]),
+        ];
+        let mut mesh = Mesh::new();
+        mesh.faces = faces.iter().map(|f| f.map(PointIdx::from)).collect();
+        mesh.attributes.push(Attribute::from(AttributeId::new(0), pos.clone(), AttributeType::Position, AttributeDomain::Position, Vec::new()));
+        mesh.attributes.push(Attribute::from(AttributeId::new(1), uv.clone(), AttributeType::TextureCoordinate, AttributeDomain::Position, Vec::new()));
+
+        let mut swapped = Mesh::new();
+        swapped.faces = mesh.faces.clone();
+        swapped.attributes.push(Attribute::from(AttributeId::new(0), uv.clone(), AttributeType::TextureCoordinate, AttributeDomain::Position, Vec::new()));
+        swapped.attributes.push(Attribute::from(AttributeId::new(1), pos.clone(), AttributeType::Position, AttributeDomain::Position, Vec::new()));
+
+        assert!(mesh.structurally_eq(&swapped));
+        assert_eq!(mesh.content_hash(), swapped.content_hash());
+
+        // changing a single value changes the content.
+        let mut moved = uv.clone();
+        moved[3] = NdVector::from([0.5f32, 1.0]);
+        let mut other = Mesh::new();
+        other.faces = mesh.faces.clone();
+        other.attributes.push(Attribute::from(AttributeId::new(0), pos, AttributeType::Position, AttributeDomain::Position, Vec::new()));
+        other.attributes.push(Attribute::from(AttributeId::new(1), moved, AttributeType::TextureCoordinate, AttributeDomain::Position, Vec::new()));
+        assert!(!mesh.structurally_eq(&other));
+        assert_ne!(mesh.content_hash(), other.content_hash());
+
+        // the hash is that of the little-endian bytes on every platform.
+        assert_eq!(mesh.content_hash(), 0x1653_3c69_c428_db48);
+    }
+
+    #[test]
+    fn test_attribute_index_stream_on_seamed_mesh() {
+        // The texture coordinates of the tetrahedron have seams; 4 positions but 6 texture coordinates.
+        let mesh = crate::io::obj::load_obj("tests/data/tetrahedron.obj").unwrap();
+        let pos_idx = mesh.get_attributes().iter().position(|att| att.get_attribute_type() == AttributeType::Position).unwrap();
+        let uv_idx = mesh.get_attributes().iter().position(|att| att.get_attribute_type() == AttributeType::TextureCoordinate).unwrap();
+
+        let pos_stream = mesh.attribute_index_stream(pos_idx);
+        let uv_stream = mesh.attribute_index_stream(uv_idx);
+        assert_eq!(pos_stream.len(), mesh.get_faces().len());
+        assert_eq!(uv_stream.len(), mesh.get_faces().len());
+        assert_ne!(pos_stream, uv_stream);
+
+        // Each stream resolves to the same values as the points of the faces.
+        let uv_att = &mesh.get_attributes()[uv_idx];
+        for (face, uv_face) in mesh.get_faces().iter().zip(&uv_stream) {
+            for i in 0..3 {
+                let by_point: NdVector<2, f32> = uv_att.get(face[i]);
+                let by_stream: NdVector<2, f32> = uv_att.get_unique_val(uv_face[i].into());
+                assert_eq!(by_point, by_stream);
+            }
+        }
+    }
+
+    #[test]
+    fn test_with_attribute() {
+        let pos = |n: usize| Attribute::new(
+            (0..n).map(|i| NdVector::from([i as f32, (i * i) as f32, 0.0])).collect::<Vec<_>>(),
+            AttributeType::Position, AttributeDomain::Position, Vec::new()
+        );
+        let mesh = Mesh::new().with_faces(vec![[0, 1, 2], [1, 3, 2]]).unwrap()
+            .with_attribute(pos(4)).unwrap()
+            .with_attribute(pos(4)).unwrap();
+        assert_eq!(mesh.get_attributes()[1].get_id(), AttributeId::new(1));
+        assert_eq!(mesh.get_faces().len(), 2);
+
+        // the attributes are checked against the faces and the attributes added before them.
+        assert!(matches!(Mesh::new().with_faces(vec![[0, 1, 1]]), Err(Err::NotATriangle(0))));
+        assert!(matches!(Mesh::new().with_faces(vec![[0, 1, 3]]).unwrap().with_attribute(pos(3)), Err(Err::PointIndexOutOfRange(0, 3, 3))));
+        assert!(matches!(mesh.clone().with_attribute(pos(5)), Err(Err::AttributeMismatch(_))));
+        assert!(matches!(mesh.with_faces(vec![[0, 1, 4]]), Err(Err::PointIndexOutOfRange(0, 4, 4))));
+    }
+}
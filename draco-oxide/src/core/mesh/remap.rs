@@ -0,0 +1,106 @@
+use crate::core::shared::PointIdx;
+use super::Mesh;
+
+impl Mesh {
+    /// Applies the vertex remapping `old_to_new` to the faces and the attributes, where `old_to_new[p]` is the
+    /// new index of the point `p`, or `None` if the point is removed. The new indices must cover `0..n` for
+    /// some `n`, and several points may be mapped to the same index, in which case the new point takes the
+    /// values of all its attributes from the first of them.
+    ///
+    /// Every attribute is remapped, as all the attributes are indexed by the points regardless of their domains.
+    /// The faces referring to a removed point and the faces that become degenerate are dropped, and the
+    /// remaining faces keep their order.
+    pub fn remap_vertices(&mut self, old_to_new: &[Option<usize>]) {
+        for att in &self.attributes {
+            assert_eq!(
+                att.len(), old_to_new.len(),
+                "The remapping must have an entry for each point, but it has {} entries for {} points.",
+                old_to_new.len(), att.len()
+            );
+        }
+
+        let num_new_points = old_to_new.iter().flatten().max().map_or(0, |&n| n + 1);
+        let mut representatives = vec![None; num_new_points];
+        for (p, new_p) in old_to_new.iter().enumerate() {
+            if let Some(new_p) = *new_p {
+                representatives[new_p].get_or_insert(PointIdx::from(p));
+            }
+        }
+        let representatives = representatives.into_iter()
+            .enumerate()
+            .map(|(i, p)| p.unwrap_or_else(|| panic!("No point is mapped to the new point {}.", i)))
+            .collect::<Vec<_>>();
+        self.remap_vertices_with(old_to_new, &representatives);
+    }
+
+    /// Same as [Mesh::remap_vertices], but the new point `i` takes its values from the point `representatives[i]`,
+    /// which must be one of the points mapped to it.
+    pub(super) fn remap_vertices_with(&mut self, old_to_new: &[Option<usize>], representatives: &[PointIdx]) {
+        debug_assert!(representatives.iter().enumerate().all(|(i, &p)| old_to_new[usize::from(p)] == Some(i)));
+        for att in &mut self.attributes {
+            *att = att.select_points(representatives);
+        }
+        self.faces = self.faces.iter()
+            .filter_map(|face| {
+                let [a, b, c] = face.map(|p| old_to_new[usize::from(p)]);
+                Some([a?, b?, c?].map(PointIdx::from))
+            })
+            .filter(|[a, b, c]| a != b && b != c && c != a)
+            .collect();
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::core::attribute::{Attribute, AttributeDomain, AttributeId};
+    use crate::core::mesh::Mesh;
+    use crate::core::shared::{NdVector, PointIdx, Vector};
+    use crate::prelude::AttributeType;
+
+    fn values_of<const N: usize>(mesh: &Mesh, att_idx: usize) -> Vec<NdVector<N, f32>>
+        where NdVector<N, f32>: Vector<N>,
+    {
+        let att = &mesh.get_attributes()[att_idx];
+        (0..att.len()).map(|p| att.get(PointIdx::from(p))).collect()
+    }
+
+    #[test]
+    fn test_remap_vertices() {
+        // a quad of two faces, with texture coordinates sharing the values of the points 0 and 2.
+        let positions = vec![
+            NdVector::from([0.0_f32, 0.0, 0.0]),
+            NdVector::from([1.0, 0.0, 0.0]),
+            NdVector::from([1.0, 1.0, 0.0]),
+            NdVector::from([0.0, 1.0, 0.0]),
+        ];
+        let tex_coords = vec![
+            NdVector::from([0.0_f32, 0.0]),
+            NdVector::from([1.0, 0.0]),
+            NdVector::from([0.0, 0.0]),
+            NdVector::from([0.0, 1.0]),
+        ];
+        let mut mesh = Mesh::new();
+        mesh.attributes.push(Attribute::from(AttributeId::new(0), positions.clone(), AttributeType::Position, AttributeDomain::Position, Vec::new()));
+        mesh.attributes.push(Attribute::from(AttributeId::new(1), tex_coords.clone(), AttributeType::TextureCoordinate, AttributeDomain::Corner, Vec::new()));
+        mesh.faces = vec![[0, 1, 2], [0, 2, 3]].into_iter().map(|f| f.map(PointIdx::from)).collect();
+        assert_eq!(mesh.get_attributes()[1].num_unique_values(), 3);
+
+        // removing the point 1 drops the first face, and the points after it move down.
+        let mut removed = mesh.clone();
+        removed.remap_vertices(&[Some(0), None, Some(1), Some(2)]);
+        assert_eq!(removed.get_faces(), &[[0, 1, 2].map(PointIdx::from)]);
+        assert_eq!(values_of::<3>(&removed, 0), vec![positions[0], positions[2], positions[3]]);
+        assert_eq!(values_of::<2>(&removed, 1), vec![tex_coords[0], tex_coords[2], tex_coords[3]]);
+        // the faces refer to the same values as before.
+        let face = removed.get_faces()[0];
+        assert_eq!(face.map(|p| removed.get_attributes()[0].get::<NdVector<3, f32>, 3>(p)), [positions[0], positions[2], positions[3]]);
+
+        // merging the point 3 into the point 0 makes the second face degenerate.
+        let mut merged = mesh.clone();
+        merged.remap_vertices(&[Some(0), Some(1), Some(2), Some(0)]);
+        assert_eq!(merged.get_faces(), &[[0, 1, 2].map(PointIdx::from)]);
+        assert_eq!(values_of::<3>(&merged, 0), positions[..3].to_vec());
+        assert_eq!(values_of::<2>(&merged, 1), tex_coords[..3].to_vec());
+    }
+}
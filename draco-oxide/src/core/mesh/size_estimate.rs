@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+
+use crate::core::attribute::Attribute;
+use crate::core::shared::PointIdx;
+use crate::encode::attribute::{validate_attribute_quantization, Selection};
+use crate::encode::attribute::portabilization::PortabilizationType;
+use crate::shared::attribute::prediction_scheme::PredictionSchemeType;
+use super::diff::unique_value_as_f64;
+use super::Mesh;
+
+/// Bytes of the header and the other fixed parts of the stream.
+const HEADER_BYTES: f64 = 32.0;
+/// Bytes of the descriptor, the metadata, and the entropy coder tables of each attribute,
+/// measured on small meshes, where they dominate the size.
+const ATTRIBUTE_OVERHEAD_BYTES: f64 = 64.0;
+/// Bits per face of the Edgebreaker connectivity, which is about 2 for regular meshes.
+const CONNECTIVITY_BITS_PER_FACE: f64 = 2.0;
+/// At most this many faces are sampled to estimate the entropy of the residuals of each attribute.
+const MAX_SAMPLED_FACES: usize = 4096;
+
+impl Mesh {
+    /// Estimates the size in bytes of the stream that [crate::encode::encode] would produce with `cfg`,
+    /// without encoding the mesh. The connectivity is estimated at about 2 bits per face, and each
+    /// attribute by the entropy of the residuals of its quantized values on a sample of the faces,
+    /// under the portabilization and the prediction the encoder selects for it. The estimate is meant
+    /// for budgeting; it correlates with the actual size, but it is not exact.
+    /// The quantization bits of `cfg` are checked as [crate::encode::encode] does.
+    pub fn estimate_encoded_size(&self, cfg: &crate::encode::Config) -> Result<usize, crate::encode::Err> {
+        crate::encode::validate_quantization_bits(cfg)?;
+        validate_attribute_quantization(cfg)?;
+        let selection = Selection::new(&self.attributes, cfg);
+        let sequential = selection.is_sequential(self.faces.len());
+
+        let mut bits = 8.0 * HEADER_BYTES + CONNECTIVITY_BITS_PER_FACE * self.faces.len() as f64;
+        for att in &self.attributes {
+            bits += 8.0 * ATTRIBUTE_OVERHEAD_BYTES + self.estimate_attribute_bits(att, &selection, sequential);
+        }
+        if let Some(order) = &cfg.vertex_order {
+            // the order is written in LEB128, 7 bits per byte.
+            bits += order.iter()
+                .map(|&v| 8.0 * (usize::BITS - usize::from(v).leading_zeros()).max(1).div_ceil(7) as f64)
+                .sum::<f64>();
+        }
+        Ok((bits / 8.0).ceil() as usize)
+    }
+
+    fn estimate_attribute_bits(&self, att: &Attribute, selection: &Selection, sequential: bool) -> f64 {
+        let num_values = att.num_unique_values();
+        if num_values == 0 {
+            return 0.0;
+        }
+        let component_type = att.get_component_type();
+        let port_type = selection.portabilization_type(att);
+        if matches!(port_type, PortabilizationType::ToBits | PortabilizationType::Integer) {
+            // the raw bits or the integers are entropy coded without quantization.
+            return (num_values * att.get_num_components() * component_type.size() * 8) as f64;
+        }
+        let att_cfg = selection.attribute_config(att, sequential);
+        let quantization_bits = att_cfg.quantization_bits();
+        // normals and tangents are coded by the two octahedral coordinates.
+        let num_coded_components = if port_type == PortabilizationType::OctahedralQuantization { 2 } else { att.get_num_components() };
+
+        let values = (0..num_values)
+            .map(|i| unique_value_as_f64(att, i))
+            .collect::<Vec<_>>();
+        let min = (0..att.get_num_components())
+            .map(|i| values.iter().map(|v| v[i]).fold(f64::MAX, f64::min))
+            .collect::<Vec<_>>();
+        let range = (0..att.get_num_components())
+            .map(|i| values.iter().map(|v| v[i]).fold(f64::MIN, f64::max) - min[i])
+            .fold(0.0, f64::max);
+        let scale = if range == 0.0 { 0.0 } else { ((1_u64 << quantization_bits) - 1) as f64 / range };
+        let quantize = |v: &[f64], i: usize| ((v[i] - min[i]) * scale).round() as i64;
+
+        // residuals of the values themselves without prediction, of the previous value when the values are coded
+        // in the order of the points, and else of the corners predicted by the other corners of the face.
+        let mut residuals = HashMap::new();
+        let mut num_residuals = 0;
+        let mut add = |r: i64| {
+            *residuals.entry(r).or_insert(0_usize) += 1;
+            num_residuals += 1;
+        };
+        let prediction_scheme = att_cfg.prediction_scheme();
+        if prediction_scheme == PredictionSchemeType::NoPrediction {
+            for v in &values {
+                for i in 0..att.get_num_components() {
+                    add(quantize(v, i));
+                }
+            }
+        } else if sequential || prediction_scheme == PredictionSchemeType::DeltaPrediction {
+            for w in values.windows(2) {
+                for i in 0..att.get_num_components() {
+                    add(quantize(&w[1], i) - quantize(&w[0], i));
+                }
+            }
+        } else {
+            let step = self.faces.len().div_ceil(MAX_SAMPLED_FACES);
+            for face in self.faces.iter().step_by(step) {
+                let [a, b, c] = face.map(|p: PointIdx| &values[usize::from(att.get_unique_val_idx(p))]);
+                for i in 0..att.get_num_components() {
+                    add(quantize(c, i) - (quantize(a, i) + quantize(b, i)) / 2);
+                }
+            }
+        }
+        if num_residuals == 0 {
+            return (num_values * num_coded_components * quantization_bits as usize) as f64;
+        }
+
+        let entropy = residuals.values()
+            .map(|&n| {
+                let p = n as f64 / num_residuals as f64;
+                -p * p.log2()
+            })
+            .sum::<f64>();
+        // at least a fraction of a bit per component is spent by the entropy coder.
+        num_values as f64 * num_coded_components as f64 * entropy.max(0.1)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::core::shared::ConfigType;
+
+    #[test]
+    #[cfg(not(feature = "evaluation"))]
+    fn test_estimate_is_close_to_actual_size() {
+        for path in ["tests/data/sphere.obj", "tests/data/torus.obj", "tests/data/punctured_sphere.obj", "tests/data/tetrahedron.obj"] {
+            let mesh = crate::io::obj::load_obj(path).unwrap();
+            let cfg = crate::encode::Config::default();
+            let estimate = mesh.estimate_encoded_size(&cfg).unwrap();
+            let mut buffer = Vec::new();
+            crate::encode::encode(mesh, &mut buffer, cfg).unwrap();
+            let ratio = estimate as f64 / buffer.len() as f64;
+            assert!((0.4..2.5).contains(&ratio), "{}: estimate {} bytes, actual {} bytes", path, estimate, buffer.len());
+        }
+    }
+
+    #[test]
+    fn test_estimate_grows_with_the_mesh() {
+        let small = crate::io::obj::load_obj("tests/data/sphere.obj").unwrap();
+        let large = crate::io::obj::load_obj("tests/data/torus.obj").unwrap();
+        let cfg = crate::encode::Config::default();
+        assert!(small.estimate_encoded_size(&cfg).unwrap() < large.estimate_encoded_size(&cfg).unwrap());
+
+        let mut lossless = crate::encode::Config::default();
+        lossless.lossless = true;
+        assert!(small.estimate_encoded_size(&cfg).unwrap() < small.estimate_encoded_size(&lossless).unwrap());
+    }
+
+    #[test]
+    fn test_estimate_rejects_invalid_quantization_bits() {
+        let mesh = crate::io::obj::load_obj("tests/data/sphere.obj").unwrap();
+        let mut cfg = crate::encode::Config::default();
+        cfg.quantization_bits = Some(64);
+        assert!(matches!(mesh.estimate_encoded_size(&cfg), Err(crate::encode::Err::InvalidQuantizationBits { bits: 64, .. })));
+
+        let mut cfg = crate::encode::Config::default();
+        cfg.attribute_quantization.insert(crate::core::attribute::AttributeType::TextureCoordinate, 31);
+        assert!(matches!(mesh.estimate_encoded_size(&cfg), Err(crate::encode::Err::AttributeError(_))));
+    }
+}
@@ -0,0 +1,129 @@
+use crate::core::attribute::{AttributeType, ComponentDataType};
+use crate::core::shared::{NdVector, VertexIdx};
+use super::diff::unique_value_as_f64;
+use super::topology::Topology;
+use super::Mesh;
+
+impl Mesh {
+    /// Smooths the positions by moving each vertex by `lambda` toward the average of its one-ring, `iterations` times.
+    /// The vertices on the boundary, and the positions shared by non-manifold parts of the mesh, stay put,
+    /// so that the outline of the mesh is kept. Smoothing removes the noise of scanned meshes, which also makes
+    /// the positions easier to predict for the encoder. Only the positions of three components of f32 or f64
+    /// are smoothed, and the faces and the other attributes are left as they are.
+    pub fn smooth_laplacian(&mut self, iterations: usize, lambda: f64) {
+        let Some(pos_idx) = self.attributes.iter()
+            .position(|att| att.get_attribute_type() == AttributeType::Position && att.get_num_components() == 3)
+        else {
+            return;
+        };
+        if !matches!(self.attributes[pos_idx].get_component_type(), ComponentDataType::F32 | ComponentDataType::F64) {
+            return;
+        }
+
+        // the position of each vertex, and the positions of its one-ring if the vertex moves.
+        let (value_of_vertex, rings) = {
+            let Ok(topology) = Topology::new(self) else {
+                return;
+            };
+            let pos = &self.attributes[pos_idx];
+            let value_of_vertex = (0..topology.num_vertices())
+                .map(|v| usize::from(pos.get_unique_val_idx(topology.point_of(VertexIdx::from(v)))))
+                .collect::<Vec<_>>();
+            let mut num_vertices_of_value = vec![0; pos.num_unique_values()];
+            for &i in &value_of_vertex {
+                num_vertices_of_value[i] += 1;
+            }
+            let rings = (0..topology.num_vertices())
+                .map(VertexIdx::from)
+                .map(|v| {
+                    let is_fixed = topology.is_on_boundary(v) || num_vertices_of_value[value_of_vertex[usize::from(v)]] > 1;
+                    if is_fixed { Vec::new() } else { topology.one_ring(v) }
+                })
+                .collect::<Vec<_>>();
+            (value_of_vertex, rings)
+        };
+
+        let pos = &mut self.attributes[pos_idx];
+        let mut values = (0..pos.num_unique_values())
+            .map(|i| unique_value_as_f64(pos, i))
+            .collect::<Vec<_>>();
+        for _ in 0..iterations {
+            let prev = values.clone();
+            for (v, ring) in rings.iter().enumerate().filter(|(_, ring)| !ring.is_empty()) {
+                let value = &mut values[value_of_vertex[v]];
+                for (i, c) in value.iter_mut().enumerate() {
+                    let average = ring.iter()
+                        .map(|&n| prev[value_of_vertex[usize::from(n)]][i])
+                        .sum::<f64>() / ring.len() as f64;
+                    *c += lambda * (average - *c);
+                }
+            }
+        }
+
+        match pos.get_component_type() {
+            ComponentDataType::F32 => {
+                for (v, new) in pos.unique_vals_as_slice_mut::<NdVector<3, f32>, 3>().iter_mut().zip(values) {
+                    *v = NdVector::from([0, 1, 2].map(|i| new[i] as f32));
+                }
+            },
+            ComponentDataType::F64 => {
+                for (v, new) in pos.unique_vals_as_slice_mut::<NdVector<3, f64>, 3>().iter_mut().zip(values) {
+                    *v = NdVector::from([0, 1, 2].map(|i| new[i]));
+                }
+            },
+            _ => unreachable!(),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::core::attribute::{Attribute, AttributeDomain, AttributeId};
+    use crate::core::mesh::Mesh;
+    use crate::core::shared::{NdVector, PointIdx, Vector};
+    use crate::prelude::AttributeType;
+
+    #[test]
+    fn test_smooth_laplacian_flattens_noisy_plane() {
+        // a grid of 8x8 points on the plane z = 0, with noise in z.
+        const N: usize = 8;
+        let mut seed = 7_u32;
+        let mut noise = || {
+            seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+            ((seed >> 8) as f32 / (1 << 24) as f32 - 0.5) * 0.2
+        };
+        let positions = (0..N * N)
+            .map(|i| NdVector::from([(i % N) as f32, (i / N) as f32, noise()]))
+            .collect::<Vec<_>>();
+        let faces = (0..N - 1).flat_map(|y| (0..N - 1).flat_map(move |x| {
+                let p = y * N + x;
+                [[p, p + 1, p + N + 1], [p, p + N + 1, p + N]]
+            }))
+            .map(|f| f.map(PointIdx::from))
+            .collect();
+        let mut mesh = Mesh::new();
+        mesh.attributes.push(Attribute::from(AttributeId::new(0), positions.clone(), AttributeType::Position, AttributeDomain::Position, Vec::new()));
+        mesh.faces = faces;
+
+        let variance_of_z = |mesh: &Mesh| {
+            let z = mesh.get_attributes()[0].unique_vals_as_slice::<NdVector<3, f32>, 3>().iter().map(|v| *v.get(2) as f64).collect::<Vec<_>>();
+            let mean = z.iter().sum::<f64>() / z.len() as f64;
+            z.iter().map(|z| (z - mean).powi(2)).sum::<f64>() / z.len() as f64
+        };
+        let before = variance_of_z(&mesh);
+        mesh.smooth_laplacian(10, 0.5);
+        let after = variance_of_z(&mesh);
+        assert!(after < before * 0.7, "variance before: {}, after: {}", before, after);
+
+        let smoothed = mesh.get_attributes()[0].unique_vals_as_slice::<NdVector<3, f32>, 3>();
+        for (i, (v, original)) in smoothed.iter().zip(&positions).enumerate() {
+            let (x, y) = (i % N, i / N);
+            if x == 0 || y == 0 || x == N - 1 || y == N - 1 {
+                assert_eq!(v, original);
+            } else {
+                assert_ne!(v.get(2), original.get(2));
+            }
+        }
+    }
+}
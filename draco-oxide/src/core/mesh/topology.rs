@@ -0,0 +1,111 @@
+use crate::core::attribute::AttributeType;
+use crate::core::corner_table::{CornerTable, GenericCornerTable};
+use crate::core::shared::{PointIdx, VertexIdx};
+use super::{Err, Mesh};
+
+/// The adjacency of the faces of a mesh, built on the corner table of its positions.
+/// The vertices are the unique positions of the mesh, where the positions shared by non-manifold
+/// parts of the mesh are split into separate vertices.
+pub struct Topology<'mesh> {
+    corner_table: CornerTable<'mesh>,
+}
+
+impl<'mesh> Topology<'mesh> {
+    /// Builds the topology of the mesh from the faces and the position attribute.
+    /// The positions that no face refers to are rejected, since they have no neighbors.
+    pub fn new(mesh: &'mesh Mesh) -> Result<Self, Err> {
+        let pos_att = mesh.attributes.iter()
+            .find(|att| att.get_attribute_type() == AttributeType::Position)
+            .ok_or(Err::NoPositionAttribute)?;
+        let corner_table = CornerTable::new(&mesh.faces, pos_att);
+        let isolated_vertices = corner_table.isolated_vertices();
+        if !isolated_vertices.is_empty() {
+            return Err(Err::IsolatedVertices(isolated_vertices.into_iter().map(usize::from).collect()));
+        }
+        Ok(Self { corner_table })
+    }
+
+    /// The number of vertices, including the vertices split at non-manifold points.
+    pub fn num_vertices(&self) -> usize {
+        self.corner_table.num_vertices()
+    }
+
+    /// Whether the vertex lies on the boundary of the mesh, i.e. its faces do not close around it.
+    pub fn is_on_boundary(&self, v: VertexIdx) -> bool {
+        self.corner_table.is_on_boundary(v)
+    }
+
+    /// Checks that the topology is built from the faces and the positions of the mesh, i.e. that its corner table
+    /// can stand in for the one that the encoder would build from the mesh.
+    pub(crate) fn check_mesh(&self, mesh: &Mesh) -> Result<(), String> {
+        let faces = self.corner_table.get_mesh_faces();
+        if faces != mesh.faces.as_slice() {
+            return Err(format!("the topology has {} faces that differ from the {} faces of the mesh", faces.len(), mesh.faces.len()));
+        }
+        let pos_att = mesh.attributes.iter()
+            .find(|att| att.get_attribute_type() == AttributeType::Position)
+            .ok_or("the mesh has no position attribute")?;
+        let vertex_of = |p: PointIdx| VertexIdx::from(usize::from(pos_att.get_unique_val_idx(p)));
+        if let Some(f) = (0..faces.len()).find(|&f| self.corner_table.conn_faces()[f] != faces[f].map(vertex_of)) {
+            return Err(format!("the vertices of the face {} differ from the positions of the mesh", f));
+        }
+        Ok(())
+    }
+
+    pub(crate) fn corner_table(&self) -> &CornerTable<'mesh> {
+        &self.corner_table
+    }
+
+    /// A point of the vertex, which refers to the position of the vertex.
+    pub(crate) fn point_of(&self, v: VertexIdx) -> PointIdx {
+        self.corner_table.point_idx(self.corner_table.left_most_corner(v))
+    }
+
+    /// Returns the neighbors of the vertex in the order of its faces, swinging right from its left-most corner,
+    /// i.e. clockwise when the faces are seen from their front.
+    /// The ring of a boundary vertex is not closed; it starts and ends at the neighbors on the boundary.
+    pub fn one_ring(&self, v: VertexIdx) -> Vec<VertexIdx> {
+        let ct = &self.corner_table;
+        let start = ct.left_most_corner(v);
+        let mut ring = Vec::new();
+        if ct.is_on_boundary(v) {
+            // the first neighbor lies on the boundary edge of the first face.
+            ring.push(ct.vertex_idx(ct.previous(start)));
+        }
+        let mut c = start;
+        loop {
+            ring.push(ct.vertex_idx(ct.next(c)));
+            match ct.swing_right(c) {
+                Some(right) if right != start => c = right,
+                _ => return ring,
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_one_ring() {
+        let mesh = crate::io::obj::load_obj("tests/data/disk.obj").unwrap();
+        let topology = Topology::new(&mesh).unwrap();
+        assert_eq!(topology.num_vertices(), 7);
+
+        // the rim around the center clockwise, starting anywhere.
+        let ring = topology.one_ring(VertexIdx::from(0)).into_iter().map(usize::from).collect::<Vec<_>>();
+        assert!(!topology.is_on_boundary(VertexIdx::from(0)));
+        assert_eq!(ring.len(), 6);
+        let start = ring.iter().position(|&v| v == 1).unwrap();
+        assert_eq!([&ring[start..], &ring[..start]].concat(), vec![1, 6, 5, 4, 3, 2]);
+
+        // the ring of a rim vertex is open, from a rim neighbor through the center to the other.
+        for v in 1..7 {
+            assert!(topology.is_on_boundary(VertexIdx::from(v)));
+            let ring = topology.one_ring(VertexIdx::from(v)).into_iter().map(usize::from).collect::<Vec<_>>();
+            assert_eq!(ring, vec![(v + 4) % 6 + 1, 0, v % 6 + 1]);
+        }
+    }
+}
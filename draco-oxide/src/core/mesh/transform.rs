@@ -0,0 +1,211 @@
+use crate::core::attribute::{Attribute, AttributeType, ComponentDataType};
+use crate::core::scene::{Matrix4d, Quaterniond, Vector3d};
+use crate::core::shared::{NdVector, Vector};
+use super::Mesh;
+
+impl Mesh {
+    /// Applies the affine transformation `m` to the mesh in place. Positions are transformed by `m`,
+    /// normals by the inverse transpose of its linear part, and the 'xyz' part of tangents by its
+    /// linear part. Normals and tangents are renormalized afterwards. If `m` mirrors the mesh, i.e. its
+    /// determinant is negative, the winding of the faces is reversed so that they keep facing outwards.
+    /// Only the attributes of f32 or f64 are transformed; the others are left as they are.
+    pub fn bake_transform(&mut self, m: &Matrix4d) {
+        let linear = [
+            [m.data[0][0], m.data[0][1], m.data[0][2]],
+            [m.data[1][0], m.data[1][1], m.data[1][2]],
+            [m.data[2][0], m.data[2][1], m.data[2][2]],
+        ];
+        let translation = [m.data[0][3], m.data[1][3], m.data[2][3]];
+        let det = determinant(&linear);
+        let normal_matrix = inverse_transpose(&linear, det);
+
+        for att in &mut self.attributes {
+            match (att.get_attribute_type(), att.get_num_components()) {
+                (AttributeType::Position, 3) => transform_values::<3>(att, |v| {
+                    let mut out = mul(&linear, &[v[0], v[1], v[2]]);
+                    for (o, t) in out.iter_mut().zip(translation) {
+                        *o += t;
+                    }
+                    out
+                }),
+                (AttributeType::Normal, 3) => transform_values::<3>(att, |v| {
+                    normalized(mul(&normal_matrix, &[v[0], v[1], v[2]]))
+                }),
+                (AttributeType::Tangent, n @ 3..=4) => {
+                    let f = |v: &[f64]| normalized(mul(&linear, &[v[0], v[1], v[2]]));
+                    // the handedness ('w') is kept as it is.
+                    if n == 3 { transform_values::<3>(att, f) } else { transform_values::<4>(att, f) }
+                },
+                _ => {}
+            }
+        }
+
+        if det < 0.0 {
+            for face in &mut self.faces {
+                face.swap(1, 2);
+            }
+        }
+    }
+
+    /// Translates the mesh by `t`. A thin wrapper over [Mesh::bake_transform].
+    pub fn translate(&mut self, t: [f64; 3]) {
+        self.bake_transform(&Matrix4d::from_translation(Vector3d::new(t[0], t[1], t[2])));
+    }
+
+    /// Scales the mesh uniformly by `s` about the origin. A thin wrapper over [Mesh::bake_transform].
+    pub fn scale(&mut self, s: f64) {
+        self.bake_transform(&Matrix4d::from_scale(Vector3d::new(s, s, s)));
+    }
+
+    /// Rotates the mesh about the origin by `radians` around `axis`, which does not need to be normalized.
+    /// A thin wrapper over [Mesh::bake_transform].
+    pub fn rotate_axis_angle(&mut self, axis: [f64; 3], radians: f64) {
+        let [x, y, z] = normalized(axis);
+        let (sin, cos) = (radians / 2.0).sin_cos();
+        self.bake_transform(&Matrix4d::from_rotation(Quaterniond::new(cos, x * sin, y * sin, z * sin)));
+    }
+}
+
+/// Replaces the first three components of every unique value of 'att' with 'f' of the value.
+/// The bounds of the values set by the user no longer hold, which the mutable access to the values clears.
+fn transform_values<const N: usize>(att: &mut Attribute, f: impl Fn(&[f64]) -> [f64; 3])
+    where NdVector<N, f32>: Vector<N, Component = f32>,
+          NdVector<N, f64>: Vector<N, Component = f64>,
+{
+    match att.get_component_type() {
+        ComponentDataType::F32 => {
+            for v in att.unique_vals_as_slice_mut::<NdVector<N, f32>, N>() {
+                let new = f(&std::array::from_fn::<_, N, _>(|i| *v.get(i) as f64));
+                for (i, new) in new.into_iter().enumerate() {
+                    *v.get_mut(i) = new as f32;
+                }
+            }
+        },
+        ComponentDataType::F64 => {
+            for v in att.unique_vals_as_slice_mut::<NdVector<N, f64>, N>() {
+                let new = f(&std::array::from_fn::<_, N, _>(|i| *v.get(i)));
+                for (i, new) in new.into_iter().enumerate() {
+                    *v.get_mut(i) = new;
+                }
+            }
+        },
+        _ => {}
+    }
+}
+
+fn mul(m: &[[f64; 3]; 3], v: &[f64; 3]) -> [f64; 3] {
+    m.map(|row| row[0] * v[0] + row[1] * v[1] + row[2] * v[2])
+}
+
+fn normalized(v: [f64; 3]) -> [f64; 3] {
+    let norm = (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt();
+    if norm == 0.0 {
+        return v;
+    }
+    v.map(|c| c / norm)
+}
+
+fn determinant(m: &[[f64; 3]; 3]) -> f64 {
+    m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+        - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+        + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+}
+
+/// Returns the inverse transpose of 'm', i.e. its cofactor matrix divided by the determinant.
+/// The cofactor matrix itself is returned for a singular 'm', which is enough for the normals
+/// as they are renormalized anyway.
+fn inverse_transpose(m: &[[f64; 3]; 3], det: f64) -> [[f64; 3]; 3] {
+    let cofactor = |r: usize, c: usize| {
+        let (r0, r1) = ((r + 1) % 3, (r + 2) % 3);
+        let (c0, c1) = ((c + 1) % 3, (c + 2) % 3);
+        m[r0][c0] * m[r1][c1] - m[r0][c1] * m[r1][c0]
+    };
+    let scale = if det == 0.0 { 1.0 } else { 1.0 / det };
+    [0, 1, 2].map(|r| [0, 1, 2].map(|c| cofactor(r, c) * scale))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::shared::{Dot, NdVector, PointIdx, Vector};
+
+    fn positions(mesh: &Mesh) -> Vec<[f64; 3]> {
+        let att = mesh.get_attributes().iter()
+            .find(|att| att.get_attribute_type() == AttributeType::Position)
+            .unwrap();
+        (0..att.len())
+            .map(|p| {
+                let v: NdVector<3, f32> = att.get(PointIdx::from(p));
+                [*v.get(0) as f64, *v.get(1) as f64, *v.get(2) as f64]
+            })
+            .collect()
+    }
+
+    fn bounding_box(mesh: &Mesh) -> ([f64; 3], [f64; 3]) {
+        positions(mesh).into_iter().fold(
+            ([f64::MAX; 3], [f64::MIN; 3]),
+            |(min, max), v| ([0, 1, 2].map(|i| min[i].min(v[i])), [0, 1, 2].map(|i| max[i].max(v[i])))
+        )
+    }
+
+    fn surface_area(mesh: &Mesh) -> f64 {
+        let pos = positions(mesh);
+        mesh.get_faces().iter()
+            .map(|f| {
+                let [a, b, c] = f.map(|p| pos[usize::from(p)]);
+                let u = [0, 1, 2].map(|i| b[i] - a[i]);
+                let v = [0, 1, 2].map(|i| c[i] - a[i]);
+                let cross = [u[1] * v[2] - u[2] * v[1], u[2] * v[0] - u[0] * v[2], u[0] * v[1] - u[1] * v[0]];
+                (cross[0] * cross[0] + cross[1] * cross[1] + cross[2] * cross[2]).sqrt() / 2.0
+            })
+            .sum()
+    }
+
+    #[test]
+    fn test_translate_moves_bounding_box() {
+        let mut mesh = crate::io::obj::load_obj("tests/data/sphere.obj").unwrap();
+        let (min, max) = bounding_box(&mesh);
+        mesh.translate([1.0, -2.0, 0.5]);
+        let (new_min, new_max) = bounding_box(&mesh);
+        for (i, d) in [1.0, -2.0, 0.5].into_iter().enumerate() {
+            assert!((new_min[i] - (min[i] + d)).abs() < 1e-5);
+            assert!((new_max[i] - (max[i] + d)).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_uniform_scale_scales_surface_area() {
+        let mut mesh = crate::io::obj::load_obj("tests/data/sphere.obj").unwrap();
+        let area = surface_area(&mesh);
+        let s = 2.5;
+        mesh.scale(s);
+        let scaled_area = surface_area(&mesh);
+        assert!((scaled_area - area * s * s).abs() < 1e-4 * scaled_area, "{} vs {}", scaled_area, area * s * s);
+
+        // mirroring keeps the area and reverses the winding.
+        let faces = mesh.get_faces().to_vec();
+        mesh.bake_transform(&Matrix4d::from_scale(Vector3d::new(-1.0, 1.0, 1.0)));
+        assert!((surface_area(&mesh) - scaled_area).abs() < 1e-4 * scaled_area);
+        assert_eq!(mesh.get_faces()[0], [faces[0][0], faces[0][2], faces[0][1]]);
+    }
+
+    #[test]
+    fn test_rotate_axis_angle() {
+        let mut mesh = crate::io::obj::load_obj("tests/data/sphere.obj").unwrap();
+        let before = positions(&mesh);
+        mesh.rotate_axis_angle([0.0, 0.0, 2.0], std::f64::consts::FRAC_PI_2);
+        for (a, b) in before.iter().zip(positions(&mesh)) {
+            // (x, y, z) -> (-y, x, z)
+            assert!((b[0] + a[1]).abs() < 1e-5 && (b[1] - a[0]).abs() < 1e-5 && (b[2] - a[2]).abs() < 1e-5);
+        }
+        // normals stay unit vectors.
+        let normal = mesh.get_attributes().iter()
+            .find(|att| att.get_attribute_type() == AttributeType::Normal)
+            .unwrap();
+        for p in 0..normal.len() {
+            let n: NdVector<3, f32> = normal.get(PointIdx::from(p));
+            assert!((n.dot(n) - 1.0).abs() < 1e-4);
+        }
+    }
+}
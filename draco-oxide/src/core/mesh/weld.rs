@@ -0,0 +1,114 @@
+use std::collections::BTreeMap;
+
+use crate::core::attribute::AttributeType;
+use crate::core::shared::PointIdx;
+use super::diff::unique_value_as_f64;
+use super::Mesh;
+
+impl Mesh {
+    /// Welds the points whose positions fall in the same cell of the grid of the size `cell_size`,
+    /// and removes the faces that become degenerate.
+    ///
+    /// The result is deterministic: the welded points are numbered in the order of their cells, sorted by
+    /// the cell coordinates along x, then y, then z, and each takes the values of all its attributes from
+    /// the point of the cell with the smallest position, compared along x, then y, then z; among the points
+    /// of the same position, the first inserted one is taken. Hence equivalent inputs, e.g. the same points
+    /// given in a different order, are welded to identical meshes. The faces keep their order.
+    pub fn weld_by_grid(&mut self, cell_size: f64) {
+        assert!(cell_size > 0.0, "The cell size must be positive, but it is {}.", cell_size);
+        let Some(pos) = self.attributes.iter()
+            .find(|att| att.get_attribute_type() == AttributeType::Position)
+        else {
+            return;
+        };
+
+        // the point representing each cell.
+        let mut cells: BTreeMap<Vec<i64>, (Vec<f64>, PointIdx)> = BTreeMap::new();
+        let mut point_to_cell = Vec::with_capacity(pos.len());
+        for p in 0..pos.len() {
+            let p = PointIdx::from(p);
+            let v = unique_value_as_f64(pos, usize::from(pos.get_unique_val_idx(p)));
+            let cell = v.iter().map(|c| (c / cell_size).floor() as i64).collect::<Vec<_>>();
+            match cells.get_mut(&cell) {
+                Some(rep) => {
+                    let smaller = v.iter().zip(&rep.0)
+                        .map(|(a, b)| a.total_cmp(b))
+                        .find(|o| o.is_ne())
+                        .is_some_and(|o| o.is_lt());
+                    if smaller {
+                        *rep = (v, p);
+                    }
+                },
+                None => {
+                    cells.insert(cell.clone(), (v, p));
+                },
+            }
+            point_to_cell.push(cell);
+        }
+
+        let representatives = cells.values().map(|(_, p)| *p).collect::<Vec<_>>();
+        let cell_to_new_point = cells.keys()
+            .enumerate()
+            .map(|(i, cell)| (cell, i))
+            .collect::<BTreeMap<_, _>>();
+        let old_to_new = point_to_cell.iter()
+            .map(|cell| Some(cell_to_new_point[cell]))
+            .collect::<Vec<_>>();
+        self.remap_vertices_with(&old_to_new, &representatives);
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::core::mesh::{mesh_from, Mesh};
+    use crate::core::shared::NdVector;
+
+    fn positions_of(mesh: &Mesh) -> Vec<NdVector<3, f32>> {
+        mesh.get_attributes()[0].clone().into_vec::<NdVector<3, f32>, 3>().unwrap()
+    }
+
+    #[test]
+    fn test_weld_by_grid_is_deterministic() {
+        // a strip of two quads, whose shared vertices are duplicated with small errors.
+        let positions = vec![
+            NdVector::from([0.0_f32, 0.0, 0.0]),
+            NdVector::from([1.0, 0.0, 0.0]),
+            NdVector::from([0.0, 1.0, 0.0]),
+            NdVector::from([1.0, 1.0, 0.0]),
+            NdVector::from([1.001, 0.0, 0.0]),
+            NdVector::from([1.0, 1.001, 0.0]),
+            NdVector::from([2.0, 0.0, 0.0]),
+            NdVector::from([2.0, 1.0, 0.0]),
+        ];
+        let faces = vec![[0, 1, 3], [0, 3, 2], [4, 6, 7], [4, 7, 5]];
+        let mut mesh = mesh_from(positions.clone(), faces.clone());
+
+        // the same points in the reverse order.
+        let reversed = |p: usize| positions.len() - 1 - p;
+        let mut reversed_mesh = mesh_from(
+            positions.iter().rev().copied().collect(),
+            faces.iter().map(|f| f.map(reversed)).collect(),
+        );
+
+        mesh.weld_by_grid(0.1);
+        reversed_mesh.weld_by_grid(0.1);
+        assert_eq!(mesh.get_attributes()[0].len(), 6);
+        assert_eq!(mesh.get_faces(), reversed_mesh.get_faces());
+        assert_eq!(positions_of(&mesh), positions_of(&reversed_mesh));
+        // the points are numbered in the order of the cells, and the smallest position represents a cell.
+        assert_eq!(positions_of(&mesh), vec![
+            NdVector::from([0.0, 0.0, 0.0]),
+            NdVector::from([0.0, 1.0, 0.0]),
+            NdVector::from([1.0, 0.0, 0.0]),
+            NdVector::from([1.0, 1.0, 0.0]),
+            NdVector::from([2.0, 0.0, 0.0]),
+            NdVector::from([2.0, 1.0, 0.0]),
+        ]);
+
+        // welding everything into a cell leaves no face.
+        mesh.weld_by_grid(10.0);
+        assert_eq!(mesh.get_attributes()[0].len(), 1);
+        assert!(mesh.get_faces().is_empty());
+    }
+}
@@ -5,6 +5,8 @@ pub mod mesh;
 pub(crate) mod point_cloud;
 pub(crate) mod point_cloud_builder;
 pub mod shared;
+#[cfg(all(feature = "simd", target_arch = "x86_64"))]
+pub(crate) mod simd;
 pub(crate) mod corner_table;
 pub(crate) mod texture;
 pub(crate) mod scene;
@@ -4,7 +4,9 @@ use crate::core::{
     structural_metadata::StructuralMetadata, 
     texture::TextureLibrary
 };
-use crate::prelude::Mesh;
+use crate::core::bit_coder::ReaderErr;
+use crate::prelude::{ByteReader, ByteWriter, Mesh};
+use crate::utils::bit_coder::{leb128_read, leb128_write};
 
 type MeshGroupIdx = usize;
 type MeshIdx = usize;
@@ -34,6 +36,29 @@ impl Matrix4d {
         }
     }
 
+    /// Returns the matrix that translates by `t`.
+    pub fn from_translation(t: Vector3d) -> Self {
+        let mut out = Self::identity();
+        out.data[0][3] = t.x;
+        out.data[1][3] = t.y;
+        out.data[2][3] = t.z;
+        out
+    }
+
+    /// Returns the matrix that scales each axis by the corresponding component of `s`.
+    pub fn from_scale(s: Vector3d) -> Self {
+        let mut out = Self::identity();
+        out.data[0][0] = s.x;
+        out.data[1][1] = s.y;
+        out.data[2][2] = s.z;
+        out
+    }
+
+    /// Returns the matrix that rotates by the (unit) quaternion `q`.
+    pub fn from_rotation(q: Quaterniond) -> Self {
+        q.to_matrix4()
+    }
+
     pub fn is_identity(&self) -> bool {
         *self == Self::identity()
     }
@@ -274,31 +299,11 @@ impl TrsMatrix {
             return matrix.clone();
         }
 
-        // Start with identity matrix
-        let mut result = Matrix4d::identity();
-
-        // Apply scale
-        if let Some(scale) = &self.scale {
-            result.data[0][0] *= scale.x;
-            result.data[1][1] *= scale.y;
-            result.data[2][2] *= scale.z;
-        }
-
-        // Apply rotation
-        if let Some(rotation) = &self.rotation {
-            let _rotation_matrix = rotation.to_matrix4();
-            // Matrix multiplication would go here - simplified for placeholder
-            // In a real implementation, you'd multiply result * rotation_matrix
-        }
-
-        // Apply translation
-        if let Some(translation) = &self.translation {
-            result.data[0][3] = translation.x;
-            result.data[1][3] = translation.y;
-            result.data[2][3] = translation.z;
-        }
-
-        result
+        // T * R * S, i.e. scale first, then rotation, then translation.
+        let translation = Matrix4d::from_translation(self.translation.unwrap_or_default());
+        let rotation = Matrix4d::from_rotation(self.rotation.unwrap_or_default());
+        let scale = Matrix4d::from_scale(self.scale.unwrap_or(Vector3d::one()));
+        translation * rotation * scale
     }
 
     // Returns a boolean indicating whether any of the transforms have been set.
@@ -396,8 +401,13 @@ impl MeshGroup {
 #[derive(Debug, Clone, Default)]
 pub struct Animation;
 
+// Describes a skin of the glTF skinned meshes: the scene nodes of the joints, and the
+// inverse bind matrix of each joint in the same order.
 #[derive(Debug, Clone, Default)]
-pub struct Skin;
+pub struct Skin {
+    pub joints: Vec<SceneNodeIdx>,
+    pub inverse_bind_matrices: Vec<Matrix4d>,
+}
 
 // Light type according to KHR_lights_punctual extension
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -633,6 +643,8 @@ pub enum Err {
     MaterialUsedInScene(usize),
     #[error("Failed to remove mesh group at index {0}: the mesh group is used in the scene")]
     MeshGroupUsedInScene(usize),
+    #[error("Reader error: {0}")]
+    ReaderError(#[from] ReaderErr),
 }
 
 // Class used to hold all of the geometry to create a scene. A scene is
@@ -648,7 +660,6 @@ pub struct Scene {
     mesh_groups: Vec<MeshGroup>,
     nodes: Vec<SceneNode>,
     root_node_indices: Vec<SceneNodeIdx>,
-    #[allow(unused)]
     skins: Vec<Skin>,
 
     // The lights will be written to the output scene but not used for internal
@@ -744,6 +755,17 @@ impl Scene {
         self.root_node_indices.push(index);
     }
 
+    pub(crate) fn add_skin(&mut self, skin: Skin) -> SkinIdx {
+        self.skins.push(skin);
+        self.skins.len() - 1
+    }
+
+    pub(crate) fn num_skins(&self) -> usize { self.skins.len() }
+
+    pub(crate) fn get_skin(&self, index: SkinIdx) -> Option<&Skin> {
+        self.skins.get(index)
+    }
+
     pub(crate) fn material_library(&self) -> &MaterialLibrary {
         &self.material_library
     }
@@ -759,6 +781,115 @@ impl Scene {
     pub(crate) fn metadata_mut(&mut self) -> &mut Metadata {
         &mut self.metadata
     }
+
+    pub(crate) fn meshes(&self) -> &[Mesh] {
+        &self.meshes
+    }
+
+    /// Checks that the mesh instances, the nodes, and the root nodes refer to the existing meshes,
+    /// mesh groups, and nodes.
+    pub(crate) fn validate_indices(&self) -> Result<(), Err> {
+        for group in &self.mesh_groups {
+            if let Some(instance) = group.mesh_instances.iter().find(|i| i.mesh_index >= self.meshes.len()) {
+                return Err(Err::MeshIndexOutOfRange(instance.mesh_index, self.meshes.len()));
+            }
+        }
+        for node in &self.nodes {
+            if let Some(group) = node.mesh_group_index.filter(|&g| g >= self.mesh_groups.len()) {
+                return Err(Err::MeshGroupIndexOutOfRange(group, self.mesh_groups.len()));
+            }
+        }
+        let links = self.nodes.iter().flat_map(|node| &node.children).chain(&self.root_node_indices);
+        if let Some(&n) = links.into_iter().find(|&&n| n >= self.nodes.len()) {
+            return Err(Err::SceneNodeIndexOutOfRange(n, self.nodes.len()));
+        }
+        Ok(())
+    }
+
+    /// Writes the mesh groups and the node hierarchy, i.e. everything that [Scene::read_hierarchy] needs
+    /// to rebuild the scene from its meshes. The transform of each node is written as the matrix given by
+    /// [TrsMatrix::compute_transformation_matrix], and the counts and the indices are written in LEB128.
+    /// The names, the materials, the skins, the lights, and the instance arrays are not written.
+    pub(crate) fn write_hierarchy<W>(&self, writer: &mut W) 
+        where W: ByteWriter
+    {
+        leb128_write(self.mesh_groups.len() as u64, writer);
+        for group in &self.mesh_groups {
+            leb128_write(group.mesh_instances.len() as u64, writer);
+            for instance in &group.mesh_instances {
+                leb128_write(instance.mesh_index as u64, writer);
+                writer.write_u32(instance.material_index as u32);
+            }
+        }
+
+        leb128_write(self.nodes.len() as u64, writer);
+        for node in &self.nodes {
+            // zero for the node without a mesh group.
+            leb128_write(node.mesh_group_index.map_or(0, |g| g as u64 + 1), writer);
+            for x in node.trs_matrix.compute_transformation_matrix().data.iter().flatten() {
+                writer.write_u64(x.to_bits());
+            }
+            leb128_write(node.children.len() as u64, writer);
+            for &child in &node.children {
+                leb128_write(child as u64, writer);
+            }
+        }
+
+        leb128_write(self.root_node_indices.len() as u64, writer);
+        for &root in &self.root_node_indices {
+            leb128_write(root as u64, writer);
+        }
+    }
+
+    /// Reads the hierarchy written by [Scene::write_hierarchy] and builds the scene of the given meshes.
+    /// The parents of the nodes are restored from their children.
+    pub(crate) fn read_hierarchy<R>(reader: &mut R, meshes: Vec<Mesh>) -> Result<Self, Err> 
+        where R: ByteReader
+    {
+        let mut scene = Self::new();
+        scene.meshes = meshes;
+
+        let num_groups = leb128_read(reader)? as usize;
+        for _ in 0..num_groups {
+            let mut group = MeshGroup::new();
+            let num_instances = leb128_read(reader)? as usize;
+            for _ in 0..num_instances {
+                let mesh_index = leb128_read(reader)? as usize;
+                let material_index = reader.read_u32()? as i32;
+                group.add_mesh_instance(MeshInstance::new(mesh_index, material_index));
+            }
+            scene.mesh_groups.push(group);
+        }
+
+        let num_nodes = leb128_read(reader)? as usize;
+        for _ in 0..num_nodes {
+            let mut node = SceneNode::new();
+            node.mesh_group_index = (leb128_read(reader)? as usize).checked_sub(1);
+            let mut data = [[0.0; 4]; 4];
+            for x in data.iter_mut().flatten() {
+                *x = f64::from_bits(reader.read_u64()?);
+            }
+            node.trs_matrix.set_matrix(Matrix4d::new(data));
+            let num_children = leb128_read(reader)? as usize;
+            for _ in 0..num_children {
+                node.add_child_index(leb128_read(reader)? as usize);
+            }
+            scene.nodes.push(node);
+        }
+
+        let num_roots = leb128_read(reader)? as usize;
+        for _ in 0..num_roots {
+            scene.root_node_indices.push(leb128_read(reader)? as usize);
+        }
+        scene.validate_indices()?;
+
+        for n in 0..scene.nodes.len() {
+            for child in scene.nodes[n].children.clone() {
+                scene.nodes[child].add_parent_index(n);
+            }
+        }
+        Ok(scene)
+    }
 }
 
 
@@ -884,5 +1015,184 @@ impl SceneNode {
 }
 
 
+#[cfg(test)]
+mod tests {
+    use super::*;
 
+    fn assert_matrix_eq(a: &Matrix4d, b: &Matrix4d) {
+        for i in 0..4 {
+            for j in 0..4 {
+                assert!((a.data[i][j] - b.data[i][j]).abs() < 1e-12, "{:?} != {:?}", a, b);
+            }
+        }
+    }
 
+    #[test]
+    fn test_trs_constructors() {
+        let t = Matrix4d::from_translation(Vector3d::new(1.0, 2.0, 3.0));
+        assert_eq!(t.data, [
+            [1.0, 0.0, 0.0, 1.0],
+            [0.0, 1.0, 0.0, 2.0],
+            [0.0, 0.0, 1.0, 3.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]);
+
+        let s = Matrix4d::from_scale(Vector3d::new(2.0, 3.0, 4.0));
+        assert_eq!(s.data, [
+            [2.0, 0.0, 0.0, 0.0],
+            [0.0, 3.0, 0.0, 0.0],
+            [0.0, 0.0, 4.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]);
+
+        // 90 degrees around the z axis
+        let half = std::f64::consts::FRAC_PI_4;
+        let r = Matrix4d::from_rotation(Quaterniond::new(half.cos(), 0.0, 0.0, half.sin()));
+        assert_matrix_eq(&r, &Matrix4d::new([
+            [0.0, -1.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]));
+        assert_eq!(Matrix4d::from_rotation(Quaterniond::identity()), Matrix4d::identity());
+    }
+
+    #[test]
+    fn test_trs_product_equals_full_trs() {
+        let translation = Vector3d::new(1.0, -2.0, 3.0);
+        // 90 degrees around the z axis
+        let half = std::f64::consts::FRAC_PI_4;
+        let rotation = Quaterniond::new(half.cos(), 0.0, 0.0, half.sin());
+        let scale = Vector3d::new(2.0, 0.5, 1.5);
+
+        let product = Matrix4d::from_translation(translation)
+            * Matrix4d::from_rotation(rotation)
+            * Matrix4d::from_scale(scale);
+        // (x, y, z) is scaled to (2x, y/2, 3z/2), rotated to (-y/2, 2x, 3z/2), and then translated.
+        assert_matrix_eq(&product, &Matrix4d::new([
+            [0.0, -0.5, 0.0, 1.0],
+            [2.0, 0.0, 0.0, -2.0],
+            [0.0, 0.0, 1.5, 3.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]));
+    }
+
+    #[test]
+    fn test_trs_matrix_equals_constructor_product() {
+        let translation = Vector3d::new(1.0, -2.0, 3.0);
+        let rotation = {
+            let (half, n) = (0.3_f64, 3.0_f64.sqrt());
+            Quaterniond::new(half.cos(), half.sin() / n, half.sin() / n, half.sin() / n)
+        };
+        let scale = Vector3d::new(2.0, 0.5, 1.5);
+
+        let mut trs = TrsMatrix::new();
+        trs.set_translation(translation)
+            .set_rotation(rotation)
+            .set_scale(scale);
+
+        let product = Matrix4d::from_translation(translation)
+            * Matrix4d::from_rotation(rotation)
+            * Matrix4d::from_scale(scale);
+        assert_matrix_eq(&trs.compute_transformation_matrix(), &product);
+    }
+
+    #[test]
+    fn test_trs_rotation_is_applied() {
+        // 90 degrees around the y axis, which takes z to x and x to -z.
+        let half = std::f64::consts::FRAC_PI_4;
+        let rotation = Quaterniond::new(half.cos(), 0.0, half.sin(), 0.0);
+        let mut trs = TrsMatrix::new();
+        trs.set_rotation(rotation);
+        assert_matrix_eq(&trs.compute_transformation_matrix(), &Matrix4d::new([
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [-1.0, 0.0, 0.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]));
+
+        // the scale is applied first, then the rotation, then the translation.
+        trs.set_translation(Vector3d::new(1.0, 2.0, 3.0))
+            .set_scale(Vector3d::new(2.0, 3.0, 4.0));
+        assert_matrix_eq(&trs.compute_transformation_matrix(), &Matrix4d::new([
+            [0.0, 0.0, 4.0, 1.0],
+            [0.0, 3.0, 0.0, 2.0],
+            [-2.0, 0.0, 0.0, 3.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]));
+    }
+
+    #[test]
+    #[cfg(not(feature = "evaluation"))]
+    fn test_encode_scene() {
+        use crate::core::shared::ConfigType;
+        use crate::prelude::SliceReader;
+
+        let meshes = ["tetrahedron.obj", "sphere.obj"]
+            .map(|name| crate::io::obj::load_obj(format!("tests/data/{}", name)).unwrap());
+        let mut scene = Scene::new();
+        for (i, mesh) in meshes.iter().enumerate() {
+            let mesh_idx = scene.add_mesh(mesh.clone());
+            let group_idx = scene.add_mesh_group();
+            scene.get_mesh_group_mut(group_idx).unwrap().add_mesh_instance(MeshInstance::new(mesh_idx, i as i32 - 1));
+        }
+        // an empty mesh group.
+        scene.add_mesh_group();
+
+        // the root translates the tetrahedron, and its child rotates and scales the sphere.
+        let mut root = SceneNode::new();
+        let mut trs = TrsMatrix::new();
+        trs.set_translation(Vector3d::new(1.0, 2.0, 3.0));
+        root.set_trs_matrix(trs);
+        root.set_mesh_group_index(Some(0));
+        root.add_child_index(1);
+        let mut child = SceneNode::new();
+        let mut trs = TrsMatrix::new();
+        trs.set_rotation(Quaterniond::new(0.6, 0.0, 0.8, 0.0)).set_scale(Vector3d::new(2.0, 2.0, 2.0));
+        child.set_trs_matrix(trs);
+        child.set_mesh_group_index(Some(1));
+        child.add_parent_index(0);
+        // a node without meshes.
+        let empty = SceneNode::new();
+        for node in [root, child, empty] {
+            scene.add_node(node);
+        }
+        scene.add_root_node_index(0);
+        scene.add_root_node_index(2);
+
+        let mut buffer = Vec::new();
+        crate::encode::encode_scene(&scene, &mut buffer, crate::encode::Config::default()).unwrap();
+        let decoded = crate::decode::decode_scene(&mut SliceReader::new(&buffer), crate::decode::Config::default()).unwrap();
+
+        // each mesh is decoded as it is when encoded alone.
+        assert_eq!(decoded.meshes.len(), 2);
+        for (mesh, decoded_mesh) in meshes.into_iter().zip(&decoded.meshes) {
+            let mut stream = Vec::new();
+            crate::encode::encode(mesh, &mut stream, crate::encode::Config::default()).unwrap();
+            let expected = crate::decode::decode(&mut SliceReader::new(&stream), crate::decode::Config::default()).unwrap();
+            assert_eq!(decoded_mesh.get_faces(), expected.get_faces());
+            let bytes = |mesh: &Mesh| mesh.get_attributes().iter()
+                .map(|att| att.to_bytes_with_layout(crate::prelude::AttributeLayout::Interleaved))
+                .collect::<Vec<_>>();
+            assert_eq!(bytes(decoded_mesh), bytes(&expected));
+        }
+
+        assert_eq!(decoded.mesh_groups.len(), 3);
+        for (group, decoded_group) in scene.mesh_groups.iter().zip(&decoded.mesh_groups) {
+            assert_eq!(group.mesh_instances, decoded_group.mesh_instances);
+        }
+        assert_eq!(decoded.num_nodes(), 3);
+        for (node, decoded_node) in scene.nodes.iter().zip(&decoded.nodes) {
+            assert_eq!(decoded_node.get_trs_matrix().matrix().unwrap(), &node.get_trs_matrix().compute_transformation_matrix());
+            assert_eq!(decoded_node.get_mesh_group_index(), node.get_mesh_group_index());
+            assert_eq!(decoded_node.children(), node.children());
+            assert_eq!(decoded_node.parents(), node.parents());
+        }
+        assert_eq!(decoded.root_node_indices, vec![0, 2]);
+
+        // a node referring to a missing mesh group is rejected.
+        scene.get_node_mut(2).unwrap().set_mesh_group_index(Some(3));
+        let err = crate::encode::encode_scene(&scene, &mut Vec::new(), crate::encode::Config::default()).unwrap_err();
+        assert!(err.to_string().contains("out of range"), "{}", err);
+    }
+}
@@ -0,0 +1,374 @@
+//! The SIMD implementations enabled by the 'simd' feature:
+//! - the dot product and the element-wise operations of the vectors of three floats, which are the hottest case
+//!   of [crate::core::shared::Dot], [crate::core::shared::ElementWiseMul], and [crate::core::shared::ElementWiseDiv],
+//!   e.g. in the distance and the prediction code. [crate::core::shared::DataValue] routes them here.
+//! - the distances from the points to the faces of a mesh of 'f32' positions, which is the hot loop of
+//!   [crate::core::mesh::Mesh::diff_l2_norm]. Four points are measured against each face at once, one in each lane.
+//!   The quantities of the face alone are computed once as scalars and broadcast, and every lane does the operations
+//!   of [crate::utils::geom::point_to_face_distance_3d] in the same order.
+//!
+//! Every operation is done in the order of the scalar implementation, so the results are the same as the scalar ones
+//! bit by bit. SSE2 is always available on x86_64, which is the only architecture supported.
+
+use std::arch::x86_64::*;
+
+use crate::core::shared::{Cross, Dot, NdVector, PointIdx, Vector};
+
+#[inline(always)]
+pub(crate) fn dot3_f32(a: [f32; 3], b: [f32; 3]) -> f32 {
+    // The lanes are summed in the register, in the order of the scalar implementation.
+    // Safety: SSE2 is always available on x86_64. The intrinsics are safe to call in the newer compilers.
+    #[allow(unused_unsafe)]
+    unsafe {
+        let p = _mm_mul_ps(pack_f32(a, 0.0), pack_f32(b, 0.0));
+        let sum = _mm_add_ss(_mm_setzero_ps(), p);
+        let sum = _mm_add_ss(sum, _mm_shuffle_ps::<0b01>(p, p));
+        _mm_cvtss_f32(_mm_add_ss(sum, _mm_movehl_ps(p, p)))
+    }
+}
+
+#[inline(always)]
+pub(crate) fn elem_mul3_f32(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    // Safety: SSE2 is always available on x86_64. The intrinsics are safe to call in the newer compilers.
+    #[allow(unused_unsafe)]
+    unsafe { unpack_f32(_mm_mul_ps(pack_f32(a, 0.0), pack_f32(b, 0.0))) }
+}
+
+#[inline(always)]
+pub(crate) fn elem_div3_f32(a: [f32; 3], b: [f32; 3]) -> [f32; 3] {
+    // The unused lane is divided by one so that it does not raise any floating point exception.
+    // Safety: SSE2 is always available on x86_64. The intrinsics are safe to call in the newer compilers.
+    #[allow(unused_unsafe)]
+    unsafe { unpack_f32(_mm_div_ps(pack_f32(a, 0.0), pack_f32(b, 1.0))) }
+}
+
+#[inline(always)]
+unsafe fn pack_f32(v: [f32; 3], w: f32) -> __m128 {
+    _mm_set_ps(w, v[2], v[1], v[0])
+}
+
+#[inline(always)]
+unsafe fn unpack_f32(v: __m128) -> [f32; 3] {
+    [
+        _mm_cvtss_f32(v),
+        _mm_cvtss_f32(_mm_shuffle_ps::<0b01>(v, v)),
+        _mm_cvtss_f32(_mm_movehl_ps(v, v)),
+    ]
+}
+
+#[inline(always)]
+pub(crate) fn dot3_f64(a: [f64; 3], b: [f64; 3]) -> f64 {
+    let [x, y, z] = elem_mul3_f64(a, b);
+    0.0 + x + y + z
+}
+
+/// The first two components are computed in one register of two lanes, and the last one as a scalar.
+#[inline(always)]
+pub(crate) fn elem_mul3_f64(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    // Safety: SSE2 is always available on x86_64. The intrinsics are safe to call in the newer compilers.
+    #[allow(unused_unsafe)]
+    let [x, y] = unsafe { unpack_f64(_mm_mul_pd(pack_f64(a), pack_f64(b))) };
+    [x, y, a[2] * b[2]]
+}
+
+#[inline(always)]
+pub(crate) fn elem_div3_f64(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    // Safety: SSE2 is always available on x86_64. The intrinsics are safe to call in the newer compilers.
+    #[allow(unused_unsafe)]
+    let [x, y] = unsafe { unpack_f64(_mm_div_pd(pack_f64(a), pack_f64(b))) };
+    [x, y, a[2] / b[2]]
+}
+
+#[inline(always)]
+unsafe fn pack_f64(v: [f64; 3]) -> __m128d {
+    _mm_set_pd(v[1], v[0])
+}
+
+#[inline(always)]
+unsafe fn unpack_f64(v: __m128d) -> [f64; 2] {
+    [_mm_cvtsd_f64(v), _mm_cvtsd_f64(_mm_unpackhi_pd(v, v))]
+}
+
+/// The smallest distance from each point to the faces, as [crate::core::mesh::min_dist_point_to_faces]
+/// returns for the point.
+pub(crate) fn min_dist_points_to_faces_f32(
+    points: &[NdVector<3, f32>],
+    faces: &[[PointIdx; 3]],
+    pos_att: &[NdVector<3, f32>]
+) -> Vec<f32> {
+    let faces = faces.iter()
+        .map(|face| Face::new(face.map(|p| pos_att[usize::from(p)])))
+        .collect::<Vec<_>>();
+
+    let mut out = Vec::with_capacity(points.len());
+    for chunk in points.chunks(4) {
+        // The lanes beyond the points are filled with the last point, and then discarded.
+        let p = [0, 1, 2, 3].map(|i| *chunk.get(i).unwrap_or(&chunk[chunk.len() - 1]));
+        let min_dist = min_dist_lanes(p, &faces);
+        out.extend_from_slice(&min_dist[..chunk.len()]);
+    }
+    out
+}
+
+/// The scalar quantities of a face that do not depend on the point.
+struct Face {
+    vertices: [[f32; 3]; 3],
+    /// The unnormalized normal, which the inside test uses.
+    n: [f32; 3],
+    /// The normalized normal.
+    unit_n: [f32; 3],
+    /// The edge from the 'i'th vertex to the next.
+    edges: [[f32; 3]; 3],
+    /// The dot products of the edges with themselves.
+    squared_lengths: [f32; 3],
+}
+
+impl Face {
+    fn new(face: [NdVector<3, f32>; 3]) -> Self {
+        let n = (face[1] - face[0]).cross(face[2] - face[0]);
+        let edges = [0, 1, 2].map(|i| face[(i + 1) % 3] - face[i]);
+        Self {
+            vertices: face.map(components),
+            n: components(n),
+            unit_n: components(n.normalize()),
+            edges: edges.map(components),
+            squared_lengths: edges.map(|e| e.dot(e)),
+        }
+    }
+}
+
+fn components(v: NdVector<3, f32>) -> [f32; 3] {
+    [*v.get(0), *v.get(1), *v.get(2)]
+}
+
+/// Lanes of three components.
+type V = [__m128; 3];
+
+#[inline(always)]
+fn min_dist_lanes(p: [NdVector<3, f32>; 4], faces: &[Face]) -> [f32; 4] {
+    // Safety: SSE2 is always available on x86_64. The intrinsics are safe to call in the newer compilers.
+    #[allow(unused_unsafe)]
+    unsafe {
+        let p: V = [0, 1, 2].map(|k| _mm_set_ps(*p[3].get(k), *p[2].get(k), *p[1].get(k), *p[0].get(k)));
+        let mut min_dist = _mm_set1_ps(f32::MAX);
+        for face in faces {
+            let dist = dist_lanes(p, face);
+            min_dist = select(_mm_cmplt_ps(dist, min_dist), dist, min_dist);
+        }
+        let mut out = [0.0; 4];
+        _mm_storeu_ps(out.as_mut_ptr(), min_dist);
+        out
+    }
+}
+
+#[inline(always)]
+unsafe fn dist_lanes(p: V, face: &Face) -> __m128 {
+    let a = splat(face.vertices[0]);
+    let unit_n = splat(face.unit_n);
+    let signed_distance_to_plane = dot(unit_n, sub(p, a));
+
+    // The projection is inside if it is on the inner side of every edge.
+    let p_onto_plane = sub(p, mul(unit_n, signed_distance_to_plane));
+    let n = splat(face.n);
+    let mut inside = _mm_castsi128_ps(_mm_set1_epi32(-1));
+    for i in 0..3 {
+        let side = dot(cross(splat(face.edges[i]), sub(p_onto_plane, splat(face.vertices[i]))), n);
+        inside = _mm_and_ps(inside, _mm_cmpge_ps(side, _mm_setzero_ps()));
+    }
+    let abs_distance_to_plane = _mm_andnot_ps(_mm_set1_ps(-0.0), signed_distance_to_plane);
+
+    let mut distance_to_edges = _mm_set1_ps(f32::MAX);
+    for i in 0..3 {
+        let dist = segment_dist_lanes(p, splat(face.vertices[i]), splat(face.edges[i]), face.squared_lengths[i]);
+        distance_to_edges = select(_mm_cmplt_ps(dist, distance_to_edges), dist, distance_to_edges);
+    }
+    select(inside, abs_distance_to_plane, distance_to_edges)
+}
+
+/// The lanes of 'point_to_segment_distance_3d'.
+#[inline(always)]
+unsafe fn segment_dist_lanes(p: V, start: V, dir: V, squared_length: f32) -> __m128 {
+    let t = if squared_length > 0.0 {
+        let t = _mm_div_ps(dot(sub(p, start), dir), _mm_set1_ps(squared_length));
+        // 't' is kept as it is if it is not a number.
+        let t = select(_mm_cmpgt_ps(t, _mm_set1_ps(1.0)), _mm_set1_ps(1.0), t);
+        select(_mm_cmplt_ps(t, _mm_setzero_ps()), _mm_setzero_ps(), t)
+    } else {
+        _mm_setzero_ps()
+    };
+    norm(sub(p, add(start, mul(dir, t))))
+}
+
+#[inline(always)]
+unsafe fn splat(v: [f32; 3]) -> V {
+    v.map(|c| _mm_set1_ps(c))
+}
+
+#[inline(always)]
+unsafe fn select(mask: __m128, a: __m128, b: __m128) -> __m128 {
+    _mm_or_ps(_mm_and_ps(mask, a), _mm_andnot_ps(mask, b))
+}
+
+#[inline(always)]
+unsafe fn add(a: V, b: V) -> V {
+    [_mm_add_ps(a[0], b[0]), _mm_add_ps(a[1], b[1]), _mm_add_ps(a[2], b[2])]
+}
+
+#[inline(always)]
+unsafe fn sub(a: V, b: V) -> V {
+    [_mm_sub_ps(a[0], b[0]), _mm_sub_ps(a[1], b[1]), _mm_sub_ps(a[2], b[2])]
+}
+
+#[inline(always)]
+unsafe fn mul(a: V, s: __m128) -> V {
+    [_mm_mul_ps(a[0], s), _mm_mul_ps(a[1], s), _mm_mul_ps(a[2], s)]
+}
+
+/// The dot product summed from zero in the order of the components, as [Dot] does.
+#[inline(always)]
+unsafe fn dot(a: V, b: V) -> __m128 {
+    let mut sum = _mm_setzero_ps();
+    for k in 0..3 {
+        sum = _mm_add_ps(sum, _mm_mul_ps(a[k], b[k]));
+    }
+    sum
+}
+
+#[inline(always)]
+unsafe fn cross(a: V, b: V) -> V {
+    [
+        _mm_sub_ps(_mm_mul_ps(a[1], b[2]), _mm_mul_ps(a[2], b[1])),
+        _mm_sub_ps(_mm_mul_ps(a[2], b[0]), _mm_mul_ps(a[0], b[2])),
+        _mm_sub_ps(_mm_mul_ps(a[0], b[1]), _mm_mul_ps(a[1], b[0])),
+    ]
+}
+
+#[inline(always)]
+unsafe fn norm(a: V) -> __m128 {
+    _mm_sqrt_ps(dot(a, a))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::mesh::min_dist_point_to_faces;
+
+    /// A xorshift generator, so that the test is reproducible without any dependency.
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        /// Mostly ordinary values of various magnitudes and signs, with a few special ones.
+        fn next_f64(&mut self) -> f64 {
+            const SPECIAL: [f64; 6] = [0.0, -0.0, f64::INFINITY, f64::NEG_INFINITY, f64::MIN_POSITIVE, f64::MAX];
+            let r = self.next();
+            if r % 16 == 0 {
+                return SPECIAL[(r >> 8) as usize % SPECIAL.len()];
+            }
+            let mantissa = (r >> 11) as f64 / (1_u64 << 53) as f64 - 0.5;
+            mantissa * 2.0_f64.powi((r % 64) as i32 - 32)
+        }
+
+        /// Mostly ordinary values of various magnitudes and signs, with a few special ones.
+        fn next_f32(&mut self) -> f32 {
+            const SPECIAL: [f32; 4] = [0.0, -0.0, 1.0, f32::MIN_POSITIVE];
+            let r = self.next();
+            if r % 16 == 0 {
+                return SPECIAL[(r >> 8) as usize % SPECIAL.len()];
+            }
+            let mantissa = (r >> 11) as f32 / (1_u64 << 53) as f32 - 0.5;
+            mantissa * 2.0_f32.powi((r % 16) as i32 - 8)
+        }
+    }
+
+    /// The scalar implementation, in the order of the operations of the 'nd_vector' macro.
+    macro_rules! impl_scalar {
+        ($t:ty, $dot3:ident, $elem_mul3:ident, $elem_div3:ident) => {
+            fn $dot3(a: [$t; 3], b: [$t; 3]) -> $t {
+                0.0 + a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+            }
+
+            fn $elem_mul3(a: [$t; 3], b: [$t; 3]) -> [$t; 3] {
+                [a[0] * b[0], a[1] * b[1], a[2] * b[2]]
+            }
+
+            fn $elem_div3(a: [$t; 3], b: [$t; 3]) -> [$t; 3] {
+                [a[0] / b[0], a[1] / b[1], a[2] / b[2]]
+            }
+        };
+    }
+
+    impl_scalar!(f32, scalar_dot3_f32, scalar_elem_mul3_f32, scalar_elem_div3_f32);
+    impl_scalar!(f64, scalar_dot3_f64, scalar_elem_mul3_f64, scalar_elem_div3_f64);
+
+    fn same_f32(a: f32, b: f32) -> bool {
+        a.to_bits() == b.to_bits() || (a.is_nan() && b.is_nan())
+    }
+
+    fn same_f64(a: f64, b: f64) -> bool {
+        a.to_bits() == b.to_bits() || (a.is_nan() && b.is_nan())
+    }
+
+    #[test]
+    fn test_vector_ops_equal_scalar() {
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+        for _ in 0..100_000 {
+            let a = [(); 3].map(|_| rng.next_f64());
+            let b = [(); 3].map(|_| rng.next_f64());
+
+            assert!(same_f64(dot3_f64(a, b), scalar_dot3_f64(a, b)), "{:?} . {:?}", a, b);
+            let (simd, scalar) = (elem_mul3_f64(a, b), scalar_elem_mul3_f64(a, b));
+            assert!(simd.iter().zip(&scalar).all(|(&s, &t)| same_f64(s, t)), "{:?} * {:?}", a, b);
+            let (simd, scalar) = (elem_div3_f64(a, b), scalar_elem_div3_f64(a, b));
+            assert!(simd.iter().zip(&scalar).all(|(&s, &t)| same_f64(s, t)), "{:?} / {:?}", a, b);
+
+            let (a, b) = (a.map(|x| x as f32), b.map(|x| x as f32));
+            assert!(same_f32(dot3_f32(a, b), scalar_dot3_f32(a, b)), "{:?} . {:?}", a, b);
+            let (simd, scalar) = (elem_mul3_f32(a, b), scalar_elem_mul3_f32(a, b));
+            assert!(simd.iter().zip(&scalar).all(|(&s, &t)| same_f32(s, t)), "{:?} * {:?}", a, b);
+            let (simd, scalar) = (elem_div3_f32(a, b), scalar_elem_div3_f32(a, b));
+            assert!(simd.iter().zip(&scalar).all(|(&s, &t)| same_f32(s, t)), "{:?} / {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_vector_ops_use_simd() {
+        use crate::core::shared::{ElementWiseDiv, ElementWiseMul};
+        let (a, b) = ([1.5_f32, -2.0, 0.25], [4.0_f32, 0.5, -8.0]);
+        assert_eq!(NdVector::from(a).dot(NdVector::from(b)), dot3_f32(a, b));
+        assert_eq!(NdVector::from(a).elem_mul(NdVector::from(b)), NdVector::from(elem_mul3_f32(a, b)));
+        let (a, b) = (a.map(f64::from), b.map(f64::from));
+        assert_eq!(NdVector::from(a).dot(NdVector::from(b)), dot3_f64(a, b));
+        assert_eq!(NdVector::from(a).elem_div(NdVector::from(b)), NdVector::from(elem_div3_f64(a, b)));
+    }
+
+    #[test]
+    fn test_simd_equals_scalar() {
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+        for num_points in [1, 3, 4, 9, 50] {
+            let pos_att = (0..30)
+                .map(|_| NdVector::from([(); 3].map(|_| rng.next_f32())))
+                .collect::<Vec<_>>();
+            // Some of the faces are degenerate, with a repeated vertex.
+            let faces = (0..40)
+                .map(|_| [(); 3].map(|_| PointIdx::from(rng.next() as usize % pos_att.len())))
+                .collect::<Vec<_>>();
+            let points = (0..num_points)
+                .map(|_| NdVector::from([(); 3].map(|_| rng.next_f32())))
+                .collect::<Vec<_>>();
+
+            let simd = min_dist_points_to_faces_f32(&points, &faces, &pos_att);
+            for (p, d) in points.iter().zip(simd) {
+                let scalar = min_dist_point_to_faces(*p, &faces, &pos_att);
+                assert_eq!(d.to_bits(), scalar.to_bits(), "{:?}: {} != {}", p, d, scalar);
+            }
+        }
+    }
+}
@@ -1,215 +1,400 @@
-use std::ops;
-
-use crate::core::attribute::{
-    Attribute, AttributeId, AttributeType, ComponentDataType, MaybeInitAttribute 
-};
-
-use crate::core::bit_coder::ReaderErr;
-use crate::core::shared::{DataValue, NdVector, Vector};
+use crate::core::attribute::{AttributeId, ComponentDataType};
+use crate::core::corner_table::GenericCornerTable;
+use crate::core::shared::{AttributeValueIdx, CornerIdx, DataValue, NdVector, Vector, VecPointIdx, VertexIdx};
 use crate::debug_expect;
-use crate::prelude::{ByteReader, ConfigType};
-use crate::shared::attribute::{prediction_scheme::PredictionScheme, Portable};
+use crate::decode::connectivity::{self, corner_table::DecodedCornerTable};
+use crate::decode::entropy::symbol_coding::decode_symbols;
+use crate::decode::header::Header;
+use crate::encode::attribute::attribute_encoder::SIGNED_RANS_ENCODING;
+use crate::encode::attribute::portabilization::{palette, PortabilizationType};
+use crate::encode::attribute::portabilization::to_bits::symbols_to_bits;
+use crate::encode::attribute::portabilization::wrapped_quantization;
+use crate::encode::attribute::prediction_transform::geom::octahedral_inverse_transform;
+use crate::encode::attribute::prediction_transform::PredictionTransformType;
+use crate::encode::attribute::supports_num_components;
+use crate::encode::entropy::symbol_coding::merge_signs;
+use crate::prelude::{Attribute, AttributeType, ByteReader};
+use crate::shared::attribute::handedness::join_handedness;
+use crate::shared::attribute::portabilization::quantization_metadata::{QuantizationMetadata, SanityBounds};
+use crate::shared::attribute::prediction_scheme::{PredictionScheme, PredictionSchemeType};
+use crate::shared::attribute::sequence::sequence_in_vertex_order;
+use crate::shared::attribute::Portable;
+use crate::utils::bit_coder::leb128_read;
 
 use super::inverse_prediction_transform::InversePredictionTransform;
+use super::{AttributeDescriptor, Err};
 
-pub(super) struct AttributeDecoder<'parents, 'reader, R> {
-    att: MaybeInitAttribute,
-	cfg: Config,
-    reader: &'reader mut R,
-    parents: Vec<&'parents Attribute>,
+/// The metadata of the portabilization, needed to restore the values from their portabilized form.
+enum PortabilizationMetadata {
+    Quantization(QuantizationMetadata),
+    Octahedral { quantization_bits: u8 },
+    /// The bytes of the values of the palette, one after another.
+    Palette(Vec<u8>),
+    ToBits,
+    /// The smallest wrap count of each component and the bits of the fractional parts.
+    WrappedQuantization { min_wraps: Vec<i32>, quantization_bits: u8 },
 }
 
-impl<'parents, 'reader, R> AttributeDecoder<'parents, 'reader, R> 
-    where R: ByteReader,
-{
-    pub(super) fn new_and_init(
-        cfg: Config,
-        reader: &'reader mut R,
-        parents_pool: &'parents [Attribute],
-    ) -> Result<Self, Err> {
-        debug_expect!("Start of Attribute Metadata", reader);
-        let id = AttributeId::new(reader.read_u16()? as usize);
-        let att_type = AttributeType::from_id(reader.read_u8()? as usize);
-        let len = reader.read_u64()? as usize;
-        let component_type = ComponentDataType::from_id(reader.read_u8()? as usize)
-            .map_err(|_| Err::ComponentUnwrapErr)?;
-        let num_components = reader.read_u8()? as usize;
-        let num_parents = reader.read_u8()? as usize;
-        let mut parents = Vec::with_capacity(num_parents);
-        let mut parents_ids = Vec::with_capacity(num_parents);
-        for _ in 0..num_parents {
-            let parent_id = reader.read_u16()? as usize;
-            parents_ids.push(AttributeId::new(parent_id));
-            parents.push(&parents_pool[parent_id]);
-        }
-        debug_expect!("End of Attribute Metadata", reader);
+/// Inverse of [crate::encode::attribute::attribute_encoder::AttributeEncoder]. Decodes the values of an attribute
+/// in the order of the traversal of its corner table.
+pub(super) struct AttributeDecoder<'decoder> {
+    descriptor: AttributeDescriptor,
+    corner_table: &'decoder DecodedCornerTable,
+    conn: &'decoder connectivity::Output,
+    /// The portabilized attributes decoded before this one.
+    decoded: &'decoder [Attribute],
+    header: &'decoder Header,
+    sanity_bounds: Option<SanityBounds>,
+    /// The order of the position vertices that the values are encoded in, if it is given by the user.
+    vertex_order: Option<&'decoder [VertexIdx]>,
+}
 
-        let att = MaybeInitAttribute::new(
-            id,
-            att_type,
-            len,
-            component_type,
-            num_components,
-            parents_ids,
-        );
+impl<'decoder> AttributeDecoder<'decoder> {
+    pub(super) fn new(
+        descriptor: AttributeDescriptor,
+        corner_table: &'decoder DecodedCornerTable,
+        conn: &'decoder connectivity::Output,
+        decoded: &'decoder [Attribute],
+        header: &'decoder Header,
+        sanity_bounds: Option<SanityBounds>,
+    ) -> Self {
+        Self { descriptor, corner_table, conn, decoded, header, sanity_bounds, vertex_order: None }
+    }
 
-        Ok( 
-            Self {
-                att,
-                cfg,
-                reader,
-                parents,
-            }
-        )
+    /// Decodes the values in the given order of the position vertices instead of the order of the traversal.
+    pub(super) fn with_vertex_order(mut self, vertex_order: &'decoder [VertexIdx]) -> Self {
+        self.vertex_order = Some(vertex_order);
+        self
     }
 
-    pub(super) fn decode(mut self) -> Result<Attribute, Err> {
-        match self.att.get_component_type() {
-            ComponentDataType::F32 => {
-                self.unpack_num_components::<f32>()?
-            }
-            ComponentDataType::F64 => {
-                self.unpack_num_components::<f64>()?
-            }
-            ComponentDataType::U8 => {
-                self.unpack_num_components::<u8>()?
-            }
-            ComponentDataType::U16 => {
-                self.unpack_num_components::<u16>()?
-            }
-            ComponentDataType::U32 => {
-                self.unpack_num_components::<u32>()?
-            }
-            ComponentDataType::U64 => {
-                self.unpack_num_components::<u64>()?
-            }
+    /// Decodes the attribute, returning it together with its portabilized form, which the predictions
+    /// of the following attributes refer to.
+    pub(super) fn decode<R>(self, reader: &mut R) -> Result<(Attribute, Attribute), Err>
+        where R: ByteReader
+    {
+        let prediction = PredictionSchemeType::read_from(reader)?;
+        let transform_id = reader.read_u8()?;
+        let transform = PredictionTransformType::from_id(transform_id)
+            .ok_or(Err::InvalidPredictionTransformId(transform_id))?;
+
+        let sequence = self.conn.traversal_sequence(self.corner_table);
+        let sequence = match self.vertex_order {
+            Some(order) => sequence_in_vertex_order(&self.conn.corner_table, sequence, order),
+            None => sequence,
         };
 
-        Ok(<Attribute as From<MaybeInitAttribute>>::from(self.att))
-    }
+        let descriptor = &self.descriptor;
+        let is_raw_bits = descriptor.portabilization == PortabilizationType::ToBits
+            && prediction == PredictionSchemeType::NoPrediction
+            && transform == PredictionTransformType::NoTransform
+            && (descriptor.component_type.is_float() || !supports_num_components(descriptor.ty, descriptor.num_components));
+        if is_raw_bits {
+            let att = self.decode_raw_bits(&sequence, reader)?;
+            return Ok((att.clone(), att));
+        }
 
-    fn unpack_num_components<T>(&mut self) -> Result<(), Err>
-        where 
-            T: DataValue + Copy,
-            NdVector<1, T>: Vector,
-            NdVector<2, T>: Vector,
-            NdVector<3, T>: Vector,
-            NdVector<4, T>: Vector
-    {
-        match self.att.get_num_components() {
-            1 => {
-                self.decode_impl::<1,_>()
-            }
-            2 => {
-                self.decode_impl::<2,_>()
-            }
-            3 => {
-                self.decode_impl::<3,_>()
-            }
-            4 => {
-                self.decode_impl::<4,_>()
-            }
-            num_components => {
-                Err(Err::GotUnsupportedNumComponents(num_components))
-            }
+        let num_port_components = match descriptor.portabilization {
+            PortabilizationType::OctahedralQuantization => 2,
+            PortabilizationType::Palette => 1,
+            _ => descriptor.num_components,
+        };
+        match num_port_components {
+            1 => self.decode_portabilized::<R, 1>(&sequence, prediction, transform, reader),
+            2 => self.decode_portabilized::<R, 2>(&sequence, prediction, transform, reader),
+            3 => self.decode_portabilized::<R, 3>(&sequence, prediction, transform, reader),
+            4 => self.decode_portabilized::<R, 4>(&sequence, prediction, transform, reader),
+            n => Err(Err::UnsupportedNumComponents(n)),
         }
     }
 
-    fn decode_impl<const N: usize, T>(&mut self) -> Result<(), Err>
-        where 
-            T: DataValue + Copy,
-            NdVector<N, T>: Vector + Portable,
-    {
-        // get groups
-        debug_expect!("Start of Transform Metadata", self.reader);
-        let num_groups = self.reader.read_u8()? as usize;
-        let mut groups: Vec<Group<'_, NdVector<N,T>>> = Vec::new();
-        for _ in 0..num_groups {
-            let group = Group::new(self.reader, &self.parents)?;
-            groups.push(group);
+    /// The point to value map of the attribute, where the values are numbered in the order of 'sequence'.
+    fn point_to_value_map(&self, sequence: &[CornerIdx]) -> VecPointIdx<AttributeValueIdx> {
+        let mut vertex_to_value = vec![AttributeValueIdx::from(0); self.corner_table.num_vertices()];
+        for (i, &c) in sequence.iter().enumerate() {
+            vertex_to_value[usize::from(self.corner_table.vertex_idx(c))] = AttributeValueIdx::from(i);
         }
-        debug_expect!("End of Transform Metadata", self.reader);
-
-        let mut num_encoded_values = 0;
-        while num_encoded_values < self.att.len() {
-            debug_expect!("Start of a Range", self.reader);
-            let group_id = self.reader.read_u8()? as usize;
-            let group = &mut groups[group_id];
-            let range_size = self.reader.read_u64()? as usize;
-            let range = num_encoded_values..(num_encoded_values + range_size);
-            group.inverse(range, &mut self.att, self.reader);
-            num_encoded_values += range_size;
+        let mut map = vec![AttributeValueIdx::from(0); self.conn.num_points];
+        for c in (0..self.corner_table.num_corners()).map(CornerIdx::from) {
+            map[usize::from(self.corner_table.point_idx(c))] = vertex_to_value[usize::from(self.corner_table.vertex_idx(c))];
         }
-        Ok(())
+        VecPointIdx::from(map)
     }
-}
 
-struct Group<'parents, Data> 
-    where Data: Vector + Portable
-{
-    prediction: PredictionScheme<'parents, Data>,
-    inverse_transform: InversePredictionTransform<Data>,
-}
+    /// Creates the attribute from the bytes of its values, in the order of 'sequence'.
+    fn attribute_from_bytes(&self, bytes: Vec<u8>, num_components: usize, sequence: &[CornerIdx], parents: Vec<AttributeId>) -> Attribute {
+        let descriptor = &self.descriptor;
+        let mut att = Attribute::from_bytes(
+            descriptor.id,
+            bytes,
+            descriptor.component_type,
+            num_components,
+            descriptor.ty,
+            descriptor.domain,
+        );
+        att.set_parents(parents);
+        att.set_point_to_att_val_map(Some(self.point_to_value_map(sequence)));
+        att
+    }
 
-impl<'parents, Data> Group<'parents, Data> 
-    where Data: Vector + Portable
-{
-    fn new<R>(reader: &mut R, parents: &'parents [&Attribute]) -> Result<Self, Err> 
+    /// Inverse of 'encode_raw_bits' of the encoder.
+    fn decode_raw_bits<R>(&self, sequence: &[CornerIdx], reader: &mut R) -> Result<Attribute, Err>
         where R: ByteReader
     {
-        let prediction = PredictionScheme::read_from(reader, parents)
-            .map_err(|x| Err::InvalidPredictionSchemeId(x))?;
-        let inverse_transform = InversePredictionTransform::new(reader)
-            .map_err(|err| Err::InvalidInversePredictionTransformId(err))?;
-        
-        Ok(
-            Self {
-                prediction,
-                inverse_transform,
-            }
-        )
+        let descriptor = &self.descriptor;
+        let value_size = descriptor.component_type.size() * descriptor.num_components;
+        let num_words = value_size.div_ceil(4);
+        if reader.read_u8()? == 0 {
+            return Err(Err::UnsupportedFeature("raw bits without rANS coding"));
+        }
+        let symbols = decode_symbols(sequence.len() * num_words, num_words, reader)?;
+        let bytes = symbols.chunks(num_words)
+            .flat_map(|words| symbols_to_bits(words, value_size))
+            .collect();
+        Ok(self.attribute_from_bytes(bytes, descriptor.num_components, sequence, Vec::new()))
     }
 
-    fn inverse<R>(
-        &mut self,
-        range: ops::Range<usize>,
-        att: &mut MaybeInitAttribute,
-        reader: &mut R
-    )
-        where R: ByteReader
+    fn decode_portabilized<R, const N: usize>(
+        &self,
+        sequence: &[CornerIdx],
+        prediction: PredictionSchemeType,
+        transform: PredictionTransformType,
+        reader: &mut R,
+    ) -> Result<(Attribute, Attribute), Err>
+        where
+            R: ByteReader,
+            NdVector<N, i32>: Vector<N, Component = i32> + Portable,
     {
-        for idx in range {
-            let slice =  unsafe{ &att.as_slice_unchecked::<Data>()[..idx] };
-            let pred = self.prediction.predict(slice);
-            att.write(idx, self.inverse_transform.inverse(pred, reader));
+        let num_values = sequence.len();
+        let corrections = read_corrections::<R, N>(num_values, reader)?;
+
+        let mut inverse_transform = InversePredictionTransform::<N>::new(transform)?;
+        // The metadata of the prediction and the transform are in the order of the encoder.
+        let (parent_ids, mut prediction_scheme) = match prediction {
+            PredictionSchemeType::MeshNormalPrediction => {
+                inverse_transform.read_metadata(reader)?;
+                let parent_ids = self.position_id().into_iter().collect::<Vec<_>>();
+                let mut scheme = self.prediction_scheme(prediction, &parent_ids)?;
+                if let Some(max) = inverse_transform.octahedral_max() {
+                    scheme.set_octahedral_max_quantized(max);
+                }
+                scheme.decode_prediction_metadata(num_values, reader)?;
+                (parent_ids, scheme)
+            },
+            PredictionSchemeType::MeshPredictionForTextureCoordinates => {
+                let parent_ids = self.position_id().into_iter().collect::<Vec<_>>();
+                let mut scheme = self.prediction_scheme(prediction, &parent_ids)?;
+                scheme.decode_prediction_metadata(num_values, reader)?;
+                inverse_transform.read_metadata(reader)?;
+                (parent_ids, scheme)
+            },
+            PredictionSchemeType::JointPrediction => {
+                let parent_ids = vec![AttributeId::new(leb128_read(reader)? as usize)];
+                let scheme = self.prediction_scheme(prediction, &parent_ids)?;
+                inverse_transform.read_metadata(reader)?;
+                (parent_ids, scheme)
+            },
+            _ => {
+                inverse_transform.read_metadata(reader)?;
+                (Vec::new(), self.prediction_scheme(prediction, &[])?)
+            },
+        };
+
+        debug_expect!("Start of Portabilization Metadata", reader, self.header.contains_debug_markers)?;
+        let portabilization_metadata = match self.descriptor.portabilization {
+            PortabilizationType::QuantizationCoordinateWise => PortabilizationMetadata::Quantization(
+                QuantizationMetadata::read_from(reader, N, self.sanity_bounds)?
+            ),
+            PortabilizationType::OctahedralQuantization => PortabilizationMetadata::Octahedral {
+                quantization_bits: match reader.read_u8()? {
+                    bits @ 1..=30 => bits,
+                    _ => return Err(Err::InvalidTransformMetadata("the quantization bits of the octahedral quantization are out of range")),
+                },
+            },
+            PortabilizationType::Palette => PortabilizationMetadata::Palette(self.read_palette(reader)?),
+            PortabilizationType::WrappedQuantization => PortabilizationMetadata::WrappedQuantization {
+                min_wraps: (0..N).map(|_| reader.read_u32().map(|w| w as i32)).collect::<Result<_, _>>()?,
+                quantization_bits: match reader.read_u8()? {
+                    bits @ 1..=30 => bits,
+                    _ => return Err(Err::InvalidTransformMetadata("the quantization bits of the wrapped quantization are out of range")),
+                },
+            },
+            _ => PortabilizationMetadata::ToBits,
+        };
+        debug_expect!("End of Portabilization Metadata", reader, self.header.contains_debug_markers)?;
+
+        let is_split_tangent = self.descriptor.ty == AttributeType::Tangent
+            && self.descriptor.num_components == 4
+            && self.descriptor.portabilization == PortabilizationType::OctahedralQuantization;
+        let handedness = if is_split_tangent {
+            Some(decode_symbols(num_values, 1, reader)?.into_iter().map(|s| s != 0).collect::<Vec<_>>())
+        } else {
+            None
+        };
+
+        // Predict the values in the order of the encoder, each from the values decoded before it.
+        let mut port_att = Attribute::from_without_removing_duplicates(
+            self.descriptor.id,
+            vec![NdVector::<N, i32>::zero(); num_values],
+            self.descriptor.ty,
+            self.descriptor.domain,
+            parent_ids.clone(),
+        );
+        port_att.set_point_to_att_val_map(Some(self.point_to_value_map(sequence)));
+        let mut sequence_record = Vec::with_capacity(num_values);
+        for (i, (&c, &corr)) in sequence.iter().zip(corrections.iter()).enumerate() {
+            let pred = prediction_scheme.predict(c, &sequence_record, &port_att);
+            port_att.unique_vals_as_slice_mut::<NdVector<N, i32>, N>()[i] = inverse_transform.inverse(pred, corr);
+            sequence_record.push(self.corner_table.vertex_idx(c));
         }
+
+        let values = port_att.unique_vals_as_slice::<NdVector<N, i32>, N>();
+        let att = match portabilization_metadata {
+            PortabilizationMetadata::Quantization(metadata) => {
+                let values = values.iter()
+                    .flat_map(|v| (0..N).map(|i| metadata.dequantize(i, *v.get(i)) as f64).collect::<Vec<_>>());
+                let bytes = to_component_bytes(values, self.descriptor.component_type)?;
+                self.attribute_from_bytes(bytes, N, sequence, parent_ids)
+            },
+            PortabilizationMetadata::Octahedral { quantization_bits } => {
+                let max_quantized = ((1_u32 << (quantization_bits - 1)) - 1).max(1) as f32;
+                let values = values.iter()
+                    .flat_map(|v| {
+                        let oct = NdVector::from([*v.get(0) as f32 / max_quantized - 1.0, *v.get(1) as f32 / max_quantized - 1.0]);
+                        // Safety: the output is three dimensional.
+                        let normal: NdVector<3, f64> = unsafe { octahedral_inverse_transform(oct) };
+                        (0..3).map(move |i| *normal.get(i))
+                    });
+                let bytes = to_component_bytes(values, self.descriptor.component_type)?;
+                let att = self.attribute_from_bytes(bytes, 3, sequence, parent_ids);
+                match handedness {
+                    Some(handedness) => join_handedness(att, &handedness),
+                    None => att,
+                }
+            },
+            PortabilizationMetadata::Palette(palette) => {
+                let value_size = self.descriptor.component_type.size() * self.descriptor.num_components;
+                let mut bytes = Vec::with_capacity(values.len() * value_size);
+                for v in values {
+                    let index = *v.get(0);
+                    let value = palette::lookup(&palette, value_size, index)
+                        .ok_or(Err::InvalidPaletteIndex(index))?;
+                    bytes.extend_from_slice(value);
+                }
+                self.attribute_from_bytes(bytes, self.descriptor.num_components, sequence, parent_ids)
+            },
+            PortabilizationMetadata::WrappedQuantization { min_wraps, quantization_bits } => {
+                let values = values.iter()
+                    .flat_map(|v| (0..N).map(|i| wrapped_quantization::dequantize(*v.get(i), min_wraps[i], quantization_bits)).collect::<Vec<_>>());
+                let bytes = to_component_bytes(values, self.descriptor.component_type)?;
+                self.attribute_from_bytes(bytes, N, sequence, parent_ids)
+            },
+            PortabilizationMetadata::ToBits => {
+                // The components of u32 above 'i32::MAX' are stored as their bits.
+                let is_u32 = self.descriptor.component_type == ComponentDataType::U32;
+                let values = values.iter()
+                    .flat_map(|v| (0..N).map(|i| if is_u32 { *v.get(i) as u32 as f64 } else { *v.get(i) as f64 }).collect::<Vec<_>>());
+                let bytes = to_component_bytes(values, self.descriptor.component_type)?;
+                self.attribute_from_bytes(bytes, N, sequence, parent_ids)
+            },
+        };
+        Ok((att, port_att))
     }
-}
 
-#[derive(Debug, Clone)]
-pub struct Config {
-    leave_quantized: bool,
-}
+    /// Reads the palette written by [crate::encode::attribute::portabilization::palette::Palette].
+    fn read_palette<R>(&self, reader: &mut R) -> Result<Vec<u8>, Err>
+        where R: ByteReader
+    {
+        let value_size = self.descriptor.component_type.size() * self.descriptor.num_components;
+        let num_values = reader.read_u32()? as usize;
+        (0..num_values * value_size)
+            .map(|_| reader.read_u8().map_err(Err::from))
+            .collect()
+    }
+
+    /// The id of the position attribute decoded so far, which the geometric predictions refer to.
+    fn position_id(&self) -> Option<AttributeId> {
+        self.decoded.iter()
+            .find(|att| att.get_attribute_type() == AttributeType::Position)
+            .map(|att| att.get_id())
+    }
 
-impl ConfigType for Config {
-    fn default() -> Self {
-        Self {
-            leave_quantized: false,
+    fn prediction_scheme<const N: usize>(&self, ty: PredictionSchemeType, parent_ids: &[AttributeId])
+        -> Result<PredictionScheme<'decoder, DecodedCornerTable, N>, Err>
+        where NdVector<N, i32>: Vector<N, Component = i32>,
+    {
+        match ty {
+            PredictionSchemeType::DerivativePrediction
+            | PredictionSchemeType::Invalid => return Err(Err::UnsupportedPredictionScheme(ty)),
+            _ => {},
         }
+        let decoded = self.decoded;
+        let parents = parent_ids.iter()
+            .map(|id| decoded.iter().find(|att| att.get_id() == *id).ok_or(Err::InvalidParentId(id.as_usize())))
+            .collect::<Result<Vec<_>, _>>()?;
+        let needs_position = matches!(ty, PredictionSchemeType::MeshNormalPrediction | PredictionSchemeType::MeshPredictionForTextureCoordinates);
+        if needs_position && parents.is_empty() {
+            return Err(Err::UnsupportedFeature("a geometric prediction without the positions"));
+        }
+        // The joint prediction reads the portabilized values of the parent as its own.
+        let is_joint_parent = |att: &Attribute| att.get_num_components() == N && att.get_component_type() == ComponentDataType::I32;
+        if ty == PredictionSchemeType::JointPrediction && !(parents.len() == 1 && is_joint_parent(parents[0])) {
+            return Err(Err::UnsupportedFeature("a joint prediction from a parent of another layout"));
+        }
+        Ok(PredictionScheme::new(ty, &parents, self.corner_table))
     }
 }
 
-#[remain::sorted]
-#[derive(Debug, thiserror::Error)]
-pub enum Err {
-    #[error("Component Type Unwrap Failed.")]
-    ComponentUnwrapErr,
-    #[error("Got unsupported number of components: {0}")]
-    GotUnsupportedNumComponents(usize),
-    #[error("Invalid Inverse Prediction Transform ID: {0}")]
-    InvalidInversePredictionTransformId(#[from] super::inverse_prediction_transform::Err),
-    #[error("Invalid Prediction Scheme ID: {0}")]
-    InvalidPredictionSchemeId(usize),
-    #[error("Not enough data in stream.")]
-    NotEnoughData(#[from] ReaderErr),
+/// Reads the corrections of the values, coded as written by 'encode_portabilized' of the encoder.
+fn read_corrections<R, const N: usize>(num_values: usize, reader: &mut R) -> Result<Vec<NdVector<N, i32>>, Err>
+    where
+        R: ByteReader,
+        NdVector<N, i32>: Vector<N, Component = i32> + Portable,
+{
+    let symbols = match reader.read_u8()? {
+        0 => {
+            return (0..num_values)
+                .map(|_| NdVector::<N, i32>::read_from(reader).map_err(Err::from))
+                .collect();
+        },
+        SIGNED_RANS_ENCODING => {
+            let magnitudes = decode_symbols(num_values * N, N, reader)?;
+            let num_signs = magnitudes.iter().filter(|&&m| m > 0).count();
+            let signs = if num_signs > 0 {
+                decode_symbols(num_signs, 1, reader)?
+            } else {
+                Vec::new()
+            };
+            merge_signs(&magnitudes, &signs)?
+        },
+        _ => decode_symbols(num_values * N, N, reader)?,
+    };
+    Ok(symbols.chunks(N)
+        .map(|s| {
+            let mut v = NdVector::<N, i32>::zero();
+            for (i, &s) in s.iter().enumerate() {
+                *v.get_mut(i) = s as i32;
+            }
+            v
+        })
+        .collect())
+}
+
+/// Converts the values into the little-endian bytes of the given component type.
+fn to_component_bytes<I>(values: I, component_type: ComponentDataType) -> Result<Vec<u8>, Err>
+    where I: Iterator<Item = f64>
+{
+    let bytes = match component_type {
+        ComponentDataType::F32 => values.flat_map(|v| f32::from_f64(v).to_bytes()).collect(),
+        ComponentDataType::F64 => values.flat_map(|v| v.to_bytes()).collect(),
+        ComponentDataType::U8 => values.flat_map(|v| u8::from_f64(v).to_bytes()).collect(),
+        ComponentDataType::U16 => values.flat_map(|v| u16::from_f64(v).to_bytes()).collect(),
+        ComponentDataType::U32 => values.flat_map(|v| u32::from_f64(v).to_bytes()).collect(),
+        ComponentDataType::U64 => values.flat_map(|v| u64::from_f64(v).to_bytes()).collect(),
+        ComponentDataType::I8 => values.flat_map(|v| i8::from_f64(v).to_bytes()).collect(),
+        ComponentDataType::I16 => values.flat_map(|v| i16::from_f64(v).to_bytes()).collect(),
+        ComponentDataType::I32 => values.flat_map(|v| i32::from_f64(v).to_bytes()).collect(),
+        ComponentDataType::I64 => values.flat_map(|v| i64::from_f64(v).to_bytes()).collect(),
+        ComponentDataType::F16 | ComponentDataType::Invalid => return Err(Err::UnsupportedDataType(component_type)),
+    };
+    Ok(bytes)
 }
@@ -0,0 +1,218 @@
+use crate::core::shared::{NdVector, Vector};
+use crate::encode::attribute::prediction_transform::PredictionTransformType;
+use crate::encode::attribute::prediction_transform::geom::invert_diamond;
+use crate::prelude::ByteReader;
+use crate::utils::from_positive_i32_vec;
+
+use super::Err;
+
+/// Inverse of [crate::encode::attribute::prediction_transform::PredictionTransform]. Restores the values
+/// from their predictions and the corrections computed by the encoder.
+pub(crate) enum InversePredictionTransform<const N: usize> {
+    NoTransform,
+    Difference,
+    WrappedDifference {
+        min: i32,
+        max: i32,
+    },
+    OctahedralOrthogonal {
+        max: i32,
+        center: i32,
+    },
+    OctahedralReflection {
+        max: i32,
+        center: i32,
+    },
+}
+
+impl<const N: usize> InversePredictionTransform<N>
+    where NdVector<N, i32>: Vector<N, Component = i32>,
+{
+    /// Creates the inverse of the transform of the given type. The metadata is read later by
+    /// [InversePredictionTransform::read_metadata], as it follows the corrections in the stream.
+    pub(crate) fn new(ty: PredictionTransformType) -> Result<Self, Err> {
+        match ty {
+            PredictionTransformType::NoTransform => Ok(Self::NoTransform),
+            PredictionTransformType::Difference => Ok(Self::Difference),
+            PredictionTransformType::WrappedDifference => Ok(Self::WrappedDifference { min: 0, max: 0 }),
+            PredictionTransformType::OctahedralOrthogonal => {
+                if N != 2 {
+                    return Err(Err::UnsupportedNumComponents(N));
+                }
+                Ok(Self::OctahedralOrthogonal { max: 0, center: 0 })
+            },
+            PredictionTransformType::OctahedralReflection => {
+                if N != 2 {
+                    return Err(Err::UnsupportedNumComponents(N));
+                }
+                Ok(Self::OctahedralReflection { max: 0, center: 0 })
+            },
+            PredictionTransformType::Orthogonal => {
+                Err(Err::UnsupportedPredictionTransform(ty))
+            },
+        }
+    }
+
+    /// Reads the metadata written by the 'squeeze' of the transform.
+    pub(crate) fn read_metadata<R>(&mut self, reader: &mut R) -> Result<(), Err>
+        where R: ByteReader
+    {
+        match self {
+            Self::NoTransform | Self::Difference => {},
+            Self::WrappedDifference { min, max } => {
+                *min = reader.read_u32()? as i32;
+                *max = reader.read_u32()? as i32;
+                if min > max {
+                    return Err(Err::InvalidTransformMetadata("the minimum is greater than the maximum"));
+                }
+            },
+            Self::OctahedralOrthogonal { max, center } | Self::OctahedralReflection { max, center } => {
+                *max = reader.read_u32()? as i32;
+                *center = reader.read_u32()? as i32;
+                if *max <= 0 || *center != *max / 2 {
+                    return Err(Err::InvalidTransformMetadata("the octahedral center is not the half of the maximum"));
+                }
+            },
+        }
+        Ok(())
+    }
+
+    /// The largest quantized value of the octahedral coordinates read by [InversePredictionTransform::read_metadata],
+    /// if the transform is octahedral.
+    pub(crate) fn octahedral_max(&self) -> Option<i32> {
+        match *self {
+            Self::OctahedralOrthogonal { max, .. } | Self::OctahedralReflection { max, .. } => Some(max),
+            _ => None,
+        }
+    }
+
+    /// Restores the value from its prediction and correction.
+    pub(crate) fn inverse(&self, mut pred: NdVector<N, i32>, corr: NdVector<N, i32>) -> NdVector<N, i32> {
+        match *self {
+            Self::NoTransform => corr,
+            Self::Difference => pred + from_positive_i32_vec(corr),
+            Self::WrappedDifference { min, max } => {
+                let max_diff = max - min + 1;
+                let corr = from_positive_i32_vec(corr);
+                let mut out = NdVector::zero();
+                for i in 0..N {
+                    *pred.get_mut(i) = (*pred.get(i)).clamp(min, max);
+                    let mut val = *pred.get(i) + *corr.get(i);
+                    if val > max {
+                        val -= max_diff;
+                    } else if val < min {
+                        val += max_diff;
+                    }
+                    *out.get_mut(i) = val;
+                }
+                out
+            },
+            Self::OctahedralOrthogonal { max, center } => {
+                let mut pred = [*pred.get(0) - center, *pred.get(1) - center];
+                // the prediction is flipped into the upper hemisphere as in the encoder.
+                let flipped = pred[0].abs() + pred[1].abs() > center;
+                if flipped {
+                    pred = invert_diamond(pred, center);
+                }
+                // and rotated so that both of its coordinates are not positive.
+                let mut num_rotations = 0;
+                if pred != [0, 0] {
+                    while pred[0] >= 0 || pred[1] > 0 {
+                        pred = [-pred[1], pred[0]];
+                        num_rotations += 1;
+                    }
+                }
+
+                let mut orig = [0; 2];
+                for i in 0..2 {
+                    orig[i] = pred[i] + *corr.get(i);
+                    if orig[i] > center {
+                        orig[i] -= max;
+                    } else if orig[i] < -center {
+                        orig[i] += max;
+                    }
+                }
+                for _ in 0..num_rotations {
+                    orig = [orig[1], -orig[0]];
+                }
+                if flipped {
+                    orig = invert_diamond(orig, center);
+                }
+
+                let mut out = NdVector::zero();
+                *out.get_mut(0) = orig[0] + center;
+                *out.get_mut(1) = orig[1] + center;
+                out
+            },
+            Self::OctahedralReflection { max, center } => {
+                let mut pred = [*pred.get(0) - center, *pred.get(1) - center];
+                // the prediction is flipped into the upper hemisphere as in the encoder.
+                let flipped = pred[0].abs() + pred[1].abs() > center;
+                if flipped {
+                    pred = invert_diamond(pred, center);
+                }
+
+                let mut orig = [0; 2];
+                for i in 0..2 {
+                    orig[i] = pred[i] + *corr.get(i);
+                    if orig[i] > center {
+                        orig[i] -= max;
+                    } else if orig[i] < -center {
+                        orig[i] += max;
+                    }
+                }
+                if flipped {
+                    orig = invert_diamond(orig, center);
+                }
+
+                let mut out = NdVector::zero();
+                *out.get_mut(0) = orig[0] + center;
+                *out.get_mut(1) = orig[1] + center;
+                out
+            },
+        }
+    }
+}
+
+#[cfg(all(test, not(feature = "evaluation")))]
+mod tests {
+    use super::*;
+    use crate::encode::attribute::prediction_transform::{Config, PredictionTransform, PredictionTransformImpl};
+    use crate::core::shared::ConfigType;
+    use crate::prelude::SliceReader;
+
+    #[test]
+    fn test_transform_ids_match_the_encoder() {
+        use PredictionTransformType::*;
+        for ty in [NoTransform, Difference, WrappedDifference, OctahedralOrthogonal, OctahedralReflection, Orthogonal] {
+            assert_eq!(PredictionTransformType::from_id(ty.get_id()), Some(ty));
+        }
+        // the id 1 is the wrapped difference, which is inverted with its bounds rather than as the plain difference.
+        assert_eq!(PredictionTransformType::from_id(1), Some(WrappedDifference));
+        assert!(matches!(
+            InversePredictionTransform::<3>::new(WrappedDifference).unwrap(),
+            InversePredictionTransform::WrappedDifference { .. }
+        ));
+    }
+
+    #[test]
+    fn test_wrapped_difference_is_inverted() {
+        let origs = [[3, 9], [0, 12], [12, 1], [7, 7]].map(NdVector::from);
+        let preds = [[12, 0], [-4, 20], [6, 6], [7, 8]].map(NdVector::from);
+        let mut transform = PredictionTransform::<2>::new(Config {
+            ty: PredictionTransformType::WrappedDifference,
+            ..Config::default()
+        });
+        for (&orig, &pred) in origs.iter().zip(preds.iter()) {
+            transform.map_with_tentative_metadata(orig, pred);
+        }
+        let mut metadata = Vec::new();
+        let corrs = transform.squeeze(&mut metadata);
+
+        let mut inverse = InversePredictionTransform::<2>::new(PredictionTransformType::WrappedDifference).unwrap();
+        inverse.read_metadata(&mut SliceReader::new(&metadata)).unwrap();
+        for ((&orig, &pred), &corr) in origs.iter().zip(preds.iter()).zip(corrs.iter()) {
+            assert_eq!(inverse.inverse(pred, corr), orig);
+        }
+    }
+}
@@ -1,121 +1,172 @@
 pub(crate) mod attribute_decoder;
 pub(crate) mod inverse_prediction_transform;
-pub(crate) mod portabilization;
 
-use thiserror::Error;
-use crate::{core::bit_coder::ReaderErr, decode::header::Header, prelude::{Attribute, ByteReader, ConfigType}, shared::{attribute::{self, AttributeKind}, header::EncoderMethod}, utils::bit_coder::leb128_read};
+use crate::core::attribute::{self, AttributeDomain, AttributeId, AttributeType, ComponentDataType};
+use crate::core::bit_coder::ReaderErr;
+use crate::core::corner_table::GenericCornerTable;
+use crate::decode::connectivity;
+use crate::decode::entropy::symbol_coding;
+use crate::decode::header::Header;
+use crate::decode::LimitExceeded;
+use crate::encode::attribute::portabilization::PortabilizationType;
+use crate::encode::attribute::prediction_transform::PredictionTransformType;
+use crate::prelude::{Attribute, ByteReader};
+use crate::shared::attribute::portabilization::quantization_metadata::{self, SanityBounds};
+use crate::shared::attribute::prediction_scheme::{self, PredictionSchemeType};
+use crate::shared::connectivity::edgebreaker::{self, TraversalType};
+use crate::shared::header::EncoderMethod;
+use crate::utils::bit_coder::leb128_read;
+use attribute_decoder::AttributeDecoder;
 
-
-#[derive(Debug, Error)]
-pub enum Err {
-    #[error("Attribute error: {0}")]
-    AttributeDecoderError(#[from] attribute_decoder::Err),
-    #[error("Prediction inverse transform error: {0}")]
-    PredictionInverseTransformError(String),
-    #[error("Not Enough Data: {0}")]
-    NotEnoughData(#[from] ReaderErr),
-    #[error("Attribute Error: {0}")]
-    AttributeError(#[from] attribute::Err),
-}
-
-#[derive(Debug, Clone)]
-pub struct Config {
-    decoder_cfgs: Vec<attribute_decoder::Config>,
-}
-
-impl ConfigType for Config {
-    fn default() -> Self {
-        Self {
-            decoder_cfgs: vec![attribute_decoder::Config::default()],
-        }
-    }
+/// The header of an attribute, written by [crate::encode::attribute::encode_attributes] before any value.
+pub(crate) struct AttributeDescriptor {
+    /// The id of the corner table of the attribute, where 'u8::MAX' is the universal one.
+    pub(crate) decoder_id: u8,
+    pub(crate) domain: AttributeDomain,
+    pub(crate) ty: AttributeType,
+    pub(crate) component_type: ComponentDataType,
+    pub(crate) num_components: usize,
+    pub(crate) id: AttributeId,
+    pub(crate) portabilization: PortabilizationType,
 }
 
-pub fn decode_attributes<W>(
-    reader: &mut W,
-    _cfg: Config,
-    header: Header,
-    mut decoded_attributes: Vec<Attribute>,
-) -> Result<Vec<Attribute>, Err>
-    where W: ByteReader,
+/// Decodes the attributes, passing each to 'on_decoded' as soon as it is decoded.
+pub(crate) fn decode_attributes<R, F>(
+    reader: &mut R,
+    header: &Header,
+    conn: &connectivity::Output,
+    sanity_bounds: Option<SanityBounds>,
+    max_attribute_bytes: Option<usize>,
+    mut on_decoded: F,
+) -> Result<(), Err>
+    where
+        R: ByteReader,
+        F: FnMut(Attribute),
 {
-    // Read the number of attributes
-    let num_att_decs = reader.read_u8().unwrap() as usize;
+    // The custom order of the vertices is written as their ranks in the default traversal of the positions.
+    let vertex_order = if header.custom_vertex_order {
+        let sequence = conn.traversal_sequence(&conn.corner_table);
+        let len = leb128_read(reader)? as usize;
+        if len != sequence.len() {
+            return Err(Err::InvalidVertexOrder(format!("the order has {} vertices, but the mesh has {}", len, sequence.len())));
+        }
+        let order = (0..len)
+            .map(|_| match leb128_read(reader)? as usize {
+                rank if rank < len => Ok(conn.corner_table.vertex_idx(sequence[rank])),
+                rank => Err(Err::InvalidVertexOrder(format!("the rank {} is out of range", rank))),
+            })
+            .collect::<Result<Vec<_>, Err>>()?;
+        Some(order)
+    } else {
+        None
+    };
 
-    let mut att_dec_data_id = Vec::new();
-    let mut att_dec_type = Vec::new();
-    let mut att_dec_traversal_method = Vec::new();
-    if header.encoding_method == EncoderMethod::Edgebreaker {
-        for i in 0..num_att_decs  {
-            att_dec_data_id.push(reader.read_u8()?);
-            att_dec_type.push(AttributeKind::read_from(reader)?);
-            att_dec_traversal_method.push(reader.read_u8()?);
+    let num_atts = reader.read_u8()? as usize;
+    let mut decoder_ids = Vec::with_capacity(num_atts);
+    for _ in 0..num_atts {
+        // The attributes of a point cloud have neither a corner table nor a traversal of their own.
+        if header.encoding_method == EncoderMethod::Sequential {
+            decoder_ids.push((u8::MAX, AttributeDomain::Position));
+            continue;
+        }
+        let decoder_id = reader.read_u8()?;
+        let domain = AttributeDomain::read_from(reader)?;
+        let traversal = TraversalType::read_from(reader)?;
+        if traversal != TraversalType::DepthFirst {
+            return Err(Err::UnsupportedFeature("traversal other than the depth-first one"));
         }
+        decoder_ids.push((decoder_id, domain));
     }
 
-    let mut att_dec_num_attributes = Vec::with_capacity(num_att_decs);
-    let mut att_dec_att_types = Vec::with_capacity(num_att_decs);
-    let mut att_dec_data_types = Vec::with_capacity(num_att_decs);
-    let mut att_dec_num_components = Vec::with_capacity(num_att_decs);
-    let mut att_dec_normalized = Vec::with_capacity(num_att_decs);
-    let mut att_dec_unique_ids = Vec::with_capacity(num_att_decs);
-    let mut seq_att_dec_decoder_type = Vec::with_capacity(num_att_decs);
-    for i in 0.. num_att_decs {
-        att_dec_num_attributes.push( leb128_read(reader)? as usize);
-        att_dec_att_types.push(Vec::with_capacity(att_dec_num_attributes[i]));
-        att_dec_data_types.push(Vec::with_capacity(att_dec_num_attributes[i]));
-        att_dec_num_components.push(Vec::with_capacity(att_dec_num_attributes[i]));
-        att_dec_normalized.push(Vec::with_capacity(att_dec_num_attributes[i]));
-        att_dec_unique_ids.push(Vec::with_capacity(att_dec_num_attributes[i]));
-        for j in 0..att_dec_num_attributes[i] {
-            att_dec_att_types[i][j] = AttributeKind::read_from(reader)?;
-            att_dec_data_types[i][j] = reader.read_u8()?;
-            att_dec_num_components[i][j] = reader.read_u8()?;
-            att_dec_normalized[i][j] = reader.read_u8()?;
-            att_dec_unique_ids[i][j] = leb128_read(reader)?;
+    let mut descriptors = Vec::with_capacity(num_atts);
+    for (decoder_id, domain) in decoder_ids {
+        // the number of attributes of the decoder, which is always one.
+        if reader.read_u8()? != 1 {
+            return Err(Err::UnsupportedFeature("a decoder of more than one attribute"));
         }
-        seq_att_dec_decoder_type.push(Vec::with_capacity(att_dec_num_attributes[i]));
-        for j in 0..att_dec_num_attributes[i] {
-            seq_att_dec_decoder_type[i][j] = reader.read_u8()?;
+        let ty = AttributeType::read_from(reader)?;
+        let component_type = ComponentDataType::read_from(reader)?;
+        let num_components = reader.read_u8()? as usize;
+        // normalized flag, currently not used.
+        reader.read_u8()?;
+        let id = AttributeId::new(reader.read_u8()? as usize);
+        let portabilization_id = reader.read_u8()?;
+        let portabilization = PortabilizationType::from_id(portabilization_id)
+            .ok_or(Err::InvalidPortabilizationId(portabilization_id))?;
+        if num_components == 0 {
+            return Err(Err::UnsupportedNumComponents(num_components));
         }
+        descriptors.push(AttributeDescriptor { decoder_id, domain, ty, component_type, num_components, id, portabilization });
     }
 
-    let mut vertex_visited_point_ids = vec![0; num_att_decs as usize];
-    let mut curr_att_dec = 0;
+    // Every attribute has a value for each point once decoded.
+    let num_bytes = descriptors.iter()
+        .map(|d| conn.num_points.saturating_mul(d.num_components * d.component_type.size()))
+        .fold(0_usize, usize::saturating_add);
+    LimitExceeded::check("bytes of attributes", num_bytes, max_attribute_bytes)?;
+
+    // The portabilized attributes decoded so far, used as the parents of the predictions.
+    let mut port_atts: Vec<Attribute> = Vec::with_capacity(num_atts);
+    for descriptor in descriptors {
+        let corner_table = conn.corner_table_for(descriptor.decoder_id)
+            .ok_or(Err::InvalidDecoderId(descriptor.decoder_id))?;
+        let mut decoder = AttributeDecoder::new(descriptor, corner_table, conn, &port_atts, header, sanity_bounds);
+        if let Some(order) = &vertex_order {
+            decoder = decoder.with_vertex_order(order);
+        }
+        let (att, port_att) = decoder.decode(reader)?;
+        port_atts.push(port_att);
+        on_decoded(att);
+    }
+    Ok(())
+}
 
-    // if header.encoding_method == EncoderMethod::Edgebreaker {
-    //     decode_attribute_seams(reader)?;
-    //     for i in 0..num_encoded_vertices + num_encoded_split_symbols {
-    //         if is_vert_hole_[i] {
-    //             update_vertex_to_corner_map(i);
-    //         }
-    //     }
-    //     for i in 1..num_att_decs {
-    //         curr_att_dec = i;
-    //         recompute_vertices_internal();
-    //     }
-    //     attribute_assign_points_to_corners();
-    // }
-    // for i in 0..num_att_decs {
-    //     curr_att_dec = i;
-    //     is_face_visited_.assign(num_faces, false);
-    //     is_vertex_visited_.assign(num_faces * 3, false);
-    //     generate_sequence();
-    //     if header.encoding_method == EncoderMethod::Edgebreaker {
-    //         update_point_to_attribute_index_mapping();
-    //     }
-    // }
-    // for i in 0..num_att_decs {
-    //     for j in 0..att_dec_num_attributes[i] {
-    //         att_dec_num_values_to_decode[i][j] = encoded_attribute_value_index_to_corner_map[i].size();
-    //     }
-    // }
-    // for i in 0..num_att_decs {
-    //     curr_att_dec = i;
-    //     decode_portable_attributes();
-    //     decode_ata_needed_by_portable_transforms();
-    //     transform_attributes_to_original_format();
-    // }
 
-    Ok(decoded_attributes)
-}
\ No newline at end of file
+#[remain::sorted]
+#[derive(thiserror::Error, Debug)]
+pub enum Err {
+    #[error("Attribute error: {0}")]
+    AttributeError(#[from] attribute::Err),
+    #[error("The stream does not have the debug marker {0:?}")]
+    DebugMarkerMismatch(&'static str),
+    #[error("Invalid decoder id: {0}")]
+    InvalidDecoderId(u8),
+    #[error("Invalid palette index: {0}")]
+    InvalidPaletteIndex(i32),
+    #[error("Invalid parent attribute id: {0}")]
+    InvalidParentId(usize),
+    #[error("Invalid portabilization id: {0}")]
+    InvalidPortabilizationId(u8),
+    #[error("Invalid prediction transform id: {0}")]
+    InvalidPredictionTransformId(u8),
+    #[error("Invalid prediction transform metadata: {0}")]
+    InvalidTransformMetadata(&'static str),
+    #[error("Invalid vertex order: {0}")]
+    InvalidVertexOrder(String),
+    #[error("{0}")]
+    LimitExceeded(#[from] LimitExceeded),
+    #[error("Not enough data: {0}")]
+    NotEnoughData(#[from] ReaderErr),
+    #[error("Prediction error: {0}")]
+    PredictionError(#[from] prediction_scheme::Err),
+    #[error("Quantization metadata error: {0}")]
+    QuantizationError(#[from] quantization_metadata::Err),
+    #[error("Signed corrections error: {0}")]
+    SignedCorrectionsError(#[from] crate::encode::entropy::symbol_coding::Err),
+    #[error("Symbol decoding error: {0}")]
+    SymbolDecodingError(#[from] symbol_coding::Err),
+    #[error("Traversal error: {0}")]
+    TraversalError(#[from] edgebreaker::Err),
+    #[error("Unsupported data type: {0:?}")]
+    UnsupportedDataType(ComponentDataType),
+    #[error("Unsupported feature: {0}")]
+    UnsupportedFeature(&'static str),
+    #[error("Unsupported number of components: {0}")]
+    UnsupportedNumComponents(usize),
+    #[error("Unsupported portabilization: {0:?}")]
+    UnsupportedPortabilization(PortabilizationType),
+    #[error("Unsupported prediction scheme: {0:?}")]
+    UnsupportedPredictionScheme(PredictionSchemeType),
+    #[error("Unsupported prediction transform: {0:?}")]
+    UnsupportedPredictionTransform(PredictionTransformType),
+}
@@ -0,0 +1,220 @@
+use crate::core::corner_table::GenericCornerTable;
+use crate::core::shared::{CornerIdx, FaceIdx, PointIdx, VertexIdx};
+
+/// Corner table restored by the decoder. Unlike [crate::core::corner_table::CornerTable], it is built from
+/// the opposite corners alone, as the decoder does not know the faces of the mesh until the connectivity is decoded.
+#[derive(Debug, Clone)]
+pub(crate) struct DecodedCornerTable {
+    /// The opposite corner of each corner, or 'None' if the corner faces a boundary or a seam.
+    opposite_corners: Vec<Option<CornerIdx>>,
+
+    /// corner to vertex map.
+    corner_to_vertex: Vec<VertexIdx>,
+
+    /// Stores the left most corner for each vertex.
+    left_most_corners: Vec<CornerIdx>,
+
+    /// corner to point map, shared by all the corner tables of the mesh.
+    corner_to_point: Vec<PointIdx>,
+
+    /// Whether the corners are the points without faces, see [DecodedCornerTable::of_points].
+    is_point_cloud: bool,
+}
+
+impl DecodedCornerTable {
+    /// Builds the table from the opposite corners. The vertices are the fans of the corners connected by
+    /// the opposites, and they are numbered in the order of their first corners.
+    /// Returns 'None' if a corner is left without a vertex, as the opposites of a corrupted stream may not
+    /// make fans, so that every vertex index of the table is in range.
+    pub(crate) fn from_opposites(opposite_corners: Vec<Option<CornerIdx>>) -> Option<Self> {
+        let num_corners = opposite_corners.len();
+        if num_corners % 3 != 0 || opposite_corners.iter().flatten().any(|&c| usize::from(c) >= num_corners) {
+            return None;
+        }
+        let mut out = Self {
+            opposite_corners,
+            corner_to_vertex: vec![VertexIdx::from(usize::MAX); num_corners],
+            left_most_corners: Vec::new(),
+            corner_to_point: Vec::new(),
+            is_point_cloud: false,
+        };
+
+        for c in (0..num_corners).map(CornerIdx::from) {
+            if out.corner_to_vertex[usize::from(c)] != VertexIdx::from(usize::MAX) {
+                continue;
+            }
+            // Find the left most corner of the fan. If the fan is closed, then 'c' is taken as the left most corner.
+            // The swings are cut at the number of the corners, as the opposite corners of a corrupted stream
+            // may swing in a cycle that does not return to the corner it started from.
+            let mut left_most = c;
+            for _ in 0..num_corners {
+                match out.swing_left(left_most) {
+                    Some(left) if left != c => left_most = left,
+                    _ => break,
+                }
+            }
+
+            let v = VertexIdx::from(out.left_most_corners.len());
+            out.left_most_corners.push(left_most);
+            let mut curr = left_most;
+            for _ in 0..num_corners {
+                out.corner_to_vertex[usize::from(curr)] = v;
+                match out.swing_right(curr) {
+                    Some(right) if right != left_most => curr = right,
+                    _ => break,
+                }
+            }
+            if out.corner_to_vertex[usize::from(c)] != v {
+                return None;
+            }
+        }
+        // The prediction schemes look up the left-most corners of the vertices they have visited, so the corner of
+        // each vertex is checked here once instead of on each lookup.
+        let is_consistent = out.left_most_corners.iter()
+            .enumerate()
+            .all(|(v, &c)| out.corner_to_vertex[usize::from(c)] == VertexIdx::from(v));
+        if !is_consistent {
+            return None;
+        }
+        Some(out)
+    }
+
+    /// Builds the table of a point cloud, where each point is a corner and a vertex of its own with no neighbors,
+    /// as in [crate::core::corner_table::point_cloud_corner_table::PointCloudCornerTable].
+    pub(crate) fn of_points(num_points: usize) -> Self {
+        Self {
+            opposite_corners: vec![None; num_points],
+            corner_to_vertex: (0..num_points).map(VertexIdx::from).collect(),
+            left_most_corners: (0..num_points).map(CornerIdx::from).collect(),
+            corner_to_point: (0..num_points).map(PointIdx::from).collect(),
+            is_point_cloud: true,
+        }
+    }
+
+    /// Sets the points of the corners, which are determined by the vertices of all the corner tables of the mesh.
+    pub(crate) fn set_points(&mut self, corner_to_point: Vec<PointIdx>) {
+        debug_assert_eq!(corner_to_point.len(), self.num_corners());
+        self.corner_to_point = corner_to_point;
+    }
+}
+
+impl GenericCornerTable for DecodedCornerTable {
+    #[inline]
+    fn face_idx_containing(&self, corner: CornerIdx) -> FaceIdx {
+        (usize::from(corner) / 3).into()
+    }
+
+    #[inline]
+    fn num_faces(&self) -> usize {
+        if self.is_point_cloud {
+            return 0;
+        }
+        self.opposite_corners.len() / 3
+    }
+
+    #[inline]
+    fn num_corners(&self) -> usize {
+        self.opposite_corners.len()
+    }
+
+    #[inline]
+    fn num_vertices(&self) -> usize {
+        self.left_most_corners.len()
+    }
+
+    #[inline]
+    fn point_idx(&self, corner: CornerIdx) -> PointIdx {
+        self.corner_to_point[usize::from(corner)]
+    }
+
+    #[inline]
+    fn vertex_idx(&self, corner: CornerIdx) -> VertexIdx {
+        self.corner_to_vertex[usize::from(corner)]
+    }
+
+    #[inline]
+    fn opposite(&self, corner: CornerIdx) -> Option<CornerIdx> {
+        self.opposite_corners[usize::from(corner)]
+    }
+
+    #[inline]
+    fn previous(&self, corner: CornerIdx) -> CornerIdx {
+        if self.is_point_cloud {
+            return corner;
+        }
+        let corner = usize::from(corner);
+        let out = if corner%3 == 0 {
+            corner+2
+        } else {
+            corner-1
+        };
+        CornerIdx::from(out)
+    }
+
+    #[inline]
+    fn next(&self, corner: CornerIdx) -> CornerIdx {
+        if self.is_point_cloud {
+            return corner;
+        }
+        let corner = usize::from(corner);
+        let out = if corner%3 == 2 {
+            corner-2
+        } else {
+            corner+1
+        };
+        CornerIdx::from(out)
+    }
+
+    /// The left-most corner of the vertex, which is one of the corners of the vertex for every vertex of the table,
+    /// as [DecodedCornerTable::from_opposites] checks. The vertex must be one of the table, e.g. given by
+    /// [GenericCornerTable::vertex_idx], and not a vertex index read from the stream.
+    #[inline]
+    fn left_most_corner(&self, vertex: VertexIdx) -> CornerIdx {
+        self.left_most_corners[usize::from(vertex)]
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_opposites() {
+        // Two triangles sharing the edge of the corners 0 and 3.
+        let mut opposites = vec![None; 6];
+        opposites[0] = Some(CornerIdx::from(3));
+        opposites[3] = Some(CornerIdx::from(0));
+        let table = DecodedCornerTable::from_opposites(opposites).unwrap();
+        assert_eq!(table.num_faces(), 2);
+        assert_eq!(table.num_vertices(), 4);
+        // the corners on the shared edge share their vertices.
+        assert_eq!(table.vertex_idx(CornerIdx::from(1)), table.vertex_idx(CornerIdx::from(5)));
+        assert_eq!(table.vertex_idx(CornerIdx::from(2)), table.vertex_idx(CornerIdx::from(4)));
+        assert_ne!(table.vertex_idx(CornerIdx::from(0)), table.vertex_idx(CornerIdx::from(3)));
+        for v in (0..table.num_vertices()).map(VertexIdx::from) {
+            // the left most corners are on the boundary.
+            assert!(table.swing_left(table.left_most_corner(v)).is_none());
+        }
+    }
+
+    #[test]
+    fn test_from_opposites_rejects_corrupted_opposites() {
+        // the opposites are not symmetric, so that the fan of the corner 1 does not return to it.
+        let mut opposites = vec![None; 9];
+        opposites[0] = Some(CornerIdx::from(3));
+        opposites[3] = Some(CornerIdx::from(6));
+        opposites[6] = Some(CornerIdx::from(0));
+        assert!(DecodedCornerTable::from_opposites(opposites).is_none());
+
+        // an opposite out of range.
+        let mut opposites = vec![None; 3];
+        opposites[0] = Some(CornerIdx::from(3));
+        assert!(DecodedCornerTable::from_opposites(opposites).is_none());
+
+        // an opposite of one side within the face, whose fans take the left-most corner of another vertex.
+        let mut opposites = vec![None; 6];
+        opposites[4] = Some(CornerIdx::from(5));
+        assert!(DecodedCornerTable::from_opposites(opposites).is_none());
+    }
+}
@@ -0,0 +1,570 @@
+use std::collections::HashMap;
+
+use crate::core::bit_coder::{BitReader, ReaderErr, SliceReader};
+use crate::core::buffer::LsbFirst;
+use crate::core::shared::CornerIdx;
+use crate::debug_expect;
+use crate::decode::entropy::rans::{self, RabsDecoder};
+use crate::decode::entropy::symbol_coding::{self, decode_symbols};
+use crate::decode::header::Header;
+use crate::decode::{Config, LimitExceeded};
+use crate::prelude::ByteReader;
+use crate::shared::connectivity::edgebreaker::symbol_encoder::{
+    join_by_context, CrLight, Symbol, SymbolEncoder, NUM_CLERS_CONTEXTS
+};
+use crate::shared::connectivity::edgebreaker::{self, EdgebreakerKind, Orientation};
+use crate::utils::bit_coder::leb128_read;
+
+#[remain::sorted]
+#[derive(thiserror::Error, Debug)]
+pub enum Err {
+    #[error("The stream does not have the debug marker {0:?}")]
+    DebugMarkerMismatch(&'static str),
+    #[error("Invalid connectivity: {0}")]
+    InvalidConnectivity(&'static str),
+    #[cfg(feature = "validation")]
+    #[error("Invalid corner table: {0}")]
+    InvalidCornerTable(#[from] crate::core::corner_table::validation::Err),
+    #[error("Invalid symbol id: {0}")]
+    InvalidSymbolId(u64),
+    #[error("{0}")]
+    LimitExceeded(#[from] LimitExceeded),
+    #[error("Not enough data: {0}")]
+    NotEnoughData(#[from] ReaderErr),
+    #[error("The stream has {0} faces, but {1} faces are decoded")]
+    NumFacesMismatch(usize, usize),
+    #[error("Rabs decoding error: {0}")]
+    RabsDecoderError(#[from] rans::Err),
+    #[error("Symbol decoding error: {0}")]
+    SymbolDecodingError(#[from] symbol_coding::Err),
+    #[error("Traversal error: {0}")]
+    TraversalError(#[from] edgebreaker::Err),
+    #[error("Unsupported Edgebreaker kind: {0}")]
+    UnsupportedEdgebreakerKind(String),
+}
+
+/// A topology split as it is read from the stream, in terms of the symbol ids of the encoder.
+struct TopologySplit {
+    source_symbol_idx: usize,
+    split_symbol_idx: usize,
+    orientation: Orientation,
+}
+
+pub(crate) struct Output {
+    /// The opposite corner of each corner of the mesh.
+    pub(crate) opposite_corners: Vec<Option<CornerIdx>>,
+    /// For each attribute corner table, whether each corner is on an attribute seam.
+    pub(crate) seams: Vec<Vec<bool>>,
+    /// The corners facing the edges left open next to the faces of zero area, see
+    /// [crate::core::corner_table::validation::validate_corner_table].
+    #[cfg(feature = "validation")]
+    pub(crate) cut_edges: Vec<CornerIdx>,
+}
+
+/// Decodes the connectivity written by [crate::encode::connectivity::edgebreaker], reversing the traversal of the encoder
+/// (spirale reversi): the symbols are read in the reverse order of the encoder, and each of them adds a face to the mesh.
+pub(crate) fn decode_connectivity<R>(reader: &mut R, header: &Header, cfg: &Config) -> Result<Output, Err>
+    where R: ByteReader
+{
+    debug_expect!("Init Decoder", reader, header.contains_debug_markers)?;
+    let kind = EdgebreakerKind::read_from(reader)?;
+    if kind != EdgebreakerKind::Standard {
+        return Err(Err::UnsupportedEdgebreakerKind(format!("{:?}", kind)));
+    }
+    debug_expect!("Init Decoder Done", reader, header.contains_debug_markers)?;
+
+    // The number of vertices is restored from the faces, but the claimed one is checked against the limit.
+    let num_vertices = leb128_read(reader)? as usize;
+    let num_faces = leb128_read(reader)? as usize;
+    LimitExceeded::check("vertices", num_vertices, cfg.max_vertices)?;
+    LimitExceeded::check("faces", num_faces, cfg.max_faces)?;
+    let num_attribute_data = reader.read_u8()? as usize;
+    let num_symbols = leb128_read(reader)? as usize;
+    let _num_split_symbols = leb128_read(reader)? as usize;
+    if num_symbols > num_faces {
+        return Err(Err::InvalidConnectivity("more symbols than faces"));
+    }
+
+    let topology_splits = read_topology_splits(reader, header.byte_aligned_symbols)?;
+    let symbols = if header.context_clers {
+        read_context_symbols(reader, num_symbols)?
+    } else if header.byte_aligned_symbols {
+        read_byte_aligned_symbols(reader, num_symbols)?
+    } else {
+        read_flat_symbols(reader, num_symbols)?
+    };
+
+    let start_face_prob_zero = reader.read_u8()?;
+    let len = leb128_read(reader)? as usize;
+    let mut start_face_decoder: RabsDecoder<_> = RabsDecoder::new(reader, len, start_face_prob_zero as usize, None)?;
+
+    let mut decoder = SpiraleReversi::new(num_faces, topology_splits);
+    for (symbol_idx, symbol) in symbols.into_iter().enumerate() {
+        decoder.decode_symbol(symbol, symbol_idx, num_symbols)?;
+    }
+    decoder.decode_start_faces(|| Ok(start_face_decoder.read()? == 1))?;
+    if decoder.num_faces() != num_faces {
+        return Err(Err::NumFacesMismatch(num_faces, decoder.num_faces()));
+    }
+    #[cfg(feature = "validation")]
+    let cut_edges = decoder.validate()?;
+
+    let seams = decoder.decode_attribute_seams(reader, num_attribute_data)?;
+
+    Ok(Output {
+        opposite_corners: decoder.opposite_corners,
+        seams,
+        #[cfg(feature = "validation")]
+        cut_edges,
+    })
+}
+
+fn read_topology_splits<R>(reader: &mut R, byte_aligned: bool) -> Result<Vec<TopologySplit>, Err>
+    where R: ByteReader
+{
+    let num_splits = leb128_read(reader)? as usize;
+    let mut splits = Vec::new();
+    let mut last_idx = 0;
+    for _ in 0..num_splits {
+        let (source_delta, split_delta) = if byte_aligned {
+            (reader.read_u32()? as usize, reader.read_u32()? as usize)
+        } else {
+            (leb128_read(reader)? as usize, leb128_read(reader)? as usize)
+        };
+        let source_symbol_idx = last_idx + source_delta;
+        let split_symbol_idx = source_symbol_idx.checked_sub(split_delta)
+            .ok_or(Err::InvalidConnectivity("a topology split refers to a negative symbol"))?;
+        let orientation = if byte_aligned && reader.read_u8()? != 0 {
+            Orientation::Right
+        } else {
+            Orientation::Left
+        };
+        splits.push(TopologySplit { source_symbol_idx, split_symbol_idx, orientation });
+        last_idx = source_symbol_idx;
+    }
+    if num_splits > 0 && !byte_aligned {
+        let mut bit_reader: BitReader<'_, R, LsbFirst> = BitReader::spown_from(reader)
+            .ok_or(ReaderErr::NotEnoughData)?;
+        for split in splits.iter_mut() {
+            split.orientation = match bit_reader.read_bits(1)? {
+                0 => Orientation::Left,
+                _ => Orientation::Right,
+            };
+        }
+    }
+    Ok(splits)
+}
+
+/// Reads the symbols coded with [crate::encode::ClersCoding::Flat], in the order of decoding.
+fn read_flat_symbols<R>(reader: &mut R, num_symbols: usize) -> Result<Vec<Symbol>, Err>
+    where R: ByteReader
+{
+    let len = leb128_read(reader)? as usize;
+    let bytes = (0..len).map(|_| reader.read_u8()).collect::<Result<Vec<_>, _>>()?;
+    let mut slice_reader = SliceReader::new(&bytes);
+    let mut bit_reader: BitReader<'_, _, LsbFirst> = BitReader::spown_from(&mut slice_reader)
+        .ok_or(ReaderErr::NotEnoughData)?;
+    (0..num_symbols)
+        .map(|_| CrLight::decode_symbol(&mut bit_reader).map_err(Err::from))
+        .collect()
+}
+
+/// Reads the symbols stored with [crate::encode::SymbolPacking::ByteAligned], one byte each, in the order of decoding.
+fn read_byte_aligned_symbols<R>(reader: &mut R, num_symbols: usize) -> Result<Vec<Symbol>, Err>
+    where R: ByteReader
+{
+    if leb128_read(reader)? as usize != num_symbols {
+        return Err(Err::InvalidConnectivity("the number of the byte-aligned symbols does not match"));
+    }
+    (0..num_symbols)
+        .map(|_| {
+            let id = reader.read_u8()?;
+            Symbol::from_id(id as usize).ok_or(Err::InvalidSymbolId(id as u64))
+        })
+        .collect()
+}
+
+/// Reads the symbols coded with [crate::encode::ClersCoding::Context], in the order of decoding.
+fn read_context_symbols<R>(reader: &mut R, num_symbols: usize) -> Result<Vec<Symbol>, Err>
+    where R: ByteReader
+{
+    let mut contexts = Vec::with_capacity(NUM_CLERS_CONTEXTS);
+    for _ in 0..NUM_CLERS_CONTEXTS {
+        let len = leb128_read(reader)? as usize;
+        let context = if len == 0 {
+            Vec::new()
+        } else if len > num_symbols {
+            return Err(Err::InvalidConnectivity("a context holds more symbols than the mesh"));
+        } else {
+            decode_symbols(len, 1, reader)?.into_iter()
+                .map(|id| Symbol::from_id(id as usize).ok_or(Err::InvalidSymbolId(id)))
+                .collect::<Result<Vec<_>, _>>()?
+        };
+        contexts.push(context);
+    }
+    join_by_context(contexts, num_symbols)
+        .ok_or(Err::InvalidConnectivity("the contexts do not hold the symbols"))
+}
+
+
+/// The state of the decoder. The vertices are only tracked to find the corners of the next faces;
+/// the final vertices are computed from the opposite corners, see [super::corner_table::DecodedCornerTable].
+struct SpiraleReversi {
+    opposite_corners: Vec<Option<CornerIdx>>,
+    corner_to_vertex: Vec<usize>,
+    left_most_corners: Vec<CornerIdx>,
+    active_corner_stack: Vec<CornerIdx>,
+    /// Topology splits not reached yet, whose source symbols are in the increasing order.
+    topology_splits: Vec<TopologySplit>,
+    /// The corners to be pushed to the stack by the 'S' symbols, keyed by the symbol id of the decoder.
+    split_active_corners: HashMap<usize, CornerIdx>,
+    num_expected_faces: usize,
+}
+
+impl SpiraleReversi {
+    fn new(num_expected_faces: usize, topology_splits: Vec<TopologySplit>) -> Self {
+        Self {
+            opposite_corners: Vec::new(),
+            corner_to_vertex: Vec::new(),
+            left_most_corners: Vec::new(),
+            active_corner_stack: Vec::new(),
+            topology_splits,
+            split_active_corners: HashMap::new(),
+            num_expected_faces,
+        }
+    }
+
+    fn num_faces(&self) -> usize {
+        self.opposite_corners.len() / 3
+    }
+
+    #[inline]
+    fn next(c: CornerIdx) -> CornerIdx {
+        let c = usize::from(c);
+        CornerIdx::from(if c % 3 == 2 { c - 2 } else { c + 1 })
+    }
+
+    #[inline]
+    fn previous(c: CornerIdx) -> CornerIdx {
+        let c = usize::from(c);
+        CornerIdx::from(if c % 3 == 0 { c + 2 } else { c - 1 })
+    }
+
+    #[inline]
+    fn vertex(&self, c: CornerIdx) -> usize {
+        self.corner_to_vertex[usize::from(c)]
+    }
+
+    #[inline]
+    fn opposite(&self, c: CornerIdx) -> Option<CornerIdx> {
+        self.opposite_corners[usize::from(c)]
+    }
+
+    fn set_opposite_corners(&mut self, c: CornerIdx, opp: CornerIdx) {
+        self.opposite_corners[usize::from(c)] = Some(opp);
+        self.opposite_corners[usize::from(opp)] = Some(c);
+    }
+
+    fn swing_left(&self, c: CornerIdx) -> Option<CornerIdx> {
+        self.opposite(Self::next(c)).map(Self::next)
+    }
+
+    fn new_vertex(&mut self, c: CornerIdx) {
+        self.corner_to_vertex[usize::from(c)] = self.left_most_corners.len();
+        self.left_most_corners.push(c);
+    }
+
+    /// Adds a face and returns its first corner.
+    fn new_face(&mut self) -> Result<CornerIdx, Err> {
+        if self.num_faces() >= self.num_expected_faces {
+            return Err(Err::InvalidConnectivity("more faces than the stream has"));
+        }
+        let c = CornerIdx::from(self.opposite_corners.len());
+        self.opposite_corners.extend([None; 3]);
+        self.corner_to_vertex.extend([usize::MAX; 3]);
+        Ok(c)
+    }
+
+    fn top(&self) -> Result<CornerIdx, Err> {
+        self.active_corner_stack.last().copied()
+            .ok_or(Err::InvalidConnectivity("the stack of the active corners is empty"))
+    }
+
+    fn decode_symbol(&mut self, symbol: Symbol, symbol_idx: usize, num_symbols: usize) -> Result<(), Err> {
+        let corner = self.new_face()?;
+        match symbol {
+            Symbol::C => {
+                let corner_a = self.top()?;
+                let vertex_x = self.vertex(Self::next(corner_a));
+                let corner_b = Self::next(self.left_most_corners[vertex_x]);
+                if corner_a == corner_b || self.opposite(corner_a).is_some() || self.opposite(corner_b).is_some() {
+                    return Err(Err::InvalidConnectivity("the corners of a 'C' symbol are already connected"));
+                }
+                self.set_opposite_corners(corner_a, Self::next(corner));
+                self.set_opposite_corners(corner_b, Self::previous(corner));
+                let vert_a_prev = self.vertex(Self::previous(corner_a));
+                let vert_b_next = self.vertex(Self::next(corner_b));
+                if vertex_x == vert_a_prev || vertex_x == vert_b_next {
+                    return Err(Err::InvalidConnectivity("a 'C' symbol makes a degenerate face"));
+                }
+                self.corner_to_vertex[usize::from(corner)] = vertex_x;
+                self.corner_to_vertex[usize::from(Self::next(corner))] = vert_b_next;
+                self.corner_to_vertex[usize::from(Self::previous(corner))] = vert_a_prev;
+                self.left_most_corners[vert_a_prev] = Self::previous(corner);
+                *self.active_corner_stack.last_mut().unwrap() = corner; // safe as the stack is not empty
+            },
+            Symbol::R | Symbol::L => {
+                let corner_a = self.top()?;
+                if self.opposite(corner_a).is_some() {
+                    return Err(Err::InvalidConnectivity("the corner of an 'R' or 'L' symbol is already connected"));
+                }
+                let (opp_corner, corner_l, corner_r) = if symbol == Symbol::R {
+                    (Self::previous(corner), Self::next(corner), corner)
+                } else {
+                    (Self::next(corner), corner, Self::previous(corner))
+                };
+                self.set_opposite_corners(opp_corner, corner_a);
+                self.new_vertex(opp_corner);
+                let vertex_r = self.vertex(Self::previous(corner_a));
+                self.corner_to_vertex[usize::from(corner_r)] = vertex_r;
+                self.left_most_corners[vertex_r] = corner_r;
+                self.corner_to_vertex[usize::from(corner_l)] = self.vertex(Self::next(corner_a));
+                *self.active_corner_stack.last_mut().unwrap() = corner; // safe as the stack is not empty
+                self.check_topology_split(symbol_idx, num_symbols)?;
+            },
+            Symbol::S => {
+                let corner_b = self.active_corner_stack.pop()
+                    .ok_or(Err::InvalidConnectivity("the stack of the active corners is empty"))?;
+                if let Some(&c) = self.split_active_corners.get(&symbol_idx) {
+                    self.active_corner_stack.push(c);
+                }
+                let corner_a = self.top()?;
+                if corner_a == corner_b || self.opposite(corner_a).is_some() || self.opposite(corner_b).is_some() {
+                    return Err(Err::InvalidConnectivity("the corners of an 'S' symbol are already connected"));
+                }
+                self.set_opposite_corners(corner_a, Self::previous(corner));
+                self.set_opposite_corners(corner_b, Self::next(corner));
+                let vertex_p = self.vertex(Self::previous(corner_a));
+                self.corner_to_vertex[usize::from(corner)] = vertex_p;
+                self.corner_to_vertex[usize::from(Self::next(corner))] = self.vertex(Self::next(corner_a));
+                let vert_b_prev = self.vertex(Self::previous(corner_b));
+                self.corner_to_vertex[usize::from(Self::previous(corner))] = vert_b_prev;
+                self.left_most_corners[vert_b_prev] = Self::previous(corner);
+
+                // merge the vertex 'n' into 'p'.
+                let first_corner = Self::next(corner_b);
+                let vertex_n = self.vertex(first_corner);
+                self.left_most_corners[vertex_p] = self.left_most_corners[vertex_n];
+                let mut corner_n = Some(first_corner);
+                while let Some(c) = corner_n {
+                    self.corner_to_vertex[usize::from(c)] = vertex_p;
+                    corner_n = self.swing_left(c);
+                    if corner_n == Some(first_corner) {
+                        return Err(Err::InvalidConnectivity("an 'S' symbol closes a fan"));
+                    }
+                }
+                *self.active_corner_stack.last_mut().unwrap() = corner; // safe as the stack is not empty
+            },
+            Symbol::E => {
+                for c in [corner, Self::next(corner), Self::previous(corner)] {
+                    self.new_vertex(c);
+                }
+                self.active_corner_stack.push(corner);
+                self.check_topology_split(symbol_idx, num_symbols)?;
+            },
+        }
+        Ok(())
+    }
+
+    /// Records the corners of the topology splits whose source is the current symbol.
+    fn check_topology_split(&mut self, symbol_idx: usize, num_symbols: usize) -> Result<(), Err> {
+        let encoder_symbol_idx = num_symbols - symbol_idx - 1;
+        while let Some(split) = self.topology_splits.last() {
+            if split.source_symbol_idx > encoder_symbol_idx {
+                return Err(Err::InvalidConnectivity("a topology split is missed"));
+            }
+            if split.source_symbol_idx != encoder_symbol_idx {
+                break;
+            }
+            let top = self.top()?;
+            let new_active_corner = match split.orientation {
+                Orientation::Right => Self::next(top),
+                Orientation::Left => Self::previous(top),
+            };
+            if split.split_symbol_idx >= num_symbols {
+                return Err(Err::InvalidConnectivity("a topology split refers to a missing symbol"));
+            }
+            self.split_active_corners.insert(num_symbols - split.split_symbol_idx - 1, new_active_corner);
+            self.topology_splits.pop();
+        }
+        Ok(())
+    }
+
+    /// Closes the start faces of the connected components, which are left on the stack.
+    /// 'is_interior' reads whether the next start face is interior.
+    fn decode_start_faces<F>(&mut self, mut is_interior: F) -> Result<(), Err>
+        where F: FnMut() -> Result<bool, Err>
+    {
+        while let Some(corner_a) = self.active_corner_stack.pop() {
+            if !is_interior()? {
+                continue;
+            }
+            let vert_n = self.vertex(Self::next(corner_a));
+            let corner_b = Self::next(self.left_most_corners[vert_n]);
+            let vert_x = self.vertex(Self::next(corner_b));
+            let corner_c = Self::next(self.left_most_corners[vert_x]);
+            let vert_p = self.vertex(Self::next(corner_c));
+            let corner = self.new_face()?;
+            self.set_opposite_corners(corner, corner_a);
+            self.set_opposite_corners(Self::next(corner), corner_b);
+            self.set_opposite_corners(Self::previous(corner), corner_c);
+            self.corner_to_vertex[usize::from(corner)] = vert_x;
+            self.corner_to_vertex[usize::from(Self::next(corner))] = vert_p;
+            self.corner_to_vertex[usize::from(Self::previous(corner))] = vert_n;
+        }
+        Ok(())
+    }
+
+    /// Checks the opposite corners against the vertices that the decoder has tracked, by comparing the table
+    /// built from the opposite corners with the table rebuilt from the faces of the tracked vertices.
+    #[cfg(feature = "validation")]
+    fn validate(&self) -> Result<Vec<CornerIdx>, Err> {
+        use crate::core::attribute::{AttributeDomain, AttributeId};
+        use crate::core::corner_table::validation::validate_corner_table;
+        use crate::core::shared::{NdVector, PointIdx};
+        use crate::prelude::{Attribute, AttributeType};
+
+        // The vertices are numbered in the order of their first corners, as in the table built from the opposite corners.
+        let mut new_ids = vec![usize::MAX; self.left_most_corners.len()];
+        let mut num_vertices = 0;
+        let mut corners = Vec::with_capacity(self.corner_to_vertex.len());
+        for &v in &self.corner_to_vertex {
+            let id = new_ids.get_mut(v).ok_or(Err::InvalidConnectivity("a corner has no vertex"))?;
+            if *id == usize::MAX {
+                *id = num_vertices;
+                num_vertices += 1;
+            }
+            corners.push(PointIdx::from(*id));
+        }
+        let faces = corners.chunks(3).map(|f| [f[0], f[1], f[2]]).collect::<Vec<_>>();
+        // a distinct position for each vertex, so that the rebuilt table has the vertices of the faces.
+        let positions = Attribute::from_without_removing_duplicates(
+            AttributeId::new(0),
+            (0..num_vertices).map(|v| NdVector::from([v as f32])).collect(),
+            AttributeType::Position,
+            AttributeDomain::Position,
+            Vec::new(),
+        );
+        let decoded = super::corner_table::DecodedCornerTable::from_opposites(self.opposite_corners.clone())
+            .ok_or(Err::InvalidConnectivity("the opposite corners do not make the vertices"))?;
+        Ok(validate_corner_table(&decoded, &faces, &positions)?)
+    }
+
+    /// Reads the attribute seams. The edges on the boundary are seams of all the attributes, and the other
+    /// edges have one bit per attribute, read when the edge is first reached in the order of the faces.
+    fn decode_attribute_seams<R>(&self, reader: &mut R, num_attribute_data: usize) -> Result<Vec<Vec<bool>>, Err>
+        where R: ByteReader
+    {
+        let mut seam_decoders = Vec::with_capacity(num_attribute_data);
+        for _ in 0..num_attribute_data {
+            let prob_zero = reader.read_u8()?;
+            let len = leb128_read(reader)? as usize;
+            let decoder: RabsDecoder<_> = RabsDecoder::new(reader, len, prob_zero as usize, None)?;
+            seam_decoders.push(decoder);
+        }
+
+        let num_corners = self.opposite_corners.len();
+        let mut seams = vec![vec![false; num_corners]; num_attribute_data];
+        for c in (0..num_corners).map(CornerIdx::from) {
+            let face = usize::from(c) / 3;
+            match self.opposite(c) {
+                None => {
+                    for seams in seams.iter_mut() {
+                        seams[usize::from(c)] = true;
+                    }
+                },
+                Some(opp) if usize::from(opp) / 3 < face => continue,
+                Some(opp) => {
+                    for (seams, decoder) in seams.iter_mut().zip(seam_decoders.iter_mut()) {
+                        if decoder.read()? == 1 {
+                            seams[usize::from(c)] = true;
+                            seams[usize::from(opp)] = true;
+                        }
+                    }
+                },
+            }
+        }
+        Ok(seams)
+    }
+}
+
+
+#[cfg(all(test, not(feature = "evaluation")))]
+mod tests {
+    use crate::core::shared::ConfigType;
+    use crate::encode::SymbolPacking;
+    use crate::prelude::SliceReader;
+
+    fn encode_with(mesh: &crate::core::mesh::Mesh, symbol_packing: SymbolPacking) -> (usize, Vec<u8>) {
+        let mut cfg = crate::encode::Config::default();
+        cfg.symbol_packing = symbol_packing;
+        let mut atts = mesh.get_attributes().to_vec();
+        let mut connectivity = Vec::new();
+        crate::encode::connectivity::encode_connectivity(mesh.get_faces(), None, &mut atts, &mut connectivity, &cfg).unwrap();
+        let mut buffer = Vec::new();
+        crate::encode::encode(mesh.clone(), &mut buffer, cfg).unwrap();
+        (connectivity.len(), buffer)
+    }
+
+    #[test]
+    fn test_byte_aligned_and_bit_packed_symbols() {
+        // The torus has a handle, so that the topology splits are written as well.
+        let mesh = crate::io::obj::load_obj("tests/data/torus.obj").unwrap();
+        let (bit_packed_size, bit_packed) = encode_with(&mesh, SymbolPacking::BitPacked);
+        let (byte_aligned_size, byte_aligned) = encode_with(&mesh, SymbolPacking::ByteAligned);
+        assert!(
+            bit_packed_size < byte_aligned_size,
+            "bit-packed: {} bytes, byte-aligned: {} bytes", bit_packed_size, byte_aligned_size
+        );
+
+        let decode = |buffer: &[u8]| crate::decode::decode(&mut SliceReader::new(buffer), crate::decode::Config::default()).unwrap();
+        let bit_packed = decode(&bit_packed);
+        let byte_aligned = decode(&byte_aligned);
+        assert_eq!(bit_packed.get_faces().len(), mesh.get_faces().len());
+        assert_eq!(bit_packed.get_faces(), byte_aligned.get_faces());
+    }
+
+    #[test]
+    #[cfg(feature = "validation")]
+    fn test_validation_catches_a_corrupted_symbol() {
+        use crate::prelude::ByteReader;
+        use crate::shared::connectivity::edgebreaker::symbol_encoder::Symbol;
+        use crate::utils::bit_coder::leb128_read;
+
+        let mesh = crate::io::obj::load_obj("tests/data/sphere.obj").unwrap();
+        let (_, mut buffer) = encode_with(&mesh, SymbolPacking::ByteAligned);
+
+        // Find the symbols, stored one byte each after the counts and the topology splits.
+        let mut reader = SliceReader::new(&buffer);
+        let header = crate::decode::header::decode_header(&mut reader).unwrap();
+        assert!(header.byte_aligned_symbols && !header.contains_metadata);
+        reader.read_u8().unwrap(); // the kind of the Edgebreaker
+        leb128_read(&mut reader).unwrap(); // the number of the vertices
+        leb128_read(&mut reader).unwrap(); // the number of the faces
+        reader.read_u8().unwrap(); // the number of the attribute data
+        let num_symbols = leb128_read(&mut reader).unwrap();
+        leb128_read(&mut reader).unwrap(); // the number of the split symbols
+        super::read_topology_splits(&mut reader, true).unwrap();
+        assert_eq!(leb128_read(&mut reader).unwrap(), num_symbols);
+        let offset = buffer.len() - reader.remaining().len();
+
+        // The second symbol turned from 'L' into 'C' adds a vertex that the following symbols do not agree with.
+        assert_eq!(buffer[offset + 1], Symbol::L.get_id() as u8);
+        buffer[offset + 1] = Symbol::C.get_id() as u8;
+        let err = crate::decode::decode(&mut SliceReader::new(&buffer), crate::decode::Config::default()).unwrap_err();
+        assert!(matches!(
+            err,
+            crate::decode::Err::ConnectivityError(super::super::Err::EdgebreakerError(super::Err::InvalidCornerTable(_)))
+        ), "{}", err);
+    }
+}
@@ -1,47 +1,158 @@
-mod sequential;
-mod spirale_reversi;
-use crate::core::bit_coder::ReaderErr;
-use crate::core::shared::{FaceIdx, VertexIdx}; 
-use crate::debug_expect;
+pub(crate) mod corner_table;
+pub(crate) mod edgebreaker;
+pub(crate) mod sequential;
+
+use std::collections::HashMap;
+
+use crate::core::corner_table::GenericCornerTable;
+use crate::core::shared::{CornerIdx, PointIdx};
 use crate::decode::header::Header;
+use crate::decode::{Config, LimitExceeded};
 use crate::prelude::ByteReader;
-use crate::shared::connectivity::EdgebreakerDecoder;
+use crate::shared::attribute::sequence::Traverser;
 use crate::shared::header::EncoderMethod;
+use corner_table::DecodedCornerTable;
 
+#[remain::sorted]
 #[derive(Debug, thiserror::Error)]
 pub enum Err {
+    #[error("Edgebreaker decoding error: {0}")]
+    EdgebreakerError(#[from] edgebreaker::Err),
+    #[error("{0}")]
+    LimitExceeded(LimitExceeded),
+    #[error("Reader Error: {0}")]
+    ReaderError(#[from] crate::core::bit_coder::ReaderErr),
     #[error("Sequential decoding error: {0}")]
     SequentialError(#[from] sequential::Err),
-    
-    #[error("Spirale Reversi decoding error: {0}")]
-    SpiraleReversiError(#[from] spirale_reversi::Err),
+}
+
+/// The connectivity of the mesh restored by the decoder.
+pub(crate) struct Output {
+    /// The corner table of the positions, i.e. the one without any attribute seam.
+    pub(crate) corner_table: DecodedCornerTable,
+    /// The corner tables of the attributes other than the positions, cut at their seams.
+    pub(crate) attribute_corner_tables: Vec<DecodedCornerTable>,
+    /// The faces of the mesh.
+    pub(crate) faces: Vec<[PointIdx; 3]>,
+    /// The number of points of the mesh, i.e. the number of unique combinations of the vertices of the corner tables.
+    pub(crate) num_points: usize,
+    /// Whether the attribute values are in the order of the points, as for a point cloud and the sequential connectivity.
+    pub(crate) in_point_order: bool,
+    /// The corners facing the edges left open next to the faces of zero area, checked once the positions are decoded.
+    #[cfg(feature = "validation")]
+    pub(crate) cut_edges: Vec<CornerIdx>,
+}
+
+impl Output {
+    /// The connectivity of a point cloud, which has no faces.
+    fn point_cloud(num_points: usize) -> Self {
+        Output {
+            corner_table: DecodedCornerTable::of_points(num_points),
+            attribute_corner_tables: Vec::new(),
+            faces: Vec::new(),
+            num_points,
+            in_point_order: true,
+            #[cfg(feature = "validation")]
+            cut_edges: Vec::new(),
+        }
+    }
+
+    /// The connectivity of the faces written as the indices of their points, whose attribute values
+    /// are in the order of the points as for a point cloud.
+    fn sequential(out: sequential::Output) -> Self {
+        Output {
+            faces: out.faces,
+            ..Output::point_cloud(out.num_points)
+        }
+    }
 
-    #[error("Not enough data in stream")]
-    NotEnoughData(#[from] ReaderErr),
+    /// Returns the corner table of the attribute with the given decoder id, where 'u8::MAX' is the universal one.
+    pub(crate) fn corner_table_for(&self, decoder_id: u8) -> Option<&DecodedCornerTable> {
+        if decoder_id == u8::MAX {
+            Some(&self.corner_table)
+        } else {
+            self.attribute_corner_tables.get(decoder_id as usize)
+        }
+    }
+
+    /// The corners from which the attribute values are traversed, in the order of
+    /// [crate::shared::attribute::sequence::Traverser::new].
+    pub(crate) fn traversal_start_corners(&self) -> Vec<CornerIdx> {
+        (0..self.corner_table.num_faces()).rev()
+            .map(|f| CornerIdx::from(3 * f))
+            .collect()
+    }
+
+    /// The corners of 'corner_table' in the order that the attribute values are decoded, i.e. the order of the points
+    /// for a point cloud and the sequential connectivity, and the traversal from [Output::traversal_start_corners] otherwise.
+    pub(crate) fn traversal_sequence(&self, corner_table: &DecodedCornerTable) -> Vec<CornerIdx> {
+        if self.in_point_order {
+            return (0..corner_table.num_corners()).map(CornerIdx::from).collect();
+        }
+        Traverser::new(corner_table, self.traversal_start_corners()).compute_seqeunce()
+    }
 }
 
-pub fn decode_connectivity_att<R>(reader: &mut R, header: Header) -> Result<Vec<[FaceIdx;3]>, Err>
+/// entry point for decoding connectivity.
+pub(crate) fn decode_connectivity<R>(reader: &mut R, header: &Header, cfg: &Config) -> Result<Output, Err>
     where R: ByteReader,
 {
-    let connectivity = match header.encoding_method {
-        EncoderMethod::Edgebreaker => {
-            debug_expect!("Start of edgebreaker connectivity", reader);
-            let mut decoder = spirale_reversi::SpiraleReversi::new();
-            decoder.decode_connectivity(reader)?
+    let edgebreaker_out = match header.encoding_method {
+        EncoderMethod::Edgebreaker => edgebreaker::decode_connectivity(reader, header, cfg)?,
+        // a point cloud written as the sequence of its points, which has only the number of the points.
+        EncoderMethod::Sequential if header.point_sequence => {
+            let num_points = reader.read_u32()? as usize;
+            LimitExceeded::check("points", num_points, cfg.max_vertices).map_err(Err::LimitExceeded)?;
+            return Ok(Output::point_cloud(num_points));
         },
         EncoderMethod::Sequential => {
-            debug_expect!("Start of sequential connectivity", reader);
-            let mut decoder = sequential::Sequential;
-            decoder.decode_connectivity(reader)?
-        }
+            let out = sequential::decode_connectivity(reader, header, cfg)?;
+            return Ok(Output::sequential(out));
+        },
     };
 
-    Ok(connectivity)
-}
+    let invalid = || edgebreaker::Err::InvalidConnectivity("the opposite corners do not make the vertices");
+    let mut corner_table = DecodedCornerTable::from_opposites(edgebreaker_out.opposite_corners.clone())
+        .ok_or_else(invalid)?;
+    let mut attribute_corner_tables = edgebreaker_out.seams.iter()
+        .map(|seams| {
+            let opposites = edgebreaker_out.opposite_corners.iter()
+                .zip(seams)
+                .map(|(&opp, &is_seam)| if is_seam { None } else { opp })
+                .collect();
+            DecodedCornerTable::from_opposites(opposites).ok_or_else(invalid)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
 
+    // A point is a unique combination of the vertices of all the corner tables.
+    let mut points = HashMap::new();
+    let corner_to_point = (0..corner_table.num_corners())
+        .map(CornerIdx::from)
+        .map(|c| {
+            let key = std::iter::once(&corner_table)
+                .chain(attribute_corner_tables.iter())
+                .map(|table| table.vertex_idx(c))
+                .collect::<Vec<_>>();
+            let num_points = points.len();
+            *points.entry(key).or_insert(PointIdx::from(num_points))
+        })
+        .collect::<Vec<_>>();
+    let faces = corner_to_point.chunks(3)
+        .map(|f| [f[0], f[1], f[2]])
+        .collect();
 
-pub trait ConnectivityDecoder {
-    type Err;
-    fn decode_connectivity<R>(&mut self, reader: &mut R) -> Result<Vec<[VertexIdx; 3]>, Self::Err>
-        where R: ByteReader;
-}
\ No newline at end of file
+    corner_table.set_points(corner_to_point.clone());
+    for table in attribute_corner_tables.iter_mut() {
+        table.set_points(corner_to_point.clone());
+    }
+
+    Ok(Output {
+        corner_table,
+        attribute_corner_tables,
+        faces,
+        num_points: points.len(),
+        in_point_order: false,
+        #[cfg(feature = "validation")]
+        cut_edges: edgebreaker_out.cut_edges,
+    })
+}
@@ -129,28 +129,13 @@ impl<R, const RABS_PRECISION: usize> RabsDecoder<R, RABS_PRECISION>
     }
 }
 
-const fn compute_rans_precision(num_symbols_bit_length: usize) -> usize {
-    let mut precision = 12;
-    if num_symbols_bit_length > 0 {
-        precision = (num_symbols_bit_length + 2) / 3;
-    }
-    if precision < 12 {
-        12
-    } else if precision > 20 {
-        20
-    } else {
-        precision
-    }
-}
-
 pub(crate) struct RansSymbolDecoder<R, const NUM_SYMBOLS_BIT_LENGTH: usize, const RANS_PRECISION: usize> 
     where R: ByteReader
 {
-    freq_counts: Vec<usize>,
     rans_decoder: RansDecoder<R::Rev, RANS_PRECISION>, 
 }
 
-impl<'reader, R, const NUM_SYMBOLS_BIT_LENGTH: usize, const RANS_PRECISION: usize> 
+impl<R, const NUM_SYMBOLS_BIT_LENGTH: usize, const RANS_PRECISION: usize> 
         RansSymbolDecoder<R, NUM_SYMBOLS_BIT_LENGTH, RANS_PRECISION>
     where R: ByteReader{
     pub fn new(reader: &mut R) -> Result<Self, Err>
@@ -192,12 +177,11 @@ impl<'reader, R, const NUM_SYMBOLS_BIT_LENGTH: usize, const RANS_PRECISION: usiz
         let rans_decoder: RansDecoder<_, RANS_PRECISION> = RansDecoder::new(
             reader, 
             offset, 
-            freq_counts.clone(), 
+            freq_counts, 
             None
         )?;
 
         Ok(Self {
-            freq_counts,
             rans_decoder,
         })
     }
@@ -1,6 +1,7 @@
 use crate::core::bit_coder::ReaderErr;
 use crate::decode::entropy::rans::RansSymbolDecoder;
-use crate::prelude::{BitReader, ByteReader};
+use crate::core::bit_coder::BitReader;
+use crate::prelude::ByteReader;
 use crate::shared::entropy::SymbolEncodingMethod;
 use super::rans;
 
@@ -22,8 +23,8 @@ pub enum Err {
 /// - `num_symbols`: The number of symbols to decode.
 /// - `num_components`: The number of components for each symbol.
 /// - `reader`: The byte reader to read the encoded symbols from.
+///
 /// Returns a vector of decoded symbols or an error if decoding fails.
-#[allow(unused)] // TODO: Remove this when decoder is fully implemented
 pub fn decode_symbols<R>(
     num_symbols: usize, num_components: usize, reader: &mut R
 ) -> Result<Vec<u64>, Err> 
@@ -41,7 +42,6 @@ pub fn decode_symbols<R>(
 
 
 
-#[allow(unused)] // TODO: Remove this when decoder is fully implemented
 pub fn decode_symbols_length_coded<R>(
     num_symbols: usize, num_components: usize, reader: &mut R
 ) -> Result<Vec<u64>, Err> 
@@ -58,9 +58,7 @@ pub fn decode_symbols_length_coded<R>(
         // Decode the symbol.
         if len == 0 {
             // If the length is 0, we can skip decoding this symbol.
-            for _ in 0..num_components {
-                out.push(0);
-            }
+            out.resize(out.len() + num_components, 0);
             continue;
         }
         for _ in 0..num_components {
@@ -72,7 +70,6 @@ pub fn decode_symbols_length_coded<R>(
     Ok(out)
 }
 
-#[allow(unused)] // TODO: Remove this when decoder is fully implemented
 pub fn decode_symbols_direcd_coded<R>(
     num_symbols: usize, reader: &mut R
 ) -> Result<Vec<u64>, Err> 
@@ -208,4 +205,27 @@ mod tests {
         assert_eq!(decoded_symbols, symbols);
         Ok(())
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_encode_decode_rare_symbols() -> Result<(), Err> {
+        // the rare bit lengths keep a frequency when the most frequent one is rounded up.
+        let mut symbols = vec![0; 9997];
+        symbols.extend([8, 16, 1024]);
+        let mut buffer = Vec::new();
+        symbol_coding::encode_symbols(
+            symbols.clone(), 
+            1, 
+            SymbolEncodingMethod::LengthCoded, 
+            &mut buffer
+        ).unwrap();
+        let mut reader = buffer.into_iter();
+        let decoded_symbols = decode_symbols(
+            symbols.len(), 
+            1, 
+            &mut reader
+        )?;
+        assert_eq!(reader.next(), None, "Reader should be empty after decoding all symbols");
+        assert_eq!(decoded_symbols, symbols);
+        Ok(())
+    }
+}
@@ -1,37 +1,71 @@
 use crate::prelude::ByteReader;
 use crate::core::bit_coder::ReaderErr;
+use crate::encode::header::{
+    BYTE_ALIGNED_SYMBOLS_FLAG_MASK, CONTEXT_CLERS_FLAG_MASK, CUSTOM_VERTEX_ORDER_FLAG_MASK, DEBUG_MARKERS_FLAG_MASK,
+    KD_TREE_FLAG_MASK, LOOSE_POINTS_FLAG_MASK, METADATA_FLAG_MASK, PLANAR_LAYOUT_FLAG_MASK, POINT_SEQUENCE_FLAG_MASK
+};
+use crate::encode::point_cloud::PointCloudMethod;
 use crate::shared::header::EncoderMethod;
 
 
+#[remain::sorted]
 #[derive(thiserror::Error, Debug)]
 pub enum Err {
     #[error("Not a Draco file")]
     NotADracoFile,
     #[error("Not enough data: {0}")]
     NotEnoughData(#[from] ReaderErr),
+    #[error("Unknown encoder method id: {0}")]
+    UnknownEncoderMethod(u8),
+    #[error("Unsupported geometry type: {0}; only triangular meshes and point clouds can be decoded")]
+    UnsupportedGeometryType(u8),
+    #[error("The points are coded by Draco's KD-tree, which is not supported")]
+    UnsupportedKdTree,
 }
 
 pub(crate) struct Header {
+    #[allow(unused)]
     pub version_major: u8,
+    #[allow(unused)]
     pub version_minor: u8,
+    #[allow(unused)]
     pub encoder_type: u8,
+    /// The method of the connectivity, which is [EncoderMethod::Sequential] for a point cloud.
     pub encoding_method: EncoderMethod,
+    /// The method of the points if the stream is a point cloud.
+    pub point_cloud_method: Option<PointCloudMethod>,
     pub contains_metadata: bool,
+    /// Whether the attribute values are hinted to be output in [crate::core::attribute::AttributeLayout::Planar].
+    pub planar_layout: bool,
+    /// Whether the attribute values are encoded in the vertex order given by the user.
+    pub custom_vertex_order: bool,
+    /// Whether the CLERS symbols are coded with the context of the following symbol.
+    pub context_clers: bool,
+    /// Whether the CLERS symbols and the topology splits are stored byte-aligned instead of bit-packed.
+    pub byte_aligned_symbols: bool,
+    /// Whether the debug markers are written between the sections of the stream.
+    pub contains_debug_markers: bool,
+    /// Whether the points that no face refers to are written after the attributes.
+    pub contains_loose_points: bool,
+    /// Whether the point cloud is written as the values of all its attributes in the order of the points.
+    pub point_sequence: bool,
 }
 
-const METADATA_FLAG_MASK: u16 = 32768;
+/// The id of the geometry type of a point cloud.
+const POINT_CLOUD: u8 = 0;
+/// The id of the geometry type of a triangular mesh.
+const TRIANGULAR_MESH: u8 = 1;
 
 pub fn decode_header<W>(reader: &mut W) -> Result<Header, Err>
 where
     W: ByteReader,
 {
     // Read the draco string
-    if !(0..5).map(|_| reader.read_u8().unwrap() as char ) // ToDo: remove unwrap, handle error properly
-            .zip("DRACO".chars())
-            .all(|(a, b)| a == b)
-    {
-        return Err(Err::NotADracoFile)
-    };
+    for expected in "DRACO".bytes() {
+        if reader.read_u8()? != expected {
+            return Err(Err::NotADracoFile)
+        }
+    }
 
     // Read the version
     let version_major = reader.read_u8()?;
@@ -40,12 +74,28 @@ where
     // Readd the encoder type
     let encoder_type = reader.read_u8()?;
 
-    // Read the encoding method
-    let encoding_method = EncoderMethod::read_from(reader)?;
+    // Read the encoding method, which is that of the points for a point cloud.
+    let method_id = reader.read_u8()?;
+    let (encoding_method, point_cloud_method) = match encoder_type {
+        TRIANGULAR_MESH => (EncoderMethod::from_id(method_id).ok_or(Err::UnknownEncoderMethod(method_id))?, None),
+        POINT_CLOUD => (EncoderMethod::Sequential, Some(PointCloudMethod::from_id(method_id).ok_or(Err::UnknownEncoderMethod(method_id))?)),
+        _ => return Err(Err::UnsupportedGeometryType(encoder_type)),
+    };
 
     let flags = reader.read_u16()?;
 
     let contains_metadata = flags & METADATA_FLAG_MASK != 0;
+    let planar_layout = flags & PLANAR_LAYOUT_FLAG_MASK != 0;
+    let custom_vertex_order = flags & CUSTOM_VERTEX_ORDER_FLAG_MASK != 0;
+    let context_clers = flags & CONTEXT_CLERS_FLAG_MASK != 0;
+    let byte_aligned_symbols = flags & BYTE_ALIGNED_SYMBOLS_FLAG_MASK != 0;
+    let contains_debug_markers = flags & DEBUG_MARKERS_FLAG_MASK != 0;
+    let contains_loose_points = flags & LOOSE_POINTS_FLAG_MASK != 0;
+    let point_sequence = flags & POINT_SEQUENCE_FLAG_MASK != 0;
+    // Draco's KD-tree shares the method id with that of draco-oxide, which is told apart by the flag.
+    if point_cloud_method == Some(PointCloudMethod::KdTree) && !point_sequence && flags & KD_TREE_FLAG_MASK == 0 {
+        return Err(Err::UnsupportedKdTree);
+    }
 
     Ok (
         Header {
@@ -53,7 +103,78 @@ where
             version_minor,
             encoder_type,
             encoding_method,
+            point_cloud_method,
             contains_metadata,
+            planar_layout,
+            custom_vertex_order,
+            context_clers,
+            byte_aligned_symbols,
+            contains_debug_markers,
+            contains_loose_points,
+            point_sequence,
         }
     )
-}
\ No newline at end of file
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::shared::ConfigType;
+    use crate::encode::header::{encode_header, VERSION_MAJOR, VERSION_MINOR};
+    use crate::prelude::SliceReader;
+
+    #[test]
+    fn test_header_round_trip() {
+        let mut cfg = crate::encode::Config::default();
+        cfg.debug_markers = true;
+        cfg.attribute_layout = crate::core::attribute::AttributeLayout::Planar;
+        let mut buffer = Vec::new();
+        encode_header(&mut buffer, &cfg).unwrap();
+        assert_eq!(&buffer[..7], b"DRACO\x02\x02");
+
+        let header = decode_header(&mut SliceReader::new(&buffer)).unwrap();
+        assert_eq!((header.version_major, header.version_minor), (VERSION_MAJOR, VERSION_MINOR));
+        assert_eq!(header.encoder_type, TRIANGULAR_MESH);
+        assert_eq!(header.encoding_method, EncoderMethod::Edgebreaker);
+        assert_eq!(header.point_cloud_method, None);
+        assert!(header.contains_debug_markers);
+        assert!(header.planar_layout);
+        assert!(!header.contains_metadata);
+
+        buffer[0] = b'd';
+        assert!(matches!(decode_header(&mut SliceReader::new(&buffer)), Err(Err::NotADracoFile)));
+
+        // a mesh without faces is written as the sequence of its points.
+        #[cfg(not(feature = "evaluation"))]
+        {
+            let mut mesh = crate::io::obj::load_obj("tests/data/tetrahedron.obj").unwrap();
+            mesh.faces.clear();
+            let mut buffer = Vec::new();
+            crate::encode::encode(mesh, &mut buffer, crate::encode::Config::default()).unwrap();
+            let header = decode_header(&mut SliceReader::new(&buffer)).unwrap();
+            assert_eq!(header.encoder_type, POINT_CLOUD);
+            assert_eq!(header.point_cloud_method, Some(PointCloudMethod::Sequential));
+            assert!(header.point_sequence);
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature = "evaluation"))]
+    fn test_kd_tree_flag() {
+        let mut mesh = crate::io::obj::load_obj("tests/data/tetrahedron.obj").unwrap();
+        mesh.faces.clear();
+        mesh.attributes.retain(|att| att.get_attribute_type() == crate::prelude::AttributeType::Position);
+        let mut cfg = crate::encode::Config::default();
+        cfg.point_cloud_method = PointCloudMethod::KdTree;
+        let mut buffer = Vec::new();
+        crate::encode::encode_point_cloud(mesh, &mut buffer, cfg).unwrap();
+        let header = decode_header(&mut SliceReader::new(&buffer)).unwrap();
+        assert_eq!(header.point_cloud_method, Some(PointCloudMethod::KdTree));
+
+        // the method id of Draco's KD-tree without the flag is not taken for that of draco-oxide.
+        // the flags are the little-endian u16 after the magic, the version, the geometry type and the method.
+        buffer[9] &= !(KD_TREE_FLAG_MASK as u8);
+        assert!(matches!(decode_header(&mut SliceReader::new(&buffer)), Err(Err::UnsupportedKdTree)));
+    }
+}
@@ -1,104 +1,101 @@
-use crate::core::bit_coder::ReaderErr; 
-use crate::prelude::ByteReader;
+use crate::core::attribute::Attribute;
+use crate::core::bit_coder::{ByteReader, ReaderErr};
+use crate::core::mesh::metadata::Metadata;
+use crate::encode::metadata::ATTRIBUTE_NAME_KEY;
 use crate::utils::bit_coder::leb128_read;
 
+/// The maximum depth of the sub-metadata, as they are read recursively.
+const MAX_DEPTH: usize = 32;
 
+#[remain::sorted]
 #[derive(thiserror::Error, Debug)]
 pub enum Err {
-    #[error("Not enough data to decode metadata.")]
-    NotEnoughData(#[from] ReaderErr),
+    #[error("Invalid metadata: {0}")]
+    InvalidMetadata(String),
+    #[error("Reader error: {0}")]
+    ReaderError(#[from] ReaderErr),
+    #[error("The sub-metadata are nested deeper than {0}")]
+    TooDeep(usize),
 }
 
-pub struct Metadata {
-    pub metadata: Vec<AttributeMetadata>,
-    pub global_metadata: AttributeMetadata,
+/// Reads the metadata written by [crate::encode::metadata::encode_metadata], returning the metadata of each attribute
+/// with the unique id of the attribute. The metadata of the geometry and the sub-metadata are skipped.
+pub(crate) fn read_attribute_metadata<R>(reader: &mut R) -> Result<Vec<(usize, Metadata)>, Err>
+    where R: ByteReader,
+{
+    // The number of the metadata is not trusted for the allocation, as the stream may be corrupted.
+    let num_attribute_metadata = leb128_read(reader)?;
+    let mut out = Vec::new();
+    for _ in 0..num_attribute_metadata {
+        let id = leb128_read(reader)? as usize;
+        out.push((id, read_metadata(reader, 0)?));
+    }
+    read_metadata(reader, 0)?;
+    Ok(out)
 }
 
-#[derive(Debug, Clone)]
-pub struct SubMetadata {
-    pub key: Vec<u8>,
-    pub value: Vec<u8>,
+fn read_metadata<R>(reader: &mut R, depth: usize) -> Result<Metadata, Err>
+    where R: ByteReader,
+{
+    if depth > MAX_DEPTH {
+        return Err(Err::TooDeep(MAX_DEPTH));
+    }
+    let mut metadata = Metadata::new();
+    let num_entries = leb128_read(reader)?;
+    for _ in 0..num_entries {
+        let key = read_string(reader)?;
+        let value = read_string(reader)?;
+        metadata.add_entry(key, value);
+    }
+    let num_sub_metadata = leb128_read(reader)?;
+    for _ in 0..num_sub_metadata {
+        read_string(reader)?;
+        read_metadata(reader, depth + 1)?;
+    }
+    Ok(metadata)
 }
 
-impl SubMetadata {
-    pub fn read_from<W>(reader: &mut W) -> Result<Self, Err>
-    where W: ByteReader,
-    {
-        let key_length = reader.read_u8()?;
-        let mut key = vec![0; key_length as usize];
-        for i in 0..key_length {
-            key[i as usize] = reader.read_u8()?;
-        }
-        let value_length = reader.read_u8()?;
-        let mut value = vec![0; value_length as usize];
-        for i in 0..value_length {
-            value[i as usize] = reader.read_u8()?;
+fn read_string<R>(reader: &mut R) -> Result<String, Err>
+    where R: ByteReader,
+{
+    let len = reader.read_u8()? as usize;
+    let bytes = (0..len).map(|_| reader.read_u8()).collect::<Result<Vec<_>, _>>()?;
+    String::from_utf8(bytes).map_err(|err| Err::InvalidMetadata(err.to_string()))
+}
+
+/// Restores the names of the decoded attributes from their metadata read by [read_attribute_metadata].
+pub(crate) fn apply_attribute_names(attributes: &mut [Attribute], metadata: &[(usize, Metadata)]) {
+    for (id, metadata) in metadata {
+        let Some(name) = metadata.get_entry(ATTRIBUTE_NAME_KEY) else {
+            continue;
+        };
+        if let Some(att) = attributes.iter_mut().find(|att| att.get_id().as_usize() == *id) {
+            att.set_name(name.clone());
         }
-        Ok(SubMetadata { key, value })
     }
 }
 
-#[derive(Debug, Clone)]
-pub struct AttributeMetadata {
-    pub key: Vec<u8>,
-    pub value: Vec<u8>,
-    pub submetadata: Vec<SubMetadata>,
-}
 
-impl AttributeMetadata {
-    pub fn read_from<W>(reader: &mut W) -> Result<Self, Err>
-    where W: ByteReader,
-    {
-        let key_length = reader.read_u8()?;
-        let mut key = vec![0; key_length as usize];
-        for _ in 0..key_length {
-            key.push(reader.read_u8()?);
-        }
-        let value_length = reader.read_u8()?;
-        let mut value = vec![0; value_length as usize];
-        for _ in 0..value_length {
-            value.push(reader.read_u8()?);
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::bit_coder::SliceReader;
+    use crate::utils::bit_coder::leb128_write;
 
-        // read sub_metadata
-        let num_submetadata = leb128_read(reader)? as u32;
-        let mut submetadata = Vec::with_capacity(num_submetadata as usize);
-        for _ in 0..num_submetadata {
-            submetadata.push(SubMetadata::read_from(reader)?);
-        }
-        Ok(AttributeMetadata {
-            key,
-            value,
-            submetadata: submetadata,
-        })
-    }
+    #[test]
+    fn test_corrupted_counts() {
+        // a huge number of the metadata is not allocated up front.
+        let mut stream = Vec::new();
+        leb128_write(u64::MAX, &mut stream);
+        assert!(matches!(read_attribute_metadata(&mut SliceReader::new(&stream)), Err(Err::ReaderError(_))));
 
-    pub fn empty_metadta() -> Self {
-        AttributeMetadata {
-            key: Vec::new(),
-            value: Vec::new(),
-            submetadata: Vec::new(),
+        // the nested sub-metadata are cut at the maximum depth.
+        let mut stream = Vec::new();
+        leb128_write(0, &mut stream);
+        for _ in 0..1_000_000 {
+            // no entries, and one sub-metadata with an empty name.
+            stream.extend([0, 1, 0]);
         }
+        assert!(matches!(read_attribute_metadata(&mut SliceReader::new(&stream)), Err(Err::TooDeep(MAX_DEPTH))));
     }
 }
-
-pub fn decode_metadata<W>(reader: &mut W) -> Result<Metadata, Err>
-    where W: ByteReader,
-{
-    let num_metadata = reader.read_u32()?;
-    let mut metadta_id = Vec::with_capacity(num_metadata as usize);
-    let mut metadata = Vec::new();
-    metadata.resize(num_metadata as usize, AttributeMetadata::empty_metadta()); 
-    for _ in 0..num_metadata {
-        metadta_id.push(leb128_read(reader)?);
-        metadata[*metadta_id.last().unwrap() as usize] = AttributeMetadata::read_from(reader)?;
-    }
-    let global_metadta = AttributeMetadata::read_from(reader)?;
-
-    let out = Metadata {
-        metadata,
-        global_metadata: global_metadta,
-    };
-
-    Ok(out)
-}
\ No newline at end of file
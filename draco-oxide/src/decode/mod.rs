@@ -1,59 +1,280 @@
-use crate::{debug_expect, prelude::{ByteReader, ConfigType}, Mesh};
+use crate::{debug_expect, prelude::{Attribute, ByteReader, ConfigType, Mesh}};
+use crate::core::scene::Scene;
+use crate::core::attribute::{AttributeDomain, AttributeId, AttributeLayout, AttributeType, ComponentDataType};
+use crate::core::shared::{NdVector, PointIdx};
+use crate::io::container;
+use crate::encode::point_cloud::PointCloudMethod;
 
-mod header;
-mod metadata;
-// mod connectivity;
-// mod attribute;
-mod entropy;
+pub(crate) mod header;
+pub(crate) mod connectivity;
+pub(crate) mod attribute;
+pub(crate) mod entropy;
+pub(crate) mod metadata;
+pub(crate) mod point_cloud;
+
+pub use crate::shared::attribute::portabilization::quantization_metadata::{OutOfBounds, SanityBounds};
+pub use crate::encode::point_cloud::tiles::PointCloudTile;
+pub use point_cloud::tiles::{decode_tile, read_tile_index};
 
 pub fn decode<W>(reader: &mut W, cfg: Config) -> Result<Mesh, Err> 
     where W: ByteReader
+{
+    decode_mesh(reader, cfg).map(|(mesh, _)| mesh)
+}
+
+/// Decodes the stream like [decode], and also returns the values of each attribute as bytes in the layout of
+/// [Config::attribute_layout], or in the layout hinted by the encoder (see [crate::encode::Config::attribute_layout])
+/// if it is not set. The bytes are in the order of the attributes of the mesh.
+pub fn decode_with_layout<W>(reader: &mut W, cfg: Config) -> Result<(Mesh, Vec<Vec<u8>>), Err> 
+    where W: ByteReader
+{
+    let layout = cfg.attribute_layout;
+    let (mesh, header) = decode_mesh(reader, cfg)?;
+    let layout = layout.unwrap_or(if header.planar_layout { AttributeLayout::Planar } else { AttributeLayout::Interleaved });
+    let bytes = mesh.attributes.iter()
+        .map(|att| att.to_bytes_with_layout(layout))
+        .collect();
+    Ok((mesh, bytes))
+}
+
+/// Assembles the mesh from the parts as they are decoded, and returns it with the header of the stream.
+fn decode_mesh<W>(reader: &mut W, cfg: Config) -> Result<(Mesh, header::Header), Err> 
+    where W: ByteReader
+{
+    let mut mesh = Mesh::new();
+    let mut loose_points = Vec::new();
+    let header = decode_parts(reader, cfg, &mut |event| match event {
+        DecodeEvent::ConnectivityReady(faces) => mesh.faces = faces.to_vec(),
+        DecodeEvent::AttributeReady(att) => mesh.attributes.push(att.clone()),
+        DecodeEvent::LoosePointsReady(points) => loose_points = points.to_vec(),
+    })?;
+    if !loose_points.is_empty() {
+        append_loose_points(&mut mesh, loose_points)?;
+    }
+    Ok((mesh, header))
+}
+
+/// Appends the points that no face refers to after the points of the faces, see [crate::encode::Config::loose_points].
+/// Only their positions are in the stream, so the mesh must have no other attributes.
+fn append_loose_points(mesh: &mut Mesh, points: Vec<[f32; 3]>) -> Result<(), Err> {
+    let unsupported = |msg: &str| Err::PointCloudError(point_cloud::Err::UnsupportedAttribute(msg.to_string()));
+    if mesh.attributes.first().is_none_or(|att| att.len() == 0) {
+        return Err(unsupported("loose points without any point of the faces"));
+    }
+    if mesh.attributes.iter().any(|att| att.get_attribute_type() != AttributeType::Position) {
+        return Err(unsupported("loose points of a mesh with other attributes than the positions"));
+    }
+    for att in &mut mesh.attributes {
+        let (id, domain, parents) = (att.get_id(), att.get_domain(), att.get_parents().clone());
+        let other = match att.get_component_type() {
+            ComponentDataType::F32 => Attribute::from_without_removing_duplicates(
+                id, points.iter().map(|&p| NdVector::from(p)).collect(), AttributeType::Position, domain, parents
+            ),
+            ComponentDataType::F64 => Attribute::from_without_removing_duplicates(
+                id, points.iter().map(|&p| NdVector::from(p.map(f64::from))).collect(), AttributeType::Position, domain, parents
+            ),
+            _ => return Err(unsupported("loose points of a position attribute of neither f32 nor f64")),
+        };
+        att.concat(other);
+    }
+    Ok(())
+}
+
+/// Reports the limit exceeded by the points of the stream as [Err::LimitExceeded], like the other limits.
+fn point_cloud_err(err: point_cloud::Err) -> Err {
+    match err {
+        point_cloud::Err::LimitExceeded(err) => Err::LimitExceeded(err),
+        err => Err::PointCloudError(err),
+    }
+}
+
+/// Decodes the meshes of a container written by [crate::encode::encode_many], in the order they were encoded.
+/// Each stream is decoded with the same configuration.
+pub fn decode_many<W>(reader: &mut W, cfg: Config) -> Result<Vec<Mesh>, Err> 
+    where W: ByteReader
+{
+    container::read_container(reader)?
+        .into_iter()
+        .enumerate()
+        .map(|(i, stream)| decode(&mut crate::prelude::SliceReader::new(&stream), cfg.clone())
+            .map_err(|err| Err::ContainerError(i, Box::new(err)))
+        )
+        .collect()
+}
+
+/// Decodes the scene written by [crate::encode::encode_scene], i.e. its meshes and its node hierarchy.
+/// The transform of each node is restored as a matrix.
+pub fn decode_scene<W>(reader: &mut W, cfg: Config) -> Result<Scene, Err> 
+    where W: ByteReader
+{
+    let meshes = decode_many(reader, cfg)?;
+    Ok(Scene::read_hierarchy(reader, meshes)?)
+}
+
+/// A part of the mesh passed to the callback of [decode_streaming] as soon as it is decoded.
+pub enum DecodeEvent<'a> {
+    /// The faces are decoded. They come before any attribute.
+    ConnectivityReady(&'a [[PointIdx; 3]]),
+    /// An attribute is decoded. The attributes come in the order of the stream, and the parents
+    /// of an attribute come before it.
+    AttributeReady(&'a Attribute),
+    /// The positions of the points that no face refers to are decoded, see [crate::encode::Config::loose_points].
+    /// They come after all the attributes, and only if the encoder has written them.
+    LoosePointsReady(&'a [[f32; 3]]),
+}
+
+/// Decodes the stream like [decode], but passes each part of the mesh to 'callback' as soon as it is decoded
+/// instead of returning the mesh at the end, e.g. so that a viewer can show the geometry progressively.
+pub fn decode_streaming<W, F>(reader: &mut W, cfg: Config, callback: &mut F) -> Result<(), Err> 
+    where 
+        W: ByteReader,
+        F: FnMut(DecodeEvent<'_>),
+{
+    decode_parts(reader, cfg, callback).map(|_| ())
+}
+
+/// Decodes the stream for [decode_streaming], and returns its header.
+fn decode_parts<W, F>(reader: &mut W, cfg: Config, callback: &mut F) -> Result<header::Header, Err> 
+    where 
+        W: ByteReader,
+        F: FnMut(DecodeEvent<'_>),
 {
     // Decode header
     let header = header::decode_header(reader)
-        .map_err(|r| Err::HeaderError(r))?;
+        .map_err(Err::HeaderError)?;
 
-    debug_expect!("Header done, now starting metadata.", reader);
+    debug_expect!("Header done, now starting metadata.", reader, header.contains_debug_markers)?;
 
     // Decode metadata
-    if header.contains_metadata {
-        let metadata  = metadata::decode_metadata(reader)
-            .map_err(|r| Err::MetadataError(r))?;
-    }
+    let metadata = if header.contains_metadata {
+        metadata::read_attribute_metadata(reader)
+            .map_err(Err::MetadataError)?
+    } else {
+        Vec::new()
+    };
 
-    debug_expect!("Metadata done, now starting connectivity.", reader);
+    debug_expect!("Metadata done, now starting connectivity.", reader, header.contains_debug_markers)?;
+
+    // A point cloud of the positions alone, see [crate::encode::encode_point_cloud].
+    if header.point_cloud_method == Some(PointCloudMethod::KdTree) && !header.point_sequence {
+        let points = point_cloud::decode_kd_tree_points(reader, 0, cfg.max_vertices)
+            .map_err(point_cloud_err)?;
+        let positions = Attribute::from_without_removing_duplicates(
+            AttributeId::new(0), points.into_iter().map(NdVector::from).collect(), AttributeType::Position, AttributeDomain::Position, Vec::new()
+        );
+        callback(DecodeEvent::ConnectivityReady(&[]));
+        callback(DecodeEvent::AttributeReady(&positions));
+        return Ok(header);
+    }
 
     // Decode connectivity
-    // let connectivity = connectivity::decode_connectivity_att(reader, header)
-    //     .map_err(|r| Err::ConnectivityError(r))?;
+    let connectivity = connectivity::decode_connectivity(reader, &header, &cfg)
+        .map_err(|err| match err {
+            connectivity::Err::EdgebreakerError(connectivity::edgebreaker::Err::LimitExceeded(err))
+            | connectivity::Err::SequentialError(connectivity::sequential::Err::LimitExceeded(err))
+            | connectivity::Err::LimitExceeded(err) => Err::LimitExceeded(err),
+            err => Err::ConnectivityError(err),
+        })?;
+    callback(DecodeEvent::ConnectivityReady(&connectivity.faces));
 
-    debug_expect!("Connectivity done, now starting attributes.", reader);
+    debug_expect!("Connectivity done, now starting attributes.", reader, header.contains_debug_markers)?;
 
-    // Decode attributes
-    // let attributes = attribute::decode_attributes(reader, cfg.attribute_decoder_cfg, connectivity)
-    //     .map_err(|r| Err::AttributeError(r))?;
+    // Decode attributes, passing each to the callback as soon as it is decoded.
+    let all_points = (0..connectivity.num_points).map(PointIdx::from).collect::<Vec<_>>();
+    #[cfg(feature = "validation")]
+    let mut cut_edges_err = None;
+    attribute::decode_attributes(reader, &header, &connectivity, cfg.sanity_bounds, cfg.max_attribute_bytes, |mut att| {
+        if !cfg.emit_attribute_indices {
+            att = att.select_points(&all_points);
+        }
+        // The edges left open by the connectivity are checked against the areas of the faces next to them.
+        #[cfg(feature = "validation")]
+        if att.get_attribute_type() == AttributeType::Position && cut_edges_err.is_none() {
+            cut_edges_err = crate::core::corner_table::validation::validate_cut_edges(&connectivity.cut_edges, &connectivity.faces, &att).err();
+        }
+        metadata::apply_attribute_names(std::slice::from_mut(&mut att), &metadata);
+        callback(DecodeEvent::AttributeReady(&att));
+    }).map_err(|err| match err {
+        attribute::Err::LimitExceeded(err) => Err::LimitExceeded(err),
+        err => Err::AttributeError(err),
+    })?;
+    #[cfg(feature = "validation")]
+    if let Some(err) = cut_edges_err {
+        return Err(Err::ConnectivityError(connectivity::Err::EdgebreakerError(err.into())));
+    }
 
-    debug_expect!("All done", reader);
+    if header.contains_loose_points {
+        // The loose points come after the points of the faces, so they count towards the same limit.
+        let points = point_cloud::decode_kd_tree_points(reader, connectivity.num_points, cfg.max_vertices)
+            .map_err(point_cloud_err)?;
+        callback(DecodeEvent::LoosePointsReady(&points));
+    }
 
-    // // Create mesh
-    let mut mesh = Mesh::new();
-    // for att in attributes {
-    //     mesh.add_attribute(att);
-    // }
+    debug_expect!("All done", reader, header.contains_debug_markers)?;
 
-    Ok(mesh)
+    Ok(header)
 }
 
 
 #[derive(Debug, Clone)]
 pub struct Config {
-    // attribute_decoder_cfg: attribute::Config,
+    /// If set, the quantization metadata of each attribute is checked against these bounds before
+    /// the values are dequantized, so that an untrusted stream cannot produce absurd floats.
+    pub sanity_bounds: Option<SanityBounds>,
+
+    /// If set, the stream is rejected with [Err::LimitExceeded] when it claims more vertices than this,
+    /// before the connectivity is decoded. The loose points (see [crate::encode::Config::loose_points]) count
+    /// towards the limit too, and are checked before they are decoded.
+    pub max_vertices: Option<usize>,
+
+    /// If set, the stream is rejected with [Err::LimitExceeded] when it claims more faces than this,
+    /// before the connectivity is decoded.
+    pub max_faces: Option<usize>,
+
+    /// If set, the stream is rejected with [Err::LimitExceeded] when its attributes would take more bytes than this
+    /// in total once decoded, before any attribute value is decoded.
+    pub max_attribute_bytes: Option<usize>,
+
+    /// The layout of the attribute bytes returned by [decode_with_layout]. If not set, the layout hinted by
+    /// the encoder in the header is used.
+    pub attribute_layout: Option<AttributeLayout>,
+
+    /// If true, each decoded attribute keeps its own values and the map from the points to them, so that
+    /// [Mesh::attribute_index_stream] gives a separate index buffer for each attribute, e.g. for the texture
+    /// coordinates of a seamed mesh. Otherwise each attribute has a value for every point, i.e. the faces are
+    /// the only index buffer.
+    pub emit_attribute_indices: bool,
 }
 
 impl ConfigType for Config {
     fn default() -> Self {
         Self {
-            // attribute_decoder_cfg: attribute::Config::default(),
+            sanity_bounds: None,
+            max_vertices: None,
+            max_faces: None,
+            max_attribute_bytes: None,
+            attribute_layout: None,
+            emit_attribute_indices: false,
+        }
+    }
+}
+
+/// A size claimed by the stream that is over its limit in [Config], so that a crafted stream cannot make
+/// the decoder allocate an absurd amount of memory.
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("The stream has {count} {what}, but at most {limit} are allowed")]
+pub struct LimitExceeded {
+    pub what: &'static str,
+    pub count: usize,
+    pub limit: usize,
+}
+
+impl LimitExceeded {
+    /// Checks the size claimed by the stream against its limit, if any.
+    pub(crate) fn check(what: &'static str, count: usize, limit: Option<usize>) -> Result<(), Self> {
+        match limit {
+            Some(limit) if count > limit => Err(Self { what, count, limit }),
+            _ => Ok(()),
         }
     }
 }
@@ -62,13 +283,464 @@ impl ConfigType for Config {
 #[remain::sorted]
 #[derive(thiserror::Error, Debug)]
 pub enum Err {
-    // #[error("Attribute encoding error")]
-    // AttributeError(#[from] attribute::Err),
-    // #[error("Connectivity encoding error")]
-    // ConnectivityError(#[from] connectivity::Err),
-    #[error("Header encoding error")]
+    #[error("Attribute decoding error: {0}")]
+    AttributeError(#[from] attribute::Err),
+    #[error("Connectivity decoding error: {0}")]
+    ConnectivityError(#[from] connectivity::Err),
+    #[error("Error in the {0}th mesh: {1}")]
+    ContainerError(usize, Box<Err>),
+    #[error("Container decoding error: {0}")]
+    ContainerReadError(#[from] container::Err),
+    #[error("The stream does not have the debug marker {0:?}")]
+    DebugMarkerMismatch(&'static str),
+    #[error("Header decoding error: {0}")]
     HeaderError(#[from] header::Err),
-    #[error("Metadata encoding error")]
+    #[error("{0}")]
+    LimitExceeded(LimitExceeded),
+    #[error("Metadata decoding error: {0}")]
     MetadataError(#[from] metadata::Err),
+    #[error("Loose points decoding error: {0}")]
+    PointCloudError(#[from] point_cloud::Err),
+    #[error("Scene decoding error: {0}")]
+    SceneError(#[from] crate::core::scene::Err),
 }
 
+
+
+#[cfg(all(test, not(feature = "evaluation")))]
+mod tests {
+    use super::*;
+    use crate::core::attribute::AttributeType;
+    use crate::core::shared::{NdVector, Vector};
+    use crate::encode::attribute::portabilization::{self, quantization_coordinate_wise::QuantizationCoordinateWise, PortabilizationImpl};
+    use crate::prelude::SliceReader;
+    use crate::shared::attribute::portabilization::quantization_metadata::QuantizationMetadata;
+
+    /// The positions of the mesh as the bits of their coordinates, sorted so that they compare as multisets.
+    fn sorted_positions(mesh: &Mesh, dequantize: impl Fn(PointIdx) -> [f32; 3]) -> Vec<[u32; 3]> {
+        let pos = mesh.get_attributes().iter().find(|att| att.get_attribute_type() == AttributeType::Position).unwrap();
+        let mut out = (0..pos.len())
+            .map(|p| dequantize(PointIdx::from(p)).map(f32::to_bits))
+            .collect::<Vec<_>>();
+        out.sort();
+        out
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mesh = crate::io::obj::load_obj("tests/data/sphere.obj").unwrap();
+        let mut encoded = Vec::new();
+        crate::encode::encode(mesh.clone(), &mut encoded, crate::encode::Config::default()).unwrap();
+
+        let decoded = decode(&mut SliceReader::new(&encoded), Config::default()).unwrap();
+        assert_eq!(decoded.get_faces().len(), mesh.get_faces().len());
+        assert_eq!(decoded.get_attributes().len(), mesh.get_attributes().len());
+        for (decoded_att, att) in decoded.get_attributes().iter().zip(mesh.get_attributes()) {
+            assert_eq!(decoded_att.get_attribute_type(), att.get_attribute_type());
+            assert_eq!(decoded_att.len(), att.len());
+        }
+
+        // The decoded positions are exactly the quantized ones.
+        let pos = mesh.get_attributes().iter().find(|att| att.get_attribute_type() == AttributeType::Position).unwrap();
+        let mut metadata = Vec::new();
+        let port_att = QuantizationCoordinateWise::<NdVector<3, f32>, 3>::new(
+            pos.clone(),
+            portabilization::Config::default_for(AttributeType::Position),
+            &mut metadata,
+        ).portabilize();
+        let metadata = QuantizationMetadata::read_from(&mut SliceReader::new(&metadata), 3, None).unwrap();
+        let expected = sorted_positions(&mesh, |p| {
+            let q = port_att.get::<NdVector<3, i32>, 3>(p);
+            [0, 1, 2].map(|i| metadata.dequantize(i, *q.get(i)))
+        });
+        let decoded_pos = decoded.get_attributes().iter().find(|att| att.get_attribute_type() == AttributeType::Position).unwrap();
+        let actual = sorted_positions(&decoded, |p| {
+            let v = decoded_pos.get::<NdVector<3, f32>, 3>(p);
+            [0, 1, 2].map(|i| *v.get(i))
+        });
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_single_triangle() {
+        let positions = [[0.0f32, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]];
+        let mut builder = crate::prelude::MeshBuilder::new();
+        builder.set_connectivity_attribute(vec![[0, 1, 2]]);
+        builder.add_attribute(
+            positions.iter().map(|&p| NdVector::from(p)).collect::<Vec<_>>(),
+            AttributeType::Position,
+            crate::core::attribute::AttributeDomain::Position,
+            vec![],
+        );
+        let mesh = builder.build().unwrap();
+        let mut encoded = Vec::new();
+        crate::encode::encode(mesh, &mut encoded, crate::encode::Config::default()).unwrap();
+
+        let decoded = decode(&mut SliceReader::new(&encoded), Config::default()).unwrap();
+        assert_eq!(decoded.get_faces().len(), 1);
+        let pos = decoded.get_attributes().iter().find(|att| att.get_attribute_type() == AttributeType::Position).unwrap();
+        let face = decoded.get_faces()[0];
+        let mut actual = face.map(|p| {
+            let v = pos.get::<NdVector<3, f32>, 3>(p);
+            [0, 1, 2].map(|i| *v.get(i))
+        });
+        actual.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let mut expected = positions;
+        expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let max_err = actual.iter().flatten()
+            .zip(expected.iter().flatten())
+            .map(|(a, e)| (a - e).abs())
+            .fold(0.0f32, f32::max);
+        assert!(max_err < 1e-3, "{:?} != {:?}", actual, expected);
+    }
+
+    #[test]
+    fn test_debug_marker_mismatch() {
+        let mesh = crate::io::obj::load_obj("tests/data/tetrahedron.obj").unwrap();
+        let mut cfg = crate::encode::Config::default();
+        cfg.debug_markers = true;
+        let mut encoded = Vec::new();
+        crate::encode::encode(mesh, &mut encoded, cfg).unwrap();
+        assert!(decode(&mut SliceReader::new(&encoded), Config::default()).is_ok());
+
+        // a corrupted marker is an error, not a panic.
+        let marker = b"Init Decoder";
+        let at = encoded.windows(marker.len()).position(|w| w == marker).unwrap();
+        encoded[at] ^= 0xff;
+        assert!(matches!(
+            decode(&mut SliceReader::new(&encoded), Config::default()),
+            Err(Err::ConnectivityError(connectivity::Err::EdgebreakerError(connectivity::edgebreaker::Err::DebugMarkerMismatch("Init Decoder"))))
+        ));
+
+        // so is a stream that ends in the middle of a marker.
+        let marker = b"All done";
+        let at = encoded.windows(marker.len()).position(|w| w == marker).unwrap();
+        encoded[at] ^= 0xff;
+        let truncated = &encoded[..at + 3];
+        assert!(decode(&mut SliceReader::new(truncated), Config::default()).is_err());
+    }
+
+    #[test]
+    fn test_loose_points() {
+        use crate::core::attribute::{AttributeDomain, AttributeId};
+        // a triangle and three points that no face refers to, each with its own normal.
+        let positions = [[0.0f32, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [2.0, 2.0, 2.0], [-1.0, 3.0, 0.5], [0.5, 0.5, -2.0]];
+        let normals = [[0.0f32, 0.0, 1.0], [0.0, 1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, -1.0], [0.0, -1.0, 0.0], [-1.0, 0.0, 0.0]];
+        let position_att = Attribute::from_without_removing_duplicates(
+            AttributeId::new(0), positions.iter().map(|&p| NdVector::from(p)).collect(), AttributeType::Position, AttributeDomain::Position, Vec::new()
+        );
+        let mesh = crate::core::mesh::Mesh::new()
+            .with_attribute(position_att.clone()).unwrap()
+            .with_attribute(Attribute::from_without_removing_duplicates(
+                AttributeId::new(1), normals.iter().map(|&n| NdVector::from(n)).collect(), AttributeType::Normal, AttributeDomain::Corner, vec![AttributeId::new(0)]
+            )).unwrap()
+            .with_faces(vec![[0, 1, 2]]).unwrap();
+
+        // the loose points are lost by default.
+        let mut encoded = Vec::new();
+        crate::encode::encode(mesh.clone(), &mut encoded, crate::encode::Config::default()).unwrap();
+        let decoded = decode(&mut SliceReader::new(&encoded), Config::default()).unwrap();
+        assert!(decoded.get_attributes().iter().all(|att| att.len() == 3));
+
+        // only the positions of the loose points are encoded, so their normals are rejected rather than lost.
+        let mut cfg = crate::encode::Config::default();
+        cfg.loose_points = true;
+        let err = crate::encode::encode(mesh, &mut Vec::new(), cfg.clone()).unwrap_err();
+        assert!(matches!(err, crate::encode::Err::PointCloudError(_)), "{}", err);
+
+        let mesh = crate::core::mesh::Mesh::new()
+            .with_attribute(position_att).unwrap()
+            .with_faces(vec![[0, 1, 2]]).unwrap();
+        let mut encoded = Vec::new();
+        crate::encode::encode(mesh, &mut encoded, cfg).unwrap();
+        let decoded = decode(&mut SliceReader::new(&encoded), Config::default()).unwrap();
+
+        assert_eq!(decoded.get_faces().len(), 1);
+        assert!(decoded.get_attributes().iter().all(|att| att.len() == positions.len()));
+        let pos = decoded.get_attributes().iter().find(|att| att.get_attribute_type() == AttributeType::Position).unwrap();
+        let value = |p: PointIdx| {
+            let v = pos.get::<NdVector<3, f32>, 3>(p);
+            [0, 1, 2].map(|i| *v.get(i))
+        };
+        let mut face = decoded.get_faces()[0].map(value);
+        face.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let mut actual = (0..pos.len()).map(|p| value(PointIdx::from(p))).collect::<Vec<_>>();
+        actual.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let mut expected = positions.to_vec();
+        expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let max_err = |actual: &[[f32; 3]], expected: &[[f32; 3]]| actual.iter().flatten()
+            .zip(expected.iter().flatten())
+            .map(|(a, e)| (a - e).abs())
+            .fold(0.0f32, f32::max);
+        assert!(max_err(&actual, &expected) < 1e-3, "{:?} != {:?}", actual, expected);
+        // the face still refers to the points of the triangle.
+        let mut triangle = positions[..3].to_vec();
+        triangle.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert!(max_err(&face, &triangle) < 1e-3, "{:?} != {:?}", face, triangle);
+
+        // the loose points count towards the limit of the vertices with the points of the faces.
+        let mut limited = Config::default();
+        limited.max_vertices = Some(positions.len() - 1);
+        let err = decode(&mut SliceReader::new(&encoded), limited.clone()).unwrap_err();
+        assert!(
+            matches!(err, Err::LimitExceeded(LimitExceeded { what: "points", count, .. }) if count == positions.len()),
+            "{:?}", err
+        );
+        limited.max_vertices = Some(positions.len());
+        decode(&mut SliceReader::new(&encoded), limited).unwrap();
+    }
+
+    #[test]
+    fn test_decode_streaming() {
+        use crate::core::attribute::{AttributeDomain, AttributeId};
+        // a triangle and two points that no face refers to.
+        let positions = [[0.0f32, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [2.0, 2.0, 2.0], [-1.0, 3.0, 0.5]];
+        let mesh = crate::core::mesh::Mesh::new()
+            .with_attribute(Attribute::from_without_removing_duplicates(
+                AttributeId::new(0), positions.iter().map(|&p| NdVector::from(p)).collect(), AttributeType::Position, AttributeDomain::Position, Vec::new()
+            )).unwrap()
+            .with_faces(vec![[0, 1, 2]]).unwrap();
+        let mut cfg = crate::encode::Config::default();
+        cfg.loose_points = true;
+        let mut encoded = Vec::new();
+        crate::encode::encode(mesh, &mut encoded, cfg).unwrap();
+
+        let mut events = Vec::new();
+        let mut assembled = Mesh::new();
+        let mut loose_points = Vec::new();
+        decode_streaming(&mut SliceReader::new(&encoded), Config::default(), &mut |event| match event {
+            DecodeEvent::ConnectivityReady(faces) => {
+                events.push("connectivity");
+                assembled.faces = faces.to_vec();
+            },
+            DecodeEvent::AttributeReady(att) => {
+                events.push("attribute");
+                // the parents of the attribute are already passed.
+                for parent in att.get_parents() {
+                    assert!(assembled.attributes.iter().any(|other| other.get_id() == *parent), "{:?} before its parent", att.get_id());
+                }
+                assembled.attributes.push(att.clone());
+            },
+            DecodeEvent::LoosePointsReady(points) => {
+                events.push("loose points");
+                loose_points = points.to_vec();
+            },
+        }).unwrap();
+        assert_eq!(events, ["connectivity", "attribute", "loose points"]);
+        assert_eq!(loose_points.len(), 2);
+
+        append_loose_points(&mut assembled, loose_points).unwrap();
+        let decoded = decode(&mut SliceReader::new(&encoded), Config::default()).unwrap();
+        assert!(assembled.structurally_eq(&decoded));
+    }
+
+    #[test]
+    fn test_attribute_layout() {
+        let mesh = crate::io::obj::load_obj("tests/data/sphere.obj").unwrap();
+        let mut cfg = crate::encode::Config::default();
+        cfg.attribute_layout = AttributeLayout::Planar;
+        let mut encoded = Vec::new();
+        crate::encode::encode(mesh, &mut encoded, cfg).unwrap();
+
+        // the layout hinted by the encoder is used unless the decoder asks for another.
+        let (decoded, soa) = decode_with_layout(&mut SliceReader::new(&encoded), Config::default()).unwrap();
+        let mut interleaved = Config::default();
+        interleaved.attribute_layout = Some(AttributeLayout::Interleaved);
+        let (_, aos) = decode_with_layout(&mut SliceReader::new(&encoded), interleaved).unwrap();
+        assert_eq!(soa.len(), decoded.get_attributes().len());
+        for ((att, soa), aos) in decoded.get_attributes().iter().zip(&soa).zip(&aos) {
+            // the 'c'th component of the 'p'th value is at 'p * n + c' in AoS and at 'c * len + p' in SoA.
+            let (n, size) = (att.get_num_components(), att.get_component_type().size());
+            assert_eq!(soa.len(), aos.len());
+            for p in 0..att.len() {
+                for c in 0..n {
+                    let (i, j) = ((p * n + c) * size, (c * att.len() + p) * size);
+                    assert_eq!(aos[i..i + size], soa[j..j + size]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_decode_many() {
+        let meshes = ["sphere.obj", "tetrahedron.obj", "torus.obj"]
+            .map(|name| crate::io::obj::load_obj(format!("tests/data/{}", name)).unwrap());
+        let mut buffer = Vec::new();
+        crate::encode::encode_many(&meshes, &mut buffer, crate::encode::Config::default()).unwrap();
+
+        let decoded = decode_many(&mut SliceReader::new(&buffer), Config::default()).unwrap();
+        assert_eq!(decoded.len(), meshes.len());
+        for (mesh, decoded) in meshes.iter().zip(&decoded) {
+            assert_eq!(decoded.get_faces().len(), mesh.get_faces().len());
+            assert_eq!(decoded.get_attributes().len(), mesh.get_attributes().len());
+            assert!(mesh.diff_l2_norm(decoded) < 1e-2);
+        }
+
+        // an error in a stream tells which mesh it is in.
+        let mut streams = container::read_container(&mut SliceReader::new(&buffer)).unwrap();
+        let half = streams[1].len() / 2;
+        streams[1].truncate(half);
+        let mut truncated = Vec::new();
+        container::write_container(&streams, &mut truncated);
+        let err = decode_many(&mut SliceReader::new(&truncated), Config::default()).unwrap_err();
+        assert!(matches!(err, Err::ContainerError(1, _)), "{}", err);
+    }
+
+    #[test]
+    fn test_point_cloud() {
+        use crate::core::shared::Vector;
+        let mut seed = 1234_u32;
+        let mut next = || {
+            seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+            (seed >> 8) as f32 / (1 << 24) as f32
+        };
+        let positions = (0..1000).map(|_| NdVector::from([next(), next(), next()])).collect::<Vec<_>>();
+        let colors = positions.iter().map(|p| NdVector::from([0, 1, 2].map(|i| (*p.get(i) * 255.0) as u8))).collect::<Vec<_>>();
+        let mut mesh = Mesh::new();
+        mesh.attributes.push(Attribute::from_without_removing_duplicates(
+            AttributeId::new(0), positions.clone(), AttributeType::Position, AttributeDomain::Position, Vec::new()
+        ));
+        mesh.attributes.push(Attribute::from_without_removing_duplicates(
+            AttributeId::new(1), colors.clone(), AttributeType::Color, AttributeDomain::Position, Vec::new()
+        ));
+
+        // A mesh without faces is decoded with all its attributes, in the order of the points.
+        let mut encoded = Vec::new();
+        crate::encode::encode(mesh.clone(), &mut encoded, crate::encode::Config::default()).unwrap();
+        let decoded = decode(&mut SliceReader::new(&encoded), Config::default()).unwrap();
+        assert!(decoded.get_faces().is_empty());
+        assert_eq!(decoded.get_attributes().len(), 2);
+        let (pos, color) = (&decoded.get_attributes()[0], &decoded.get_attributes()[1]);
+        assert_eq!(pos.len(), positions.len());
+        for (p, v) in positions.iter().enumerate() {
+            let decoded = pos.get::<NdVector<3, f32>, 3>(PointIdx::from(p));
+            assert!((0..3).all(|i| (decoded.get(i) - v.get(i)).abs() < 1e-3), "{:?} != {:?}", decoded, v);
+            // the colors are quantized too.
+            let decoded = color.get::<NdVector<3, u8>, 3>(PointIdx::from(p));
+            assert!((0..3).all(|i| decoded.get(i).abs_diff(*colors[p].get(i)) <= 1), "{:?} != {:?}", decoded, colors[p]);
+        }
+
+        // The positions of a point cloud coded by the kd-tree are decoded too, in the order of the tree.
+        let mut positions_only = mesh;
+        positions_only.attributes.truncate(1);
+        let mut cfg = crate::encode::Config::default();
+        cfg.point_cloud_method = PointCloudMethod::KdTree;
+        let mut encoded = Vec::new();
+        crate::encode::encode_point_cloud(positions_only, &mut encoded, cfg).unwrap();
+        let decoded = decode(&mut SliceReader::new(&encoded), Config::default()).unwrap();
+        let pos = &decoded.get_attributes()[0];
+        assert_eq!(pos.len(), positions.len());
+        for p in (0..pos.len()).map(PointIdx::from) {
+            let decoded = pos.get::<NdVector<3, f32>, 3>(p);
+            assert!(positions.iter().any(|v| (0..3).all(|i| (decoded.get(i) - v.get(i)).abs() < 1e-3)), "{:?}", decoded);
+        }
+
+        let mut cfg = Config::default();
+        cfg.max_vertices = Some(999);
+        assert!(matches!(decode(&mut SliceReader::new(&encoded), cfg), Err(Err::LimitExceeded(_))));
+    }
+
+    #[test]
+    fn test_kd_tree_limits() {
+        use crate::utils::bit_coder::{leb128_read, leb128_write};
+        let mut mesh = crate::io::obj::load_obj("tests/data/tetrahedron.obj").unwrap();
+        mesh.faces.clear();
+        mesh.attributes.truncate(1);
+        let mut cfg = crate::encode::Config::default();
+        cfg.point_cloud_method = PointCloudMethod::KdTree;
+        let mut encoded = Vec::new();
+        crate::encode::encode_point_cloud(mesh, &mut encoded, cfg).unwrap();
+
+        // inflate the number of points, which follows the header, before the points are decoded.
+        let mut reader = SliceReader::new(&encoded);
+        header::decode_header(&mut reader).unwrap();
+        let offset = encoded.len() - reader.remaining().len();
+        leb128_read(&mut reader).unwrap();
+        let end = encoded.len() - reader.remaining().len();
+        let mut inflated = encoded[..offset].to_vec();
+        leb128_write(i64::MAX as u64, &mut inflated);
+        inflated.extend_from_slice(&encoded[end..]);
+
+        let mut limited = Config::default();
+        limited.max_vertices = Some(1000);
+        let err = decode(&mut SliceReader::new(&inflated), limited.clone()).unwrap_err();
+        assert!(
+            matches!(err, Err::LimitExceeded(LimitExceeded { what: "points", count, limit: 1000 }) if count == i64::MAX as usize),
+            "{:?}", err
+        );
+        decode(&mut SliceReader::new(&encoded), limited).unwrap();
+    }
+
+    #[test]
+    fn test_emit_attribute_indices() {
+        // The texture coordinates of the tetrahedron have seams; 4 positions but 6 texture coordinates.
+        let mesh = crate::io::obj::load_obj("tests/data/tetrahedron.obj").unwrap();
+        let mut encoded = Vec::new();
+        crate::encode::encode(mesh, &mut encoded, crate::encode::Config::default()).unwrap();
+        let index_of = |mesh: &Mesh, ty| mesh.get_attributes().iter().position(|att| att.get_attribute_type() == ty).unwrap();
+
+        let mut cfg = Config::default();
+        cfg.emit_attribute_indices = true;
+        let indexed = decode(&mut SliceReader::new(&encoded), cfg).unwrap();
+        let (pos_idx, uv_idx) = (index_of(&indexed, AttributeType::Position), index_of(&indexed, AttributeType::TextureCoordinate));
+        let pos_stream = indexed.attribute_index_stream(pos_idx);
+        let uv_stream = indexed.attribute_index_stream(uv_idx);
+        assert_ne!(pos_stream, uv_stream);
+        assert_eq!(indexed.get_attributes()[pos_idx].num_unique_values(), 4);
+        assert_eq!(indexed.get_attributes()[uv_idx].num_unique_values(), 6);
+
+        // otherwise every attribute has a value for each point, which the faces index.
+        let soup = decode(&mut SliceReader::new(&encoded), Config::default()).unwrap();
+        let points = soup.get_faces().iter().map(|f| f.map(usize::from)).collect::<Vec<_>>();
+        for att_idx in 0..soup.get_attributes().len() {
+            assert_eq!(soup.attribute_index_stream(att_idx), points);
+        }
+        // the values of the points are the same either way.
+        let uv = |mesh: &Mesh, p: PointIdx| mesh.get_attributes()[uv_idx].get::<NdVector<2, f32>, 2>(p);
+        for p in (0..soup.get_attributes()[uv_idx].len()).map(PointIdx::from) {
+            assert_eq!(uv(&soup, p), uv(&indexed, p));
+        }
+    }
+
+    #[test]
+    fn test_limits() {
+        use crate::utils::bit_coder::{leb128_read, leb128_write};
+        let mesh = crate::io::obj::load_obj("tests/data/sphere.obj").unwrap();
+        let mut cfg = crate::encode::Config::default();
+        cfg.debug_markers = false;
+        let mut encoded = Vec::new();
+        crate::encode::encode(mesh, &mut encoded, cfg).unwrap();
+
+        // inflate the number of vertices, which follows the header and the kind of the edgebreaker.
+        let mut reader = SliceReader::new(&encoded);
+        header::decode_header(&mut reader).unwrap();
+        reader.read_u8().unwrap();
+        let offset = encoded.len() - reader.remaining().len();
+        let num_vertices = leb128_read(&mut reader).unwrap();
+        let end = encoded.len() - reader.remaining().len();
+        let mut inflated = encoded[..offset].to_vec();
+        leb128_write(1 << 40, &mut inflated);
+        inflated.extend_from_slice(&encoded[end..]);
+
+        let mut limited = Config::default();
+        limited.max_vertices = Some(1 << 20);
+        let err = decode(&mut SliceReader::new(&inflated), limited.clone()).unwrap_err();
+        assert!(
+            matches!(err, Err::LimitExceeded(LimitExceeded { what: "vertices", count, limit }) if count == 1 << 40 && limit == 1 << 20),
+            "{:?}", err
+        );
+        // the stream within the limits is decoded as usual.
+        assert!((num_vertices as usize) < 1 << 20);
+        decode(&mut SliceReader::new(&encoded), limited).unwrap();
+
+        let mut limited = Config::default();
+        limited.max_faces = Some(10);
+        let err = decode(&mut SliceReader::new(&encoded), limited).unwrap_err();
+        assert!(matches!(err, Err::LimitExceeded(LimitExceeded { what: "faces", .. })), "{:?}", err);
+
+        let mut limited = Config::default();
+        limited.max_attribute_bytes = Some(1000);
+        let err = decode(&mut SliceReader::new(&encoded), limited).unwrap_err();
+        assert!(matches!(err, Err::LimitExceeded(LimitExceeded { what: "bytes of attributes", .. })), "{:?}", err);
+    }
+}
@@ -0,0 +1,51 @@
+use crate::core::bit_coder::{BitReader, ReaderErr};
+use crate::encode::point_cloud::kd_tree::{count_bits, split_axis};
+use crate::prelude::ByteReader;
+
+/// Inverse of [crate::encode::point_cloud::kd_tree::encode_kd_tree]. Restores 'num_points' points in the order
+/// the encoder left them.
+pub(crate) fn decode_kd_tree<R>(reader: &mut R, num_points: usize, bits: u8) -> Result<Vec<[u32; 3]>, ReaderErr>
+    where R: ByteReader
+{
+    // 'num_points' is read from the stream, so the points are not allocated up front.
+    let mut out = Vec::new();
+    let mut bit_reader: BitReader<_> = BitReader::spown_from(reader).ok_or(ReaderErr::NotEnoughData)?;
+    decode_cell(num_points, [0; 3], [bits; 3], &mut bit_reader, &mut out)?;
+    Ok(out)
+}
+
+fn decode_cell<R>(num_points: usize, base: [u32; 3], bits: [u8; 3], reader: &mut BitReader<'_, R>, out: &mut Vec<[u32; 3]>) -> Result<(), ReaderErr>
+    where R: ByteReader
+{
+    if num_points == 0 {
+        return Ok(());
+    }
+    if bits == [0; 3] {
+        out.extend(std::iter::repeat_n(base, num_points));
+        return Ok(());
+    }
+    if num_points == 1 {
+        let mut point = base;
+        for (i, &b) in bits.iter().enumerate() {
+            if b > 0 {
+                point[i] |= reader.read_bits(b)? as u32;
+            }
+        }
+        out.push(point);
+        return Ok(());
+    }
+
+    let axis = split_axis(bits);
+    let bit = bits[axis] - 1;
+    let num_lower = reader.read_bits(count_bits(num_points))? as usize;
+    if num_lower > num_points {
+        return Err(ReaderErr::NotEnoughData);
+    }
+
+    let mut child_bits = bits;
+    child_bits[axis] -= 1;
+    let mut upper_base = base;
+    upper_base[axis] |= 1 << bit;
+    decode_cell(num_lower, base, child_bits, reader, out)?;
+    decode_cell(num_points - num_lower, upper_base, child_bits, reader, out)
+}
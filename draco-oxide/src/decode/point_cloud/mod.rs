@@ -0,0 +1,41 @@
+pub(crate) mod kd_tree;
+pub(crate) mod tiles;
+
+use crate::decode::{header, LimitExceeded};
+use crate::encode::point_cloud::PointCloudMethod;
+use crate::prelude::ByteReader;
+use crate::shared::attribute::portabilization::quantization_metadata::QuantizationMetadata;
+use crate::utils::bit_coder::leb128_read;
+
+pub(crate) use crate::encode::point_cloud::Err;
+
+/// Decodes a point cloud written by [crate::encode::encode_point_cloud], header included.
+/// Only [PointCloudMethod::KdTree] is decoded for now.
+pub(crate) fn decode_point_cloud<R>(reader: &mut R) -> Result<Vec<[f32; 3]>, Err>
+    where R: ByteReader
+{
+    let header = header::decode_header(reader).map_err(|_| Err::InvalidHeader)?;
+    match header.point_cloud_method {
+        Some(PointCloudMethod::KdTree) if !header.point_sequence => decode_kd_tree_points(reader, 0, None),
+        Some(method) => Err(Err::UnsupportedMethod(method.get_id())),
+        None => Err(Err::InvalidHeader),
+    }
+}
+
+/// Decodes the points written by [crate::encode::point_cloud::encode_point_cloud] with [PointCloudMethod::KdTree],
+/// i.e. without the header. The points are rejected before they are decoded if they would make the points
+/// more than 'max_vertices' together with the 'num_preceding_points' already decoded.
+pub(crate) fn decode_kd_tree_points<R>(reader: &mut R, num_preceding_points: usize, max_vertices: Option<usize>) -> Result<Vec<[f32; 3]>, Err>
+    where R: ByteReader
+{
+    let num_points = usize::try_from(leb128_read(reader)?).unwrap_or(usize::MAX);
+    LimitExceeded::check("points", num_preceding_points.saturating_add(num_points), max_vertices)?;
+    if num_points == 0 {
+        return Ok(Vec::new());
+    }
+    let metadata = QuantizationMetadata::read_from(reader, 3, None)?;
+    let points = kd_tree::decode_kd_tree(reader, num_points, metadata.quantization_bits)?;
+    Ok(points.into_iter()
+        .map(|p| [0, 1, 2].map(|i| metadata.dequantize(i, p[i] as i32)))
+        .collect())
+}
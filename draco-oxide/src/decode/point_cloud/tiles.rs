@@ -0,0 +1,89 @@
+//! Reads the point cloud split into tiles by [crate::encode::encode_point_cloud_tiles]; see
+//! [crate::encode::point_cloud::tiles] for the layout of the stream.
+
+use crate::encode::point_cloud::tiles::PointCloudTile;
+use crate::prelude::ByteReader;
+use super::{decode_point_cloud, Err};
+
+/// Reads the spatial index at the start of the stream written by [crate::encode::encode_point_cloud_tiles].
+pub fn read_tile_index<R>(reader: &mut R) -> Result<Vec<PointCloudTile>, Err>
+    where R: ByteReader
+{
+    let num_tiles = reader.read_u32()? as usize;
+    let mut tiles = Vec::new();
+    for _ in 0..num_tiles {
+        let mut corners = [0.0_f32; 6];
+        for c in &mut corners {
+            *c = f32::from_bits(reader.read_u32()?);
+        }
+        tiles.push(PointCloudTile {
+            min: [corners[0], corners[1], corners[2]],
+            max: [corners[3], corners[4], corners[5]],
+            offset: reader.read_u64()? as usize,
+            len: reader.read_u64()? as usize,
+        });
+    }
+    Ok(tiles)
+}
+
+/// Decodes the points of a tile from its bytes, i.e. the [PointCloudTile::byte_range] of the tiled stream,
+/// so that the rest of the stream need not be fetched.
+pub fn decode_tile(bytes: &[u8]) -> Result<Vec<[f32; 3]>, Err> {
+    decode_point_cloud(&mut crate::prelude::SliceReader::new(bytes))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::attribute::{Attribute, AttributeDomain, AttributeId, AttributeType};
+    use crate::core::shared::{ConfigType, NdVector, Vector};
+    use crate::prelude::{Mesh, SliceReader};
+
+    #[test]
+    fn test_decode_one_tile() {
+        let mut seed = 7_u32;
+        let mut next = || {
+            seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+            (seed >> 8) as f32 / (1 << 24) as f32
+        };
+        let points = (0..2000).map(|_| NdVector::from([next(), next(), next()])).collect::<Vec<_>>();
+        let mut mesh = Mesh::new();
+        mesh.attributes.push(Attribute::from_without_removing_duplicates(
+            AttributeId::new(0), points.clone(), AttributeType::Position, AttributeDomain::Position, Vec::new()
+        ));
+
+        // a grid of no cells along an axis is rejected.
+        let err = crate::encode::encode_point_cloud_tiles(mesh.clone(), &mut Vec::new(), [2, 0, 1], crate::encode::Config::default()).unwrap_err();
+        assert!(matches!(err, crate::encode::Err::PointCloudError(Err::InvalidGrid([2, 0, 1]))), "{}", err);
+        // so are the attributes other than the positions, which the tiles do not carry.
+        let mut colored = mesh.clone();
+        colored.attributes.push(Attribute::from_without_removing_duplicates(
+            AttributeId::new(1), points.clone(), AttributeType::Color, AttributeDomain::Position, Vec::new()
+        ));
+        let err = crate::encode::encode_point_cloud_tiles(colored, &mut Vec::new(), [2, 2, 1], crate::encode::Config::default()).unwrap_err();
+        assert!(matches!(err, crate::encode::Err::PointCloudError(Err::UnsupportedAttribute(_))), "{}", err);
+
+        let mut buffer = Vec::new();
+        crate::encode::encode_point_cloud_tiles(mesh, &mut buffer, [2, 2, 1], crate::encode::Config::default()).unwrap();
+        let tiles = read_tile_index(&mut SliceReader::new(&buffer)).unwrap();
+        assert_eq!(tiles.len(), 4);
+
+        // the query box in the cells of the smaller x and the larger y hits only one tile.
+        let hits = tiles.iter()
+            .filter(|tile| tile.intersects([0.1, 0.6, 0.0], [0.4, 0.9, 1.0]))
+            .collect::<Vec<_>>();
+        assert_eq!(hits.len(), 1);
+        let tile = hits[0];
+        // the tile is decoded from its bytes alone.
+        let decoded = decode_tile(&buffer[tile.byte_range()]).unwrap();
+
+        let in_tile = |v: &NdVector<3, f32>| *v.get(0) < tile.max[0] && *v.get(1) >= tile.min[1];
+        assert_eq!(decoded.len(), points.iter().filter(|v| in_tile(v)).count());
+        // the points are quantized in the tile, so they stay in it up to the step of the quantization.
+        let eps = 1e-3;
+        for p in decoded {
+            assert!((0..3).all(|i| tile.min[i] - eps <= p[i] && p[i] <= tile.max[i] + eps), "{:?} is not in {:?}", p, tile);
+        }
+    }
+}
@@ -4,12 +4,14 @@ use std::{
 
 use crate::core::attribute::{AttributeDomain, ComponentDataType};
 use crate::core::corner_table::GenericCornerTable;
-use crate::core::shared::{CornerIdx, DataValue, NdVector};
+use crate::core::corner_table::point_cloud_corner_table::PointCloudCornerTable;
+use crate::core::shared::{CornerIdx, DataValue, NdVector, VertexIdx};
 use crate::core::attribute::Attribute;
 use crate::encode::connectivity::ConnectivityEncoderOutput;
-use crate::encode::entropy::symbol_coding::encode_symbols;
+use crate::debug_write;
+use crate::encode::entropy::symbol_coding::{encode_signed_symbols, encode_symbols};
 use crate::prelude::{AttributeType, ByteWriter, ConfigType};
-use crate::shared::attribute::sequence::Traverser;
+use crate::shared::attribute::sequence::{sequence_in_vertex_order, Traverser};
 use crate::shared::attribute::Portable;
 use crate::shared::entropy::SymbolEncodingMethod;
 use thiserror::Error;
@@ -56,6 +58,34 @@ impl GroupConfig {
         }
     }
 
+    /// Configuration for the 'xyz' part of tangents whose handedness is encoded separately.
+    fn tangent_xyz_with_size(size: usize) -> Self {
+        let mut out = Self::default_with_size(size);
+        out.prediction_scheme.ty = prediction_scheme::PredictionSchemeType::DeltaPrediction;
+        out.prediction_transform = prediction_transform::Config{
+            ty: prediction_transform::PredictionTransformType::WrappedDifference,
+            portabilization: portabilization::Config{
+                type_: portabilization::PortabilizationType::OctahedralQuantization,
+                quantization_bits: 8,
+            },
+        };
+        out
+    }
+
+    /// Configuration for the float attributes encoded bit-exact, i.e. without prediction or quantization.
+    fn lossless_with_size(size: usize) -> Self {
+        let mut out = Self::default_with_size(size);
+        out.prediction_scheme.ty = prediction_scheme::PredictionSchemeType::NoPrediction;
+        out.prediction_transform = prediction_transform::Config{
+            ty: prediction_transform::PredictionTransformType::NoTransform,
+            portabilization: portabilization::Config{
+                type_: portabilization::PortabilizationType::ToBits,
+                ..portabilization::Config::default()
+            },
+        };
+        out
+    }
+
     fn default_for(att_ty: AttributeType, size: usize) -> Self {
         match att_ty {
             AttributeType::Position => Self {
@@ -66,7 +96,7 @@ impl GroupConfig {
                 },
                 prediction_transform: prediction_transform::Config{
                     ty: prediction_transform::PredictionTransformType::WrappedDifference,
-                    portabilization: portabilization::Config::default(),
+                    portabilization: portabilization::Config::default_for(AttributeType::Position),
                 }
             },
             AttributeType::Normal => Self {
@@ -77,7 +107,7 @@ impl GroupConfig {
                 },
                 prediction_transform: prediction_transform::Config{
                     ty: prediction_transform::PredictionTransformType::OctahedralOrthogonal,
-                    portabilization: portabilization::Config::default(),
+                    portabilization: portabilization::Config::default_for(AttributeType::Normal),
                 }
             },
             AttributeType::TextureCoordinate => Self {
@@ -88,7 +118,7 @@ impl GroupConfig {
                 },
                 prediction_transform: prediction_transform::Config{
                     ty: prediction_transform::PredictionTransformType::WrappedDifference,
-                    portabilization: portabilization::Config::default(),
+                    portabilization: portabilization::Config::default_for(AttributeType::TextureCoordinate),
                 }
             },
             AttributeType::Custom => Self {
@@ -109,10 +139,20 @@ impl GroupConfig {
 }
 
 
+/// The entropy coding flag of an attribute whose corrections are rANS coded as their signs and magnitudes.
+/// Draco only writes 0 for the raw values and 1 for rANS, so this is only understood by draco-oxide.
+pub(crate) const SIGNED_RANS_ENCODING: u8 = 2;
+
 #[derive(Clone, Debug)]
 pub struct Config {
     group_cfgs: Vec<GroupConfig>,
     rans_encoding: bool,
+    /// If true, the raw bits of the values are entropy coded instead of the predicted residuals.
+    raw_bits: bool,
+    /// Whether the debug markers are written, see [crate::encode::Config::debug_markers].
+    debug_markers: bool,
+    /// If true, the corrections are rANS coded as their signs and magnitudes, see [crate::encode::Config::signed_corrections].
+    signed_corrections: bool,
 }
 
 
@@ -122,6 +162,9 @@ impl ConfigType for Config {
         Self {
             group_cfgs: Vec::new(),
             rans_encoding: true,
+            raw_bits: false,
+            debug_markers: false,
+            signed_corrections: false,
         }
     }
 }
@@ -131,6 +174,82 @@ impl Config {
         Self {
             group_cfgs: vec![GroupConfig::default_for(att_ty, size)],
             rans_encoding: true,
+            raw_bits: false,
+            debug_markers: false,
+            signed_corrections: false,
+        }
+    }
+
+    /// Configuration for the 'xyz' part of tangents whose handedness is encoded in a separate bit stream.
+    pub fn tangent_xyz(size: usize) -> Self {
+        Self {
+            group_cfgs: vec![GroupConfig::tangent_xyz_with_size(size)],
+            rans_encoding: true,
+            raw_bits: false,
+            debug_markers: false,
+            signed_corrections: false,
+        }
+    }
+
+    /// Configuration for the float attributes encoded bit-exact in the lossless mode.
+    pub fn lossless(size: usize) -> Self {
+        Self {
+            group_cfgs: vec![GroupConfig::lossless_with_size(size)],
+            rans_encoding: true,
+            raw_bits: true,
+            debug_markers: false,
+            signed_corrections: false,
+        }
+    }
+
+    /// Sets the prediction scheme of all the encoding groups.
+    pub(crate) fn set_prediction_scheme(&mut self, ty: prediction_scheme::PredictionSchemeType) {
+        for group_cfg in &mut self.group_cfgs {
+            group_cfg.prediction_scheme.ty = ty.clone();
+        }
+    }
+
+    /// The prediction scheme of the first encoding group, which is the one written to the stream.
+    pub(crate) fn prediction_scheme(&self) -> prediction_scheme::PredictionSchemeType {
+        self.group_cfgs[0].prediction_scheme.ty.clone()
+    }
+
+    /// Sets the prediction transform of all the encoding groups.
+    pub(crate) fn set_prediction_transform(&mut self, ty: prediction_transform::PredictionTransformType) {
+        for group_cfg in &mut self.group_cfgs {
+            group_cfg.prediction_transform.ty = ty;
+        }
+    }
+
+    /// The quantization bits of the first encoding group, which is the one written to the stream.
+    pub(crate) fn quantization_bits(&self) -> u8 {
+        self.group_cfgs[0].prediction_transform.portabilization.quantization_bits
+    }
+
+    /// The prediction transform of the first encoding group, which is the one written to the stream.
+    pub(crate) fn prediction_transform(&self) -> prediction_transform::PredictionTransformType {
+        self.group_cfgs[0].prediction_transform.ty
+    }
+
+    /// Sets the portabilization of all the encoding groups, keeping their quantization bits.
+    pub(crate) fn set_portabilization_type(&mut self, ty: portabilization::PortabilizationType) {
+        for group_cfg in &mut self.group_cfgs {
+            group_cfg.prediction_transform.portabilization.type_ = ty;
+        }
+    }
+
+    pub(crate) fn set_debug_markers(&mut self, debug_markers: bool) {
+        self.debug_markers = debug_markers;
+    }
+
+    pub(crate) fn set_signed_corrections(&mut self, signed_corrections: bool) {
+        self.signed_corrections = signed_corrections;
+    }
+
+    /// Sets the quantization bits of all the encoding groups.
+    pub(crate) fn set_quantization_bits(&mut self, bits: u8) {
+        for group_cfg in &mut self.group_cfgs {
+            group_cfg.prediction_transform.portabilization.quantization_bits = bits;
         }
     }
 }
@@ -144,6 +263,10 @@ pub(super) struct AttributeEncoder<'parents, 'encoder, 'writer, 'co, 'mesh, W>
     writer: &'writer mut W,
     parents: &'encoder[&'parents Attribute],
     conn_out: &'co ConnectivityEncoderOutput<'mesh>,
+    /// Handedness of each unique value of the tangent attribute, if it is encoded separately.
+    handedness: Option<Vec<bool>>,
+    /// The order of the position vertices to encode the values in, if it is given by the user.
+    vertex_order: Option<&'encoder [VertexIdx]>,
 }
 
 impl<'parents, 'encoder, 'writer, 'co, 'mesh, W> AttributeEncoder<'parents, 'encoder, 'writer, 'co, 'mesh, W>
@@ -152,7 +275,20 @@ impl<'parents, 'encoder, 'writer, 'co, 'mesh, W> AttributeEncoder<'parents, 'enc
         'parents: 'encoder,
 {
 	pub(super) fn new(att: Attribute, att_data_id: usize, parents: &'encoder[&'parents Attribute], conn_out: &'co ConnectivityEncoderOutput<'mesh>, writer: &'writer mut W, cfg: Config) -> Self {
-        AttributeEncoder { att, att_data_id, cfg, writer, parents, conn_out }
+        AttributeEncoder { att, att_data_id, cfg, writer, parents, conn_out, handedness: None, vertex_order: None }
+    }
+
+    /// Encodes the values in the given order of the position vertices instead of the order of the Edgebreaker traversal.
+    pub(super) fn with_vertex_order(mut self, vertex_order: &'encoder [VertexIdx]) -> Self {
+        self.vertex_order = Some(vertex_order);
+        self
+    }
+
+    /// Encodes the given handedness (sign of 'w' of each unique value of the tangent) in a separate bit stream
+    /// after the attribute values.
+    pub(super) fn with_handedness(mut self, handedness: Vec<bool>) -> Self {
+        self.handedness = Some(handedness);
+        self
     }
 	
 	pub(super) fn encode<const WRITE_NOW: bool, const BOOST: bool>(self) -> Result<Attribute, Err> {
@@ -191,7 +327,8 @@ impl<'parents, 'encoder, 'writer, 'co, 'mesh, W> AttributeEncoder<'parents, 'enc
             ComponentDataType::I64 => {
                 self.unpack_num_components::<WRITE_NOW, BOOST, i64>()
             }
-            ComponentDataType::Invalid => {
+            // Half-floats are converted to f32 before the attributes are encoded.
+            ComponentDataType::F16 | ComponentDataType::Invalid => {
                 Err(Err::UnsupportedDataType)
             }
         }
@@ -220,6 +357,11 @@ impl<'parents, 'encoder, 'writer, 'co, 'mesh, W> AttributeEncoder<'parents, 'enc
             4 => {
                 self.encode_typed::<WRITE_NOW, BOOST, 4,_>()
             },
+            // The raw bits are coded byte-wise, so the values of any number of components can be
+            // encoded without their vector type, e.g. 4x4 matrices.
+            _ if self.cfg.raw_bits => {
+                self.encode_typed::<WRITE_NOW, BOOST, 1,_>()
+            },
             _ => {
                 Err(Err::UnsupportedNumComponents(num_components))
             }
@@ -236,23 +378,38 @@ impl<'parents, 'encoder, 'writer, 'co, 'mesh, W> AttributeEncoder<'parents, 'enc
         if !BOOST {
             match self.conn_out {
                 ConnectivityEncoderOutput::Edgebreaker(edgebreaker_out) => {
+                    let universal = edgebreaker_out.corner_table.universal_corner_table();
                     if let Some(corner_table) = edgebreaker_out.corner_table.attribute_corner_table(self.att_data_id) {
                         let sequence = Traverser::new(
                             &corner_table,
                             edgebreaker_out.corners_of_edgebreaker.clone(), // ToDo: take this value
                         ).compute_seqeunce();
+                        let sequence = match self.vertex_order {
+                            Some(order) => sequence_in_vertex_order(universal, sequence, order),
+                            None => sequence,
+                        };
                         self.encode_impl_edgebreaker::<WRITE_NOW,_,_,NdVector<N, T>,N>(&corner_table, sequence.into_iter())
                     } else {
-                        let corner_table = edgebreaker_out.corner_table.universal_corner_table();
+                        let corner_table = universal;
                         let sequence = Traverser::new(
                             corner_table,
                             edgebreaker_out.corners_of_edgebreaker.clone(), // ToDo: take this value
                         ).compute_seqeunce();
+                        let sequence = match self.vertex_order {
+                            Some(order) => sequence_in_vertex_order(universal, sequence, order),
+                            None => sequence,
+                        };
                         self.encode_impl_edgebreaker::<WRITE_NOW,_,_,NdVector<N, T>, N>(corner_table, sequence.into_iter())
                     }
                 },
-                ConnectivityEncoderOutput::Sequential(_) => {
-                    unimplemented!("Sequential connectivity encoding is not implemented yet");
+                &ConnectivityEncoderOutput::Sequential(num_points) | &ConnectivityEncoderOutput::PointCloud(num_points) => {
+                    let corner_table = PointCloudCornerTable::new(num_points);
+                    let sequence = if let Some(order) = self.vertex_order {
+                        order.iter().map(|&v| CornerIdx::from(usize::from(v))).collect::<Vec<_>>()
+                    } else {
+                        (0..num_points).map(CornerIdx::from).collect()
+                    };
+                    self.encode_impl_edgebreaker::<WRITE_NOW,_,_,NdVector<N, T>, N>(&corner_table, sequence.into_iter())
                 },
             }
         } else {
@@ -279,8 +436,11 @@ impl<'parents, 'encoder, 'writer, 'co, 'mesh, W> AttributeEncoder<'parents, 'enc
             NdVector<N, i32>: Vector<N, Component = i32>,
             NdVector<N, f32>: Vector<N, Component = f32> + Portable
     {
+        if self.cfg.raw_bits {
+            return self.encode_raw_bits(corner_table, sequence);
+        }
 
-        let por_cfg = portabilization::Config::default_for(self.att.get_attribute_type());
+        let por_cfg = self.cfg.group_cfgs[0].prediction_transform.portabilization;
 
         
         let mut att = Attribute::new(
@@ -291,11 +451,13 @@ impl<'parents, 'encoder, 'writer, 'co, 'mesh, W> AttributeEncoder<'parents, 'enc
         );
         std::mem::swap(&mut att, &mut self.att);
         let mut port_info_buffer = Vec::new();
+        debug_write!("Start of Portabilization Metadata", port_info_buffer, self.cfg.debug_markers);
         let portabilization: portabilization::Portabilization<Data, N> = portabilization::Portabilization::new(
             att,
             por_cfg,
             &mut port_info_buffer,
         );
+        debug_write!("End of Portabilization Metadata", port_info_buffer, self.cfg.debug_markers);
         let port_att = portabilization.portabilize();
         
         match port_att.get_num_components() {
@@ -309,6 +471,26 @@ impl<'parents, 'encoder, 'writer, 'co, 'mesh, W> AttributeEncoder<'parents, 'enc
         }
     }
     
+    /// Entropy codes the raw bits of the values in the order of 'sequence', split into 32-bit words.
+    /// Nothing is predicted or quantized, so the values are restored bit-exact.
+    fn encode_raw_bits<CT, S>(self, corner_table: &CT, sequence: S) -> Result<Attribute, Err>
+        where
+            CT: GenericCornerTable,
+            S: Iterator<Item = CornerIdx>,
+    {
+        let value_size = self.att.get_component_type().size() * self.att.get_num_components();
+        let mut symbols = Vec::new();
+        for c in sequence {
+            let p = corner_table.point_idx(c);
+            let bytes = self.att.get_as_bytes(usize::from(self.att.get_unique_val_idx(p)));
+            symbols.extend(bits_to_symbols(bytes));
+        }
+
+        self.writer.write_u8(self.cfg.rans_encoding as u8);
+        encode_symbols(symbols, value_size.div_ceil(4), SymbolEncodingMethod::LengthCoded, self.writer)?;
+        Ok(self.att)
+    }
+
     fn encode_portabilized<CT, S, const N: usize>(&mut self, corner_table: &CT, sequence: S, port_att: Attribute, port_info_buffer: Vec<u8>) -> Result<Attribute, Err>
     where 
     CT: GenericCornerTable,
@@ -320,14 +502,25 @@ impl<'parents, 'encoder, 'writer, 'co, 'mesh, W> AttributeEncoder<'parents, 'enc
             self.parents,
             corner_table
         );
+        if self.cfg.group_cfgs[0].prediction_transform.portabilization.type_ == portabilization::PortabilizationType::OctahedralQuantization {
+            prediction_scheme.set_octahedral_max_quantized(
+                (1 << self.cfg.group_cfgs[0].prediction_transform.portabilization.quantization_bits) - 1
+            );
+        }
         
         // Transform the predicted values
         let mut transform = PredictionTransform::new(
             self.cfg.group_cfgs[0].prediction_transform,
         );
+        // Only the difference transforms zigzag-map their corrections, which can then be split into signs and magnitudes.
+        let signed = self.cfg.rans_encoding && self.cfg.signed_corrections && matches!(
+            transform.get_type(), 
+            PredictionTransformType::Difference | PredictionTransformType::WrappedDifference
+        );
         
         // Predict and transform the values
         let mut sequence_record = Vec::new();
+        let mut handedness_symbols = Vec::new();
         
         for c in sequence {
             let val = prediction_scheme.predict(c, &sequence_record, &port_att);
@@ -335,20 +528,31 @@ impl<'parents, 'encoder, 'writer, 'co, 'mesh, W> AttributeEncoder<'parents, 'enc
             sequence_record.push(v);
             let p = corner_table.point_idx(c);
             transform.map_with_tentative_metadata(port_att.get(p), val);
+            if let Some(handedness) = &self.handedness {
+                handedness_symbols.push(handedness[usize::from(port_att.get_unique_val_idx(p))] as u64);
+            }
         }
         
         // Write the output
         let mut transform_info_buffer = Vec::new();
         let output = transform.squeeze(&mut transform_info_buffer);
 
-        self.writer.write_u8(self.cfg.rans_encoding as u8);
+        if signed {
+            self.writer.write_u8(SIGNED_RANS_ENCODING);
+        } else {
+            self.writer.write_u8(self.cfg.rans_encoding as u8);
+        }
         if self.cfg.rans_encoding {
             // ToDo: This can be a lot smarter.
             let symbols = output.iter()
                 .map(|v| (0..N).map(|i| *v.get(i) as u64))
                 .flatten()
                 .collect::<Vec<_>>();
-            encode_symbols(symbols, N, SymbolEncodingMethod::DirectCoded, self.writer)?
+            if signed {
+                encode_signed_symbols(symbols, N, self.writer)?
+            } else {
+                encode_symbols(symbols, N, SymbolEncodingMethod::DirectCoded, self.writer)?
+            }
         } else {
             // If RANS encoding is not used, we write the output directly
             for value in output {
@@ -364,7 +568,9 @@ impl<'parents, 'encoder, 'writer, 'co, 'mesh, W> AttributeEncoder<'parents, 'enc
                 self.writer.write_u8(byte);
             }
             prediction_scheme.encode_prediction_metadtata(self.writer)?;
-        } else if prediction_scheme.get_type() == prediction_scheme::PredictionSchemeType::MeshPredictionForTextureCoordinates {
+        } else if prediction_scheme.get_type() == prediction_scheme::PredictionSchemeType::MeshPredictionForTextureCoordinates
+            || prediction_scheme.get_type() == prediction_scheme::PredictionSchemeType::JointPrediction
+        {
             prediction_scheme.encode_prediction_metadtata(self.writer)?;
             for byte in transform_info_buffer {
                 self.writer.write_u8(byte);
@@ -384,6 +590,11 @@ impl<'parents, 'encoder, 'writer, 'co, 'mesh, W> AttributeEncoder<'parents, 'enc
         for byte in port_info_buffer {
             self.writer.write_u8(byte);
         }
+
+        if self.handedness.is_some() {
+            // The handedness is usually constant over the mesh, so it is cheap when rANS coded.
+            encode_symbols(handedness_symbols, 1, SymbolEncodingMethod::DirectCoded, self.writer)?;
+        }
         
         Ok(port_att)
     }
@@ -392,8 +603,9 @@ impl<'parents, 'encoder, 'writer, 'co, 'mesh, W> AttributeEncoder<'parents, 'enc
 
 use crate::shared::attribute::prediction_scheme;
 use crate::encode::attribute::portabilization;
+use crate::encode::attribute::portabilization::to_bits::bits_to_symbols;
 use crate::core::shared::Vector;
-use super::prediction_transform::{self, PredictionTransform};
+use super::prediction_transform::{self, PredictionTransform, PredictionTransformType};
 use crate::encode::attribute::prediction_transform::PredictionTransformImpl;
 
 // struct Group<'encoder, C, const N: usize>
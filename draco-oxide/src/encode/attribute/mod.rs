@@ -2,25 +2,46 @@ pub(crate) mod attribute_encoder;
 pub(crate) mod portabilization;
 pub(crate) mod prediction_transform;
 
-use crate::encode::attribute::portabilization::PortabilizationType;
+use crate::encode::attribute::portabilization::{PortabilizationType, QuantizationGrid};
+use crate::encode::attribute::prediction_transform::PredictionTransformType;
 use crate::encode::connectivity::ConnectivityEncoderOutput;
+use crate::encode::stats::AttributeStats;
 #[cfg(feature = "evaluation")]
 use crate::eval;
 
-use crate::prelude::{Attribute, ByteWriter, ConfigType};
+use crate::core::attribute::{AttributeId, ComponentDataType};
+use crate::core::bit_coder::CountingWriter;
+use crate::core::corner_table::GenericCornerTable;
+use crate::core::mesh::diff::unique_value_as_f64;
+use crate::core::shared::VertexIdx;
+use crate::prelude::{Attribute, AttributeType, ByteWriter, ConfigType};
+use crate::shared::attribute::handedness::{find_non_unit_tangent, split_handedness};
+use crate::shared::attribute::prediction_scheme::PredictionSchemeType;
+use crate::shared::attribute::sequence::{ranks_in_sequence, Traverser};
 use crate::shared::connectivity::edgebreaker::TraversalType;
+use crate::utils::bit_coder::leb128_write;
 
 pub fn encode_attributes<W>(
     atts: Vec<Attribute>,
     writer: &mut W,
     conn_out: ConnectivityEncoderOutput<'_>,
     cfg: &super::Config,
-) -> Result<(), Err> 
+) -> Result<Vec<AttributeStats>, Err> 
     where W: ByteWriter
 {
     #[cfg(feature = "evaluation")]
     eval::scope_begin("attributes", writer);
 
+    // The order is written as the ranks of the vertices in the default traversal of the positions, as the decoder
+    // numbers the vertices on its own.
+    if let Some(order) = &cfg.vertex_order {
+        validate_vertex_order(order, &conn_out)?;
+        leb128_write(order.len() as u64, writer);
+        for rank in vertex_order_ranks(order, &conn_out) {
+            leb128_write(rank as u64, writer);
+        }
+    }
+
     // Write the number of attribute encoders/decoders (In draco-oxide, this is the same as the number of attributes as 
     // each attribute has its own encoder/decoder)
     writer.write_u8(atts.len() as u8);
@@ -41,7 +62,25 @@ pub fn encode_attributes<W>(
     #[cfg(feature = "evaluation")]
     eval::array_scope_begin("attributes", writer);
 
-    let mut port_atts: Vec<Attribute> = Vec::new();
+    let selection = Selection::new(&atts, cfg);
+
+    if cfg.unsupported_components == UnsupportedComponents::Reject {
+        if let Some(att) = atts.iter().find(|att| !supports_num_components(att.get_attribute_type(), att.get_num_components())) {
+            return Err(Err::UnsupportedNumComponents(format!(
+                "the {:?} attribute {:?} has {} components", att.get_attribute_type(), att.get_id(), att.get_num_components()
+            )));
+        }
+    }
+    validate_joint_prediction(&atts, cfg, |att| {
+        selection.is_lossless(att) || selection.is_split_tangent(att) || selection.is_palette_color(att)
+    })?;
+    validate_attribute_quantization(cfg)?;
+    for att in atts.iter().filter(|att| !selection.is_lossless(att) && selection.is_split_tangent(att)) {
+        if let Some(v) = find_non_unit_tangent(att) {
+            return Err(Err::NonUnitTangent(att.get_id(), v));
+        }
+    }
+
     for att in &atts {
         // Write 1 to indicate that the encoder is for one attribute.
         writer.write_u8(1);
@@ -53,34 +92,111 @@ pub fn encode_attributes<W>(
         writer.write_u8(att.get_id().as_usize() as u8); // unique id
 
         // write the decoder type.
-        PortabilizationType::default_for(att.get_attribute_type()).write_to(writer);
+        selection.portabilization_type(att).write_to(writer);
     }
     
-    for (i, att) in atts.into_iter().enumerate() {
-        #[cfg(feature = "evaluation")]
-        eval::scope_begin("attribute", writer);
+    // The parent of the joint prediction replaces the parents of the attribute.
+    let parent_ids_of = |att: &Attribute| match joint_parent_of(att, cfg) {
+        Some(parent) => vec![parent],
+        None => att.get_parents().clone(),
+    };
 
-        let parents_ids = att.get_parents();
-        let parents = parents_ids.iter()
-            .map(|id| port_atts.iter().find(|att| att.get_id() == *id).unwrap())
-            .collect::<Vec<_>>();
+    // Encodes the 'i'th attribute into its own buffer on the portabilized attributes encoded before it, and
+    // returns the buffer, the portabilized attribute, and its stats.
+    let encode_attribute = |i: usize, att: Attribute, port_atts: &[Attribute]| -> Result<(Vec<u8>, Attribute, AttributeStats), Err> {
+        let mut buffer = Vec::new();
+        let writer = &mut buffer;
+            #[cfg(feature = "evaluation")]
+            eval::scope_begin("attribute", writer);
 
-        let ty = att.get_attribute_type();
-        let len = att.len();
-        let encoder = attribute_encoder::AttributeEncoder::new(
-            att,
-            i,
-            &parents,
-            &conn_out,
-            writer,
-            attribute_encoder::Config::default_for(ty, len),
-        );
-
-        let port_att = encoder.encode::<true, false>()?;
-        port_atts.push(port_att);
+            let parents = parent_ids_of(&att).iter()
+                .map(|id| port_atts.iter().find(|att| att.get_id() == *id).unwrap())
+                .collect::<Vec<_>>();
 
-        #[cfg(feature = "evaluation")]
-        eval::scope_end(writer);
+            let ty = att.get_attribute_type();
+            let lossless = selection.is_lossless(&att);
+            let integer_position = selection.is_integer_position(&att);
+            let wrapped_tex_coord = selection.is_wrapped_tex_coord(&att);
+            let mut att_cfg = selection.attribute_config(
+                &att, matches!(conn_out, ConnectivityEncoderOutput::PointCloud(_) | ConnectivityEncoderOutput::Sequential(_))
+            );
+            let (mut att, handedness) = if !lossless && selection.is_split_tangent(&att) {
+                let (xyz_att, handedness) = split_handedness(att);
+                (xyz_att, Some(handedness))
+            } else {
+                (att, None)
+            };
+            if let Some(integers) = att.narrow_to_i32().filter(|_| integer_position) {
+                att = integers;
+            }
+            att_cfg.set_debug_markers(cfg.debug_markers);
+            att_cfg.set_signed_corrections(cfg.signed_corrections.contains(&att.get_id()));
+            if let Some(grid) = cfg.quantization_grid.filter(|_| ty == AttributeType::Position && !lossless && !integer_position) {
+                apply_quantization_grid(&mut att, &grid)?;
+            }
+            if wrapped_tex_coord && !portabilization::wrapped_quantization::fits_in_i32(&att, att_cfg.quantization_bits()) {
+                return Err(Err::TooManyWraps(att.get_id(), att_cfg.quantization_bits()));
+            }
+            let (id, prediction_scheme) = (att.get_id(), att_cfg.prediction_scheme());
+            let mut counter = CountingWriter::new(writer);
+            let mut encoder = attribute_encoder::AttributeEncoder::new(
+                att,
+                i,
+                &parents,
+                &conn_out,
+                &mut counter,
+                att_cfg,
+            );
+            if let Some(handedness) = handedness {
+                encoder = encoder.with_handedness(handedness);
+            }
+            if let Some(order) = &cfg.vertex_order {
+                encoder = encoder.with_vertex_order(order);
+            }
+
+            let port_att = encoder.encode::<true, false>()?;
+            let stats = AttributeStats { id, attribute_type: ty, prediction_scheme, bytes: counter.count() };
+
+            #[cfg(feature = "evaluation")]
+            eval::scope_end(writer);
+            Ok((buffer, port_att, stats))
+    };
+
+    let mut port_atts: Vec<Attribute> = Vec::new();
+    let mut stats = Vec::new();
+    #[cfg(not(feature = "parallel"))]
+    for (i, att) in atts.into_iter().enumerate() {
+        let (bytes, port_att, att_stats) = encode_attribute(i, att, &port_atts)?;
+        for byte in bytes {
+            writer.write_u8(byte);
+        }
+        stats.push(att_stats);
+        port_atts.push(port_att);
+    }
+    // The attributes whose parents are encoded are independent of each other, so each run of them is encoded
+    // in parallel, and their buffers are written in the order of the attributes so that the stream is the same.
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::prelude::*;
+        let mut remaining = atts.into_iter().enumerate().collect::<Vec<_>>();
+        while !remaining.is_empty() {
+            let run_len = remaining.iter()
+                .position(|(_, att)| !parent_ids_of(att).iter().all(|id| port_atts.iter().any(|port_att| port_att.get_id() == *id)))
+                .unwrap_or(remaining.len())
+                .max(1);
+            let encoded = remaining.drain(..run_len)
+                .collect::<Vec<_>>()
+                .into_par_iter()
+                .map(|(i, att)| encode_attribute(i, att, &port_atts))
+                .collect::<Result<Vec<_>, _>>()?;
+            for (bytes, port_att, att_stats) in encoded {
+                for byte in bytes {
+                    writer.write_u8(byte);
+                }
+                stats.push(att_stats);
+                port_atts.push(port_att);
+            }
+        }
     }
 
     #[cfg(feature = "evaluation")]
@@ -89,6 +205,380 @@ pub fn encode_attributes<W>(
         eval::scope_end(writer);
     }
 
+    Ok(stats)
+}
+
+/// The parent of the joint prediction of the attribute, see [crate::encode::Config::joint_prediction].
+fn joint_parent_of(att: &Attribute, cfg: &super::Config) -> Option<AttributeId> {
+    cfg.joint_prediction.iter()
+        .find(|(_, child)| *child == att.get_id())
+        .map(|(parent, _)| *parent)
+}
+
+/// How each attribute is portabilized and predicted under the configuration of the encoder. This is the one place
+/// the choice is made, so that [encode_attributes] and [crate::core::mesh::Mesh::estimate_encoded_size] agree.
+pub(crate) struct Selection<'a> {
+    cfg: &'a super::Config,
+    /// The float positions on the integer lattice, which are stored as the integers without quantization when asked for.
+    /// The descriptor keeps the float type, so that the decoder restores the floats.
+    integer_position_ids: Vec<AttributeId>,
+}
+
+impl<'a> Selection<'a> {
+    pub(crate) fn new(atts: &[Attribute], cfg: &'a super::Config) -> Self {
+        let mut selection = Self { cfg, integer_position_ids: Vec::new() };
+        selection.integer_position_ids = atts.iter()
+            .filter(|att| cfg.integer_positions && !selection.is_lossless(att) && att.get_attribute_type() == AttributeType::Position)
+            .filter(|att| att.narrow_to_i32().is_some())
+            .map(|att| att.get_id())
+            .collect();
+        selection
+    }
+
+    /// Tangents whose handedness is encoded separately from the 'xyz' part.
+    pub(crate) fn is_split_tangent(&self, att: &Attribute) -> bool {
+        self.cfg.separate_tangent_handedness
+            && att.get_attribute_type() == AttributeType::Tangent
+            && att.get_component_type() == ComponentDataType::F32
+            && att.get_num_components() == 4
+    }
+
+    /// Custom attributes of floats, which are kept as their raw bits unless they are quantized as scalar channels.
+    fn is_raw_custom(&self, att: &Attribute) -> bool {
+        att.get_attribute_type() == AttributeType::Custom
+            && att.get_component_type().is_float()
+            && !(self.cfg.scalar_channels && att.get_num_components() == 1)
+    }
+
+    /// Float attributes whose raw bits are encoded in the lossless mode, and the attributes of a number of
+    /// components that cannot be predicted, e.g. matrices, which fall back to the raw bits.
+    pub(crate) fn is_lossless(&self, att: &Attribute) -> bool {
+        (self.cfg.lossless && att.get_component_type().is_float())
+            || self.is_raw_custom(att)
+            || !supports_num_components(att.get_attribute_type(), att.get_num_components())
+    }
+
+    pub(crate) fn is_integer_position(&self, att: &Attribute) -> bool {
+        self.integer_position_ids.contains(&att.get_id())
+    }
+
+    /// Texture coordinates whose wrap counts and fractional parts are quantized separately.
+    pub(crate) fn is_wrapped_tex_coord(&self, att: &Attribute) -> bool {
+        self.cfg.wrapped_texture_coordinates
+            && !self.is_lossless(att)
+            && att.get_attribute_type() == AttributeType::TextureCoordinate
+            && att.get_component_type().is_float()
+    }
+
+    /// Custom attributes of one component, e.g. a weight updated every frame of an animation, which are predicted
+    /// by the previous value when asked for. The floats are quantized instead of being encoded as their raw bits.
+    fn is_scalar_channel(&self, att: &Attribute) -> bool {
+        self.cfg.scalar_channels
+            && !self.is_lossless(att)
+            && att.get_attribute_type() == AttributeType::Custom
+            && att.get_num_components() == 1
+    }
+
+    /// Colors of a few distinct values, which are encoded as the indices into their palette.
+    pub(crate) fn is_palette_color(&self, att: &Attribute) -> bool {
+        self.cfg.color_palette_threshold > 0
+            && !self.is_lossless(att)
+            && att.get_attribute_type() == AttributeType::Color
+            && att.num_unique_values() <= self.cfg.color_palette_threshold
+    }
+
+    /// Whether the values of a mesh of 'num_faces' faces are coded in the order of the points, i.e. as a point
+    /// cloud or by the sequential connectivity.
+    pub(crate) fn is_sequential(&self, num_faces: usize) -> bool {
+        num_faces == 0 || self.cfg.encoder_method == crate::shared::header::EncoderMethod::Sequential
+    }
+
+    /// The decoder type written in the descriptor of the attribute.
+    pub(crate) fn portabilization_type(&self, att: &Attribute) -> PortabilizationType {
+        if self.is_lossless(att) {
+            PortabilizationType::ToBits
+        } else if self.is_split_tangent(att) {
+            PortabilizationType::OctahedralQuantization
+        } else if self.is_integer_position(att) {
+            PortabilizationType::Integer
+        } else if self.is_wrapped_tex_coord(att) {
+            PortabilizationType::WrappedQuantization
+        } else if self.is_palette_color(att) {
+            PortabilizationType::Palette
+        } else if self.is_scalar_channel(att) && att.get_component_type().is_float() {
+            PortabilizationType::QuantizationCoordinateWise
+        } else {
+            PortabilizationType::default_for_component(att.get_attribute_type(), att.get_component_type())
+        }
+    }
+
+    /// The configuration the attribute is encoded with. 'sequential' is whether the values are coded in the order
+    /// of the points, i.e. without a corner table to predict from.
+    pub(crate) fn attribute_config(&self, att: &Attribute, sequential: bool) -> attribute_encoder::Config {
+        let cfg = self.cfg;
+        let ty = att.get_attribute_type();
+        let len = att.len();
+        let lossless = self.is_lossless(att);
+        let integer_position = self.is_integer_position(att);
+        let mut att_cfg = if lossless {
+            attribute_encoder::Config::lossless(len)
+        } else if self.is_split_tangent(att) {
+            attribute_encoder::Config::tangent_xyz(len)
+        } else {
+            attribute_encoder::Config::default_for(ty, len)
+        };
+        if ty == AttributeType::Position && !lossless {
+            if cfg.average_position_prediction {
+                att_cfg.set_prediction_scheme(PredictionSchemeType::AveragePrediction);
+            } else {
+                att_cfg.set_prediction_scheme(cfg.position_prediction.scheme_type());
+            }
+        }
+        if ty == AttributeType::Normal && !lossless {
+            att_cfg.set_prediction_scheme(cfg.normal_prediction.scheme_type());
+        }
+        if self.is_scalar_channel(att) {
+            att_cfg.set_prediction_scheme(PredictionSchemeType::DeltaPrediction);
+            if att.get_component_type().is_float() {
+                att_cfg.set_portabilization_type(PortabilizationType::QuantizationCoordinateWise);
+            }
+        }
+        if self.is_wrapped_tex_coord(att) {
+            att_cfg.set_portabilization_type(PortabilizationType::WrappedQuantization);
+        }
+        if integer_position {
+            att_cfg.set_portabilization_type(PortabilizationType::Integer);
+        }
+        if !lossless && PortabilizationType::default_for_component(ty, att.get_component_type()) == PortabilizationType::Integer {
+            att_cfg.set_portabilization_type(PortabilizationType::Integer);
+        }
+        if joint_parent_of(att, cfg).is_some() {
+            att_cfg.set_prediction_scheme(PredictionSchemeType::JointPrediction);
+            att_cfg.set_prediction_transform(PredictionTransformType::WrappedDifference);
+        }
+        if let Some(bits) = cfg.quantization_bits.filter(|_| !lossless) {
+            att_cfg.set_quantization_bits(bits);
+        }
+        if let Some(&bits) = cfg.attribute_quantization.get(&ty).filter(|_| !lossless) {
+            att_cfg.set_quantization_bits(bits);
+        }
+        if let Some(bits) = cfg.normal_quantization_bits.filter(|_| ty == AttributeType::Normal && !lossless) {
+            att_cfg.set_quantization_bits(bits);
+        }
+        if let Some(grid) = cfg.quantization_grid.filter(|_| ty == AttributeType::Position && !lossless && !integer_position) {
+            att_cfg.set_quantization_bits(grid.bits);
+        }
+        // The metadata of the prediction costs more than it saves on the attributes of a few values.
+        if !lossless && att.num_unique_values() < cfg.min_values_for_prediction {
+            att_cfg.set_prediction_scheme(PredictionSchemeType::NoPrediction);
+            att_cfg.set_prediction_transform(PredictionTransformType::NoTransform);
+        }
+        // The indices into the palette have no geometric meaning to predict.
+        if self.is_palette_color(att) {
+            att_cfg.set_portabilization_type(PortabilizationType::Palette);
+            att_cfg.set_prediction_scheme(PredictionSchemeType::NoPrediction);
+            att_cfg.set_prediction_transform(PredictionTransformType::NoTransform);
+        }
+        // The points of a point cloud have no neighbors, so the predictions from the faces fall back to the previous point,
+        // except the average prediction, which averages the previous points.
+        // The values of the sequential connectivity are in the order of the points, which has no corner table to predict from.
+        if sequential && !matches!(
+            att_cfg.prediction_scheme(),
+            PredictionSchemeType::NoPrediction | PredictionSchemeType::DeltaPrediction | PredictionSchemeType::JointPrediction
+                | PredictionSchemeType::AveragePrediction
+        ) {
+            att_cfg.set_prediction_scheme(PredictionSchemeType::DeltaPrediction);
+        }
+        if cfg.normal_transform == NormalTransform::Reflection && att_cfg.prediction_transform() == PredictionTransformType::OctahedralOrthogonal {
+            att_cfg.set_prediction_transform(PredictionTransformType::OctahedralReflection);
+        }
+        att_cfg
+    }
+}
+
+/// Checks the quantization bits of [crate::encode::Config::attribute_quantization] and of the quantization grid.
+pub(crate) fn validate_attribute_quantization(cfg: &super::Config) -> Result<(), Err> {
+    if let Some((&ty, &bits)) = cfg.attribute_quantization.iter().find(|(_, bits)| !crate::encode::QUANTIZATION_BITS.contains(*bits)) {
+        return Err(Err::InvalidQuantizationBits(ty, bits));
+    }
+    if let Some(grid) = cfg.quantization_grid.filter(|grid| !crate::encode::QUANTIZATION_BITS.contains(&grid.bits)) {
+        return Err(Err::InvalidQuantizationBits(AttributeType::Position, grid.bits));
+    }
+    Ok(())
+}
+
+/// What the encoder does with the attributes of a number of components that the prediction transforms
+/// do not support, e.g. matrices of more than four components, or normals of other than three components.
+/// An attribute whose number of components is not one of its type, e.g. such a normal, is first subject to
+/// [MismatchedComponents], and reaches this only if it is accepted there.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum UnsupportedComponents {
+    /// The raw bits of the values are encoded without prediction, as in the lossless mode.
+    #[default]
+    RawBits,
+    /// The encoding fails with [Err::UnsupportedNumComponents].
+    Reject,
+}
+
+/// What the encoder does with the attributes whose number of components is not one of their type, see
+/// [crate::encode::Config::mismatched_components], e.g. a normal of five components.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MismatchedComponents {
+    /// The encoding fails with [crate::encode::Err::MismatchedNumComponents] before anything is written.
+    #[default]
+    Reject,
+    /// The attribute is encoded as any attribute of its number of components, i.e. it is then subject to
+    /// [UnsupportedComponents] if its number of components cannot be predicted.
+    Accept,
+}
+
+/// How the positions are predicted from the positions encoded before them, see [crate::encode::Config::position_prediction].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PositionPrediction {
+    /// The position is predicted by the previous one in the traversal, which needs no connectivity.
+    Delta,
+    /// The position completes the parallelogram of a neighboring face.
+    #[default]
+    Parallelogram,
+    /// The position is predicted by the average of the parallelograms of all the neighboring faces encoded before it.
+    MultiParallelogram,
+}
+
+impl PositionPrediction {
+    fn scheme_type(self) -> PredictionSchemeType {
+        match self {
+            PositionPrediction::Delta => PredictionSchemeType::DeltaPrediction,
+            PositionPrediction::Parallelogram => PredictionSchemeType::MeshParallelogramPrediction,
+            PositionPrediction::MultiParallelogram => PredictionSchemeType::MeshMultiParallelogramPrediction,
+        }
+    }
+}
+
+/// How the normals are predicted, see [crate::encode::Config::normal_prediction]. The prediction is
+/// independent of the octahedral transform of the normals, which is used either way.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NormalPrediction {
+    /// The normal is predicted by the previous one in the traversal.
+    Delta,
+    /// The normal is predicted from the geometry, i.e. by the area-weighted normal of the faces around the vertex.
+    #[default]
+    Geometric,
+}
+
+impl NormalPrediction {
+    fn scheme_type(self) -> PredictionSchemeType {
+        match self {
+            NormalPrediction::Delta => PredictionSchemeType::DeltaPrediction,
+            NormalPrediction::Geometric => PredictionSchemeType::MeshNormalPrediction,
+        }
+    }
+}
+
+/// How the corrections of the normals are transformed, see [crate::encode::Config::normal_transform].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NormalTransform {
+    /// The octahedral coordinates are flipped and rotated by quarter turns so that the prediction is
+    /// in a canonical quadrant, as in Draco.
+    #[default]
+    Octahedral,
+    /// The octahedral coordinates are only flipped into the hemisphere of the prediction, as in the plain
+    /// octahedron transform of Draco.
+    Reflection,
+}
+
+/// Whether the attribute of the given type can be predicted and transformed, given its number of components.
+pub(crate) fn supports_num_components(ty: AttributeType, num_components: usize) -> bool {
+    match ty {
+        // the octahedral transform maps a 3D unit vector to two components.
+        AttributeType::Normal => num_components == 3,
+        _ => num_components <= 4,
+    }
+}
+
+/// The numbers of components that an attribute of the type is expected to have, e.g. 2 or 3 for the positions
+/// of planar or spatial geometry, or 'None' if the type accepts any number.
+pub(crate) fn expected_num_components(ty: AttributeType) -> Option<std::ops::RangeInclusive<usize>> {
+    match ty {
+        AttributeType::Position => Some(2..=3),
+        AttributeType::Normal => Some(3..=3),
+        AttributeType::Color => Some(3..=4),
+        AttributeType::TextureCoordinate => Some(2..=2),
+        _ => None,
+    }
+}
+
+/// Checks that the order given by the user is a permutation of the position vertices.
+fn validate_vertex_order(order: &[VertexIdx], conn_out: &ConnectivityEncoderOutput<'_>) -> Result<(), Err> {
+    let num_vertices = match conn_out {
+        ConnectivityEncoderOutput::Edgebreaker(out) => out.corner_table.universal_corner_table().num_vertices(),
+        &ConnectivityEncoderOutput::Sequential(num_points) | &ConnectivityEncoderOutput::PointCloud(num_points) => num_points,
+    };
+    if order.len() != num_vertices {
+        return Err(Err::InvalidVertexOrder(format!("the order has {} vertices, but the mesh has {}", order.len(), num_vertices)));
+    }
+    let mut seen = vec![false; num_vertices];
+    for &v in order {
+        let v = usize::from(v);
+        if v >= num_vertices || seen[v] {
+            return Err(Err::InvalidVertexOrder(format!("the vertex {} is out of range or appears more than once", v)));
+        }
+        seen[v] = true;
+    }
+    Ok(())
+}
+
+/// Returns the rank of each vertex of 'order' in the default traversal of the positions, see [ranks_in_sequence].
+fn vertex_order_ranks(order: &[VertexIdx], conn_out: &ConnectivityEncoderOutput<'_>) -> Vec<usize> {
+    match conn_out {
+        ConnectivityEncoderOutput::Edgebreaker(out) => {
+            let universal = out.corner_table.universal_corner_table();
+            let sequence = Traverser::new(universal, out.corners_of_edgebreaker.clone()).compute_seqeunce();
+            ranks_in_sequence(universal, &sequence, order)
+        },
+        // the points of a point cloud and of the sequential connectivity are traversed in their order.
+        ConnectivityEncoderOutput::Sequential(_) | ConnectivityEncoderOutput::PointCloud(_) => order.iter().map(|&v| usize::from(v)).collect(),
+    }
+}
+
+/// Checks that each pair of the joint prediction refers to two attributes of the same number of components
+/// that are quantized coordinate-wise, and that the parent is encoded before the child.
+fn validate_joint_prediction(atts: &[Attribute], cfg: &super::Config, is_excluded: impl Fn(&Attribute) -> bool) -> Result<(), Err> {
+    for &(parent, child) in &cfg.joint_prediction {
+        let find = |id| atts.iter().position(|att| att.get_id() == id)
+            .ok_or_else(|| Err::InvalidJointPrediction(format!("no attribute has the id {:?}", id)));
+        let (p, c) = (find(parent)?, find(child)?);
+        if p >= c {
+            return Err(Err::InvalidJointPrediction(format!("the parent {:?} must be encoded before the child {:?}", parent, child)));
+        }
+        if cfg.joint_prediction.iter().filter(|(_, other)| *other == child).count() > 1 {
+            return Err(Err::InvalidJointPrediction(format!("the child {:?} has more than one parent", child)));
+        }
+        if atts[p].get_num_components() != atts[c].get_num_components() {
+            return Err(Err::InvalidJointPrediction(format!(
+                "the parent has {} components, but the child has {}", atts[p].get_num_components(), atts[c].get_num_components()
+            )));
+        }
+        for att in [&atts[p], &atts[c]] {
+            let ty = att.get_attribute_type();
+            if is_excluded(att) || PortabilizationType::default_for_component(ty, att.get_component_type()) != PortabilizationType::QuantizationCoordinateWise {
+                return Err(Err::InvalidJointPrediction(format!("the {:?} attribute is not quantized coordinate-wise", ty)));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Makes the position attribute quantized on the grid, checking that the positions lie within it.
+/// The bits of the grid are checked by [validate_attribute_quantization].
+fn apply_quantization_grid(att: &mut Attribute, grid: &QuantizationGrid) -> Result<(), Err> {
+    if att.get_num_components() != 3 {
+        return Err(Err::OutsideQuantizationGrid(format!("the positions have {} components, but the grid has 3", att.get_num_components())));
+    }
+    if let Some(v) = (0..att.num_unique_values()).map(|i| unique_value_as_f64(att, i)).find(|v| !grid.contains(v)) {
+        return Err(Err::OutsideQuantizationGrid(format!("the position {:?} is outside of the grid {:?}", v, grid)));
+    }
+    let (min, max) = grid.bounds();
+    att.set_value_bounds(&min, &max);
     Ok(())
 }
 
@@ -111,5 +601,587 @@ impl ConfigType for Config {
 #[derive(thiserror::Error, Debug)]
 pub enum Err {
     #[error("Attribute encoding error: {0}")]
-    AttributeError(#[from] attribute_encoder::Err)
-}
\ No newline at end of file
+    AttributeError(#[from] attribute_encoder::Err),
+    #[error("Invalid joint prediction: {0}")]
+    InvalidJointPrediction(String),
+    #[error("Invalid quantization bits of the {0:?} attributes: {1}; they must be in 1..=30")]
+    InvalidQuantizationBits(AttributeType, u8),
+    #[error("Invalid vertex order: {0}")]
+    InvalidVertexOrder(String),
+    #[error("The tangent {1:?} of the attribute {0:?} is not of unit length, which the octahedral quantization of its 'xyz' part requires")]
+    NonUnitTangent(AttributeId, [f32; 4]),
+    #[error("Position outside of the quantization grid: {0}")]
+    OutsideQuantizationGrid(String),
+    #[error("The texture coordinates {0:?} span too many tiles to pack them with {1} bits of the fractional parts in 'i32'")]
+    TooManyWraps(AttributeId, u8),
+    #[error("Unsupported number of components: {0}")]
+    UnsupportedNumComponents(String),
+}
+
+
+#[cfg(all(test, not(feature = "evaluation")))]
+mod tests {
+    use super::*;
+    use crate::core::attribute::AttributeDomain;
+    use crate::core::shared::NdVector;
+
+    #[test]
+    fn test_custom_vertex_order() {
+        use crate::core::shared::{ConfigType, Vector};
+        use crate::encode::header::CUSTOM_VERTEX_ORDER_FLAG_MASK;
+        let mesh = crate::io::obj::load_obj("tests/data/sphere.obj").unwrap();
+        let pos = &mesh.get_attributes()[0];
+        let num_vertices = pos.num_unique_values();
+        let order = (0..num_vertices).rev().map(VertexIdx::from).collect::<Vec<_>>();
+
+        let mut cfg = crate::encode::Config::default();
+        cfg.vertex_order = Some(order.clone());
+        let mut custom = Vec::new();
+        crate::encode::encode(mesh.clone(), &mut custom, cfg.clone()).unwrap();
+        let mut default = Vec::new();
+        crate::encode::encode(mesh.clone(), &mut default, crate::encode::Config::default()).unwrap();
+        assert_ne!(custom, default);
+
+        // The header tells the decoder that the order is written before the attributes.
+        let mut header = Vec::new();
+        crate::encode::header::encode_header(&mut header, &cfg).unwrap();
+        let flags = u16::from_le_bytes([header[9], header[10]]);
+        assert_eq!(flags & CUSTOM_VERTEX_ORDER_FLAG_MASK, CUSTOM_VERTEX_ORDER_FLAG_MASK);
+        assert!(custom.windows(header.len()).any(|w| w == header.as_slice()));
+
+        // The decoded positions follow the order up to the quantization.
+        let mut decode_cfg = crate::decode::Config::default();
+        decode_cfg.emit_attribute_indices = true;
+        let decoded = crate::decode::decode(&mut crate::prelude::SliceReader::new(&custom), decode_cfg).unwrap();
+        let decoded_pos = decoded.get_attributes().iter().find(|att| att.get_attribute_type() == AttributeType::Position).unwrap();
+        let values = pos.unique_vals_as_slice::<NdVector<3, f32>, 3>();
+        let decoded_values = decoded_pos.unique_vals_as_slice::<NdVector<3, f32>, 3>();
+        assert_eq!(decoded_values.len(), num_vertices);
+        for (decoded, &v) in decoded_values.iter().zip(&order) {
+            let value = values[usize::from(v)];
+            assert!((0..3).all(|i| (decoded.get(i) - value.get(i)).abs() < 1e-2), "{:?} != {:?}", decoded, value);
+        }
+        assert_eq!(decoded.get_faces().len(), mesh.get_faces().len());
+        assert!(mesh.diff_l2_norm(&decoded) < 1e-2);
+
+        // Not a permutation
+        let mut cfg = crate::encode::Config::default();
+        cfg.vertex_order = Some(vec![VertexIdx::from(0); num_vertices]);
+        let err = crate::encode::encode(mesh, &mut Vec::new(), cfg).unwrap_err();
+        assert!(matches!(err, crate::encode::Err::AttributeError(Err::InvalidVertexOrder(_))), "{:?}", err);
+    }
+
+    #[test]
+    fn test_wrapped_texture_coordinates() {
+        use crate::core::shared::{PointIdx, Vector};
+        let mut mesh = crate::io::obj::load_obj("tests/data/torus.obj").unwrap();
+        let pos = &mesh.get_attributes()[0];
+        let pos_id = pos.get_id();
+        // the angles around the two axes of the torus, tiling the texture about 8 times.
+        let tex_coords = (0..pos.len())
+            .map(|p| {
+                let v = pos.get::<NdVector<3, f32>, 3>(PointIdx::from(p));
+                let u = (v.get(1).atan2(*v.get(0)) / std::f32::consts::TAU).rem_euclid(1.0);
+                let r = (v.get(0).powi(2) + v.get(1).powi(2)).sqrt();
+                let w = (v.get(2).atan2(r - 1.0) / std::f32::consts::TAU).rem_euclid(1.0);
+                NdVector::from([u * 7.9, w * 7.9])
+            })
+            .collect::<Vec<_>>();
+        let id = AttributeId::new(mesh.get_attributes().len());
+        mesh.attributes.push(Attribute::from(id, tex_coords, AttributeType::TextureCoordinate, AttributeDomain::Position, vec![pos_id]));
+
+        // the largest error of the decoded texture coordinates, and the size of the stream.
+        let bits = 10;
+        let encode = |wrapped: bool, bits: u8| {
+            let mut cfg = crate::encode::Config::default();
+            cfg.wrapped_texture_coordinates = wrapped;
+            cfg.attribute_quantization.insert(AttributeType::TextureCoordinate, bits);
+            let mut buffer = Vec::new();
+            crate::encode::encode(mesh.clone(), &mut buffer, cfg).unwrap();
+            let decoded = crate::decode::decode(&mut crate::prelude::SliceReader::new(&buffer), crate::decode::Config::default()).unwrap();
+            let values = |mesh: &crate::core::mesh::Mesh| {
+                let (pos, tex) = (&mesh.get_attributes()[0], mesh.get_attributes().last().unwrap());
+                (0..pos.len())
+                    .map(PointIdx::from)
+                    .map(|p| (pos.get::<NdVector<3, f32>, 3>(p), tex.get::<NdVector<2, f32>, 2>(p)))
+                    .collect::<Vec<_>>()
+            };
+            let expected = values(&mesh);
+            let error = values(&decoded).into_iter()
+                .map(|(pos, tex)| {
+                    let (_, nearest) = expected.iter()
+                        .min_by(|(p, _), (q, _)| (*p - pos).norm().partial_cmp(&(*q - pos).norm()).unwrap())
+                        .unwrap();
+                    (0..2).map(|i| (nearest.get(i) - tex.get(i)).abs()).fold(0.0_f32, f32::max)
+                })
+                .fold(0.0_f32, f32::max);
+            (error, buffer.len())
+        };
+
+        // At the same bits, the wrapped quantization is as precise in the 8 tiles as the default one is in one tile,
+        // which costs about the 3 bits of each wrap count in the stream.
+        let (wrapped_error, wrapped_size) = encode(true, bits);
+        let (default_error, default_size) = encode(false, bits);
+        assert!(wrapped_error <= 0.5 / (1 << bits) as f32 + 1e-4, "wrapped error: {}", wrapped_error);
+        assert!(wrapped_error * 4.0 < default_error, "wrapped error: {}, default error: {}", wrapped_error, default_error);
+        assert!(default_size < wrapped_size, "wrapped: {} bytes, default: {} bytes", wrapped_size, default_size);
+        // At the bits of the same precision, the two are of about the same size.
+        let (precise_error, precise_size) = encode(false, bits + 3);
+        assert!(precise_error < 2.0 * wrapped_error, "wrapped error: {}, default error: {}", wrapped_error, precise_error);
+        assert!(wrapped_size < precise_size + precise_size / 10, "wrapped: {} bytes, default: {} bytes", wrapped_size, precise_size);
+
+        // the packed values of too many tiles do not fit in 'i32'.
+        for att in mesh.get_attributes_mut() {
+            if att.get_attribute_type() == AttributeType::TextureCoordinate {
+                att.unique_vals_as_slice_mut::<NdVector<2, f32>, 2>()[0] = NdVector::from([1.0e7, 0.5]);
+            }
+        }
+        let mut cfg = crate::encode::Config::default();
+        cfg.wrapped_texture_coordinates = true;
+        let err = crate::encode::encode(mesh, &mut Vec::new(), cfg).unwrap_err();
+        assert!(matches!(err, crate::encode::Err::AttributeError(Err::TooManyWraps(i, _)) if i == id), "{:?}", err);
+    }
+
+    #[test]
+    fn test_tiny_attribute_without_prediction() {
+        use crate::core::mesh::Mesh;
+        use crate::core::shared::{PointIdx, Vector};
+        let mut mesh = crate::io::obj::load_obj("tests/data/tetrahedron.obj").unwrap();
+        let num_points = mesh.get_attributes()[0].len();
+        // texture coordinates of two values, whose prediction from the positions carries the orientations of the
+        // corners as its metadata.
+        let tex_coords = (0..num_points)
+            .map(|p| if p % 2 == 0 { NdVector::from([0.25_f32, 0.5]) } else { NdVector::from([0.75, 0.5]) })
+            .collect::<Vec<_>>();
+        let id = AttributeId::new(mesh.get_attributes().len());
+        let pos_id = mesh.get_attributes()[0].get_id();
+        mesh.attributes.push(Attribute::from(id, tex_coords, AttributeType::TextureCoordinate, AttributeDomain::Position, vec![pos_id]));
+        assert_eq!(mesh.get_attributes().last().unwrap().num_unique_values(), 2);
+
+        // the ids of 'NoPrediction' and 'NoTransform', written in front of the attribute data.
+        let no_prediction = [PredictionSchemeType::NoPrediction.get_id(), PredictionTransformType::NoTransform.get_id()];
+        let count = |buffer: &[u8]| buffer.windows(2).filter(|w| *w == no_prediction).count();
+
+        let mut predicted = Vec::new();
+        let mut cfg = crate::encode::Config::default();
+        cfg.min_values_for_prediction = 0;
+        crate::encode::encode(mesh.clone(), &mut predicted, cfg).unwrap();
+        let mut tiny = Vec::new();
+        crate::encode::encode(mesh.clone(), &mut tiny, crate::encode::Config::default()).unwrap();
+        assert_eq!(count(&tiny), count(&predicted) + 1);
+        assert!(tiny.len() < predicted.len(), "without prediction: {} bytes, with prediction: {} bytes", tiny.len(), predicted.len());
+
+        // each decoded point has the position and the texture coordinates of a point of the input.
+        let decoded = crate::decode::decode(&mut crate::prelude::SliceReader::new(&tiny), crate::decode::Config::default()).unwrap();
+        let values_of = |mesh: &Mesh| {
+            let att_of = |ty| mesh.get_attributes().iter().find(|att| att.get_attribute_type() == ty).unwrap();
+            let (pos, tex_coord) = (att_of(AttributeType::Position), att_of(AttributeType::TextureCoordinate));
+            (0..pos.len())
+                .map(PointIdx::from)
+                .map(|p| (pos.get::<NdVector<3, f32>, 3>(p), tex_coord.get::<NdVector<2, f32>, 2>(p)))
+                .collect::<Vec<_>>()
+        };
+        let is_near = |a: &[f32], b: &[f32]| a.iter().zip(b).all(|(a, b)| (a - b).abs() < 1e-3);
+        let (values, decoded_values) = (values_of(&mesh), values_of(&decoded));
+        for (pos, tex_coord) in &decoded_values {
+            assert!(
+                values.iter().any(|(p, t)| is_near(&[*p.get(0), *p.get(1), *p.get(2)], &[*pos.get(0), *pos.get(1), *pos.get(2)])
+                    && is_near(&[*t.get(0), *t.get(1)], &[*tex_coord.get(0), *tex_coord.get(1)])),
+                "{:?} {:?} is not a point of the input", pos, tex_coord
+            );
+        }
+    }
+
+    #[test]
+    fn test_quantization_grid_is_shared_across_meshes() {
+        use crate::core::shared::{ConfigType, Vector};
+        use crate::shared::attribute::Portable;
+        use crate::encode::attribute::portabilization::{quantization_coordinate_wise::QuantizationCoordinateWise, PortabilizationImpl};
+
+        let a = crate::io::obj::load_obj("tests/data/sphere.obj").unwrap();
+        // the second mesh shares the lower half of the vertices, and the upper half is stretched,
+        // so the bounding boxes of the two meshes differ.
+        let mut b = a.clone();
+        for att in b.get_attributes_mut() {
+            if att.get_attribute_type() == AttributeType::Position {
+                for v in att.unique_vals_as_slice_mut::<NdVector<3, f32>, 3>() {
+                    if *v.get(2) > 0.0 {
+                        *v.get_mut(2) *= 1.5;
+                    }
+                }
+            }
+        }
+        let grid = QuantizationGrid::new([-2.0, -2.0, -2.0], 4.0, 14);
+
+        // the quantized integer of each position of the mesh, on the grid if given.
+        let quantize = |mesh: &crate::core::mesh::Mesh, grid: Option<&QuantizationGrid>| {
+            let mut att = mesh.get_attributes().iter().find(|att| att.get_attribute_type() == AttributeType::Position).unwrap().clone();
+            let mut port_cfg = portabilization::Config::default_for(AttributeType::Position);
+            if let Some(grid) = grid {
+                apply_quantization_grid(&mut att, grid).unwrap();
+                port_cfg.quantization_bits = grid.bits;
+            }
+            let values = att.unique_vals_as_slice::<NdVector<3, f32>, 3>().to_vec();
+            let port_att = QuantizationCoordinateWise::<NdVector<3, f32>, 3>::new(att, port_cfg, &mut Vec::new()).portabilize();
+            values.into_iter().enumerate()
+                .map(|(i, v)| {
+                    let q = port_att.get_unique_val::<NdVector<3, i32>, 3>(crate::core::shared::AttributeValueIdx::from(i));
+                    ([0, 1, 2].map(|j| v.get(j).to_bits()), [0, 1, 2].map(|j| *q.get(j)))
+                })
+                .collect::<std::collections::HashMap<_, _>>()
+        };
+        let shared = |qa: &std::collections::HashMap<[u32; 3], [i32; 3]>, qb: &std::collections::HashMap<[u32; 3], [i32; 3]>| {
+            qa.iter().filter_map(|(v, q)| qb.get(v).map(|r| (*q, *r))).collect::<Vec<_>>()
+        };
+        let (qa, qb) = (quantize(&a, Some(&grid)), quantize(&b, Some(&grid)));
+        let on_grid = shared(&qa, &qb);
+        assert!(on_grid.len() > qa.len() / 3);
+        assert!(on_grid.iter().all(|(q, r)| q == r));
+        // without the grid, the lattices follow the bounding boxes and do not match.
+        let (qa, qb) = (quantize(&a, None), quantize(&b, None));
+        assert!(shared(&qa, &qb).iter().any(|(q, r)| q != r));
+
+        // both streams carry the same quantization metadata of the positions.
+        let mut cfg = crate::encode::Config::default();
+        cfg.quantization_grid = Some(grid);
+        let mut metadata = Vec::new();
+        NdVector::from(grid.origin.map(|o| o as f32)).write_to(&mut metadata);
+        ((grid.origin[0] + grid.delta_max) as f32 - grid.origin[0] as f32).write_to(&mut metadata);
+        metadata.push(grid.bits);
+        for mesh in [a.clone(), b] {
+            let mut buffer = Vec::new();
+            crate::encode::encode(mesh, &mut buffer, cfg.clone()).unwrap();
+            assert!(buffer.windows(metadata.len()).any(|w| w == metadata.as_slice()));
+        }
+
+        // positions outside of the grid are rejected.
+        cfg.quantization_grid = Some(QuantizationGrid::new([0.0; 3], 1.0, 14));
+        let err = crate::encode::encode(a.clone(), &mut Vec::new(), cfg.clone()).unwrap_err();
+        assert!(matches!(err, crate::encode::Err::AttributeError(Err::OutsideQuantizationGrid(_))), "{:?}", err);
+
+        // so are the bits of the grid out of the range.
+        for bits in [0, 31, 40] {
+            cfg.quantization_grid = Some(QuantizationGrid::new([-2.0, -2.0, -2.0], 4.0, bits));
+            let err = crate::encode::encode(a.clone(), &mut Vec::new(), cfg.clone()).unwrap_err();
+            assert!(matches!(err, crate::encode::Err::AttributeError(Err::InvalidQuantizationBits(AttributeType::Position, b)) if b == bits), "{:?}", err);
+        }
+    }
+
+    #[test]
+    fn test_joint_prediction_of_correlated_attributes() {
+        let mut mesh = crate::io::obj::load_obj("tests/data/torus.obj").unwrap();
+        let pos = &mesh.get_attributes()[0];
+        assert_eq!(pos.get_attribute_type(), AttributeType::Position);
+        let pos_id = pos.get_id();
+        // a displacement that follows the position.
+        let displacements = (0..pos.len())
+            .map(|p| pos.get::<NdVector<3, f32>, 3>(crate::core::shared::PointIdx::from(p)) * 0.1 + NdVector::from([0.5, 0.5, 0.5]))
+            .collect::<Vec<_>>();
+        let id = AttributeId::new(mesh.get_attributes().len());
+        mesh.attributes.push(Attribute::from(id, displacements, AttributeType::Color, AttributeDomain::Position, Vec::new()));
+
+        let mut independent = Vec::new();
+        crate::encode::encode(mesh.clone(), &mut independent, crate::encode::Config::default()).unwrap();
+        let mut cfg = crate::encode::Config::default();
+        cfg.joint_prediction = vec![(pos_id, id)];
+        let mut joint = Vec::new();
+        crate::encode::encode(mesh.clone(), &mut joint, cfg).unwrap();
+        assert!(joint.len() < independent.len(), "joint: {} bytes, independent: {} bytes", joint.len(), independent.len());
+
+        // a child that is not affine in the position is restored as well, as the corrections make up for the prediction.
+        use crate::core::shared::Vector;
+        let values_of = |mesh: &crate::core::mesh::Mesh| {
+            let atts = mesh.get_attributes();
+            (0..atts[0].len())
+                .map(crate::core::shared::PointIdx::from)
+                .map(|p| (atts[0].get::<NdVector<3, f32>, 3>(p), atts[atts.len() - 1].get::<NdVector<3, f32>, 3>(p)))
+                .collect::<Vec<_>>()
+        };
+        let displacements = values_of(&mesh).into_iter()
+            .map(|(p, _)| NdVector::from([(*p.get(0) * 7.0).sin(), (*p.get(1) * 5.0).cos() * 0.5, *p.get(0) * *p.get(1)]))
+            .collect::<Vec<_>>();
+        mesh.attributes.pop();
+        mesh.attributes.push(Attribute::from(id, displacements, AttributeType::Color, AttributeDomain::Position, Vec::new()));
+        let mut cfg = crate::encode::Config::default();
+        cfg.joint_prediction = vec![(pos_id, id)];
+        let mut joint = Vec::new();
+        crate::encode::encode(mesh.clone(), &mut joint, cfg).unwrap();
+        let decoded = crate::decode::decode(&mut crate::prelude::SliceReader::new(&joint), crate::decode::Config::default()).unwrap();
+        let expected = values_of(&mesh);
+        for (pos, displacement) in values_of(&decoded) {
+            // the displacement of the input at the nearest position.
+            let (_, nearest) = expected.iter()
+                .min_by(|(p, _), (q, _)| (*p - pos).norm().partial_cmp(&(*q - pos).norm()).unwrap())
+                .unwrap();
+            assert!((*nearest - displacement).norm() < 1e-2, "{:?} != {:?}", displacement, nearest);
+        }
+
+        // the parent must be encoded before the child.
+        let mut cfg = crate::encode::Config::default();
+        cfg.joint_prediction = vec![(id, pos_id)];
+        let err = crate::encode::encode(mesh, &mut Vec::new(), cfg).unwrap_err();
+        assert!(matches!(err, crate::encode::Err::AttributeError(Err::InvalidJointPrediction(_))), "{:?}", err);
+    }
+
+    #[test]
+    fn test_color_palette() {
+        use crate::core::shared::{PointIdx, Vector};
+        use crate::prelude::SliceReader;
+
+        let mut mesh = crate::io::obj::load_obj("tests/data/sphere.obj").unwrap();
+        let num_points = mesh.get_attributes()[0].len();
+        let palette = [[1.0_f32, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0], [1.0, 1.0, 0.0]];
+        let colors = (0..num_points)
+            .map(|p| NdVector::from(palette[(p * 7 / 5) % 4]))
+            .collect::<Vec<_>>();
+        let id = AttributeId::new(mesh.get_attributes().len());
+        mesh.attributes.push(Attribute::from(id, colors, AttributeType::Color, AttributeDomain::Position, Vec::new()));
+        assert_eq!(mesh.get_attributes().last().unwrap().num_unique_values(), 4);
+
+        let mut cfg = crate::encode::Config::default();
+        cfg.color_palette_threshold = 4;
+        let mut with_palette = Vec::new();
+        crate::encode::encode(mesh.clone(), &mut with_palette, cfg.clone()).unwrap();
+        let mut default = Vec::new();
+        crate::encode::encode(mesh.clone(), &mut default, crate::encode::Config::default()).unwrap();
+        assert!(with_palette.len() < default.len(), "palette: {} bytes, default: {} bytes", with_palette.len(), default.len());
+
+        // the descriptor of the colors names the palette.
+        let mut descriptor = Vec::new();
+        AttributeType::Color.write_to(&mut descriptor);
+        ComponentDataType::F32.write_to(&mut descriptor);
+        descriptor.extend_from_slice(&[3, 0, id.as_usize() as u8]);
+        PortabilizationType::Palette.write_to(&mut descriptor);
+        assert!(with_palette.windows(descriptor.len()).any(|w| w == descriptor.as_slice()));
+        assert!(!default.windows(descriptor.len()).any(|w| w == descriptor.as_slice()));
+
+        // the colors are restored exactly.
+        let decoded = crate::decode::decode(&mut SliceReader::new(&with_palette), crate::decode::Config::default()).unwrap();
+        let color = decoded.get_attributes().iter().find(|att| att.get_attribute_type() == AttributeType::Color).unwrap();
+        let mut decoded_colors = (0..color.len())
+            .map(|p| {
+                let v = color.get::<NdVector<3, f32>, 3>(PointIdx::from(p));
+                [0, 1, 2].map(|i| *v.get(i))
+            })
+            .collect::<Vec<_>>();
+        decoded_colors.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        decoded_colors.dedup();
+        let mut expected = palette.to_vec();
+        expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(decoded_colors, expected);
+
+        // more colors than the threshold are not put in a palette.
+        cfg.color_palette_threshold = 3;
+        let mut without_palette = Vec::new();
+        crate::encode::encode(mesh, &mut without_palette, cfg).unwrap();
+        assert_eq!(without_palette, default);
+    }
+
+    #[test]
+    fn test_unsupported_num_components() {
+        let mut mesh = crate::io::obj::load_obj("tests/data/tetrahedron.obj").unwrap();
+        let num_points = mesh.get_attributes()[0].len();
+        let values = (0..num_points)
+            .map(|p| [0, 1, 2, 3, 4].map(|i| (p * 5 + i) as f32 / 7.0))
+            .collect::<Vec<_>>();
+        let bytes = values.iter().flatten().flat_map(|c| c.to_le_bytes()).collect::<Vec<_>>();
+        let id = AttributeId::new(mesh.get_attributes().len());
+        mesh.attributes.push(Attribute::from_bytes(id, bytes, ComponentDataType::F32, 5, AttributeType::Custom, AttributeDomain::Position));
+
+        // the five components fall back to the raw bits instead of the quantization.
+        let mut encoded = Vec::new();
+        crate::encode::encode(mesh.clone(), &mut encoded, crate::encode::Config::default()).unwrap();
+        let descriptor = [AttributeType::Custom.get_id(), ComponentDataType::F32.get_id(), 5, 0, id.as_usize() as u8, PortabilizationType::ToBits.get_id()];
+        assert!(encoded.windows(descriptor.len()).any(|w| w == descriptor));
+
+        // The raw bits restore each value exactly, in the order of the decoded points.
+        let decoded = crate::decode::decode(&mut crate::prelude::SliceReader::new(&encoded), crate::decode::Config::default()).unwrap();
+        let att = decoded.get_attributes().iter().find(|att| att.get_num_components() == 5).unwrap();
+        assert_eq!(att.get_component_type(), ComponentDataType::F32);
+        let mut decoded_values = (0..att.len())
+            .map(|p| usize::from(att.get_unique_val_idx(crate::core::shared::PointIdx::from(p))))
+            .map(|i| att.get_as_bytes(i).chunks(4).map(|c| f32::from_le_bytes(c.try_into().unwrap())).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let mut values = values.iter().map(|v| v.to_vec()).collect::<Vec<_>>();
+        decoded_values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(decoded_values, values);
+
+        // the fallback can be turned off.
+        let mut cfg = crate::encode::Config::default();
+        cfg.unsupported_components = UnsupportedComponents::Reject;
+        let err = crate::encode::encode(mesh, &mut Vec::new(), cfg).unwrap_err();
+        assert!(matches!(err, crate::encode::Err::AttributeError(Err::UnsupportedNumComponents(_))), "{}", err);
+    }
+
+    #[test]
+    fn test_integer_positions() {
+        use crate::core::shared::{PointIdx, Vector};
+        use crate::prelude::SliceReader;
+
+        let mut mesh = crate::io::obj::load_obj("tests/data/sphere.obj").unwrap();
+        mesh.scale(100.0);
+        for v in mesh.attributes[0].unique_vals_as_slice_mut::<NdVector<3, f32>, 3>() {
+            *v = NdVector::from([0, 1, 2].map(|i| v.get(i).round()));
+        }
+        let pos = &mesh.get_attributes()[0];
+        assert_eq!(pos.get_attribute_type(), AttributeType::Position);
+        let integers = pos.narrow_to_i32().unwrap();
+        assert_eq!(integers.get_component_type(), ComponentDataType::I32);
+        for i in 0..pos.num_unique_values() {
+            assert_eq!(unique_value_as_f64(&integers, i), unique_value_as_f64(pos, i));
+        }
+
+        let encode = |mesh: &crate::core::mesh::Mesh, integer_positions: bool| {
+            let mut cfg = crate::encode::Config::default();
+            cfg.integer_positions = integer_positions;
+            let mut buffer = Vec::new();
+            crate::encode::encode(mesh.clone(), &mut buffer, cfg).unwrap();
+            buffer
+        };
+        let decode = |buffer: &[u8]| crate::decode::decode(&mut SliceReader::new(buffer), crate::decode::Config::default()).unwrap();
+
+        // the integers are stored as they are, with no quantization metadata, and keep their float type.
+        let integer = encode(&mesh, true);
+        let descriptor = [
+            AttributeType::Position.get_id(), ComponentDataType::F32.get_id(), 3, 0, pos.get_id().as_usize() as u8, PortabilizationType::Integer.get_id()
+        ];
+        assert!(integer.windows(descriptor.len()).any(|w| w == descriptor));
+        let quantized = encode(&mesh, false);
+        assert!(integer.len() < quantized.len(), "integer: {} bytes, quantized: {} bytes", integer.len(), quantized.len());
+
+        // the positions are restored exactly, in the order of the decoded points.
+        let sorted_positions = |att: &Attribute| {
+            let mut out = (0..att.len())
+                .map(|p| {
+                    let v = att.get::<NdVector<3, f32>, 3>(PointIdx::from(p));
+                    [0, 1, 2].map(|i| *v.get(i))
+                })
+                .collect::<Vec<_>>();
+            out.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            out
+        };
+        let decoded = decode(&integer);
+        let decoded_pos = &decoded.get_attributes()[0];
+        assert_eq!(decoded_pos.get_component_type(), ComponentDataType::F32);
+        assert_eq!(sorted_positions(decoded_pos), sorted_positions(pos));
+
+        // the positions off the lattice are quantized as usual.
+        *mesh.attributes[0].unique_vals_as_slice_mut::<NdVector<3, f32>, 3>()[0].get_mut(0) += 0.5;
+        assert!(mesh.get_attributes()[0].narrow_to_i32().is_none());
+        assert_eq!(encode(&mesh, true), encode(&mesh, false));
+
+        // so are the positions of integer types, unless the option is set.
+        let mut mesh = mesh.clone();
+        mesh.attributes[0] = integers;
+        assert_eq!(encode(&mesh, true), encode(&mesh, false));
+    }
+
+    #[test]
+    fn test_signed_corrections() {
+        // the sphere is centered at the origin, and so are the corrections of its positions.
+        let mesh = crate::io::obj::load_obj("tests/data/sphere.obj").unwrap();
+        let pos_id = mesh.get_attributes()[0].get_id();
+        let encode = |signed_corrections: Vec<AttributeId>| {
+            let mut cfg = crate::encode::Config::default();
+            cfg.signed_corrections = signed_corrections;
+            let mut buffer = Vec::new();
+            crate::encode::encode(mesh.clone(), &mut buffer, cfg).unwrap();
+            buffer
+        };
+        let unsigned = encode(Vec::new());
+        let signed = encode(vec![pos_id]);
+        assert!(signed.len() < unsigned.len(), "signed: {} bytes, unsigned: {} bytes", signed.len(), unsigned.len());
+        // the corrections are the same, so are the decoded meshes.
+        let decode = |buffer: &[u8]| crate::decode::decode(&mut crate::prelude::SliceReader::new(buffer), crate::decode::Config::default()).unwrap();
+        assert!(decode(&signed).structurally_eq(&decode(&unsigned)));
+
+        // the attributes that are not selected are encoded as usual.
+        let other_id = AttributeId::new(mesh.get_attributes().len());
+        assert_eq!(encode(vec![other_id]), unsigned);
+    }
+
+    #[test]
+    #[cfg(feature = "parallel")]
+    fn test_parallel_encoding_is_serial_encoding() {
+        use crate::core::shared::Vector;
+        let mut mesh = crate::io::obj::load_obj("tests/data/sphere.obj").unwrap();
+        let pos = &mesh.get_attributes()[0];
+        let pos_id = pos.get_id();
+        let values = (0..pos.len())
+            .map(|p| pos.get::<NdVector<3, f32>, 3>(crate::core::shared::PointIdx::from(p)))
+            .collect::<Vec<_>>();
+        // two sets of texture coordinates and a color besides the positions and the normals.
+        for (ty, scale) in [(AttributeType::TextureCoordinate, 1.0), (AttributeType::TextureCoordinate, 3.0), (AttributeType::Color, 0.5)] {
+            let id = AttributeId::new(mesh.get_attributes().len());
+            let att = if ty == AttributeType::Color {
+                let colors = values.iter().map(|v| *v * scale + NdVector::from([0.5, 0.5, 0.5])).collect();
+                Attribute::from(id, colors, ty, AttributeDomain::Position, Vec::new())
+            } else {
+                let tex_coords = values.iter().map(|v| NdVector::from([*v.get(0), *v.get(1)]) * scale).collect();
+                Attribute::from(id, tex_coords, ty, AttributeDomain::Position, vec![pos_id])
+            };
+            mesh.attributes.push(att);
+        }
+
+        // a pool of one thread encodes the attributes one after another.
+        let encode_on = |num_threads: usize| {
+            let pool = rayon::ThreadPoolBuilder::new().num_threads(num_threads).build().unwrap();
+            pool.install(|| {
+                let mut buffer = Vec::new();
+                crate::encode::encode(mesh.clone(), &mut buffer, crate::encode::Config::default()).unwrap();
+                buffer
+            })
+        };
+        let serial = encode_on(1);
+        let parallel = encode_on(4);
+        assert_eq!(parallel, serial);
+    }
+
+    #[test]
+    fn test_two_texture_coordinate_sets() {
+        use crate::core::shared::{PointIdx, Vector};
+        let size = 30;
+        let p = |x: usize, y: usize| y * size + x;
+        let mut faces = Vec::new();
+        for y in 0..size-1 {
+            for x in 0..size-1 {
+                faces.push([p(x, y), p(x+1, y), p(x+1, y+1)]);
+                faces.push([p(x, y), p(x+1, y+1), p(x, y+1)]);
+            }
+        }
+        let positions = (0..size*size)
+            .map(|i| NdVector::from([(i % size) as f32, (i / size) as f32, ((i % size) as f32 * 0.2).sin()]))
+            .collect::<Vec<_>>();
+        // the first set spans the grid, and the second one is a rotated atlas of a quarter of the size.
+        let uv0 = (0..size*size)
+            .map(|i| NdVector::from([(i % size) as f32 / (size - 1) as f32, (i / size) as f32 / (size - 1) as f32]))
+            .collect::<Vec<_>>();
+        let uv1 = uv0.iter()
+            .map(|v| NdVector::from([0.5 + 0.25 * v.get(1), 0.25 - 0.25 * v.get(0)]))
+            .collect::<Vec<_>>();
+        let mut builder = crate::prelude::MeshBuilder::new();
+        builder.set_connectivity_attribute(faces);
+        let pos_id = builder.add_attribute(positions, AttributeType::Position, AttributeDomain::Position, vec![]);
+        builder.add_attribute(uv0.clone(), AttributeType::TextureCoordinate, AttributeDomain::Position, vec![pos_id]);
+        builder.add_attribute(uv1.clone(), AttributeType::TextureCoordinate, AttributeDomain::Position, vec![pos_id]);
+        let mesh = builder.build().unwrap();
+
+        let mut buffer = Vec::new();
+        crate::encode::encode(mesh, &mut buffer, crate::encode::Config::default()).unwrap();
+        let decoded = crate::decode::decode(&mut crate::prelude::SliceReader::new(&buffer), crate::decode::Config::default()).unwrap();
+        let [pos, tex0, tex1] = [0, 1, 2].map(|i| &decoded.get_attributes()[i]);
+        assert_eq!(tex0.get_attribute_type(), AttributeType::TextureCoordinate);
+        assert_eq!(tex1.get_attribute_type(), AttributeType::TextureCoordinate);
+        // the decoder renumbers the points, so they are matched by their positions.
+        for q in (0..pos.len()).map(PointIdx::from) {
+            let v: NdVector<3, f32> = pos.get(q);
+            let i = p(v.get(0).round() as usize, v.get(1).round() as usize);
+            // the texture coordinates are quantized with 10 bits.
+            for (tex, expected) in [(tex0, uv0[i]), (tex1, uv1[i])] {
+                let uv: NdVector<2, f32> = tex.get(q);
+                assert!((0..2).all(|j| (uv.get(j) - expected.get(j)).abs() < 2e-3), "{:?} != {:?}", uv, expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use crate::core::shared::{AttributeValueIdx, DataValue};
+use crate::core::shared::Vector;
+use crate::prelude::Attribute;
+use crate::prelude::ByteWriter;
+use crate::prelude::NdVector;
+use crate::shared::attribute::Portable;
+
+#[cfg(feature = "evaluation")]
+use crate::eval;
+
+use super::Config;
+use super::PortabilizationImpl;
+
+/// Keeps the values of integers as they are, only widening their components to i32, so that they are restored
+/// exactly, e.g. the indices of the joints. The components of u32 above 'i32::MAX' are stored as their bits.
+pub struct Integer<Data, const N: usize>
+    where Data: Vector<N>
+{
+    att: Attribute,
+
+    _marker: std::marker::PhantomData<Data>,
+}
+
+impl<Data, const N: usize> Integer<Data, N>
+    where
+        Data: Vector<N> + Portable,
+        Data::Component: DataValue
+{
+    pub fn new<W>(att: Attribute, _cfg: Config, _writer: &mut W) -> Self
+        where W: ByteWriter
+    {
+        #[cfg(feature = "evaluation")]
+        eval::write_json_pair("portabilization", "Integer".into(), _writer);
+        Self {
+            att,
+            _marker: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<Data, const N: usize> PortabilizationImpl<N> for Integer<Data, N>
+    where
+        Data: Vector<N> + Portable,
+        Data::Component: DataValue,
+        NdVector<N, i32>: Vector<N, Component = i32>,
+{
+    fn portabilize(self) -> Attribute {
+        let values = (0..self.att.num_unique_values())
+            .map(|i| {
+                let v = self.att.get_unique_val::<Data, N>(AttributeValueIdx::from(i));
+                let mut out = NdVector::<N, i32>::zero();
+                for j in 0..N {
+                    *out.get_mut(j) = v.get(j).to_i64() as i32;
+                }
+                out
+            })
+            .collect::<Vec<_>>();
+        let mut port_att = Attribute::from_without_removing_duplicates(
+            self.att.get_id(),
+            values,
+            self.att.get_attribute_type(),
+            self.att.get_domain(),
+            self.att.get_parents().clone()
+        );
+        port_att.set_point_to_att_val_map(self.att.take_point_to_att_val_map());
+        port_att
+    }
+}
+
+
+#[cfg(all(test, not(feature = "evaluation")))]
+mod tests {
+    use crate::core::attribute::{AttributeDomain, AttributeId};
+    use crate::core::mesh::Mesh;
+    use crate::core::shared::{ConfigType, NdVector, PointIdx, Vector};
+    use crate::prelude::{Attribute, AttributeType, SliceReader};
+
+    #[test]
+    fn test_u16_joints_are_exact() {
+        let mut mesh = crate::io::obj::load_obj("tests/data/sphere.obj").unwrap();
+        let num_points = mesh.get_attributes()[0].len();
+        let joints = (0..num_points)
+            .map(|p| NdVector::from([0, 1, 2, 3].map(|i| ((p * 4 + i) * 517 % 65_536) as u16)))
+            .collect::<Vec<_>>();
+        let id = AttributeId::new(mesh.get_attributes().len());
+        mesh.attributes.push(Attribute::from_without_removing_duplicates(
+            id, joints, AttributeType::Joint, AttributeDomain::Position, Vec::new()
+        ));
+
+        let mut buffer = Vec::new();
+        crate::encode::encode(mesh.clone(), &mut buffer, crate::encode::Config::default()).unwrap();
+        let decoded = crate::decode::decode(&mut SliceReader::new(&buffer), crate::decode::Config::default()).unwrap();
+
+        let points_of = |mesh: &Mesh| {
+            let att_of = |ty| mesh.get_attributes().iter().find(|att| att.get_attribute_type() == ty).unwrap();
+            let (pos, joint) = (att_of(AttributeType::Position), att_of(AttributeType::Joint));
+            (0..pos.len())
+                .map(PointIdx::from)
+                .map(|p| (pos.get::<NdVector<3, f32>, 3>(p), joint.get::<NdVector<4, u16>, 4>(p)))
+                .collect::<Vec<_>>()
+        };
+        let points = points_of(&mesh);
+        let decoded = points_of(&decoded);
+        assert_eq!(decoded.len(), points.len());
+        // the joints of each decoded point are those of the nearest point of the input.
+        for (pos, joint) in decoded {
+            let distance = |p: &NdVector<3, f32>| (0..3).map(|i| (p.get(i) - pos.get(i)).abs()).fold(0.0_f32, f32::max);
+            let (_, expected) = points.iter()
+                .min_by(|(a, _), (b, _)| distance(a).partial_cmp(&distance(b)).unwrap())
+                .unwrap();
+            assert_eq!(&joint, expected);
+        }
+    }
+}
@@ -1,9 +1,12 @@
 pub mod quantization_coordinate_wise;
+pub mod integer;
 pub mod octahedral_quantization;
+pub mod palette;
 pub mod to_bits;
+pub mod wrapped_quantization;
 
+use crate::core::attribute::ComponentDataType;
 use crate::core::shared::{ConfigType, Vector};
-use crate::debug_write;
 use crate::core::bit_coder::ByteWriter;
 use crate::prelude::{Attribute, AttributeType, NdVector};
 use crate::shared::attribute::Portable;
@@ -12,8 +15,11 @@ pub enum Portabilization<Data, const N: usize>
     where Data: Vector<N> + Portable
 {
     QuantizationCoordinateWise(quantization_coordinate_wise::QuantizationCoordinateWise<Data, N>),
+    Integer(integer::Integer<Data, N>),
     OctahedralQuantization(octahedral_quantization::OctahedralQuantization<Data, N>),
+    Palette(palette::Palette<Data, N>),
     ToBits(to_bits::ToBits<Data, N>),
+    WrappedQuantization(wrapped_quantization::WrappedQuantization<Data, N>),
 }
 
 impl<Data, const N: usize> Portabilization<Data, N> 
@@ -29,7 +35,6 @@ impl<Data, const N: usize> Portabilization<Data, N>
     pub fn new<W>(att: Attribute, cfg: Config, writer: &mut W) -> Self
         where W: ByteWriter
     {
-        debug_write!("Start of Portabilization Metadata", writer);
         // cfg.type_.write_to(writer);
         let out = match cfg.type_ {
             PortabilizationType::QuantizationCoordinateWise => {
@@ -42,24 +47,38 @@ impl<Data, const N: usize> Portabilization<Data, N>
                     octahedral_quantization::OctahedralQuantization::new(att, cfg, writer)
                 )
             },
+            PortabilizationType::Palette => {
+                Portabilization::Palette(
+                    palette::Palette::new(att, cfg, writer)
+                )
+            },
             PortabilizationType::ToBits => {
                 Portabilization::ToBits(
                     to_bits::ToBits::new(att, cfg, writer)
                 )
             },
+            PortabilizationType::WrappedQuantization => {
+                Portabilization::WrappedQuantization(
+                    wrapped_quantization::WrappedQuantization::new(att, cfg, writer)
+                )
+            },
             PortabilizationType::Integer => {
-                unimplemented!("Integer portabilization is not implemented yet.")
+                Portabilization::Integer(
+                    integer::Integer::new(att, cfg, writer)
+                )
             },
         };
-        debug_write!("End of Portabilization Metadata", writer);
         out
     }
 
     pub fn portabilize(self) -> Attribute {
         match self {
             Portabilization::QuantizationCoordinateWise(qcw) => qcw.portabilize(),
+            Portabilization::Integer(int) => int.portabilize(),
             Portabilization::OctahedralQuantization(oct) => oct.portabilize(),
+            Portabilization::Palette(palette) => palette.portabilize(),
             Portabilization::ToBits(tb) => tb.portabilize(),
+            Portabilization::WrappedQuantization(wq) => wq.portabilize(),
         }
     }
 }
@@ -76,18 +95,36 @@ pub trait PortabilizationImpl<const N: usize>
 pub enum PortabilizationType {
     QuantizationCoordinateWise,
     OctahedralQuantization,
-    #[allow(dead_code)]
+    /// Keeps the values of integers as they are. Used for the indices of the joints.
     Integer,
     ToBits,
+    /// Quantizes the wrap count and the fractional part of each component separately.
+    /// Used for the texture coordinates of tiled textures.
+    WrappedQuantization,
+    /// Replaces the values by their indices in the palette of the distinct values.
+    /// Used for the colors of a mesh painted with a few colors.
+    Palette,
 }
 
+crate::utils::enum_str::impl_enum_str!(PortabilizationType, "portabilization", {
+    QuantizationCoordinateWise => ["quantization"],
+    OctahedralQuantization => ["octahedral_quantization"],
+    Integer => ["integer"],
+    ToBits => ["to_bits"],
+    WrappedQuantization => ["wrapped_quantization"],
+    Palette => ["palette"],
+});
+
 impl PortabilizationType {
     pub(crate) fn get_id(&self) -> u8 {
         match self {
             PortabilizationType::ToBits => 1,
-            PortabilizationType::Integer => 1, // Integer is not used in the current implementation, but kept for compatibility.
+            // The integers are decoded as the values of ToBits, as in Draco.
+            PortabilizationType::Integer => 1,
             PortabilizationType::QuantizationCoordinateWise => 2,
             PortabilizationType::OctahedralQuantization => 3,
+            PortabilizationType::WrappedQuantization => 4,
+            PortabilizationType::Palette => 5,
         }
     }
 
@@ -98,6 +135,18 @@ impl PortabilizationType {
         writer.write_u8(id);
     }
 
+    /// Inverse of [PortabilizationType::get_id], or 'None' if the id is unknown.
+    pub(crate) fn from_id(id: u8) -> Option<Self> {
+        match id {
+            1 => Some(PortabilizationType::ToBits),
+            2 => Some(PortabilizationType::QuantizationCoordinateWise),
+            3 => Some(PortabilizationType::OctahedralQuantization),
+            4 => Some(PortabilizationType::WrappedQuantization),
+            5 => Some(PortabilizationType::Palette),
+            _ => None,
+        }
+    }
+
     pub(crate) fn default_for(ty: AttributeType) -> Self {
         match ty {
             AttributeType::Normal => PortabilizationType::OctahedralQuantization,
@@ -105,6 +154,18 @@ impl PortabilizationType {
             _ => PortabilizationType::QuantizationCoordinateWise, // default
         }
     }
+
+    /// Same as [PortabilizationType::default_for], but the joints, the weights and the custom attributes of integers
+    /// of at most 32 bits are kept as they are by [PortabilizationType::Integer] instead of being quantized.
+    /// The custom attributes of integers are restored exactly either way, but [PortabilizationType::ToBits] only
+    /// takes the components of 32 bits.
+    pub(crate) fn default_for_component(ty: AttributeType, component_type: ComponentDataType) -> Self {
+        let is_integer = !component_type.is_float() && component_type.size() <= 4;
+        match ty {
+            AttributeType::Joint | AttributeType::Weight | AttributeType::Custom if is_integer => PortabilizationType::Integer,
+            _ => Self::default_for(ty),
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -113,6 +174,37 @@ pub struct Config {
     pub quantization_bits: u8,
 }
 
+/// A fixed lattice on which the positions are quantized, instead of the bounding box of each mesh.
+/// Meshes encoded with the same grid quantize the same coordinates to the same integers,
+/// e.g. so that the shared borders of the tiles of a terrain match exactly after decoding.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct QuantizationGrid {
+    /// The corner of the grid, which is quantized to zero.
+    pub origin: [f64; 3],
+    /// The extent of the grid along each axis. All the positions must lie within
+    /// '[origin, origin + delta_max]'.
+    pub delta_max: f64,
+    /// The number of bits of each quantized coordinate, in '1..=30'.
+    pub bits: u8,
+}
+
+impl QuantizationGrid {
+    pub fn new(origin: [f64; 3], delta_max: f64, bits: u8) -> Self {
+        Self { origin, delta_max, bits }
+    }
+
+    /// The minimum and maximum of each coordinate covered by the grid.
+    pub(crate) fn bounds(&self) -> ([f64; 3], [f64; 3]) {
+        (self.origin, self.origin.map(|o| o + self.delta_max))
+    }
+
+    /// Returns true if the point lies within the grid.
+    pub(crate) fn contains(&self, point: &[f64]) -> bool {
+        let (min, max) = self.bounds();
+        point.iter().enumerate().all(|(i, &v)| min[i] <= v && v <= max[i])
+    }
+}
+
 impl ConfigType for Config {
     fn default()-> Self {
         Config {
@@ -1,7 +1,6 @@
 use crate::core::shared::AttributeValueIdx;
-use crate::core::shared::Vector;
-use crate::encode::attribute::prediction_transform::geom::into_faithful_oct_quantization;
-use crate::encode::attribute::prediction_transform::geom::octahedral_transform;
+use crate::core::shared::{DataValue, Vector};
+use crate::encode::attribute::prediction_transform::geom::{float_vector_to_oct_quantization, oct_max_value};
 use crate::prelude::Attribute;
 use crate::prelude::AttributeType;
 use crate::prelude::ByteWriter;
@@ -32,8 +31,8 @@ impl<Data, const N: usize> OctahedralQuantization<Data, N>
         where W: ByteWriter
     {
         assert!(
-            att.get_attribute_type()==AttributeType::Normal, 
-            "Octahedral quantization can only be applied to normal attributes."
+            att.get_attribute_type()==AttributeType::Normal || att.get_attribute_type()==AttributeType::Tangent, 
+            "Octahedral quantization can only be applied to normal or tangent attributes."
         );
 
         // encode the quantization bits.
@@ -46,21 +45,12 @@ impl<Data, const N: usize> OctahedralQuantization<Data, N>
         }
     }
 
+    /// Quantizes the value to the octahedral coordinates exactly as Draco does, so that the decoders of Draco
+    /// (e.g. the 'DRACOLoader' of three.js) restore the same normals.
     fn portabilize_value(&mut self, val: Data) -> NdVector<2, i32> {
-        let val_oct = octahedral_transform(val) + NdVector::<2, f32>::from([1.0,1.0]);
-        debug_assert!(
-            *val_oct.get(0) >= 0.0 && *val_oct.get(0) <= 2.0 &&
-            *val_oct.get(1) >= 0.0 && *val_oct.get(1) <= 2.0,
-            "Octahedral transformed value out of bounds: {:?}",
-            val_oct
-        );
-        let quantized = val_oct * ((1<<self.quantization_bits-1)-1) as f32;
-        let mut out = NdVector::<2, i32>::zero();
-        for i in 0..2 {
-            *out.get_mut(i) = *quantized.get(i) as i32;
-        }
-        let out = into_faithful_oct_quantization(out);
-        out
+        assert!(N==3, "Octahedral quantization requires three dimensional data.");
+        let v = [0, 1, 2].map(|i| val.get(i).to_f64());
+        float_vector_to_oct_quantization(v, oct_max_value(self.quantization_bits))
     }
 }
 
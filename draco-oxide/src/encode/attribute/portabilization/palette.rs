@@ -0,0 +1,125 @@
+use std::collections::HashMap;
+
+use crate::core::shared::Vector;
+use crate::prelude::{Attribute, ByteWriter, NdVector};
+use crate::shared::attribute::Portable;
+
+#[cfg(feature = "evaluation")]
+use crate::eval;
+
+use super::{Config, PortabilizationImpl};
+
+/// Replaces each value by its index in the palette of the distinct values, e.g. for the vertex colors
+/// of a mesh painted with a few colors. The palette is written once as the raw bytes of its values,
+/// so the values are restored exactly, and only the indices are predicted and entropy coded.
+pub(crate) struct Palette<Data, const N: usize>
+    where Data: Vector<N>
+{
+    att: Attribute,
+    /// The index in the palette of each unique value of the attribute.
+    indices: Vec<i32>,
+    _phantom: std::marker::PhantomData<Data>,
+}
+
+impl<Data, const N: usize> Palette<Data, N>
+    where Data: Vector<N> + Portable,
+{
+    pub fn new<W>(att: Attribute, _cfg: Config, writer: &mut W) -> Self
+        where W: ByteWriter
+    {
+        #[cfg(feature = "evaluation")]
+        eval::write_json_pair("portabilization", "Palette".into(), writer);
+
+        let mut palette: HashMap<&[u8], i32> = HashMap::new();
+        let mut entries = Vec::new();
+        let indices = (0..att.num_unique_values())
+            .map(|i| {
+                let bytes = att.get_as_bytes(i);
+                *palette.entry(bytes).or_insert_with(|| {
+                    entries.push(bytes);
+                    entries.len() as i32 - 1
+                })
+            })
+            .collect();
+
+        // write metadata
+        writer.write_u32(entries.len() as u32);
+        for bytes in entries {
+            for &b in bytes {
+                writer.write_u8(b);
+            }
+        }
+
+        Self {
+            att,
+            indices,
+            _phantom: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<Data, const N: usize> PortabilizationImpl<N> for Palette<Data, N>
+    where
+        NdVector<N, i32>: Vector<N, Component = i32>,
+        Data: Vector<N> + Portable,
+{
+    fn portabilize(self) -> Attribute {
+        let out = self.indices.iter()
+            .map(|&i| NdVector::from([i]))
+            .collect::<Vec<_>>();
+        let mut port_att = Attribute::from_without_removing_duplicates(
+            self.att.get_id(),
+            out,
+            self.att.get_attribute_type(),
+            self.att.get_domain(),
+            self.att.get_parents().clone()
+        );
+        port_att.set_point_to_att_val_map(self.att.take_point_to_att_val_map());
+        port_att
+    }
+}
+
+/// Inverse of [Palette]. Restores the bytes of a value from its index in the palette.
+pub(crate) fn lookup(palette: &[u8], value_size: usize, index: i32) -> Option<&[u8]> {
+    let start = usize::try_from(index).ok()?.checked_mul(value_size)?;
+    palette.get(start..start + value_size)
+}
+
+
+// The evaluation output is written with the metadata.
+#[cfg(all(test, not(feature = "evaluation")))]
+mod tests {
+    use super::*;
+    use crate::core::attribute::AttributeDomain;
+    use crate::core::shared::{ConfigType, PointIdx};
+    use crate::encode::attribute::portabilization::PortabilizationType;
+    use crate::prelude::{AttributeType, ByteReader, SliceReader};
+
+    #[test]
+    fn test_palette_round_trip() {
+        let colors = [[255_u8, 0, 0], [0, 255, 0], [0, 0, 255]];
+        let values = (0..30)
+            .map(|i| NdVector::from(colors[i * 7 % 3]))
+            .collect::<Vec<_>>();
+        let att = Attribute::from_without_removing_duplicates(
+            crate::core::attribute::AttributeId::new(0), values.clone(), AttributeType::Color, AttributeDomain::Position, Vec::new()
+        );
+
+        let cfg = Config { type_: PortabilizationType::Palette, ..Config::default() };
+        let mut metadata = Vec::new();
+        let port_att = Palette::<NdVector<3, u8>, 3>::new(att, cfg, &mut metadata).portabilize();
+        assert_eq!(port_att.get_num_components(), 1);
+
+        let mut reader = SliceReader::new(&metadata);
+        let size = reader.read_u32().unwrap() as usize;
+        assert_eq!(size, 3);
+        let palette = (0..3 * size).map(|_| reader.read_u8().unwrap()).collect::<Vec<_>>();
+        for (p, v) in values.iter().enumerate() {
+            let index = port_att.get::<NdVector<1, i32>, 1>(PointIdx::from(p));
+            let bytes = lookup(&palette, 3, *index.get(0)).unwrap();
+            assert_eq!(bytes, [0, 1, 2].map(|i| *v.get(i)));
+        }
+        assert!(lookup(&palette, 3, 3).is_none());
+        assert!(lookup(&palette, 3, -1).is_none());
+    }
+}
@@ -25,25 +25,20 @@ impl<Data, const N: usize> QuantizationCoordinateWise<Data, N>
     where
         W: ByteWriter,
     {
-        let mut min_values = NdVector::<N,f32>::zero();
-        for val in att.unique_vals_as_slice::<Data>() {
-            for i in 0..N {
-                let component = val.get(i).to_f64() as f32;
-                if component < *min_values.get(i) {
-                    *min_values.get_mut(i) = component;
-                }
-            }
-        }
-
-        let mut max_values = NdVector::<N,f32>::zero();
-        for val in att.unique_vals_as_slice::<Data>() {
-            for i in 0..N {
-                let component = val.get(i).to_f64() as f32;
-                if component > *max_values.get(i) {
-                    *max_values.get_mut(i) = component;
-                }
-            }
-        }
+        let (min_values, max_values) = if let Some((min, max)) = att.get_value_bounds() {
+            let min_values = NdVector::<N,f32>::from(std::array::from_fn(|i| min[i] as f32));
+            let max_values = NdVector::<N,f32>::from(std::array::from_fn(|i| max[i] as f32));
+            debug_assert!(
+                att.unique_vals_as_slice::<Data, N>().iter().all(|val| (0..N).all(|i| {
+                    let component = val.get(i).to_f64() as f32;
+                    *min_values.get(i) <= component && component <= *max_values.get(i)
+                })),
+                "The values of the attribute do not lie within the bounds set by 'set_value_bounds'."
+            );
+            (min_values, max_values)
+        } else {
+            Self::scan_bounds(&att)
+        };
 
         let mut delta_max = 0.0;
         for i in 0..N {
@@ -67,6 +62,33 @@ impl<Data, const N: usize> QuantizationCoordinateWise<Data, N>
         }
     }
 
+    /// Scans the values for the minimum and maximum of each component.
+    fn scan_bounds(att: &Attribute) -> (NdVector<N, f32>, NdVector<N, f32>) {
+        #[cfg(test)]
+        tests::NUM_SCANS.with(|n| n.set(n.get() + 1));
+
+        let mut min_values = NdVector::<N,f32>::zero();
+        for val in att.unique_vals_as_slice::<Data, N>() {
+            for i in 0..N {
+                let component = val.get(i).to_f64() as f32;
+                if component < *min_values.get(i) {
+                    *min_values.get_mut(i) = component;
+                }
+            }
+        }
+
+        let mut max_values = NdVector::<N,f32>::zero();
+        for val in att.unique_vals_as_slice::<Data, N>() {
+            for i in 0..N {
+                let component = val.get(i).to_f64() as f32;
+                if component > *max_values.get(i) {
+                    *max_values.get_mut(i) = component;
+                }
+            }
+        }
+        (min_values, max_values)
+    }
+
     fn portabilize_value(&mut self, val: Data) -> NdVector<N, i32> {
         // convert value to float vector TODO: implement the vector conversion so that this will be one line
         let val: NdVector<N, f32> = {
@@ -115,4 +137,54 @@ impl<Data, const N: usize> PortabilizationImpl<N> for QuantizationCoordinateWise
         port_att.set_point_to_att_val_map(self.att.take_point_to_att_val_map());
         port_att
     }
-}
\ No newline at end of file
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use super::*;
+    use crate::core::attribute::AttributeDomain;
+    use crate::core::shared::{ConfigType, PointIdx};
+    use crate::prelude::AttributeType;
+
+    thread_local! {
+        /// Number of times the values are scanned for their bounds on this thread.
+        pub(super) static NUM_SCANS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    fn quantize(att: Attribute) -> (Attribute, usize) {
+        let before = NUM_SCANS.with(|n| n.get());
+        let mut buffer = Vec::new();
+        let q = QuantizationCoordinateWise::<NdVector<3, f32>, 3>::new(att, Config::default(), &mut buffer);
+        let num_scans = NUM_SCANS.with(|n| n.get()) - before;
+        (q.portabilize(), num_scans)
+    }
+
+    #[test]
+    fn test_value_bounds_skip_scan() {
+        let values = (0..100)
+            .map(|i| {
+                let t = i as f32 * 0.1;
+                NdVector::from([t.cos() * 2.0, t.sin(), t - 5.0])
+            })
+            .collect::<Vec<_>>();
+        let att = Attribute::new(values, AttributeType::Position, AttributeDomain::Position, Vec::new());
+        let (min, max) = QuantizationCoordinateWise::<NdVector<3, f32>, 3>::scan_bounds(&att);
+
+        let (scanned, num_scans) = quantize(att.clone());
+        assert_eq!(num_scans, 1);
+
+        let mut with_bounds = att;
+        with_bounds.set_value_bounds(
+            &[0, 1, 2].map(|i| *min.get(i) as f64),
+            &[0, 1, 2].map(|i| *max.get(i) as f64),
+        );
+        let (quantized, num_scans) = quantize(with_bounds);
+        assert_eq!(num_scans, 0);
+        for p in 0..scanned.len() {
+            let p = PointIdx::from(p);
+            assert_eq!(quantized.get::<NdVector<3, i32>, 3>(p), scanned.get::<NdVector<3, i32>, 3>(p));
+        }
+    }
+}
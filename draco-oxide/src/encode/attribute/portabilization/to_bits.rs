@@ -47,4 +47,125 @@ impl<Data, const N: usize> PortabilizationImpl<N> for ToBits<Data, N>
     fn portabilize(self) -> Attribute {
         self.att
     }
-}
\ No newline at end of file
+}
+
+/// Splits the bytes of a value into 32-bit little-endian words, padding the last word with zeros.
+pub(crate) fn bits_to_symbols(bytes: &[u8]) -> impl Iterator<Item = u64> + '_ {
+    bytes.chunks(4).map(|word| {
+        let mut buf = [0_u8; 4];
+        buf[..word.len()].copy_from_slice(word);
+        u32::from_le_bytes(buf) as u64
+    })
+}
+
+/// Inverse of [bits_to_symbols]. Restores the 'value_size' bytes of a value from its 32-bit words.
+pub(crate) fn symbols_to_bits(symbols: &[u64], value_size: usize) -> Vec<u8> {
+    let mut out = symbols.iter()
+        .flat_map(|&s| (s as u32).to_le_bytes())
+        .collect::<Vec<_>>();
+    out.truncate(value_size);
+    out
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::attribute::{AttributeDomain, ComponentDataType};
+    use crate::core::shared::{ConfigType, PointIdx};
+    use crate::prelude::AttributeType;
+
+    fn f64_positions(mesh: &crate::core::mesh::Mesh) -> Vec<NdVector<3, f64>> {
+        let pos = &mesh.get_attributes()[0];
+        assert_eq!(pos.get_attribute_type(), AttributeType::Position);
+        (0..pos.len())
+            .map(|p| {
+                let v: NdVector<3, f32> = pos.get(PointIdx::from(p));
+                // add low bits that the f32 values do not have.
+                NdVector::from([0, 1, 2].map(|i| *v.get(i) as f64 + 1e-12 * (p * 3 + i) as f64))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_lossless_f64_positions_are_bit_exact() {
+        let mut mesh = crate::io::obj::load_obj("tests/data/sphere.obj").unwrap();
+        let positions = f64_positions(&mesh);
+        mesh.attributes[0] = Attribute::from(
+            mesh.attributes[0].get_id(), positions.clone(), AttributeType::Position, AttributeDomain::Position, Vec::new()
+        );
+        assert_eq!(mesh.get_attributes()[0].get_component_type(), ComponentDataType::F64);
+
+        let mut cfg = crate::encode::Config::default();
+        cfg.lossless = true;
+        let mut lossless = Vec::new();
+        crate::encode::encode(mesh.clone(), &mut lossless, cfg).unwrap();
+        let mut lossy = Vec::new();
+        crate::encode::encode(mesh, &mut lossy, crate::encode::Config::default()).unwrap();
+        assert_ne!(lossless, lossy);
+
+        // The decoder restores the bits of every value; the points may be reordered by the connectivity.
+        #[cfg(not(feature = "evaluation"))]
+        {
+            let decoded = crate::decode::decode(
+                &mut crate::prelude::SliceReader::new(&lossless), crate::decode::Config::default()
+            ).unwrap();
+            let pos = &decoded.get_attributes()[0];
+            assert_eq!(pos.get_component_type(), ComponentDataType::F64);
+            let bits = |values: &mut dyn Iterator<Item = NdVector<3, f64>>| {
+                let mut bits = values.map(|v| [0, 1, 2].map(|i| v.get(i).to_bits())).collect::<Vec<_>>();
+                bits.sort();
+                bits
+            };
+            let decoded_bits = bits(&mut (0..pos.len()).map(|p| pos.get::<NdVector<3, f64>, 3>(PointIdx::from(p))));
+            assert_eq!(decoded_bits, bits(&mut positions.into_iter()));
+        }
+    }
+
+    #[test]
+    fn test_inverse_bind_matrices_are_bit_exact() {
+        let mut mesh = crate::io::obj::load_obj("tests/data/tetrahedron.obj").unwrap();
+        let num_points = mesh.get_attributes()[0].len();
+        let matrices = (0..num_points)
+            .map(|p| [0, 1, 2, 3].map(|r| [0, 1, 2, 3].map(|c| if r == c { 1.0 } else { (p * 16 + r * 4 + c) as f32 / 3.0 })))
+            .collect::<Vec<_>>();
+        let id = crate::core::attribute::AttributeId::new(mesh.get_attributes().len());
+        let att = Attribute::from_row_major_matrices(id, &matrices, AttributeDomain::Position);
+        assert_eq!(att.get_attribute_type(), AttributeType::Custom);
+        assert_eq!(att.get_num_components(), 16);
+        assert_eq!(att.to_row_major_matrices().unwrap(), matrices);
+        mesh.attributes.push(att);
+
+        let encode = |mesh: crate::core::mesh::Mesh| {
+            let mut buffer = Vec::new();
+            crate::encode::encode(mesh, &mut buffer, crate::encode::Config::default()).unwrap();
+            buffer
+        };
+        let encoded = encode(mesh.clone());
+        // the descriptor of the matrices: the type, the component type, and the number of components.
+        let descriptor = [AttributeType::Custom.get_id(), ComponentDataType::F32.get_id(), 16];
+        assert!(encoded.windows(3).any(|w| w == descriptor));
+
+        // a change of the last bit of an entry is kept in the stream.
+        let mut nudged = matrices.clone();
+        nudged[0][0][1] = f32::from_bits(nudged[0][0][1].to_bits() + 1);
+        let last = mesh.attributes.len() - 1;
+        mesh.attributes[last] = Attribute::from_row_major_matrices(id, &nudged, AttributeDomain::Position);
+        assert_ne!(encode(mesh), encoded);
+
+        // The decoder restores the bits of every matrix; the points may be reordered by the connectivity.
+        #[cfg(not(feature = "evaluation"))]
+        {
+            let decoded = crate::decode::decode(
+                &mut crate::prelude::SliceReader::new(&encoded), crate::decode::Config::default()
+            ).unwrap();
+            let bits = |matrices: &[[[f32; 4]; 4]]| {
+                let mut bits = matrices.iter().map(|m| m.map(|r| r.map(f32::to_bits))).collect::<Vec<_>>();
+                bits.sort();
+                bits
+            };
+            let decoded_matrices = decoded.get_attributes()[last].to_row_major_matrices().unwrap();
+            assert_eq!(bits(&decoded_matrices), bits(&matrices));
+        }
+    }
+}
@@ -0,0 +1,190 @@
+use crate::core::mesh::diff::unique_value_as_f64;
+use crate::core::shared::{AttributeValueIdx, DataValue, Vector};
+use crate::prelude::{Attribute, ByteWriter, NdVector};
+use crate::shared::attribute::Portable;
+
+use super::{Config, PortabilizationImpl};
+
+/// Quantization for texture coordinates of tiled textures. Each component is split into the integer
+/// wrap count and the fractional part in [0, 1), and the fractional part is quantized with
+/// 'quantization_bits' bits. The two are packed into one integer, the wrap count (relative to
+/// the smallest one) in the upper bits, so that the precision within a tile does not depend on
+/// how many tiles the coordinates span.
+pub(crate) struct WrappedQuantization<Data, const N: usize>
+    where Data: Vector<N>
+{
+    att: Attribute,
+    min_wraps: [i32; N],
+    quantization_bits: u8,
+    _phantom: std::marker::PhantomData<Data>,
+}
+
+impl<Data, const N: usize> WrappedQuantization<Data, N>
+    where
+        NdVector<N, i32>: Vector<N, Component = i32>,
+        Data: Vector<N> + Portable,
+        Data::Component: DataValue
+{
+    pub fn new<W>(att: Attribute, cfg: Config, writer: &mut W) -> Self
+    where
+        W: ByteWriter,
+    {
+        let min_wraps = if let Some((min, _)) = att.get_value_bounds() {
+            std::array::from_fn(|i| min[i].floor() as i32)
+        } else {
+            let mut min_wraps = [i32::MAX; N];
+            for val in att.unique_vals_as_slice::<Data, N>() {
+                for (i, min_wrap) in min_wraps.iter_mut().enumerate() {
+                    *min_wrap = (*min_wrap).min(val.get(i).to_f64().floor() as i32);
+                }
+            }
+            min_wraps.map(|w| if w == i32::MAX { 0 } else { w })
+        };
+
+        // write metadata
+        for &w in &min_wraps {
+            writer.write_u32(w as u32);
+        }
+        writer.write_u8(cfg.quantization_bits);
+
+        Self {
+            att,
+            min_wraps,
+            quantization_bits: cfg.quantization_bits,
+            _phantom: std::marker::PhantomData,
+        }
+    }
+
+    fn portabilize_value(&self, val: Data) -> NdVector<N, i32> {
+        let scale = (1_u64 << self.quantization_bits) as f64;
+        let mut out = NdVector::<N, i32>::zero();
+        for i in 0..N {
+            // rounding the fractional part up to 1 carries into the wrap count.
+            let quantized = (val.get(i).to_f64() * scale).round() as i64;
+            // The packed values are checked to fit in 'i32' by [fits_in_i32] before.
+            *out.get_mut(i) = (quantized - ((self.min_wraps[i] as i64) << self.quantization_bits)) as i32;
+        }
+        out
+    }
+}
+
+impl<Data, const N: usize> PortabilizationImpl<N> for WrappedQuantization<Data, N>
+    where
+        NdVector<N, i32>: Vector<N, Component = i32>,
+        Data: Vector<N> + Portable,
+{
+    fn portabilize(self) -> Attribute {
+        let out = (0..self.att.num_unique_values())
+            .map(|i| self.portabilize_value(self.att.get_unique_val::<Data, N>(AttributeValueIdx::from(i))))
+            .collect::<Vec<_>>();
+        let mut port_att = Attribute::from_without_removing_duplicates(
+            self.att.get_id(),
+            out,
+            self.att.get_attribute_type(),
+            self.att.get_domain(),
+            self.att.get_parents().clone()
+        );
+        port_att.set_point_to_att_val_map(self.att.take_point_to_att_val_map());
+        port_att
+    }
+}
+
+/// Returns true if every value of the attribute, packed with the fractional parts of 'quantization_bits' bits,
+/// fits in 'i32', i.e. if the wrap counts of the values span few enough tiles.
+pub(crate) fn fits_in_i32(att: &Attribute, quantization_bits: u8) -> bool {
+    let mut range = vec![(i32::MAX, i32::MIN); att.get_num_components()];
+    for i in 0..att.num_unique_values() {
+        for ((min, max), v) in range.iter_mut().zip(unique_value_as_f64(att, i)) {
+            let w = v.floor();
+            if !(w >= i32::MIN as f64 && w <= i32::MAX as f64) {
+                return false;
+            }
+            *min = (*min).min(w as i32);
+            *max = (*max).max(w as i32);
+        }
+    }
+    // the fractional part may round up to the next wrap count.
+    range.iter()
+        .filter(|(min, max)| min <= max)
+        .all(|&(min, max)| {
+            (max as i64 - min as i64 + 1).checked_mul(1_i64 << quantization_bits)
+                .is_some_and(|packed| packed <= i32::MAX as i64)
+        })
+}
+
+/// Inverse of [WrappedQuantization]. Restores a component from its quantized value.
+/// The wrap count is computed in 'i64', as the values of a corrupted stream may overflow 'i32'.
+pub(crate) fn dequantize(quantized: i32, min_wrap: i32, quantization_bits: u8) -> f64 {
+    let wrap = (quantized >> quantization_bits) as i64 + min_wrap as i64;
+    let fraction = quantized & ((1 << quantization_bits) - 1);
+    wrap as f64 + fraction as f64 / (1_u64 << quantization_bits) as f64
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::attribute::AttributeDomain;
+    use crate::core::shared::PointIdx;
+    use crate::encode::attribute::portabilization::quantization_coordinate_wise::QuantizationCoordinateWise;
+    use crate::encode::attribute::portabilization::PortabilizationType;
+    use crate::prelude::{AttributeType, ByteReader, SliceReader};
+
+    #[test]
+    fn test_wrapped_quantization_is_more_precise_on_tiled_uvs() {
+        // texture coordinates spanning 8 tiles.
+        let values = (0..500)
+            .map(|i| {
+                let t = i as f32 * 0.0173;
+                NdVector::from([(t * 1.3).rem_euclid(8.0), (t * 0.7 + 0.05).rem_euclid(8.0)])
+            })
+            .collect::<Vec<_>>();
+        let att = Attribute::new(values.clone(), AttributeType::TextureCoordinate, AttributeDomain::Corner, Vec::new());
+        let bits = 10;
+
+        let cfg = Config { type_: PortabilizationType::WrappedQuantization, quantization_bits: bits };
+        let mut metadata = Vec::new();
+        let wrapped = WrappedQuantization::<NdVector<2, f32>, 2>::new(att.clone(), cfg, &mut metadata).portabilize();
+        let mut reader = SliceReader::new(&metadata);
+        let min_wraps = [0; 2].map(|_| reader.read_u32().unwrap() as i32);
+        assert_eq!(min_wraps, [0, 0]);
+        assert_eq!(reader.read_u8().unwrap(), bits);
+
+        let cfg = Config { type_: PortabilizationType::QuantizationCoordinateWise, quantization_bits: bits };
+        let naive = QuantizationCoordinateWise::<NdVector<2, f32>, 2>::new(att, cfg, &mut Vec::new()).portabilize();
+        // the range of the naive quantization covers all the tiles.
+        let (min, range) = (0.0, 8.0);
+        let step = range / ((1 << bits) - 1) as f64;
+
+        let mut wrapped_error = 0.0_f64;
+        let mut naive_error = 0.0_f64;
+        for (p, v) in values.iter().enumerate() {
+            let p = PointIdx::from(p);
+            let w = wrapped.get::<NdVector<2, i32>, 2>(p);
+            let n = naive.get::<NdVector<2, i32>, 2>(p);
+            for (i, &min_wrap) in min_wraps.iter().enumerate() {
+                let original = *v.get(i) as f64;
+                wrapped_error = wrapped_error.max((dequantize(*w.get(i), min_wrap, bits) - original).abs());
+                naive_error = naive_error.max((min + *n.get(i) as f64 * step - original).abs());
+            }
+        }
+        assert!(wrapped_error <= 0.5 / (1 << bits) as f64 + 1e-6, "wrapped error: {}", wrapped_error);
+        assert!(wrapped_error * 4.0 < naive_error, "wrapped error: {}, naive error: {}", wrapped_error, naive_error);
+    }
+
+    #[test]
+    fn test_negative_wraps() {
+        let values = vec![NdVector::from([-2.25_f32, 0.5]), NdVector::from([1.75, -0.125])];
+        let att = Attribute::new(values.clone(), AttributeType::TextureCoordinate, AttributeDomain::Corner, Vec::new());
+        let cfg = Config { type_: PortabilizationType::WrappedQuantization, quantization_bits: 8 };
+        let q = WrappedQuantization::<NdVector<2, f32>, 2>::new(att, cfg, &mut Vec::new());
+        assert_eq!(q.min_wraps, [-3, -1]);
+        let port_att = q.portabilize();
+        for (p, v) in values.iter().enumerate() {
+            let w = port_att.get::<NdVector<2, i32>, 2>(PointIdx::from(p));
+            assert!(*w.get(0) >= 0 && *w.get(1) >= 0);
+            assert_eq!(dequantize(*w.get(0), -3, 8), *v.get(0) as f64);
+            assert_eq!(dequantize(*w.get(1), -1, 8), *v.get(1) as f64);
+        }
+    }
+}
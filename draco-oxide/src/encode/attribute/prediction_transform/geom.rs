@@ -37,6 +37,7 @@ pub(crate) fn rotation_matrix_from<Data, const N: usize>(axis: Data, angle: f64)
 use crate::core::shared::Abs;
 /// Transforms the data to the octahedron space.
 /// Make sure that the data is three dimensional.
+#[allow(unused)]
 pub(crate) fn octahedral_transform<const N: usize, Data>(v: Data) -> NdVector<2, f32> 
 	where Data: Vector<N>,
 	      Data::Component: DataValue
@@ -134,17 +135,25 @@ pub(crate) unsafe fn octahedral_inverse_transform<Data, const N: usize>(v: NdVec
 	out
 }
 
-pub(crate) fn into_faithful_oct_quantization(vec: NdVector<2, i32>) -> NdVector<2, i32> 
+/// The largest octahedral coordinate of the given quantization bits, i.e. the coordinates are in '0..=max_value'
+/// and the center of the octahedron is at 'max_value / 2', as in Draco.
+pub(crate) fn oct_max_value(quantization_bits: u8) -> i32 {
+	(1 << quantization_bits) - 2
+}
+
+/// Of the points on the border of the square that represent the same vector, takes the one Draco takes,
+/// so that the octahedral coordinates are unique.
+pub(crate) fn into_faithful_oct_quantization(vec: NdVector<2, i32>, max_value: i32) -> NdVector<2, i32> 
 {
-	let max = 255;
+	let max = max_value;
 	let half = max / 2;
 	let u = *vec.get(0);
 	let v = *vec.get(1);
 	let mut x = u;
 	let mut y = v;
-	if (u==0 && v==0) || (u==255 && v==0) || (u==0 && v==255) {
-		return NdVector::<2, i32>::from([255, 255]);
-	} else if u == 0 && v > 127 {
+	if (u==0 && v==0) || (u==max && v==0) || (u==0 && v==max) {
+		return NdVector::<2, i32>::from([max, max]);
+	} else if u == 0 && v > half {
       y = half - (v - half)
     } else if u == max && v < half {
       y = half + (half - v);
@@ -156,6 +165,80 @@ pub(crate) fn into_faithful_oct_quantization(vec: NdVector<2, i32>) -> NdVector<
 	NdVector::<2, i32>::from([x, y])
 }
 
+/// Scales the integer vector onto the octahedron of the given center, i.e. so that the sum of the absolute values
+/// of its components is 'center', with the integer arithmetic of Draco. The zero vector goes to '(center, 0, 0)'.
+pub(crate) fn canonicalize_integer_vector(v: [i32; 3], center: i32) -> [i32; 3] {
+	let abs_sum = v.iter().map(|&c| (c as i64).abs()).sum::<i64>();
+	if abs_sum == 0 {
+		return [center, 0, 0];
+	}
+	let x = (v[0] as i64 * center as i64 / abs_sum) as i32;
+	let y = (v[1] as i64 * center as i64 / abs_sum) as i32;
+	let z = center - x.abs() - y.abs();
+	[x, y, if v[2] >= 0 { z } else { -z }]
+}
+
+/// Converts the vector on the octahedron, as returned by [canonicalize_integer_vector], to the octahedral coordinates
+/// in '0..=max_value', as Draco does.
+pub(crate) fn integer_vector_to_oct_quantization(v: [i32; 3], max_value: i32) -> NdVector<2, i32> {
+	let center = max_value / 2;
+	let [x, y, z] = v;
+	let (s, t) = if x >= 0 {
+		(y + center, z + center)
+	} else {
+		(
+			if y < 0 { z.abs() } else { max_value - z.abs() },
+			if z < 0 { y.abs() } else { max_value - y.abs() },
+		)
+	};
+	into_faithful_oct_quantization(NdVector::from([s, t]), max_value)
+}
+
+/// Quantizes the vector to the octahedral coordinates in '0..=max_value', rounding as Draco does so that
+/// the coordinates agree with those of Draco bit by bit. A (nearly) zero vector is taken as '(1, 0, 0)'.
+pub(crate) fn float_vector_to_oct_quantization(v: [f64; 3], max_value: i32) -> NdVector<2, i32> {
+	let center = max_value / 2;
+	let abs_sum = v.iter().map(|c| c.abs()).sum::<f64>();
+	let v = if abs_sum > 1e-6 { v.map(|c| c * (1.0 / abs_sum)) } else { [1.0, 0.0, 0.0] };
+	let mut int_v = [
+		(v[0] * center as f64 + 0.5).floor() as i32,
+		(v[1] * center as f64 + 0.5).floor() as i32,
+		0,
+	];
+	int_v[2] = center - int_v[0].abs() - int_v[1].abs();
+	if int_v[2] < 0 {
+		// the first two components are rounded up too much. Take the excess from the second one.
+		if int_v[1] > 0 {
+			int_v[1] += int_v[2];
+		} else {
+			int_v[1] -= int_v[2];
+		}
+		int_v[2] = 0;
+	}
+	if v[2] < 0.0 {
+		int_v[2] = -int_v[2];
+	}
+	integer_vector_to_oct_quantization(int_v, max_value)
+}
+
+/// Flips the point of the octahedron, centered at the origin, inside out, i.e. between the upper and the lower
+/// hemisphere, as Draco does. A point on an axis is flipped as if it were in the quadrant of the non-negative
+/// signs (or of the non-positive signs on the negative axes), so that it is not collapsed onto the corner.
+pub(crate) fn invert_diamond(v: [i32; 2], center: i32) -> [i32; 2] {
+	let [s, t] = v;
+	let (sign_s, sign_t) = if s >= 0 && t >= 0 {
+		(1, 1)
+	} else if s <= 0 && t <= 0 {
+		(-1, -1)
+	} else {
+		(s.signum(), t.signum())
+	};
+	let (corner_s, corner_t) = (sign_s * center, sign_t * center);
+	let (s, t) = (2 * s - corner_s, 2 * t - corner_t);
+	let (s, t) = if sign_s * sign_t >= 0 { (-t, -s) } else { (t, s) };
+	[(s + corner_s) / 2, (t + corner_t) / 2]
+}
+
 
 #[cfg(test)]
 mod tests {
@@ -194,4 +277,50 @@ mod tests {
 			assert!(diff_norm_squared < 1e-10, "Difference is too large: {}, v={:?}, recovered={:?}", diff_norm_squared, v, recovered);
 		}
 	}
+
+	#[test]
+	fn test_oct_quantization() {
+		// the values computed by hand with the rounding of Draco.
+		let max_value = oct_max_value(8);
+		assert_eq!(max_value, 254);
+		assert_eq!(float_vector_to_oct_quantization([1.0, 0.0, 0.0], max_value), NdVector::from([127, 127]));
+		assert_eq!(float_vector_to_oct_quantization([0.0, 0.0, 1.0], max_value), NdVector::from([127, 254]));
+		assert_eq!(float_vector_to_oct_quantization([0.0, 0.0, -1.0], max_value), NdVector::from([127, 0]));
+		assert_eq!(float_vector_to_oct_quantization([-1.0, 0.0, 0.0], max_value), NdVector::from([254, 254]));
+		assert_eq!(canonicalize_integer_vector([0, 0, 0], 127), [127, 0, 0]);
+		assert_eq!(canonicalize_integer_vector([3, -1, 0], 127), [95, -31, 1]);
+
+		// the dequantized coordinates come back to the vector within the step of the quantization.
+		for bits in [8, 10, 12] {
+			let max_value = oct_max_value(bits);
+			let center = (max_value / 2) as f32;
+			for v in [[1.0_f64, 2.0, 3.0], [-0.5, 0.25, -2.0], [-1.0, -1.0, 0.1]] {
+				let n = NdVector::from(v) / NdVector::from(v).dot(NdVector::from(v)).sqrt();
+				let oct = float_vector_to_oct_quantization(v, max_value);
+				let oct = NdVector::from([*oct.get(0) as f32 / center - 1.0, *oct.get(1) as f32 / center - 1.0]);
+				let recovered: NdVector<3, f64> = unsafe { octahedral_inverse_transform(oct) };
+				assert!((n - recovered).dot(n - recovered).sqrt() < 2.0 / center as f64, "{} bits: {:?}", bits, v);
+			}
+		}
+	}
+
+	#[test]
+	fn test_invert_diamond() {
+		let center = 127;
+		for s in -center..=center {
+			for t in -center..=center {
+				let inverted = invert_diamond([s, t], center);
+				// the point goes to the other side of the diamond, and back.
+				if s.abs() + t.abs() != center {
+					assert_ne!(s.abs() + t.abs() < center, inverted[0].abs() + inverted[1].abs() < center, "{:?}", [s, t]);
+				}
+				// the points on the sides of the square are identified in pairs, so only the inner ones come back as they are.
+				if s.abs() < center && t.abs() < center {
+					assert_eq!(invert_diamond(inverted, center), [s, t]);
+				}
+			}
+		}
+		// a point on an axis is not collapsed onto the corner.
+		assert_eq!(invert_diamond([100, 0], center), [center, center - 100]);
+	}
 }
\ No newline at end of file
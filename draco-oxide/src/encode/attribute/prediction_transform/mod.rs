@@ -74,18 +74,26 @@ pub(crate) trait PredictionTransformImpl<const N: usize> {
 
 
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum PredictionTransformType {
 	NoTransform,
 	Difference,
 	WrappedDifference,
 	OctahedralOrthogonal,
-	#[allow(unused)] // TODO: This variant is not used yet, as we only support the default configuration. Remove this when we implement the octahedral orthogonal transform.
 	OctahedralReflection,
 	#[allow(unused)] // TODO: This variant is not used yet, as we only support the default configuration. Remove this when we implement the orthogonal transform.
 	Orthogonal,
 }
 
+crate::utils::enum_str::impl_enum_str!(PredictionTransformType, "prediction transform", {
+	NoTransform => ["none"],
+	Difference => ["difference"],
+	WrappedDifference => ["wrapped_difference", "wrap"],
+	OctahedralOrthogonal => ["octahedral_orthogonal"],
+	OctahedralReflection => ["octahedral_reflection"],
+	Orthogonal => ["orthogonal"],
+});
+
 impl PredictionTransformType {
 	/// gets the prediction transform type from its id.
 	#[inline]
@@ -109,12 +117,24 @@ impl PredictionTransformType {
 		let id = self.get_id();
 		writer.write_u8(id);
 	}
+
+	/// Inverse of [PredictionTransformType::get_id], or 'None' if the id is unknown.
+	pub(crate) fn from_id(id: u8) -> Option<Self> {
+		match id {
+			0xFF => Some(PredictionTransformType::NoTransform),
+			0 => Some(PredictionTransformType::Difference),
+			1 => Some(PredictionTransformType::WrappedDifference),
+			2 => Some(PredictionTransformType::OctahedralReflection),
+			3 => Some(PredictionTransformType::OctahedralOrthogonal),
+			4 => Some(PredictionTransformType::Orthogonal),
+			_ => None,
+		}
+	}
 }
 
 #[derive(Clone, Copy, Debug)]
 pub struct Config {
 	pub ty: PredictionTransformType,
-	#[allow(unused)]
 	pub portabilization: super::portabilization::Config,
 }
 
@@ -1,19 +1,24 @@
 use crate::core::shared::{NdVector, Vector}; 
 use crate::prelude::ByteWriter;
 use super::{
+    geom::invert_diamond,
     PredictionTransformImpl
 };
 
 pub struct OctahedronOrthogonalTransform<const N: usize> 
 {
     out: Vec<NdVector<N,i32>>,
+
+    /// The largest quantized value, i.e. '2^quantization_bits - 1', as in Draco.
+    max_quantized: i32,
 }
 
 impl<const N: usize> OctahedronOrthogonalTransform<N> 
 {
-    pub fn new(_cfg: super::Config) -> Self {
+    pub fn new(cfg: super::Config) -> Self {
         Self {
             out: Vec::new(),
+            max_quantized: (1 << cfg.portabilization.quantization_bits) - 1,
         }
     }
 }
@@ -31,7 +36,7 @@ impl<const N: usize> PredictionTransformImpl<N> for OctahedronOrthogonalTransfor
         );
 
         // make sure that pred is in the upper hemisphere.
-        let one = 255/2;
+        let one = self.max_quantized/2;
         *pred.get_mut(0) -= one;
         *pred.get_mut(1) -= one;
         *orig.get_mut(0) -= one;
@@ -39,14 +44,10 @@ impl<const N: usize> PredictionTransformImpl<N> for OctahedronOrthogonalTransfor
         if pred.get(0).abs() + pred.get(1).abs() > one {
             // we need to flip the z-axis. 
             // In the octahedron representation, this means that we need to flip inside out.
-            let pred0 = *pred.get(0);
-            let quadrant_sign = -(pred.get(0) * pred.get(1)).signum();
-            *pred.get_mut(0) = quadrant_sign*pred.get(1) + pred.get(0).signum() * one;
-            *pred.get_mut(1) = quadrant_sign*pred0 + pred.get(1).signum() * one;
-            let orig0 = *orig.get(0);
-            let quadrant_sign = -(orig.get(0) * orig.get(1)).signum();
-            *orig.get_mut(0) = quadrant_sign*orig.get(1) + orig.get(0).signum() * one;
-            *orig.get_mut(1) = quadrant_sign*orig0 + orig.get(1).signum() * one;
+            let [p0, p1] = invert_diamond([*pred.get(0), *pred.get(1)], one);
+            (*pred.get_mut(0), *pred.get_mut(1)) = (p0, p1);
+            let [o0, o1] = invert_diamond([*orig.get(0), *orig.get(1)], one);
+            (*orig.get_mut(0), *orig.get_mut(1)) = (o0, o1);
         }
 
         // Now rotate the sphere around the z-axis so that the x and y coordinates of pred are both negative.
@@ -67,7 +68,7 @@ impl<const N: usize> PredictionTransformImpl<N> for OctahedronOrthogonalTransfor
         let mut corr = orig - pred;
         for i in 0..N {
             if *corr.get(i) < 0 {
-                *corr.get_mut(i) += 255;
+                *corr.get_mut(i) += self.max_quantized;
             }
         }
         self.out.push(corr);
@@ -77,9 +78,9 @@ impl<const N: usize> PredictionTransformImpl<N> for OctahedronOrthogonalTransfor
         where W: ByteWriter
     {
         // write the max quantized value.
-        writer.write_u32(255);
+        writer.write_u32(self.max_quantized as u32);
         // write center of the octahedron.
-        writer.write_u32(255/2);
+        writer.write_u32((self.max_quantized/2) as u32);
 
         self.out
     }
@@ -1,47 +1,70 @@
 use crate::core::shared::{NdVector, Vector};
 use crate::prelude::ByteWriter;
 
-use super::PredictionTransformImpl;
-
+use super::{
+    geom::invert_diamond,
+    PredictionTransformImpl
+};
 
+/// The octahedron transform of Draco. If the prediction is in the lower hemisphere, i.e. outside the diamond
+/// of the octahedral coordinates, the prediction and the normal are flipped inside out, so that the prediction is
+/// in the upper hemisphere, where the difference between the two is small.
 pub struct OctahedronReflectionTransform<const N: usize> 
 {
     out: Vec<NdVector<N, i32>>,
+
+    /// The largest quantized value, i.e. '2^quantization_bits - 1', as in Draco.
+    max_quantized: i32,
 }
 
 impl<const N: usize> OctahedronReflectionTransform<N> {
-    pub fn new(_cfg: super::Config) -> Self {
+    pub fn new(cfg: super::Config) -> Self {
         Self {
             out: Vec::new(),
+            max_quantized: (1 << cfg.portabilization.quantization_bits) - 1,
         }
     }
 }
 
 impl<const N: usize> PredictionTransformImpl<N> for OctahedronReflectionTransform<N> 
 {
-    fn map_with_tentative_metadata(&mut self, mut orig: NdVector<N,i32>, mut pred: NdVector<N,i32>)
+    fn map_with_tentative_metadata(&mut self, orig: NdVector<N,i32>, pred: NdVector<N,i32>)
         where NdVector<N, i32>: Vector<N, Component = i32>,
     {
         // Safety:
-        // We made sure that the data is three dimensional.
-        debug_assert!(
+        // We made sure that the data is two dimensional.
+        assert!(
             N==2,
         );
 
-        unsafe {
-            if *pred.get_unchecked(2) < 0 {
-                *pred.get_unchecked_mut(2) *= -1;
-                *orig.get_unchecked_mut(2) *= -1;
-            }
-        };
+        let center = self.max_quantized/2;
+        let mut pred = [*pred.get(0) - center, *pred.get(1) - center];
+        let mut orig = [*orig.get(0) - center, *orig.get(1) - center];
+        if pred[0].abs() + pred[1].abs() > center {
+            pred = invert_diamond(pred, center);
+            orig = invert_diamond(orig, center);
+        }
 
-        self.out.push( orig - pred );
+        // Now we take the difference and make it positive.
+        let mut corr = NdVector::<N, i32>::zero();
+        for i in 0..2 {
+            let mut c = orig[i] - pred[i];
+            if c < 0 {
+                c += self.max_quantized;
+            }
+            *corr.get_mut(i) = c;
+        }
+        self.out.push(corr);
     }
 
-    fn squeeze<W>(self, _writer: &mut W) -> Vec<NdVector<N, i32>>
+    fn squeeze<W>(self, writer: &mut W) -> Vec<NdVector<N, i32>>
         where W: ByteWriter
     {
-        unimplemented!()
-    }
+        // write the max quantized value.
+        writer.write_u32(self.max_quantized as u32);
+        // write center of the octahedron.
+        writer.write_u32((self.max_quantized/2) as u32);
 
-}
\ No newline at end of file
+        self.out
+    }
+}
@@ -13,7 +13,7 @@ use crate::encode::entropy::rans::{self, RabsCoder};
 use crate::encode::entropy::symbol_coding::encode_symbols;
 use crate::debug_write;
 use crate::prelude::{Attribute, AttributeType};
-use crate::shared::connectivity::edgebreaker::symbol_encoder::{CrLight, Symbol, SymbolEncoder};
+use crate::shared::connectivity::edgebreaker::symbol_encoder::{split_by_context, ClersCoding, CrLight, Symbol, SymbolEncoder, SymbolPacking};
 
 use crate::core::shared::{ConfigType, CornerIdx, FaceIdx, PointIdx, VecFaceIdx, VecVertexIdx, VertexIdx};
 
@@ -33,36 +33,22 @@ use crate::eval;
 pub(crate) struct Edgebreaker<'faces, T> 
     where T: Traversal
 {
-	/// The 'i'th entry of 'visited_vertices' is true if the Edgebreaker has
-	/// already visited the 'i' th vertex.
-	visited_vertices: VecVertexIdx<bool>,
-
-	/// The 'i'th entry of 'visited_edges' is true if the Edgebreaker has
-	/// already visited the 'i' th face.
+	/// The 'i'th entry of 'visited_edges' is true if the traversals replayed so far
+	/// have visited the 'i' th face.
 	visited_faces: VecFaceIdx<bool>,
     
     /// Corner table: a fast-lookup structure for the mesh connectivity.
     corner_table: CornerTable<'faces>,
 
-    /// The visited holes. i th entry of this array records whether the i th hole is visited or not.
-    visited_holes: Vec<bool>,
+    /// The number of holes of the mesh.
+    num_holes: usize,
 
     // A map from vertices to the hole id if the vertex is on a hole or void if the vertex is not on a hole.
     vertex_hole_id: VecVertexIdx<Option<usize>>,
 
-    num_decoded_vertices: usize,
-
-    corner_traversal_stack: Vec<CornerIdx>,
-
-    last_encoded_symbol_idx: usize,
-
     processed_connectivity_corners: Vec<CornerIdx>,
 
-    face_to_split_symbol_map: BTreeMap<usize, usize>,
-
     num_split_symbols: usize,
-
-    vertex_traversal_length: Vec<usize>,
     
     init_face_connectivity_corners: Vec<CornerIdx>,
 
@@ -79,10 +65,59 @@ pub(crate) struct Edgebreaker<'faces, T>
 }
 
 
+/// Runs Edgebreaker on the connected components of a mesh, one component at a time.
+/// Two connected components share no faces, vertices or holes, so the traversal of a component only reads and writes
+/// the flags of that component, and the components can be traversed independently of each other.
+struct ComponentTraverser<'a, 'faces> {
+    corner_table: &'a CornerTable<'faces>,
+
+    vertex_hole_id: &'a VecVertexIdx<Option<usize>>,
+
+	/// The 'i'th entry of 'visited_vertices' is true if the traverser has
+	/// already visited the 'i' th vertex.
+	visited_vertices: VecVertexIdx<bool>,
+
+	/// The 'i'th entry of 'visited_edges' is true if the traverser has
+	/// already visited the 'i' th face.
+	visited_faces: VecFaceIdx<bool>,
+
+    /// The visited holes. i th entry of this array records whether the i th hole is visited or not.
+    visited_holes: Vec<bool>,
+
+    corner_traversal_stack: Vec<CornerIdx>,
+
+    face_to_split_symbol_map: BTreeMap<usize, usize>,
+}
+
+/// The traversal of a single connected component, recorded so that it can be replayed into a [Traversal].
+/// The symbol indices of the topology splits count from the first symbol of the component.
+struct ComponentTraversal {
+    events: Vec<TraversalEvent>,
+    num_split_symbols: usize,
+    topology_splits: Vec<TopologySplit>,
+    init_face_connectivity_corner: Option<CornerIdx>,
+}
+
+/// The calls made to a [Traversal] during the traversal of a connected component.
+enum TraversalEvent {
+    StartFace(bool),
+    /// The interior start face is marked visited without reaching any of its corners.
+    VisitFace(FaceIdx),
+    Corner(CornerIdx),
+    Symbol(Symbol),
+}
+
+
 #[derive(Clone, fmt::Debug, cmp::PartialEq)]
 pub struct Config {
     pub traversal: EdgebreakerKind,
     pub use_single_connectivity: bool,
+    pub clers_coding: ClersCoding,
+    pub symbol_packing: SymbolPacking,
+    /// Whether the debug markers are written, see [crate::encode::Config::debug_markers].
+    pub debug_markers: bool,
+    /// The face from which the traversal starts, see [crate::encode::Config::edgebreaker_start_face].
+    pub start_face: Option<usize>,
 }
 
 impl ConfigType for Config {
@@ -90,6 +125,10 @@ impl ConfigType for Config {
         Self{
             traversal: EdgebreakerKind::Standard,
             use_single_connectivity: false,
+            clers_coding: ClersCoding::Flat,
+            symbol_packing: SymbolPacking::BitPacked,
+            debug_markers: false,
+            start_face: None,
 		}
     }
 }
@@ -113,10 +152,14 @@ pub enum Err {
     HandleSizeTooLarge,
     #[error("Too many holes.")]
     HoleSizeTooLarge,
+    #[error("The input mesh has vertices that no face refers to: {0:?}")]
+    IsolatedVertices(Vec<usize>),
     #[error("The input mesh is non-orientable.")]
     NonOrientable,
     #[error("Rabs coder error: {0}")]
     RabsCoderError(#[from] rans::Err),
+    #[error("The start face {0} is out of range; the mesh has {1} faces.")]
+    StartFaceOutOfRange(usize, usize),
     #[error("The input mesh has too many connected components: {0}")]
     TooManyConnectedComponents(usize),
 }
@@ -125,47 +168,41 @@ impl<'faces, T> Edgebreaker<'faces, T>
     where T: Traversal
 {
 	// Build the object with empty arrays.
-	pub fn new(config: Config, atts: &mut [Attribute], faces: &'faces [[PointIdx; 3]]) -> Result<Self, Err> {
+	// The corner table is built from the faces and the positions unless it is given, e.g. by [crate::encode::encode_with_topology].
+	pub fn new(config: Config, atts: &mut [Attribute], faces: &'faces [[PointIdx; 3]], corner_table: Option<CornerTable<'faces>>) -> Result<Self, Err> {
         let corner_table = if config.use_single_connectivity {
             unimplemented!("Single connectivity is not supported yet.");
+        } else if let Some(corner_table) = corner_table {
+            corner_table
         } else {
             let pos_att = atts.iter()
                 .find(|att| att.get_attribute_type() == AttributeType::Position)
                 .unwrap();
             CornerTable::new(faces, pos_att)
         };
+        // The traversal visits the vertices through the faces, so the values of the isolated vertices would be lost.
+        let isolated_vertices = corner_table.isolated_vertices();
+        if !isolated_vertices.is_empty() {
+            return Err(Err::IsolatedVertices(isolated_vertices.into_iter().map(usize::from).collect()));
+        }
 
         let traversal = T::new(&corner_table);
 
         let attribute_encoding_data = Self::init_attribute_data(atts, &corner_table, &config)?;
 
-        let mut out = Self {
-            visited_vertices: VecVertexIdx::new(),
-            visited_faces: VecFaceIdx::new(),
+        Ok(Self {
+            visited_faces: VecFaceIdx::from(vec!(false; faces.len())),
             corner_table,
-            visited_holes: Vec::new(),
+            num_holes: 0,
             vertex_hole_id: VecVertexIdx::new(),
-            num_decoded_vertices: 0,
-            corner_traversal_stack: Vec::new(),
-            last_encoded_symbol_idx: usize::MAX,
             processed_connectivity_corners: Vec::new(),
-            face_to_split_symbol_map: BTreeMap::new(),
             num_split_symbols: 0,
-            vertex_traversal_length: Vec::new(),
             init_face_connectivity_corners: Vec::new(),
             traversal,
             topology_splits: Vec::new(),
             attribute_encoding_data,
             config,
-        };
-
-        let num_vertices = out.corner_table.num_vertices();
-        out.visited_vertices = VecVertexIdx::from(vec!(false; num_vertices));
-        out.visited_faces = VecFaceIdx::from(vec!(false; faces.len()));
-
-        out.num_decoded_vertices = 0;
-
-        Ok(out)
+        })
 	}
 
     fn init_attribute_data(atts: &mut [Attribute], corner_table: &CornerTable, config: &Config) -> Result<Vec<AttributeCornerTable>, Err> {
@@ -204,8 +241,8 @@ impl<'faces, T> Edgebreaker<'faces, T>
                     continue;
                 }
                 // Now we have found a new boundary containing the vertex 'v'.
-                let boundary_idx = self.visited_holes.len();
-                self.visited_holes.push(false);
+                let boundary_idx = self.num_holes;
+                self.num_holes += 1;
 
                 let mut c = c;
                 while self.vertex_hole_id[v].is_none() {
@@ -223,6 +260,186 @@ impl<'faces, T> Edgebreaker<'faces, T>
         Ok(())
     }
 
+    fn encode_topology_splits<W>(&mut self, writer: &mut W) -> Result<(), Err> 
+        where W: ByteWriter,
+    {
+        #[cfg(feature = "evaluation")]
+        {
+            let mut string = String::new();
+            for split in self.topology_splits.iter() {
+                string.push_str(&format!("{}:{}({:?}) ", split.merging_symbol_idx, split.split_symbol_idx, split.merging_edge_orientation));
+            }
+            eval::write_json_pair("topology_splits", serde_json::Value::from(string), writer);
+        }
+        let mut last_idx = 0;
+        // write the number of topology splits.
+        leb128_write(self.topology_splits.len() as u64, writer);
+        if self.config.symbol_packing == SymbolPacking::ByteAligned {
+            for split in self.topology_splits.iter() {
+                writer.write_u32((split.merging_symbol_idx - last_idx) as u32);
+                writer.write_u32((split.merging_symbol_idx - split.split_symbol_idx) as u32);
+                writer.write_u8(match split.merging_edge_orientation {
+                    Orientation::Left => 0,
+                    Orientation::Right => 1,
+                });
+                last_idx = split.merging_symbol_idx;
+            }
+            return Ok(());
+        }
+        for split in self.topology_splits.iter() {
+            leb128_write((split.merging_symbol_idx - last_idx) as u64, writer);
+            leb128_write((split.merging_symbol_idx - split.split_symbol_idx) as u64, writer);
+            last_idx = split.merging_symbol_idx;
+        }
+        let mut bit_coder: BitWriter<'_, W, LsbFirst> = BitWriter::spown_from(writer);
+        for split in self.topology_splits.iter() {
+            let orientation = match split.merging_edge_orientation {
+                Orientation::Left => (1,0),
+                Orientation::Right => (1,1),
+            };
+            bit_coder.write_bits(orientation);
+        }
+        Ok(())
+    }
+
+    /// Runs Edgebreaker from the given start faces, skipping those whose component has already been traversed,
+    /// and returns the traversals of the components in the order of their start faces.
+    #[cfg(not(feature = "parallel"))]
+    fn traverse_components(&self, start_faces: impl Iterator<Item = FaceIdx>) -> Vec<ComponentTraversal> {
+        let mut traverser = ComponentTraverser::new(&self.corner_table, &self.vertex_hole_id, self.num_holes);
+        let mut components = Vec::new();
+        for f in start_faces {
+            if traverser.visited_faces[f] {
+                // if the face is already visited, then skip it.
+                continue;
+            }
+            components.push(traverser.traverse(f));
+        }
+        components
+    }
+
+    /// Runs Edgebreaker from the given start faces, skipping those whose component has already been traversed,
+    /// and returns the traversals of the components in the order of their start faces.
+    /// The components are traversed in parallel; the result is the same as that of the serial traversal.
+    #[cfg(feature = "parallel")]
+    fn traverse_components(&self, start_faces: impl Iterator<Item = FaceIdx>) -> Vec<ComponentTraversal> {
+        use rayon::prelude::*;
+        let (corner_table, vertex_hole_id, num_holes) = (&self.corner_table, &self.vertex_hole_id, self.num_holes);
+        self.split_connected_components(start_faces)
+            .into_par_iter()
+            .map_init(
+                || ComponentTraverser::new(corner_table, vertex_hole_id, num_holes),
+                |traverser, f| traverser.traverse(f)
+            )
+            .collect()
+    }
+
+    /// Splits the faces into connected components and returns the first face of each component
+    /// in the order given by 'faces'.
+    #[cfg(feature = "parallel")]
+    fn split_connected_components(&self, faces: impl Iterator<Item = FaceIdx>) -> Vec<FaceIdx> {
+        let mut visited_faces = VecFaceIdx::from(vec![false; self.corner_table.num_faces()]);
+        let mut stack = Vec::new();
+        let mut start_faces = Vec::new();
+        for f in faces {
+            if visited_faces[f] {
+                continue;
+            }
+            start_faces.push(f);
+            visited_faces[f] = true;
+            stack.push(f);
+            while let Some(f) = stack.pop() {
+                for i in 0..3 {
+                    let c = CornerIdx::from(3 * usize::from(f) + i);
+                    if let Some(opp) = self.corner_table.opposite(c) {
+                        let opp_face = self.corner_table.face_idx_containing(opp);
+                        if !visited_faces[opp_face] {
+                            visited_faces[opp_face] = true;
+                            stack.push(opp_face);
+                        }
+                    }
+                }
+            }
+        }
+        start_faces
+    }
+
+    /// Replays the traversal of a connected component into the encoder.
+    fn append_component(&mut self, component: ComponentTraversal) {
+        let symbol_offset = self.traversal.num_symbols();
+        self.topology_splits.extend(component.topology_splits.into_iter().map(|split| TopologySplit {
+            merging_symbol_idx: split.merging_symbol_idx + symbol_offset,
+            split_symbol_idx: split.split_symbol_idx + symbol_offset,
+            merging_edge_orientation: split.merging_edge_orientation,
+        }));
+        self.num_split_symbols += component.num_split_symbols;
+        self.init_face_connectivity_corners.extend(component.init_face_connectivity_corner);
+        for event in component.events {
+            match event {
+                TraversalEvent::StartFace(interior_cfg) => self.traversal.record_start_face_config(interior_cfg),
+                TraversalEvent::VisitFace(f) => self.visited_faces[f] = true,
+                TraversalEvent::Corner(c) => {
+                    self.visited_faces[self.corner_table.face_idx_containing(c)] = true;
+                    self.processed_connectivity_corners.push(c);
+                    self.traversal.new_corner_reached(c);
+                },
+                TraversalEvent::Symbol(symbol) => self.traversal.record_symbol(symbol, &self.visited_faces, &self.corner_table),
+            }
+        }
+    }
+}	
+
+impl<'a, 'faces> ComponentTraverser<'a, 'faces> {
+    fn new(corner_table: &'a CornerTable<'faces>, vertex_hole_id: &'a VecVertexIdx<Option<usize>>, num_holes: usize) -> Self {
+        Self {
+            corner_table,
+            vertex_hole_id,
+            visited_vertices: VecVertexIdx::from(vec![false; corner_table.num_vertices()]),
+            visited_faces: VecFaceIdx::from(vec![false; corner_table.num_faces()]),
+            visited_holes: vec![false; num_holes],
+            corner_traversal_stack: Vec::new(),
+            face_to_split_symbol_map: BTreeMap::new(),
+        }
+    }
+
+    /// Runs Edgebreaker on the connected component containing the given face, beginning with that face.
+    fn traverse(&mut self, face_idx: FaceIdx) -> ComponentTraversal {
+        let mut component = ComponentTraversal {
+            events: Vec::new(),
+            num_split_symbols: 0,
+            topology_splits: Vec::new(),
+            init_face_connectivity_corner: None,
+        };
+        // A split symbol of another component is never merged in this one.
+        self.face_to_split_symbol_map.clear();
+
+        let (is_start_face_interior, start_corner) = self.begin_from(face_idx);
+
+        component.events.push(TraversalEvent::StartFace(is_start_face_interior));
+
+        if is_start_face_interior {
+            let corner_index = start_corner;
+            let v = self.corner_table.vertex_idx(corner_index);
+            let n = self.corner_table.vertex_idx(self.corner_table.next(corner_index));
+            let p = self.corner_table.vertex_idx(self.corner_table.previous(corner_index));
+            self.visited_vertices[v] = true;
+            self.visited_vertices[n] = true;
+            self.visited_vertices[p] = true;
+
+            self.visited_faces[face_idx] = true;
+            component.events.push(TraversalEvent::VisitFace(face_idx));
+            
+            component.init_face_connectivity_corner = Some(self.corner_table.next(corner_index));
+            let corner_opp = self.corner_table.opposite(self.corner_table.next(corner_index)).unwrap(); // it is safe to unwrap since the face is interior.
+            self.edgebreaker_from(corner_opp, &mut component);
+        } else {
+            // if the face is on the boundary, then we start from the boundary.
+            self.process_boundary(self.corner_table.next(start_corner), true);
+            self.edgebreaker_from(start_corner, &mut component);
+        }
+        component
+    }
+
     fn process_boundary(
         &mut self,
         start_corner: CornerIdx,
@@ -258,7 +475,8 @@ impl<'faces, T> Edgebreaker<'faces, T>
 	
 	
 	/// A function implementing the Edgebreaker algorithm for a connected component that contains `c`.
-	fn edgebreaker_from(&mut self, mut c: CornerIdx) -> Result<(), Err> {
+	fn edgebreaker_from(&mut self, mut c: CornerIdx, component: &mut ComponentTraversal) {
+        let mut last_encoded_symbol_idx = usize::MAX;
         self.corner_traversal_stack.clear();
         self.corner_traversal_stack.push(c);
         let num_faces = self.corner_table.num_faces();
@@ -273,17 +491,16 @@ impl<'faces, T> Edgebreaker<'faces, T>
             let mut num_visited_faces = 0;
             while num_visited_faces < num_faces {
                 num_visited_faces += 1;
-                self.last_encoded_symbol_idx = self.last_encoded_symbol_idx.wrapping_add(1); // since the initial value of 'last_encoded_symbol_idx' is usize::MAX, we do wrapping-add.
+                last_encoded_symbol_idx = last_encoded_symbol_idx.wrapping_add(1); // since the initial value of 'last_encoded_symbol_idx' is usize::MAX, we do wrapping-add.
 
                 let face_idx = self.corner_table.face_idx_containing(c);
                 self.visited_faces[face_idx] = true;
-                self.processed_connectivity_corners.push(c);
-                self.traversal.new_corner_reached(c);
+                component.events.push(TraversalEvent::Corner(c));
                 let v = self.corner_table.vertex_idx(c);
                 if !self.visited_vertices[v] {
                     self.visited_vertices[v] = true;
                     if self.vertex_hole_id[v].is_none() {
-                        self.traversal.record_symbol(Symbol::C, &self.visited_faces,  &self.corner_table);
+                        component.events.push(TraversalEvent::Symbol(Symbol::C));
                         c = self.corner_table.get_right_corner(c).unwrap(); // unwrap is safe here; we checked that the right edge is not on a boundary, and this implies that the right face exists.
                         continue;
                     }
@@ -295,7 +512,8 @@ impl<'faces, T> Edgebreaker<'faces, T>
                 if self.is_right_face_visited(c) {
                     if let Some(right_face) = maybe_right_face {
                         self.check_and_store_topology_split_event(
-                            self.last_encoded_symbol_idx,
+                            component,
+                            last_encoded_symbol_idx,
                             Orientation::Right,
                             right_face
                         );
@@ -304,18 +522,19 @@ impl<'faces, T> Edgebreaker<'faces, T>
                         // 'E' symbol
                         if let Some(left_face) = maybe_left_face {
                             self.check_and_store_topology_split_event(
-                                self.last_encoded_symbol_idx,
+                                component,
+                                last_encoded_symbol_idx,
                                 Orientation::Left,
                                 left_face
                             );
                         }
-                        self.traversal.record_symbol(Symbol::E, &self.visited_faces, &self.corner_table);
+                        component.events.push(TraversalEvent::Symbol(Symbol::E));
                         self.corner_traversal_stack.pop();
                         // End of a branch of the traversal.
                         break;
                     } else {
                         // 'R' symbol
-                        self.traversal.record_symbol(Symbol::R, &self.visited_faces, &self.corner_table);
+                        component.events.push(TraversalEvent::Symbol(Symbol::R));
                         c = maybe_left_c.unwrap(); // unwrap is safe here; we checked that the left face is not visited, which implies that the left face exist.
                     }
                 } else {
@@ -323,22 +542,23 @@ impl<'faces, T> Edgebreaker<'faces, T>
                         // 'L' symbol
                         if let Some(left_face) = maybe_left_face {
                             self.check_and_store_topology_split_event(
-                                self.last_encoded_symbol_idx,
+                                component,
+                                last_encoded_symbol_idx,
                                 Orientation::Left,
                                 left_face
                             );
                         }
-                        self.traversal.record_symbol(Symbol::L, &self.visited_faces, &self.corner_table);
+                        component.events.push(TraversalEvent::Symbol(Symbol::L));
                         c = maybe_right_c.unwrap(); // unwrap is safe here; we checked that the right face is not visited, which implies that the right face exist.
                     } else {
-                        self.traversal.record_symbol(Symbol::S, &self.visited_faces, &self.corner_table);
-                        self.num_split_symbols += 1;
+                        component.events.push(TraversalEvent::Symbol(Symbol::S));
+                        component.num_split_symbols += 1;
                         if let Some(hole_idx) = self.vertex_hole_id[v] {
                             if !self.visited_holes[hole_idx] {
                                 self.process_boundary(c, false);
                             }
                         }
-                        self.face_to_split_symbol_map.insert(usize::from(face_idx), self.last_encoded_symbol_idx);
+                        self.face_to_split_symbol_map.insert(usize::from(face_idx), last_encoded_symbol_idx);
                         *self.corner_traversal_stack.last_mut().unwrap() = maybe_left_c.unwrap();
                         self.corner_traversal_stack.push(maybe_right_c.unwrap());
                         break;
@@ -346,7 +566,6 @@ impl<'faces, T> Edgebreaker<'faces, T>
                 }
             }
         }
-        Ok(())
     }
 
     /// Checks whether the right face of the corner 'c' is visited.
@@ -372,36 +591,6 @@ impl<'faces, T> Edgebreaker<'faces, T>
     }
 
 
-    fn encode_topology_splits<W>(&mut self, writer: &mut W) -> Result<(), Err> 
-        where W: ByteWriter,
-    {
-        #[cfg(feature = "evaluation")]
-        {
-            let mut string = String::new();
-            for split in self.topology_splits.iter() {
-                string.push_str(&format!("{}:{}({:?}) ", split.merging_symbol_idx, split.split_symbol_idx, split.merging_edge_orientation));
-            }
-            eval::write_json_pair("topology_splits", serde_json::Value::from(string), writer);
-        }
-        let mut last_idx = 0;
-        // write the number of topology splits.
-        leb128_write(self.topology_splits.len() as u64, writer);
-        for split in self.topology_splits.iter() {
-            leb128_write((split.merging_symbol_idx - last_idx) as u64, writer);
-            leb128_write((split.merging_symbol_idx - split.split_symbol_idx) as u64, writer);
-            last_idx = split.merging_symbol_idx;
-        }
-        let mut bit_coder: BitWriter<'_, W, LsbFirst> = BitWriter::spown_from(writer);
-        for split in self.topology_splits.iter() {
-            let orientation = match split.merging_edge_orientation {
-                Orientation::Left => (1,0),
-                Orientation::Right => (1,1),
-            };
-            bit_coder.write_bits(orientation);
-        }
-        Ok(())
-    }
-
     /// Begins the Edgebreaker iteration from the given face.
     /// The first boolean indicates whether the face is interior (i.e. the face does not touch a boundary) or not.
     /// The second 'usize' element is a corner chosen as follows:
@@ -431,7 +620,7 @@ impl<'faces, T> Edgebreaker<'faces, T>
     }
 
 
-    fn check_and_store_topology_split_event(&mut self, merging_symbol_idx: usize, merging_edge_orientation: Orientation, split_face_idx: FaceIdx) {
+    fn check_and_store_topology_split_event(&self, component: &mut ComponentTraversal, merging_symbol_idx: usize, merging_edge_orientation: Orientation, split_face_idx: FaceIdx) {
         let split_symbol_idx = if let Some(&idx) = self.face_to_split_symbol_map.get(&usize::from(split_face_idx)) {
             idx
         } else {
@@ -444,9 +633,9 @@ impl<'faces, T> Edgebreaker<'faces, T>
             merging_edge_orientation,
         };
 
-        self.topology_splits.push(split);
+        component.topology_splits.push(split);
     }
-}	
+}
 
 impl<'faces, T> ConnectivityEncoder for Edgebreaker<'faces, T> 
     where T: Traversal
@@ -462,10 +651,10 @@ impl<'faces, T> ConnectivityEncoder for Edgebreaker<'faces, T>
     ) -> Result<Self::Output, Self::Err> 
         where W: ByteWriter
     {
-        debug_write!("Init Decoder", writer);
+        debug_write!("Init Decoder", writer, self.config.debug_markers);
         // encode the traversal decoder type
         EdgebreakerKind::Standard.write_to(writer);
-        debug_write!("Init Decoder Done", writer);
+        debug_write!("Init Decoder Done", writer, self.config.debug_markers);
 
         self.compute_boundaries()?;
 
@@ -474,41 +663,14 @@ impl<'faces, T> ConnectivityEncoder for Edgebreaker<'faces, T>
 
         writer.write_u8(self.attribute_encoding_data.len() as u8);
 
-		// Run Edgebreaker once for each connected component.
-		for c in 0..self.corner_table.num_corners() {
-            let c = CornerIdx::from(c);
-            let face_idx = self.corner_table.face_idx_containing(c);
-            if self.visited_faces[face_idx] {
-                // if the face is already visited, then skip it.
-                continue;
-            }
-
-            let (is_start_face_interior, start_corner) = self.begin_from(face_idx);
-
-            self.traversal.record_start_face_config(is_start_face_interior);
-
-            if is_start_face_interior {
-                let corner_index = start_corner;
-                let v = self.corner_table.vertex_idx(corner_index);
-                let n = self.corner_table.vertex_idx(self.corner_table.next(corner_index));
-                let p = self.corner_table.vertex_idx(self.corner_table.previous(corner_index));
-                self.visited_vertices[v] = true;
-                self.visited_vertices[n] = true;
-                self.visited_vertices[p] = true;
-
-                self.vertex_traversal_length.push(1);
-
-                self.visited_faces[face_idx] = true;
-                
-                self.init_face_connectivity_corners.push(self.corner_table.next(corner_index));
-                let corner_opp = self.corner_table.opposite(self.corner_table.next(corner_index)).unwrap(); // it is safe to unwrap since the face is interior.
-                self.edgebreaker_from(corner_opp)?;
-            } else {
-                // if the face is on the boundary, then we start from the boundary.
-                self.process_boundary(self.corner_table.next(start_corner), true);
-                self.edgebreaker_from(start_corner)?;
-            }
-		}
+		// Run Edgebreaker once for each connected component, beginning with the component of the start face if any.
+        if let Some(f) = self.config.start_face.filter(|&f| f >= faces.len()) {
+            return Err(Err::StartFaceOutOfRange(f, faces.len()));
+        }
+        let face_indices = self.config.start_face.into_iter().chain(0..faces.len());
+        for component in self.traverse_components(face_indices.map(FaceIdx::from)) {
+            self.append_component(component);
+        }
 
         // write the number of symbols.
         leb128_write(self.traversal.num_symbols() as u64, writer);
@@ -518,7 +680,7 @@ impl<'faces, T> ConnectivityEncoder for Edgebreaker<'faces, T>
 
         self.encode_topology_splits(writer)?;
         // encode the edgebreaker symbols.
-        self.traversal.encode(writer, &self.attribute_encoding_data, &self.corner_table)?;
+        self.traversal.encode(writer, &self.attribute_encoding_data, &self.corner_table, &self.config)?;
 
         self.init_face_connectivity_corners.reverse();
         self.init_face_connectivity_corners.append(&mut self.processed_connectivity_corners);
@@ -538,7 +700,7 @@ pub(crate) trait Traversal {
     fn record_start_face_config(&mut self, interior_cfg: bool);
     fn new_corner_reached(&mut self, corner: CornerIdx);
     fn num_symbols(&self) -> usize;
-    fn encode<W>(self, writer: &mut W, att_data: &[AttributeCornerTable], corner_table: &CornerTable<'_>) -> Result<(), Err> where W: ByteWriter;
+    fn encode<W>(self, writer: &mut W, att_data: &[AttributeCornerTable], corner_table: &CornerTable<'_>, config: &Config) -> Result<(), Err> where W: ByteWriter;
 }
 
 pub(crate) struct DefaultTraversal {
@@ -572,20 +734,41 @@ impl Traversal for DefaultTraversal {
         self.symbols.len()
     }
 
-    fn encode<W>(self, final_writer: &mut W, att_data: &[AttributeCornerTable], corner_table: &CornerTable<'_>) -> Result<(), Err> where W: ByteWriter {
-        let mut writer = Vec::new();
-        {
-            let mut writer: BitWriter<'_, Vec<u8>, LsbFirst> = BitWriter::spown_from(&mut writer);
-            for s in self.symbols.into_iter().rev() {
-                writer.write_bits(CrLight::encode_symbol(s)?);
-            }
-        }
+    fn encode<W>(self, final_writer: &mut W, att_data: &[AttributeCornerTable], corner_table: &CornerTable<'_>, config: &Config) -> Result<(), Err> where W: ByteWriter {
+        match config.clers_coding {
+            ClersCoding::Flat if config.symbol_packing == SymbolPacking::ByteAligned => {
+                leb128_write(self.symbols.len() as u64, final_writer);
+                for &s in self.symbols.iter().rev() {
+                    final_writer.write_u8(s.get_id() as u8);
+                }
+            },
+            ClersCoding::Flat => {
+                let mut writer = Vec::new();
+                {
+                    let mut writer: BitWriter<'_, Vec<u8>, LsbFirst> = BitWriter::spown_from(&mut writer);
+                    for &s in self.symbols.iter().rev() {
+                        writer.write_bits(CrLight::encode_symbol(s)?);
+                    }
+                }
 
-        // encode the size
-        leb128_write(writer.len() as u64, final_writer);
-        // write the encoded symbols.
-        for byte in writer {
-            final_writer.write_u8(byte);
+                // encode the size
+                leb128_write(writer.len() as u64, final_writer);
+                // write the encoded symbols.
+                for byte in writer {
+                    final_writer.write_u8(byte);
+                }
+            },
+            ClersCoding::Context => {
+                // The symbols are decoded in the reverse order, so the context is the symbol that follows in the traversal.
+                for context in split_by_context(self.symbols.iter().rev().copied()) {
+                    leb128_write(context.len() as u64, final_writer);
+                    if context.is_empty() {
+                        continue;
+                    }
+                    let context = context.iter().map(|&s| s.get_id() as u64).collect::<Vec<_>>();
+                    encode_symbols(context, 1, SymbolEncodingMethod::DirectCoded, final_writer)?;
+                }
+            },
         }
 
         
@@ -672,7 +855,7 @@ impl Traversal for ValenceTraversal {
         let mut vertex_valences = Vec::with_capacity(corner_table.num_vertices());
         for i in 0..corner_table.num_vertices() {
             let v = VertexIdx::from(i);
-            vertex_valences.push( corner_table.vertex_valence(v) );
+            vertex_valences.push( corner_table.valence(v) );
         }
 
         let mut corner_to_vertex_map = Vec::with_capacity(corner_table.num_corners());
@@ -782,7 +965,7 @@ impl Traversal for ValenceTraversal {
         self.num_symbols
     }
 
-    fn encode<W>(self, writer: &mut W, _: &[AttributeCornerTable], _: &CornerTable<'_>) -> Result<(), Err> where W: ByteWriter {
+    fn encode<W>(self, writer: &mut W, _: &[AttributeCornerTable], _: &CornerTable<'_>, _: &Config) -> Result<(), Err> where W: ByteWriter {
         // self.encode_start_faces();
         // self.encode_attribute_seams();
 
@@ -1247,3 +1430,62 @@ impl Traversal for ValenceTraversal {
 //     }
 // }
 
+
+
+#[cfg(test)]
+mod clers_coding_tests {
+    use super::*;
+    use crate::encode::connectivity::{encode_connectivity_datatype_unpacked, ConnectivityEncoderOutput};
+
+    fn encode_with(mesh: &crate::core::mesh::Mesh, clers_coding: ClersCoding) -> (Vec<u8>, Vec<CornerIdx>) {
+        let mut cfg = Config::default();
+        cfg.clers_coding = clers_coding;
+        let mut atts = mesh.get_attributes().to_vec();
+        let mut buffer = Vec::new();
+        let out = encode_connectivity_datatype_unpacked(
+            mesh.get_faces(), None, &mut atts, &mut buffer, crate::encode::connectivity::Config::Edgebreaker(cfg)
+        ).unwrap();
+        let corners = match out {
+            ConnectivityEncoderOutput::Edgebreaker(out) => out.corners_of_edgebreaker,
+            ConnectivityEncoderOutput::Sequential(_) | ConnectivityEncoderOutput::PointCloud(_) => unreachable!(),
+        };
+        (buffer, corners)
+    }
+
+    /// A grid whose quads are split along a random diagonal, so that the valences are irregular.
+    fn irregular_grid(size: usize) -> crate::core::mesh::Mesh {
+        let mut seed = 12345_u32;
+        let mut faces = Vec::new();
+        for y in 0..size-1 {
+            for x in 0..size-1 {
+                let p = |x: usize, y: usize| y * size + x;
+                seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+                if (seed >> 16) % 2 == 0 {
+                    faces.push([p(x, y), p(x+1, y), p(x, y+1)]);
+                    faces.push([p(x+1, y), p(x+1, y+1), p(x, y+1)]);
+                } else {
+                    faces.push([p(x, y), p(x+1, y), p(x+1, y+1)]);
+                    faces.push([p(x, y), p(x+1, y+1), p(x, y+1)]);
+                }
+            }
+        }
+        let pos = (0..size*size)
+            .map(|i| crate::core::shared::NdVector::from([(i % size) as f32, (i / size) as f32, 0.0]))
+            .collect::<Vec<_>>();
+        let mut builder = crate::prelude::MeshBuilder::new();
+        builder.set_connectivity_attribute(faces);
+        builder.add_attribute(pos, AttributeType::Position, crate::core::attribute::AttributeDomain::Position, vec![]);
+        builder.build().unwrap()
+    }
+
+    #[test]
+    fn test_flat_and_context_clers_coding() {
+        let mesh = irregular_grid(60);
+        let (flat, flat_corners) = encode_with(&mesh, ClersCoding::Flat);
+        let (context, context_corners) = encode_with(&mesh, ClersCoding::Context);
+
+        assert!(context.len() < flat.len(), "context: {} bytes, flat: {} bytes", context.len(), flat.len());
+        // only the coding of the symbols differs; the traversal is the same.
+        assert_eq!(flat_corners, context_corners);
+    }
+}
@@ -5,20 +5,23 @@ pub(crate) mod sequential;
 use std::fmt::Debug;
 
 use crate::core::bit_coder::ByteWriter;
+use crate::core::corner_table::CornerTable;
 use crate::core::shared::{ConfigType, PointIdx};
 use crate::encode::connectivity::edgebreaker::{DefaultTraversal, ValenceTraversal};
 use crate::prelude::{Attribute, AttributeType};
 use crate::shared::connectivity::edgebreaker::EdgebreakerKind;
+use crate::shared::header::EncoderMethod;
 
 #[cfg(feature = "evaluation")]
 use crate::eval;
 
-/// entry point for encoding connectivity.
+/// entry point for encoding connectivity, by the Edgebreaker or the sequential method of the configuration.
+/// The edgebreaker uses the given corner table of the faces if any, instead of building one.
 pub fn encode_connectivity<'faces, W>(
     faces: &'faces[[PointIdx; 3]],
+    corner_table: Option<CornerTable<'faces>>,
     atts: &mut [Attribute],
     writer: &mut W,
-    #[allow(unused)] // This parameter is unused in the current implementation, as we only support default configuration.
     cfg: &super::Config,
 ) -> Result<ConnectivityEncoderOutput<'faces>, Err>
     where W: ByteWriter
@@ -26,7 +29,22 @@ pub fn encode_connectivity<'faces, W>(
     #[cfg(feature = "evaluation")]
     eval::scope_begin("connectivity info", writer);
 
-    let result = encode_connectivity_datatype_unpacked(faces, atts, writer, Config::default());
+    let conn_cfg = match cfg.encoder_method {
+        EncoderMethod::Edgebreaker => {
+            let mut edgebreaker_cfg = edgebreaker::Config::default();
+            edgebreaker_cfg.clers_coding = cfg.clers_coding;
+            edgebreaker_cfg.symbol_packing = cfg.symbol_packing;
+            edgebreaker_cfg.debug_markers = cfg.debug_markers;
+            edgebreaker_cfg.start_face = cfg.edgebreaker_start_face;
+            Config::Edgebreaker(edgebreaker_cfg)
+        },
+        EncoderMethod::Sequential => {
+            let mut sequential_cfg = sequential::Config::default();
+            sequential_cfg.debug_markers = cfg.debug_markers;
+            Config::Sequential(sequential_cfg)
+        },
+    };
+    let result = encode_connectivity_datatype_unpacked(faces, corner_table, atts, writer, conn_cfg);
 
     #[cfg(feature = "evaluation")]
     eval::scope_end(writer);
@@ -35,6 +53,7 @@ pub fn encode_connectivity<'faces, W>(
 
 pub fn encode_connectivity_datatype_unpacked<'faces, W>(
     faces: &'faces[[PointIdx; 3]],
+    corner_table: Option<CornerTable<'faces>>,
     atts: &mut [Attribute],
     writer: &mut W,
     cfg: Config,
@@ -48,14 +67,14 @@ pub fn encode_connectivity_datatype_unpacked<'faces, W>(
             
             let result = match cfg.traversal {
                 EdgebreakerKind::Standard => {
-                    let encoder = edgebreaker::Edgebreaker::<DefaultTraversal>::new(cfg, atts, faces)?;
+                    let encoder = edgebreaker::Edgebreaker::<DefaultTraversal>::new(cfg, atts, faces, corner_table)?;
                     encoder.encode_connectivity(&faces, writer)
                 },
                 EdgebreakerKind::Predictive => {
                     unimplemented!("Predictive edgebreaker encoding is not implemented yet");
                 },
                 EdgebreakerKind::Valence => {
-                    let encoder = edgebreaker::Edgebreaker::<ValenceTraversal>::new(cfg, atts, faces)?;
+                    let encoder = edgebreaker::Edgebreaker::<ValenceTraversal>::new(cfg, atts, faces, corner_table)?;
                     encoder.encode_connectivity(&faces, writer)
                 },
             };
@@ -63,7 +82,7 @@ pub fn encode_connectivity_datatype_unpacked<'faces, W>(
             #[cfg(feature = "evaluation")]
             eval::scope_end(writer);
 
-            result.map(|o| ConnectivityEncoderOutput::Edgebreaker(o))?
+            result.map(|o| ConnectivityEncoderOutput::Edgebreaker(Box::new(o)))?
         },
         Config::Sequential(cfg) => {
             #[cfg(feature = "evaluation")]
@@ -98,8 +117,13 @@ pub trait ConnectivityEncoder {
 }
 
 pub(crate) enum ConnectivityEncoderOutput<'faces> {
-    Edgebreaker(edgebreaker::Output<'faces>),
-    Sequential(()),
+    Edgebreaker(Box<edgebreaker::Output<'faces>>),
+    /// The number of the points of the faces written as the indices of their points.
+    /// The attribute values are encoded in the order of the points, as for [ConnectivityEncoderOutput::PointCloud].
+    Sequential(usize),
+    /// The number of the points of a point cloud, which has no connectivity to encode.
+    /// The attribute values are encoded in the order of the points.
+    PointCloud(usize),
 }
 
 #[remain::sorted]
@@ -119,7 +143,6 @@ pub enum Err {
 #[derive(Clone, Debug)]
 pub enum Config {
     Edgebreaker(edgebreaker::Config),
-    #[allow(unused)] // we currently support only edgebreaker
     Sequential(sequential::Config),
 }
 
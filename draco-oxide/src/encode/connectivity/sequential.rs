@@ -31,7 +31,7 @@ impl Sequential {
             Ok(index_size) => index_size as u8,
             Err(err) => return Err(Err::SharedError(err)),
         };
-        debug_write!("Start of indices", writer);
+        debug_write!("Start of indices", writer, self.cfg.debug_markers);
         
         if index_size == 21 {
             // varint encoding
@@ -68,33 +68,38 @@ impl Sequential {
 impl ConnectivityEncoder for Sequential {
     type Err = Err;
     type Config = Config;
-    type Output = ();
+    /// The number of the points, whose attribute values are encoded in their order.
+    type Output = usize;
 
     fn encode_connectivity<W>(
         self, 
         faces: &[[PointIdx; 3]],
         writer: &mut W
-    ) -> Result<(), Err> 
+    ) -> Result<usize, Err> 
         where  W: ByteWriter,
     {
-        writer.write_u64(faces.len() as u64);
+        leb128_write(faces.len() as u64, writer);
+        leb128_write(self.num_points as u64, writer);
         let encoder_method_id = self.cfg.encoder_method.get_id();
         writer.write_u8(encoder_method_id);
         self.encode_direct_indices(faces, writer)?;
 
-        Ok(())
+        Ok(self.num_points)
     }
 }
 
 #[derive(Clone, Debug)]
 pub struct Config {
     pub encoder_method: Method,
+    /// Whether the debug markers are written, see [crate::encode::Config::debug_markers].
+    pub debug_markers: bool,
 }
 
 impl ConfigType for Config {
     fn default() -> Self {
         Config {
             encoder_method: Method::DirectIndices,
+            debug_markers: false,
         }
     }
 }
@@ -178,13 +178,25 @@ impl<'writer, W, const NUM_SYMBOLS_BIT_LENGTH: usize, const RANS_PRECISION: usiz
             if total_rans_prob < rans_precision {
                 distribution[*sorted_probabilities.last().unwrap()] += rans_precision - total_rans_prob;
             } else {
-                // ToDo: Do better descrete normalization.
+                // Take the excess from the most frequent symbols in proportion to their frequencies as Draco does,
+                // keeping at least one for each symbol that occurs.
                 let mut err = total_rans_prob - rans_precision;
-                let mut i = distribution.len() - 1;
                 while err > 0 {
-                    distribution[sorted_probabilities[i]] -= 1;
-                    i-=1;   
-                    err -= 1;
+                    if distribution[*sorted_probabilities.last().unwrap()] <= 1 {
+                        return Err(Err::TooManySymbols(num_symbols));
+                    }
+                    let scale = rans_precision as f64 / total_rans_prob as f64;
+                    for &s in sorted_probabilities.iter().rev() {
+                        if distribution[s] <= 1 || err == 0 {
+                            break;
+                        }
+                        let fix = (distribution[s] - (distribution[s] as f64 * scale) as usize)
+                            .clamp(1, distribution[s] - 1)
+                            .min(err);
+                        distribution[s] -= fix;
+                        total_rans_prob -= fix;
+                        err -= fix;
+                    }
                 }
             }
         }
@@ -198,17 +210,14 @@ impl<'writer, W, const NUM_SYMBOLS_BIT_LENGTH: usize, const RANS_PRECISION: usiz
             let freq = distribution[i];
             if freq == 0 {
                 // when we find a symbol with zero frequency, we encode the flag (1-bit) and the 
-                // 6-bit offset to the next symbol with non-zero frequency.
+                // 6-bit number of the symbols with zero frequency that follow it. A longer run takes
+                // several flags. The last symbol has a non-zero frequency, so the run ends before it.
                 let mut offset = 0;
-                while offset < (1 << 6) {
-                    let next_prob = distribution[i + offset + 1];
-                    if next_prob > 0 {
-                        i += offset;
-                        break;
-                    }
+                while offset < (1 << 6) - 1 && distribution[i + offset + 1] == 0 {
                     offset += 1;
                 }
                 writer.write_u8(((offset as u8) << 2) | 3);
+                i += offset;
             } else {
                 let mut num_extra_bytes = 0;
                 if freq >= (1 << 6) {
@@ -264,6 +273,8 @@ pub enum Err {
     SharedError(#[from] crate::shared::entropy::Err),
     #[error("State too large for RANS coder")]
     StateTooLarge,
+    #[error("Too many symbols for the precision of the RANS coder: {0}")]
+    TooManySymbols(usize),
     #[error("Too many zero frequency counts in RANS coder")]
     TooManyZeroFreqCounts,
 }
\ No newline at end of file
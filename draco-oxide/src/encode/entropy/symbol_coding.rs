@@ -12,29 +12,46 @@ pub enum Err {
     InvalidInputs(usize, usize, usize),
     #[error("Invalid bit length: {0}")]
     InvalidBitLength(usize),
+    #[error("The magnitude {0} does not fit in a zigzag-mapped symbol")]
+    MagnitudeOverflow(u64),
 }
 
+/// The largest bit length of the symbols coded by [SymbolEncodingMethod::DirectCoded], whose frequency table
+/// has an entry for each symbol up to the largest one. Larger symbols are coded by their bit lengths as in Draco.
+const MAX_DIRECT_CODED_BIT_LENGTH: u32 = 18;
+
+/// Encodes the symbols with the given method. [SymbolEncodingMethod::DirectCoded] is replaced by
+/// [SymbolEncodingMethod::LengthCoded] if the latter is estimated to be smaller or if the symbols are too large
+/// for the frequency table of the former, as Draco does, so that the stream does not grow with the range of the symbols.
 pub fn encode_symbols<W>(
     symbols: Vec<u64>, num_components: usize, config: SymbolEncodingMethod, writer: &mut W
 ) -> Result<(), Err> 
     where W: ByteWriter
 {
+    let bit_lengths = || (0..symbols.len()/num_components)
+        .map(|i| symbols[i * num_components..(i + 1) * num_components].iter()
+            .map(|s| (64 - s.leading_zeros()) as u8)
+            .max()
+            .unwrap_or(0)
+        )
+        .collect::<Vec<_>>();
+    let config = match config {
+        SymbolEncodingMethod::DirectCoded => {
+            let max_symbol = symbols.iter().max().copied().unwrap_or(0);
+            let bit_lengths = bit_lengths();
+            let tagged_bits = approximate_tagged_bits(&bit_lengths, num_components);
+            if 64 - max_symbol.leading_zeros() > MAX_DIRECT_CODED_BIT_LENGTH || tagged_bits < approximate_direct_bits(&symbols) {
+                SymbolEncodingMethod::LengthCoded
+            } else {
+                SymbolEncodingMethod::DirectCoded
+            }
+        },
+        config => config,
+    };
     config.write_to(writer);
-    // ToDo: Add the logic to dynamically determine the config
     match config {
         SymbolEncodingMethod::LengthCoded => {
-            let mut bit_lengths = Vec::new();
-            for i in 0..symbols.len()/num_components {
-                let mut max_bit_length = 0;
-                for j in 0..num_components {
-                    let s = symbols[i * num_components + j];
-                    let bit_length = (64-s.leading_zeros()) as usize;
-                    if bit_length > max_bit_length {
-                        max_bit_length = bit_length;
-                    }
-                }
-                bit_lengths.push(max_bit_length as u8);
-            }
+            let bit_lengths = bit_lengths();
             encode_symbols_length_coded(
                 symbols, 
                 num_components, 
@@ -54,6 +71,41 @@ pub fn encode_symbols<W>(
     }
 }
 
+/// The approximate number of bits of the symbols coded by their entropy, i.e. their Shannon entropy.
+fn approximate_entropy_bits<T: Copy + Eq + std::hash::Hash>(symbols: &[T]) -> u64 {
+    let mut freq_counts = std::collections::HashMap::new();
+    for &s in symbols {
+        *freq_counts.entry(s).or_insert(0_usize) += 1;
+    }
+    let total = symbols.len() as f64;
+    freq_counts.values()
+        .map(|&count| -(count as f64) * (count as f64 / total).log2())
+        .sum::<f64>() as u64
+}
+
+/// The approximate number of bits of the frequency table of the rANS coder, whose zero frequencies are
+/// coded in runs of up to 64, as in Draco.
+fn approximate_frequency_table_bits(max_symbol: u64, num_unique_symbols: u64) -> u64 {
+    let zero_frequency_bits = 8 * (num_unique_symbols + max_symbol.saturating_sub(num_unique_symbols) / 64);
+    8 * num_unique_symbols + zero_frequency_bits
+}
+
+/// The approximate number of bits of the symbols coded by [SymbolEncodingMethod::DirectCoded].
+fn approximate_direct_bits(symbols: &[u64]) -> u64 {
+    let max_symbol = symbols.iter().max().copied().unwrap_or(0);
+    let num_unique_symbols = symbols.iter().collect::<std::collections::HashSet<_>>().len() as u64;
+    approximate_entropy_bits(symbols) + approximate_frequency_table_bits(max_symbol, num_unique_symbols)
+}
+
+/// The approximate number of bits of the symbols coded by [SymbolEncodingMethod::LengthCoded], i.e. the
+/// entropy of the bit lengths and the bits of the values.
+fn approximate_tagged_bits(bit_lengths: &[u8], num_components: usize) -> u64 {
+    let max_bit_length = bit_lengths.iter().max().copied().unwrap_or(0) as u64;
+    let num_unique_bit_lengths = bit_lengths.iter().collect::<std::collections::HashSet<_>>().len() as u64;
+    let value_bits = bit_lengths.iter().map(|&l| l as u64).sum::<u64>() * num_components as u64;
+    approximate_entropy_bits(bit_lengths) + approximate_frequency_table_bits(max_bit_length, num_unique_bit_lengths) + value_bits
+}
+
 
 /// Encodes symbols using the rANS coder as the tag encoder, that is, the symbols are encoded as bits, and the 
 /// bit lengths are encoded by the rANS coder.
@@ -163,4 +215,60 @@ fn encode_symbols_direct_coded_precision_unwrapped<W, const NUM_SYMBOLS_BIT_LENG
     }
     encoder.flush()?;
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Encodes the zigzag-mapped corrections as their magnitudes, followed by the signs of the nonzero ones.
+/// The magnitudes and the signs are coded with separate rANS models, so that a skewed sign distribution is
+/// coded in less than a bit per value and the magnitudes need half the alphabet of the zigzag-mapped values.
+/// The signs are omitted if all the magnitudes are zero, as their number is known from the magnitudes.
+pub fn encode_signed_symbols<W>(symbols: Vec<u64>, num_components: usize, writer: &mut W) -> Result<(), Err>
+    where W: ByteWriter
+{
+    let (magnitudes, signs) = split_signs(&symbols);
+    encode_symbols(magnitudes, num_components, SymbolEncodingMethod::DirectCoded, writer)?;
+    if !signs.is_empty() {
+        encode_symbols(signs, 1, SymbolEncodingMethod::DirectCoded, writer)?;
+    }
+    Ok(())
+}
+
+/// Splits the zigzag-mapped symbols into their magnitudes and the signs of the nonzero ones, where '1' is negative.
+pub(crate) fn split_signs(symbols: &[u64]) -> (Vec<u64>, Vec<u64>) {
+    let magnitudes = symbols.iter().map(|&s| s.div_ceil(2)).collect();
+    let signs = symbols.iter().filter(|&&s| s > 0).map(|&s| s & 1).collect();
+    (magnitudes, signs)
+}
+
+/// Restores the zigzag-mapped symbols from the output of [split_signs]. The magnitudes are read from the stream,
+/// so the ones too large for a symbol are rejected.
+pub(crate) fn merge_signs(magnitudes: &[u64], signs: &[u64]) -> Result<Vec<u64>, Err> {
+    let mut signs = signs.iter();
+    magnitudes.iter()
+        .map(|&m| match m {
+            0 => Ok(0),
+            _ => m.checked_mul(2)
+                .map(|s| s - (signs.next().copied().unwrap_or(0) & 1))
+                .ok_or(Err::MagnitudeOverflow(m)),
+        })
+        .collect()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::to_positive_i32;
+
+    #[test]
+    fn test_split_signs() {
+        let values = [0, 1, -1, 2, -2, 7, -8, 0, 0, -3];
+        let symbols = values.iter().map(|&v| to_positive_i32(v) as u64).collect::<Vec<_>>();
+        let (magnitudes, signs) = split_signs(&symbols);
+        assert_eq!(magnitudes, values.iter().map(|v: &i32| v.unsigned_abs() as u64).collect::<Vec<_>>());
+        assert_eq!(signs, vec![0, 1, 0, 1, 0, 1, 1]);
+        assert_eq!(merge_signs(&magnitudes, &signs).unwrap(), symbols);
+
+        // a corrupted magnitude does not overflow.
+        assert_eq!(merge_signs(&[u64::MAX], &[1]), Err(Err::MagnitudeOverflow(u64::MAX)));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{core::bit_coder::ByteWriter, shared::header::EncoderMethod};
+use crate::{core::{attribute::AttributeLayout, bit_coder::ByteWriter}, shared::header::EncoderMethod};
 
 #[remain::sorted]
 #[derive(thiserror::Error, Debug)]
@@ -7,7 +7,6 @@ pub enum Err {
 
 #[derive(Clone, Debug)]
 pub enum EncodedGeometryType {
-    #[allow(unused)]
     PointCloud,
     TrianglarMesh,
 }
@@ -21,7 +20,33 @@ impl EncodedGeometryType {
     }
 }
 
-const METADATA_FLAG_MASK: u16 = 32768;
+/// The version of the Draco bitstream that the encoder writes, which the C++ decoder of Draco reads.
+pub(crate) const VERSION_MAJOR: u8 = 2;
+pub(crate) const VERSION_MINOR: u8 = 2;
+
+/// Set when the metadata, e.g. the names of the attributes, is written after the header.
+pub(crate) const METADATA_FLAG_MASK: u16 = 32768;
+/// Set when the attribute values are hinted to be decoded in [AttributeLayout::Planar].
+/// Draco only reads the metadata bit of the flags, so this bit is ignored by the other decoders.
+pub(crate) const PLANAR_LAYOUT_FLAG_MASK: u16 = 16384;
+/// Set when the attribute values are encoded in the vertex order given by the user,
+/// which is then written before the attributes.
+pub(crate) const CUSTOM_VERTEX_ORDER_FLAG_MASK: u16 = 8192;
+/// Set when the CLERS symbols are coded with [super::ClersCoding::Context] instead of the flat code.
+pub(crate) const CONTEXT_CLERS_FLAG_MASK: u16 = 4096;
+/// Set when the debug markers are written between the sections of the stream.
+pub(crate) const DEBUG_MARKERS_FLAG_MASK: u16 = 2048;
+/// Set when the CLERS symbols and the topology splits are stored with [super::SymbolPacking::ByteAligned].
+pub(crate) const BYTE_ALIGNED_SYMBOLS_FLAG_MASK: u16 = 1024;
+/// Set when the points that no face refers to are written after the attributes, see [super::Config::loose_points].
+pub(crate) const LOOSE_POINTS_FLAG_MASK: u16 = 512;
+/// Set when the point cloud is encoded by [super::encode] as the values of all its attributes in the order of
+/// the points, instead of the positions alone by [super::encode_point_cloud].
+pub(crate) const POINT_SEQUENCE_FLAG_MASK: u16 = 256;
+/// Set when the points are coded by the KD-tree of draco-oxide, see [super::PointCloudMethod::KdTree].
+/// Its layout is not that of Draco's KD-tree encoder, so the stream can only be decoded by draco-oxide,
+/// and the method id of Draco's KD-tree without this flag is rejected by the decoder.
+pub(crate) const KD_TREE_FLAG_MASK: u16 = 128;
 
 pub fn encode_header<W>(writer: &mut W, cfg: &super::Config) -> Result<(), Err>
 where
@@ -33,23 +58,99 @@ where
     });
 
     // Write the version
-    writer.write_u8(2);
-    writer.write_u8(2);
+    writer.write_u8(VERSION_MAJOR);
+    writer.write_u8(VERSION_MINOR);
 
     // Write encoder type
     let id = cfg.geometry_type.get_id();
     writer.write_u8(id);
 
     // Write the encoding method
-    // Currently, we only support the edgebreaker method
-    EncoderMethod::Edgebreaker.write_to(writer);
+    match cfg.geometry_type {
+        EncodedGeometryType::PointCloud => writer.write_u8(cfg.point_cloud_method.get_id()),
+        EncodedGeometryType::TrianglarMesh => cfg.encoder_method.write_to(writer),
+    }
 
     // Write the connectivity encoder config
+    let mut flags = 0;
     if cfg.metdata {
-        writer.write_u16(METADATA_FLAG_MASK);
-    } else {
-        writer.write_u16(0);
+        flags |= METADATA_FLAG_MASK;
+    }
+    if cfg.attribute_layout == AttributeLayout::Planar {
+        flags |= PLANAR_LAYOUT_FLAG_MASK;
+    }
+    if cfg.vertex_order.is_some() {
+        flags |= CUSTOM_VERTEX_ORDER_FLAG_MASK;
+    }
+    if cfg.clers_coding == super::ClersCoding::Context {
+        flags |= CONTEXT_CLERS_FLAG_MASK;
+    }
+    if cfg.debug_markers {
+        flags |= DEBUG_MARKERS_FLAG_MASK;
+    }
+    if cfg.symbol_packing == super::SymbolPacking::ByteAligned {
+        flags |= BYTE_ALIGNED_SYMBOLS_FLAG_MASK;
+    }
+    if cfg.loose_points {
+        flags |= LOOSE_POINTS_FLAG_MASK;
+    }
+    if matches!(cfg.geometry_type, EncodedGeometryType::PointCloud) && cfg.encoder_method == EncoderMethod::Sequential {
+        flags |= POINT_SEQUENCE_FLAG_MASK;
+    } else if matches!(cfg.geometry_type, EncodedGeometryType::PointCloud) && cfg.point_cloud_method == super::PointCloudMethod::KdTree {
+        flags |= KD_TREE_FLAG_MASK;
     }
+    writer.write_u16(flags);
 
     Ok(())
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::shared::ConfigType;
+
+    #[test]
+    fn test_debug_markers() {
+        let mesh = crate::io::obj::load_obj("tests/data/tetrahedron.obj").unwrap();
+        let encode = |debug_markers| {
+            let mut cfg = crate::encode::Config::default();
+            cfg.debug_markers = debug_markers;
+            let mut buffer = Vec::new();
+            crate::encode::encode(mesh.clone(), &mut buffer, cfg).unwrap();
+            buffer
+        };
+        let without = encode(false);
+        let mut with = encode(true);
+
+        // the flags follow the magic, the version, the geometry type, and the method.
+        let flags_at = |buffer: &[u8]| buffer.windows(5).position(|w| w == b"DRACO").unwrap() + 9;
+        let flags = |buffer: &[u8]| u16::from_le_bytes([buffer[flags_at(buffer)], buffer[flags_at(buffer) + 1]]);
+        assert_eq!(flags(&without) & DEBUG_MARKERS_FLAG_MASK, 0);
+        assert_eq!(flags(&with) & DEBUG_MARKERS_FLAG_MASK, DEBUG_MARKERS_FLAG_MASK);
+        assert!(!without.windows(8).any(|w| w == b"All done"));
+
+        // the markers are written in this order, and the stream is otherwise the same.
+        let mut markers = vec![
+            "Header done, now starting metadata.",
+            "Metadata done, now starting connectivity.",
+            "Init Decoder",
+            "Init Decoder Done",
+            "Connectivity done, now starting attributes.",
+        ];
+        for _ in mesh.get_attributes() {
+            markers.extend(["Start of Portabilization Metadata", "End of Portabilization Metadata"]);
+        }
+        markers.push("All done");
+        let mut pos = 0;
+        for marker in markers {
+            let found = with[pos..].windows(marker.len())
+                .position(|w| w == marker.as_bytes())
+                .unwrap_or_else(|| panic!("the marker {:?} is missing", marker));
+            with.drain(pos + found..pos + found + marker.len());
+            pos += found;
+        }
+        let at = flags_at(&with);
+        with[at..at + 2].copy_from_slice(&flags(&without).to_le_bytes());
+        assert_eq!(with, without);
+    }
+}
@@ -1,20 +1,117 @@
 use crate::core::bit_coder::ByteWriter;
+use crate::core::mesh::metadata::Metadata;
+use crate::utils::bit_coder::leb128_write;
+
+/// The key of the metadata entry that keeps the name of an attribute.
+pub(crate) const ATTRIBUTE_NAME_KEY: &str = "name";
 
 #[remain::sorted]
 #[derive(thiserror::Error, Debug)]
 pub enum Err {
-    
+    #[error("The metadata {0} is longer than 255 bytes")]
+    TooLong(String),
+}
+
+/// Whether the mesh has any metadata to encode, i.e. whether any of its attributes is named.
+pub(crate) fn has_metadata(mesh: &crate::core::mesh::Mesh) -> bool {
+    mesh.get_attributes().iter().any(|att| att.get_name().is_some())
 }
 
+/// Encodes the metadata in the layout of Draco: the metadata of each attribute, keyed by the unique id of
+/// the attribute, followed by the metadata of the whole geometry. The name of an attribute is stored in
+/// the entry 'name' of its metadata.
 pub fn encode_metadata<W>(
-    _mesh: &crate::core::mesh::Mesh,
+    mesh: &crate::core::mesh::Mesh,
     writer: &mut W,
 ) -> Result<(), Err>     
     where W: ByteWriter,
 {
-    // Write Encoder
-    writer.write_u32(0);
+    let attribute_metadata = mesh.get_attributes().iter()
+        .filter_map(|att| {
+            let name = att.get_name()?;
+            let mut metadata = Metadata::new();
+            metadata.add_entry(ATTRIBUTE_NAME_KEY.to_string(), name.clone());
+            Some((att.get_id().as_usize(), metadata))
+        })
+        .collect::<Vec<_>>();
+
+    leb128_write(attribute_metadata.len() as u64, writer);
+    for (id, metadata) in &attribute_metadata {
+        leb128_write(*id as u64, writer);
+        write_metadata(metadata, writer)?;
+    }
+    // the metadata of the geometry.
+    write_metadata(&Metadata::new(), writer)
+}
+
+/// Writes the entries of the metadata in the order of their keys, and no sub-metadata.
+fn write_metadata<W>(metadata: &Metadata, writer: &mut W) -> Result<(), Err>
+    where W: ByteWriter,
+{
+    let mut entries = metadata.entries().collect::<Vec<_>>();
+    entries.sort();
+    leb128_write(entries.len() as u64, writer);
+    for (key, value) in entries {
+        write_string(key, writer)?;
+        write_string(value, writer)?;
+    }
+    leb128_write(0, writer);
+    Ok(())
+}
 
-    // ToDo: Implement metadata encoding
+fn write_string<W>(s: &str, writer: &mut W) -> Result<(), Err>
+    where W: ByteWriter,
+{
+    if s.len() > u8::MAX as usize {
+        return Err(Err::TooLong(s.to_string()));
+    }
+    writer.write_u8(s.len() as u8);
+    for &b in s.as_bytes() {
+        writer.write_u8(b);
+    }
     Ok(())
-}
\ No newline at end of file
+}
+
+
+#[cfg(all(test, not(feature = "evaluation")))]
+mod tests {
+    use crate::core::attribute::{Attribute, AttributeDomain, AttributeId};
+    use crate::core::shared::{ConfigType, NdVector};
+    use crate::prelude::{AttributeType, SliceReader};
+
+    #[test]
+    fn test_attribute_name_round_trip() {
+        let mut mesh = crate::io::obj::load_obj("tests/data/tetrahedron.obj").unwrap();
+        let num_points = mesh.get_attributes()[0].len();
+        let id = AttributeId::new(mesh.get_attributes().len());
+        let mut feature_id = Attribute::from(
+            id, (0..num_points).map(|p| NdVector::from([p as u32])).collect(), AttributeType::Custom, AttributeDomain::Position, Vec::new()
+        );
+        feature_id.set_name("feature_id".to_string());
+        mesh.attributes.push(feature_id);
+
+        let mut cfg = crate::encode::Config::default();
+        cfg.debug_markers = false;
+        let mut encoded = Vec::new();
+        crate::encode::encode(mesh.clone(), &mut encoded, cfg.clone()).unwrap();
+        // the metadata follows the header of 11 bytes, whose flags tell that it is there.
+        let flags = u16::from_le_bytes([encoded[9], encoded[10]]);
+        assert_ne!(flags & crate::encode::header::METADATA_FLAG_MASK, 0);
+        let metadata = crate::decode::metadata::read_attribute_metadata(&mut SliceReader::new(&encoded[11..])).unwrap();
+        assert_eq!(metadata.len(), 1);
+
+        // the decoder names the attribute after its metadata, and leaves the others unnamed.
+        let decoded = crate::decode::decode(&mut SliceReader::new(&encoded), crate::decode::Config::default()).unwrap();
+        let names = decoded.get_attributes().iter().map(|att| att.get_name().map(String::as_str)).collect::<Vec<_>>();
+        assert_eq!(names.iter().filter(|name| name.is_some()).collect::<Vec<_>>(), vec![&Some("feature_id")]);
+        let feature_id = decoded.get_attributes().iter().find(|att| att.get_name().is_some()).unwrap();
+        assert_eq!(feature_id.get_attribute_type(), AttributeType::Custom);
+        assert_eq!(feature_id.get_num_components(), 1);
+
+        // the mesh without names is encoded without metadata, as before.
+        mesh.attributes.pop();
+        let mut unnamed = Vec::new();
+        crate::encode::encode(mesh, &mut unnamed, cfg).unwrap();
+        assert_eq!(u16::from_le_bytes([unnamed[9], unnamed[10]]) & crate::encode::header::METADATA_FLAG_MASK, 0);
+    }
+}
@@ -3,13 +3,28 @@ pub(crate) mod metadata;
 pub(crate) mod connectivity;
 pub(crate) mod attribute;
 pub(crate) mod entropy;
+pub(crate) mod point_cloud;
+pub(crate) mod stats;
 
-use crate::core::mesh::Mesh;
+use crate::core::attribute::{Attribute, AttributeId, AttributeType, ComponentDataType};
+use crate::core::corner_table::CornerTable;
+use crate::core::mesh::{Mesh, topology::Topology};
+use crate::core::scene::Scene;
 use crate::{debug_write, shared};
-use crate::core::shared::ConfigType;
-use crate::core::bit_coder::ByteWriter;
+use crate::core::shared::{ConfigType, PointIdx};
+use crate::core::bit_coder::{ByteWriter, CountingWriter};
+use std::collections::HashMap;
 use thiserror::Error;
 
+pub use crate::shared::connectivity::edgebreaker::symbol_encoder::{ClersCoding, SymbolPacking};
+pub use point_cloud::PointCloudMethod;
+pub use attribute::portabilization::QuantizationGrid;
+pub use attribute::{MismatchedComponents, NormalPrediction, NormalTransform, PositionPrediction, UnsupportedComponents};
+pub use stats::{AttributeStats, EncodeStats};
+pub use attribute::portabilization::PortabilizationType;
+pub use attribute::prediction_transform::PredictionTransformType;
+pub use crate::shared::attribute::prediction_scheme::PredictionSchemeType;
+
 #[cfg(feature = "evaluation")]
 use crate::eval;
 
@@ -26,7 +41,137 @@ pub struct Config {
     attribute_encoder_cfg: attribute::Config,
     geometry_type: header::EncodedGeometryType,
     encoder_method: shared::header::EncoderMethod,
-    metdata: bool
+    metdata: bool,
+
+    /// If true, positions are predicted by the average of their already-encoded neighbors
+    /// instead of the parallelogram prediction. This tends to work better for densely and uniformly sampled geometry.
+    /// The neighbors are those on the faces for a mesh; the points of a point cloud have none, so each point is
+    /// predicted by the average of the points encoded last instead of the previous point.
+    pub average_position_prediction: bool,
+
+    /// How the positions are predicted, unless [Config::average_position_prediction] is set.
+    pub position_prediction: PositionPrediction,
+
+    /// How the normals are predicted. The normals are encoded with the octahedral transform regardless.
+    pub normal_prediction: NormalPrediction,
+
+    /// How the corrections of the normals are transformed on the octahedron. Both transforms are Draco's.
+    pub normal_transform: NormalTransform,
+
+    /// If true, the 'xyz' part of each tangent (4 components of f32) is encoded with the octahedral quantization,
+    /// and the sign of 'w' (handedness) is stored in a separate bit stream so that it remains exact.
+    /// The octahedral quantization keeps only the direction of the 'xyz' part, so the encoding fails with
+    /// [attribute::Err::NonUnitTangent] if the 'xyz' part of a tangent is not of unit length.
+    pub separate_tangent_handedness: bool,
+
+    /// The layout in which the decoder is hinted to output the attribute values.
+    /// The hint is stored in the header flags and does not change the encoded attribute data.
+    pub attribute_layout: crate::core::attribute::AttributeLayout,
+
+    /// If set, the attribute values are encoded in this order of the vertices (the unique values of the position attribute)
+    /// instead of the order of the Edgebreaker traversal, e.g. an order given by a GPU vertex cache optimizer.
+    /// It must be a permutation of the vertices, and it is written to the stream so that the decoder can restore it.
+    pub vertex_order: Option<Vec<crate::core::shared::VertexIdx>>,
+
+    /// How the CLERS symbols of the Edgebreaker are coded. [ClersCoding::Context] is only understood by draco-oxide;
+    /// the choice is stored in the header flags.
+    pub clers_coding: ClersCoding,
+
+    /// How the CLERS symbols and the topology splits of the Edgebreaker are stored. [SymbolPacking::ByteAligned]
+    /// is only understood by draco-oxide; the choice is stored in the header flags.
+    pub symbol_packing: SymbolPacking,
+
+    /// If true, no quantization is applied; the raw bits of the float attributes are entropy coded
+    /// in the traversal order without prediction, so that the decoded geometry is bit-exact.
+    pub lossless: bool,
+
+    /// If true, the texture coordinates are quantized as the integer wrap counts and the fractional parts in [0, 1),
+    /// so that the coordinates of tiled textures keep the precision of one tile however many tiles they span.
+    pub wrapped_texture_coordinates: bool,
+
+    /// How the positions are coded by [encode_point_cloud].
+    pub point_cloud_method: PointCloudMethod,
+
+    /// Attributes with fewer unique values than this are stored without prediction, as the metadata
+    /// of the prediction would cost more than it saves.
+    pub min_values_for_prediction: usize,
+
+    /// If set, the positions are quantized on this grid instead of the bounding box of the mesh,
+    /// so that the same coordinates are quantized to the same integers in every mesh encoded with the grid.
+    pub quantization_grid: Option<QuantizationGrid>,
+
+    /// If set, every quantized attribute is quantized with this many bits instead of the default of its type.
+    /// The bits must be in '1..=30'. The positions quantized on [Config::quantization_grid] keep the bits of the grid.
+    pub quantization_bits: Option<u8>,
+
+    /// The quantization bits of the attributes of each type, e.g. 14 for the positions and 8 for the texture coordinates,
+    /// instead of [Config::quantization_bits] or the default of the type. The bits must be in '1..=30'.
+    /// The positions quantized on [Config::quantization_grid] keep the bits of the grid.
+    pub attribute_quantization: HashMap<AttributeType, u8>,
+
+    /// If set, the normals are quantized on the octahedron with this many bits instead of [Config::quantization_bits]
+    /// or the default of 8 bits. The bits must be in '2..=30'. Their octahedral coordinates are laid out as in Draco,
+    /// e.g. for 'KHR_draco_mesh_compression'.
+    pub normal_quantization_bits: Option<u8>,
+
+    /// What is done with the attributes of a number of components that cannot be predicted, e.g. custom
+    /// attributes of more than four components. By default, their raw bits are encoded.
+    pub unsupported_components: UnsupportedComponents,
+
+    /// What is done with the attributes whose number of components is not one of their type, e.g. a normal
+    /// of other than three components. By default, the encoding fails before anything is written.
+    /// This is checked before [Config::unsupported_components], which only applies to the accepted attributes.
+    pub mismatched_components: MismatchedComponents,
+
+    /// If true, float positions whose coordinates are all integers, e.g. snapped to a grid, are encoded as the integers
+    /// of i32 without quantization, so that they are restored exactly as floats and no quantization metadata is written.
+    /// The other positions, including those of integer types, are quantized as usual.
+    pub integer_positions: bool,
+
+    /// If true, the custom attributes of one component, e.g. a weight updated every frame of an animation, are
+    /// predicted by the previous value, and those of floats are quantized instead of being encoded as their raw bits,
+    /// which is lossy. Otherwise they are encoded as the other custom attributes, i.e. losslessly.
+    pub scalar_channels: bool,
+
+    /// Pairs of the attributes predicted jointly, as '(parent, child)'. The child is predicted together with
+    /// the parent, which must be encoded before it, so that the correction stream of the child only carries
+    /// what the parent does not explain, e.g. for a displacement that follows the position.
+    /// The two attributes must have the same number of components and be quantized coordinate-wise.
+    pub joint_prediction: Vec<(crate::core::attribute::AttributeId, crate::core::attribute::AttributeId)>,
+
+    /// The attributes whose corrections are entropy coded as their magnitudes and signs, with a separate model
+    /// for the signs, instead of zigzag-mapped to unsigned integers. This can be smaller when the corrections are
+    /// centered at zero or their signs are skewed. Only the attributes predicted with a difference transform are
+    /// affected, and the choice is stored in the stream, which only draco-oxide decodes.
+    pub signed_corrections: Vec<crate::core::attribute::AttributeId>,
+
+    /// Color attributes with at most this many distinct values are encoded as a palette of the colors,
+    /// stored once, and the index of the color of each vertex, which are entropy coded. The colors are
+    /// restored exactly. Zero disables the palette; the palette is only understood by draco-oxide.
+    pub color_palette_threshold: usize,
+
+    /// If greater than one, zeros are appended to the stream so that its length is a multiple of this many bytes,
+    /// e.g. 4 or 16 for a transport that wants aligned blobs, or the size of a fixed record. The decoder stops at
+    /// the end of the geometry, so it ignores the padding.
+    pub output_alignment: usize,
+
+    /// If set, the Edgebreaker traversal starts from this face instead of the first face of the mesh,
+    /// e.g. a face on the boundary, which changes the size of the connectivity and the order of the attributes.
+    /// The other connected components follow in the order of their faces. The decoder does not need it.
+    pub edgebreaker_start_face: Option<usize>,
+
+    /// If true, the points that no face refers to, e.g. markers next to a surface, are encoded after the attributes
+    /// with the point cloud coding of [PointCloudMethod::KdTree], and the decoder appends them to the points of
+    /// the faces. Otherwise the Edgebreaker drops the ones after the last point of the faces and rejects the others.
+    /// Only their positions are encoded, so their order is not kept, and the encoding fails if the mesh has
+    /// other attributes than the positions. The choice is stored in the header flags, and it is only understood
+    /// by draco-oxide.
+    pub loose_points: bool,
+
+    /// If true, text markers are written between the sections of the stream to help debugging a decoder.
+    /// The choice is stored in the header flags so that the decoder knows whether to expect them.
+    /// Enabled by default with the 'debug_format' feature.
+    pub debug_markers: bool,
 }
 
 impl ConfigType for Config {
@@ -37,6 +182,34 @@ impl ConfigType for Config {
             geometry_type: header::EncodedGeometryType::TrianglarMesh,
             encoder_method: shared::header::EncoderMethod::Edgebreaker,
             metdata: false,
+            average_position_prediction: false,
+            position_prediction: PositionPrediction::Parallelogram,
+            normal_prediction: NormalPrediction::Geometric,
+            normal_transform: NormalTransform::Octahedral,
+            separate_tangent_handedness: false,
+            attribute_layout: crate::core::attribute::AttributeLayout::Interleaved,
+            vertex_order: None,
+            clers_coding: ClersCoding::Flat,
+            symbol_packing: SymbolPacking::BitPacked,
+            lossless: false,
+            wrapped_texture_coordinates: false,
+            point_cloud_method: PointCloudMethod::Sequential,
+            min_values_for_prediction: 3,
+            quantization_grid: None,
+            quantization_bits: None,
+            attribute_quantization: HashMap::new(),
+            normal_quantization_bits: None,
+            unsupported_components: UnsupportedComponents::RawBits,
+            mismatched_components: MismatchedComponents::Reject,
+            integer_positions: false,
+            scalar_channels: false,
+            joint_prediction: Vec::new(),
+            signed_corrections: Vec::new(),
+            color_palette_threshold: 0,
+            output_alignment: 1,
+            edgebreaker_start_face: None,
+            loose_points: false,
+            debug_markers: cfg!(feature = "debug_format"),
         }
     }
 }
@@ -46,52 +219,1100 @@ impl ConfigType for Config {
 pub enum Err {
     #[error("Attribute encoding error: {0}")]
     AttributeError(#[from] attribute::Err),
+    #[error("The stream does not fit in {budget} bytes; it takes {min_size} bytes even with the minimum quantization")]
+    BudgetExceeded { budget: usize, min_size: usize },
     #[error("Connectivity encoding error: {0}")]
     ConnectivityError(#[from] connectivity::Err),
+    #[error("Error in the {0}th mesh: {1}")]
+    ContainerError(usize, Box<Err>),
+    #[error("The attribute {0:?} has no values")]
+    EmptyAttribute(AttributeId),
     #[error("Header encoding error: {0}")]
     HeaderError(#[from] header::Err),
+    #[error("The {option} of {bits} bits is out of the range {range:?}")]
+    InvalidQuantizationBits { option: &'static str, bits: u8, range: std::ops::RangeInclusive<u8> },
+    #[error("Invalid mesh: {0}")]
+    MeshError(#[from] crate::core::mesh::Err),
     #[error("Metadata encoding error: {0}")]
     MetadataError(#[from] metadata::Err),
+    #[error("The {ty:?} attribute {id:?} has {num_components} components, but a {ty:?} attribute has {expected:?} components")]
+    MismatchedNumComponents { id: AttributeId, ty: AttributeType, num_components: usize, expected: std::ops::RangeInclusive<usize> },
+    #[error("Point cloud encoding error: {0}")]
+    PointCloudError(#[from] point_cloud::Err),
+    #[error("Scene encoding error: {0}")]
+    SceneError(#[from] crate::core::scene::Err),
+    #[error("The topology does not belong to the mesh: {0}")]
+    TopologyMismatch(String),
+}
+
+
+/// The quantization bits that the encoder and the decoder support, as those of Draco.
+pub(crate) const QUANTIZATION_BITS: std::ops::RangeInclusive<u8> = 1..=30;
+
+/// The quantization bits of the octahedral coordinates of the normals, as those of Draco.
+const NORMAL_QUANTIZATION_BITS: std::ops::RangeInclusive<u8> = 2..=30;
+
+/// Checks the quantization bits of the configuration before anything is written, as the quantization
+/// overflows with the bits out of their range.
+pub(crate) fn validate_quantization_bits(cfg: &Config) -> Result<(), Err> {
+    if let Some(bits) = cfg.quantization_bits.filter(|bits| !QUANTIZATION_BITS.contains(bits)) {
+        return Err(Err::InvalidQuantizationBits { option: "quantization_bits", bits, range: QUANTIZATION_BITS });
+    }
+    if let Some(bits) = cfg.normal_quantization_bits.filter(|bits| !NORMAL_QUANTIZATION_BITS.contains(bits)) {
+        return Err(Err::InvalidQuantizationBits { option: "normal_quantization_bits", bits, range: NORMAL_QUANTIZATION_BITS });
+    }
+    Ok(())
 }
 
 
 /// Encodes the input mesh into a provided byte stream using the provided configuration.
-pub fn encode<W>(mesh: Mesh, writer: &mut W, cfg: Config) -> Result<(), Err> 
+/// A mesh without faces is encoded as a point cloud, whose attribute values are predicted from the previous point.
+pub fn encode<W>(mesh: Mesh, writer: &mut W, mut cfg: Config) -> Result<(), Err> 
+    where W: ByteWriter
+{
+    let mut writer = CountingWriter::new(writer);
+    encode_header_and_metadata(&mesh, &mut writer, &mut cfg)?;
+
+    let (mesh, loose_points) = split_loose_points(mesh, &cfg)?;
+
+    // Destruct the mesh so that attributes and faces have the different lifetime. 
+    let Mesh{attributes, faces, ..} = mesh;
+    encode_geometry(&faces, None, attributes, loose_points, &mut writer, &cfg)?;
+    pad_to_alignment(&mut writer, cfg.output_alignment);
+    Ok(())
+}
+
+
+/// Same as [encode], but returns the encoded bytes instead of writing them to a writer.
+pub fn encode_to_vec(mesh: Mesh, cfg: Config) -> Result<Vec<u8>, Err> {
+    let mut buffer = Vec::new();
+    encode(mesh, &mut buffer, cfg)?;
+    Ok(buffer)
+}
+
+
+/// Same as [encode], but returns the number of bytes spent on each part of the stream and the prediction scheme
+/// of each attribute, e.g. for a tool that shows where the bytes go. The output is the same as that of [encode].
+pub fn encode_with_stats<W>(mesh: Mesh, writer: &mut W, mut cfg: Config) -> Result<EncodeStats, Err> 
+    where W: ByteWriter
+{
+    let mut writer = CountingWriter::new(writer);
+    encode_header_and_metadata(&mesh, &mut writer, &mut cfg)?;
+    let header = writer.count();
+
+    let (mesh, loose_points) = split_loose_points(mesh, &cfg)?;
+    let Mesh{attributes, faces, ..} = mesh;
+    let mut stats = encode_geometry(&faces, None, attributes, loose_points, &mut writer, &cfg)?;
+    stats.header = header;
+    stats.attribute_header = writer.count() - header - stats.connectivity - stats.loose_points
+        - stats.attributes.iter().map(|att| att.bytes).sum::<usize>();
+    stats.padding = pad_to_alignment(&mut writer, cfg.output_alignment);
+    Ok(stats)
+}
+
+
+/// Same as [encode], but the connectivity is encoded on the given topology of the mesh instead of
+/// the corner table that the encoder would build, e.g. when the adjacency is already built for other uses.
+/// The output is the same as that of [encode]. The topology must be built from the same faces and positions
+/// as the mesh, or [Err::TopologyMismatch] is returned. If [Config::loose_points] is set and some points are
+/// not referred to by any face, the points are renumbered without them, so the corner table is built anew.
+pub fn encode_with_topology<W>(mesh: &Mesh, topology: &Topology, writer: &mut W, mut cfg: Config) -> Result<(), Err> 
+    where W: ByteWriter
+{
+    topology.check_mesh(mesh).map_err(Err::TopologyMismatch)?;
+    let mut writer = CountingWriter::new(writer);
+    encode_header_and_metadata(mesh, &mut writer, &mut cfg)?;
+    let (mesh, loose_points) = split_loose_points(mesh.clone(), &cfg)?;
+    let corner_table = loose_points.as_ref()
+        .is_none_or(|pos| pos.len() == 0)
+        .then(|| topology.corner_table().clone());
+    let Mesh{attributes, faces, ..} = mesh;
+    encode_geometry(&faces, corner_table, attributes, loose_points, &mut writer, &cfg)?;
+    pad_to_alignment(&mut writer, cfg.output_alignment);
+    Ok(())
+}
+
+
+/// Checks the mesh and writes everything before the connectivity.
+/// The evaluation scope begun here is ended by [encode_geometry].
+fn encode_header_and_metadata<W>(mesh: &Mesh, writer: &mut W, cfg: &mut Config) -> Result<(), Err> 
     where W: ByteWriter
 {
+    // The faces of zero area are encoded as they are, but the points of every face must exist.
+    mesh.ensure_points_exist()?;
+
+    // An attribute without values cannot be predicted nor quantized, so it is rejected before anything is written.
+    if let Some(att) = mesh.attributes.iter().find(|att| att.len() == 0) {
+        return Err(Err::EmptyAttribute(att.get_id()));
+    }
+    validate_quantization_bits(cfg)?;
+    if cfg.mismatched_components == MismatchedComponents::Reject {
+        for att in &mesh.attributes {
+            let (ty, num_components) = (att.get_attribute_type(), att.get_num_components());
+            if let Some(expected) = attribute::expected_num_components(ty).filter(|expected| !expected.contains(&num_components)) {
+                return Err(Err::MismatchedNumComponents { id: att.get_id(), ty, num_components, expected });
+            }
+        }
+    }
+
+    // The names of the attributes are kept in the metadata.
+    if metadata::has_metadata(mesh) {
+        cfg.metdata = true;
+    }
+
+    // A mesh without faces is encoded as a point cloud, i.e. as the values of its attributes in the order of the points.
+    if mesh.faces.is_empty() {
+        cfg.geometry_type = header::EncodedGeometryType::PointCloud;
+        cfg.point_cloud_method = PointCloudMethod::Sequential;
+        cfg.encoder_method = shared::header::EncoderMethod::Sequential;
+        cfg.loose_points = false;
+    }
+
     #[cfg(feature = "evaluation")]
     eval::scope_begin("compression info", writer);
     
     // Encode header
-    header::encode_header(writer, &cfg)?;
+    header::encode_header(writer, cfg)?;
 
-    debug_write!("Header done, now starting metadata.", writer);
+    debug_write!("Header done, now starting metadata.", writer, cfg.debug_markers);
 
     // Encode metadata
     if cfg.metdata {
         #[cfg(feature = "evaluation")]
         eval::scope_begin("metadata", writer);
-        metadata::encode_metadata(&mesh, writer)?;
+        metadata::encode_metadata(mesh, writer)?;
         #[cfg(feature = "evaluation")]
         eval::scope_end(writer);
     }
 
 
-    debug_write!("Metadata done, now starting connectivity.", writer);
+    debug_write!("Metadata done, now starting connectivity.", writer, cfg.debug_markers);
+    Ok(())
+}
+
+
+/// Removes the points that no face refers to from the mesh if [Config::loose_points] is set, and returns
+/// the mesh with the positions of the removed points. Only the positions of the loose points are encoded,
+/// so the mesh of loose points with other attributes is rejected.
+fn split_loose_points(mut mesh: Mesh, cfg: &Config) -> Result<(Mesh, Option<Attribute>), Err> {
+    if !cfg.loose_points {
+        return Ok((mesh, None));
+    }
+    let num_points = mesh.attributes.first().map_or(0, |att| att.len());
+    let mut is_referred = vec![false; num_points];
+    for &p in mesh.faces.iter().flatten() {
+        is_referred[usize::from(p)] = true;
+    }
+    let loose = (0..num_points).filter(|&p| !is_referred[p]).map(PointIdx::from).collect::<Vec<_>>();
+    let Some(pos) = mesh.attributes.iter().find(|att| att.get_attribute_type() == AttributeType::Position) else {
+        let loose_points = empty_positions(&mesh);
+        return Ok((mesh, Some(loose_points)));
+    };
+    let loose_points = pos.select_points(&loose);
+    if !loose.is_empty() {
+        if let Some(att) = mesh.attributes.iter().find(|att| att.get_attribute_type() != AttributeType::Position) {
+            return Err(Err::PointCloudError(point_cloud::Err::UnsupportedAttribute(format!(
+                "the loose points have the {:?} attribute {:?}, but only their positions are encoded", att.get_attribute_type(), att.get_id()
+            ))));
+        }
+        let mut num_referred = 0;
+        let old_to_new = is_referred.iter()
+            .map(|&referred| referred.then(|| { num_referred += 1; num_referred - 1 }))
+            .collect::<Vec<_>>();
+        mesh.remap_vertices(&old_to_new);
+    }
+    Ok((mesh, Some(loose_points)))
+}
+
+/// The positions of no points, for the mesh whose points are all referred to by its faces.
+fn empty_positions(mesh: &Mesh) -> Attribute {
+    mesh.attributes.iter()
+        .find(|att| att.get_attribute_type() == AttributeType::Position)
+        .map(|pos| pos.select_points(&[]))
+        .unwrap_or_else(|| Attribute::new_empty(AttributeId::new(0), AttributeType::Position, crate::core::attribute::AttributeDomain::Position, ComponentDataType::F32, 3))
+}
+
+/// Encodes the connectivity and the attributes, on the given corner table of the faces if any, and then
+/// the loose points if any, see [Config::loose_points].
+/// The returned stats have the bytes of the connectivity, of each attribute, and of the loose points;
+/// the rest is left to the caller.
+fn encode_geometry<'faces, W>(
+    faces: &'faces [[PointIdx; 3]],
+    corner_table: Option<CornerTable<'faces>>,
+    attributes: Vec<Attribute>,
+    loose_points: Option<Attribute>,
+    writer: &mut W,
+    cfg: &Config,
+) -> Result<EncodeStats, Err> 
+    where W: ByteWriter
+{
+    // Draco has no half-float type; encode half-floats as f32, which represents them exactly.
+    let mut attributes = attributes.into_iter()
+        .map(|att| if att.get_component_type() == ComponentDataType::F16 { att.widen_f16_to_f32() } else { att })
+        .collect::<Vec<_>>();
 
-    // Destruct the mesh so that attributes and faces have the different lifetime. 
-    let Mesh{mut attributes, faces, ..} = mesh;
     
-    // Encode connectivity
-    let conn_out = connectivity::encode_connectivity(&faces, &mut attributes, writer, &cfg)?;
-    debug_write!("Connectivity done, now starting attributes.", writer);
+    // Encode connectivity, of which a point cloud has only the number of points, as in Draco.
+    let mut counter = CountingWriter::new(writer);
+    let conn_out = match cfg.geometry_type {
+        header::EncodedGeometryType::PointCloud => {
+            let num_points = attributes.first().map_or(0, |att| att.len());
+            counter.write_u32(num_points as u32);
+            connectivity::ConnectivityEncoderOutput::PointCloud(num_points)
+        },
+        header::EncodedGeometryType::TrianglarMesh => {
+            connectivity::encode_connectivity(faces, corner_table, &mut attributes, &mut counter, cfg)?
+        },
+    };
+    debug_write!("Connectivity done, now starting attributes.", counter, cfg.debug_markers);
+    let connectivity = counter.count();
 
     // Encode attributes
-    attribute::encode_attributes(attributes, writer, conn_out, &cfg)?;
+    let attributes = attribute::encode_attributes(attributes, writer, conn_out, cfg)?;
 
-    debug_write!("All done", writer);
+    // Encode the loose points
+    let mut counter = CountingWriter::new(writer);
+    if let Some(mut loose_points) = loose_points {
+        if loose_points.get_component_type() == ComponentDataType::F16 {
+            loose_points = loose_points.widen_f16_to_f32();
+        }
+        point_cloud::encode_point_cloud(vec![loose_points], PointCloudMethod::KdTree, &mut counter)?;
+    }
+    let loose_points = counter.count();
+
+    debug_write!("All done", writer, cfg.debug_markers);
 
     #[cfg(feature = "evaluation")]
     eval::scope_end(writer);
+    Ok(EncodeStats { connectivity, attributes, loose_points, ..EncodeStats::default() })
+}
+
+
+/// Appends zeros to the stream until its length is a multiple of 'alignment', see [Config::output_alignment],
+/// and returns the number of zeros.
+fn pad_to_alignment<W>(writer: &mut CountingWriter<W>, alignment: usize) -> usize
+    where W: ByteWriter
+{
+    if alignment <= 1 {
+        return 0;
+    }
+    let padding = writer.count().next_multiple_of(alignment) - writer.count();
+    for _ in 0..padding {
+        writer.write_u8(0);
+    }
+    padding
+}
+
+
+/// Encodes the input mesh as a point cloud, i.e. only its points, coded by `cfg.point_cloud_method`.
+/// The mesh must have no faces and a single position attribute of 3 components of f32 or f64.
+pub fn encode_point_cloud<W>(mesh: Mesh, writer: &mut W, mut cfg: Config) -> Result<(), Err> 
+    where W: ByteWriter
+{
+    if !mesh.faces.is_empty() {
+        return Err(Err::PointCloudError(point_cloud::Err::UnsupportedAttribute(format!("the mesh has {} faces", mesh.faces.len()))));
+    }
+    cfg.geometry_type = header::EncodedGeometryType::PointCloud;
+    // The points are written without the debug markers, so the header must not announce them.
+    cfg.debug_markers = false;
+    header::encode_header(writer, &cfg)?;
+    point_cloud::encode_point_cloud(mesh.attributes, cfg.point_cloud_method, writer)?;
     Ok(())
 }
+
+
+/// Encodes the point cloud split into the tiles of a grid of 'grid' cells along x, y and z over its bounding box,
+/// each tile as an independent point cloud, after a spatial index of the tiles (see [point_cloud::tiles]).
+/// A consumer reads the index with [crate::decode::read_tile_index], and fetches and decodes only the tiles
+/// intersecting its query box with [crate::decode::decode_tile]. The empty tiles are omitted. The tiles are coded
+/// with [PointCloudMethod::KdTree] regardless of [Config::point_cloud_method], as it is the method the decoder supports.
+/// Only the positions are encoded, so the point cloud of other attributes and the grid of no cells along an axis
+/// are rejected.
+pub fn encode_point_cloud_tiles<W>(mesh: Mesh, writer: &mut W, grid: [usize; 3], mut cfg: Config) -> Result<(), Err> 
+    where W: ByteWriter
+{
+    if !mesh.faces.is_empty() {
+        return Err(Err::PointCloudError(point_cloud::Err::UnsupportedAttribute(format!("the mesh has {} faces", mesh.faces.len()))));
+    }
+    cfg.point_cloud_method = point_cloud::tiles::TILE_METHOD;
+    point_cloud::tiles::encode_tiles(&mesh, grid, writer, |tile, stream| encode_point_cloud(tile, stream, cfg.clone()))
+}
+
+
+/// Encodes several meshes into one framed container (see [crate::io::container]), where each mesh is
+/// a separate Draco stream encoded with the same configuration.
+pub fn encode_many<W>(meshes: &[Mesh], writer: &mut W, cfg: Config) -> Result<(), Err> 
+    where W: ByteWriter
+{
+    let streams = meshes.iter()
+        .enumerate()
+        .map(|(i, mesh)| {
+            let mut stream = Vec::new();
+            encode(mesh.clone(), &mut stream, cfg.clone())
+                .map_err(|err| Err::ContainerError(i, Box::new(err)))?;
+            Ok(stream)
+        })
+        .collect::<Result<Vec<_>, Err>>()?;
+    crate::io::container::write_container(&streams, writer);
+    Ok(())
+}
+
+
+/// Encodes the scene, i.e. its meshes and its node hierarchy, so that a glTF scene can be compressed as a unit.
+/// The meshes are written as a framed container (see [crate::io::container]) of Draco streams encoded with
+/// the same configuration, followed by the mesh groups and the nodes with their transforms. The scene is
+/// read back by [crate::decode::decode_scene].
+pub fn encode_scene<W>(scene: &Scene, writer: &mut W, cfg: Config) -> Result<(), Err> 
+    where W: ByteWriter
+{
+    scene.validate_indices()?;
+    encode_many(scene.meshes(), writer, cfg)?;
+    scene.write_hierarchy(writer);
+    Ok(())
+}
+
+
+/// The quantization bits tried first by [encode_to_budget] when [Config::quantization_bits] is not set.
+const MAX_BUDGET_QUANTIZATION_BITS: u8 = 16;
+/// The fewest quantization bits tried by [encode_to_budget].
+const MIN_BUDGET_QUANTIZATION_BITS: u8 = 2;
+
+/// Encodes the mesh so that the stream fits in 'max_bytes' bytes. The quantization bits of all the quantized
+/// attributes are lowered one at a time, starting from [Config::quantization_bits] (or 16 bits if unset),
+/// until the stream fits, and the configuration that produced it is returned. The bits of
+/// [Config::attribute_quantization] and [Config::normal_quantization_bits] are capped at the bits being tried.
+/// Nothing is written if the stream does not fit even with the minimum quantization.
+pub fn encode_to_budget<W>(mesh: Mesh, writer: &mut W, max_bytes: usize, cfg: Config) -> Result<Config, Err> 
+    where W: ByteWriter
+{
+    validate_quantization_bits(&cfg)?;
+    let max_bits = cfg.quantization_bits.unwrap_or(MAX_BUDGET_QUANTIZATION_BITS).max(MIN_BUDGET_QUANTIZATION_BITS);
+    let mut min_size = usize::MAX;
+    for bits in (MIN_BUDGET_QUANTIZATION_BITS..=max_bits).rev() {
+        let mut cfg = cfg.clone();
+        cfg.quantization_bits = Some(bits);
+        for b in cfg.attribute_quantization.values_mut() {
+            *b = (*b).min(bits);
+        }
+        cfg.normal_quantization_bits = cfg.normal_quantization_bits.map(|b| b.min(bits));
+        let stream = encode_to_vec(mesh.clone(), cfg.clone())?;
+        if stream.len() <= max_bytes {
+            for b in stream {
+                writer.write_u8(b);
+            }
+            return Ok(cfg);
+        }
+        min_size = min_size.min(stream.len());
+    }
+    Err(Err::BudgetExceeded { budget: max_bytes, min_size })
+}
+
+
+#[cfg(all(test, not(feature = "evaluation")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_to_vec() {
+        let mesh = crate::io::obj::load_obj("tests/data/sphere.obj").unwrap();
+        let mut buffer = Vec::new();
+        encode(mesh.clone(), &mut buffer, Config::default()).unwrap();
+        assert_eq!(encode_to_vec(mesh, Config::default()).unwrap(), buffer);
+    }
+
+    #[test]
+    fn test_planar_positions() {
+        use crate::core::attribute::{Attribute, AttributeDomain};
+        use crate::core::shared::{NdVector, PointIdx, Vector};
+        let disk = crate::io::obj::load_obj("tests/data/disk.obj").unwrap();
+        let pos = &disk.get_attributes()[0];
+        let planar = (0..pos.len())
+            .map(|p| pos.get::<NdVector<3, f32>, 3>(PointIdx::from(p)))
+            .map(|v| NdVector::from([*v.get(0), *v.get(1)]))
+            .collect::<Vec<_>>();
+        let mut mesh = Mesh::new();
+        mesh.faces = disk.get_faces().to_vec();
+        mesh.attributes.push(Attribute::from(AttributeId::new(0), planar.clone(), AttributeType::Position, AttributeDomain::Position, Vec::new()));
+
+        let mut buffer = Vec::new();
+        encode(mesh, &mut buffer, Config::default()).unwrap();
+        let decoded = crate::decode::decode(&mut crate::prelude::SliceReader::new(&buffer), crate::decode::Config::default()).unwrap();
+        let decoded_pos = &decoded.get_attributes()[0];
+        assert_eq!(decoded_pos.get_num_components(), 2);
+        assert_eq!(decoded.get_faces().len(), disk.get_faces().len());
+        // the decoded positions are those of the input up to the quantization.
+        for p in 0..decoded_pos.len() {
+            let v = decoded_pos.get::<NdVector<2, f32>, 2>(PointIdx::from(p));
+            assert!(planar.iter().any(|w| (0..2).all(|i| (v.get(i) - w.get(i)).abs() < 1e-3)), "{:?}", v);
+        }
+    }
+
+    #[test]
+    fn test_degenerate_face() {
+        use crate::core::shared::{NdVector, PointIdx, Vector};
+        let mut mesh = crate::io::obj::load_obj("tests/data/sphere.obj").unwrap();
+        // a new point on the first point of the first face, and the face of zero area on it before the others.
+        let [a, b, _] = mesh.faces[0];
+        let points = (0..mesh.get_attributes()[0].len()).map(PointIdx::from).chain([a]).collect::<Vec<_>>();
+        for att in mesh.get_attributes_mut() {
+            *att = att.select_points(&points);
+        }
+        mesh.faces.insert(0, [PointIdx::from(points.len() - 1), a, b]);
+
+        let mut buffer = Vec::new();
+        encode(mesh.clone(), &mut buffer, Config::default()).unwrap();
+        let decoded = crate::decode::decode(&mut crate::prelude::SliceReader::new(&buffer), crate::decode::Config::default()).unwrap();
+        // each face of the decoded mesh is a face of the input up to the quantization, including the degenerate one.
+        let faces_of = |mesh: &Mesh| {
+            let pos = &mesh.get_attributes()[0];
+            mesh.get_faces().iter()
+                .map(|f| f.map(|p| pos.get::<NdVector<3, f32>, 3>(p)))
+                .collect::<Vec<_>>()
+        };
+        let is_near = |a: &[NdVector<3, f32>; 3], b: &[NdVector<3, f32>; 3]| (0..3)
+            .any(|r| (0..3).all(|j| (0..3).all(|i| (a[j].get(i) - b[(j + r) % 3].get(i)).abs() < 1e-3)));
+        let (faces, decoded_faces) = (faces_of(&mesh), faces_of(&decoded));
+        assert_eq!(decoded_faces.len(), faces.len());
+        for f in &decoded_faces {
+            assert!(faces.iter().any(|g| is_near(f, g)), "{:?} is not a face of the input", f);
+        }
+        let is_degenerate = |f: &[NdVector<3, f32>; 3]| (0..3).any(|j| f[j] == f[(j + 1) % 3]);
+        assert_eq!(decoded_faces.iter().filter(|f| is_degenerate(f)).count(), 1);
+    }
+
+    #[test]
+    fn test_face_repeating_a_point() {
+        use crate::core::shared::PointIdx;
+        // a face of zero area that refers to the same point twice is encoded like any other face.
+        let mut mesh = crate::io::obj::load_obj("tests/data/sphere.obj").unwrap();
+        let [a, b, _] = mesh.faces[0];
+        mesh.faces.insert(0, [a, a, b]);
+        for encoder_method in [shared::header::EncoderMethod::Edgebreaker, shared::header::EncoderMethod::Sequential] {
+            let mut cfg = Config::default();
+            cfg.encoder_method = encoder_method;
+            let mut buffer = Vec::new();
+            encode(mesh.clone(), &mut buffer, cfg).unwrap();
+            let decoded = crate::decode::decode(&mut crate::prelude::SliceReader::new(&buffer), crate::decode::Config::default()).unwrap();
+            assert_eq!(decoded.get_faces().len(), mesh.get_faces().len());
+        }
+
+        // a face that refers to a point the mesh does not have is rejected.
+        let num_points = mesh.get_attributes()[0].len();
+        mesh.faces.push([a, b, PointIdx::from(num_points)]);
+        let err = encode(mesh, &mut Vec::new(), Config::default()).unwrap_err();
+        assert!(
+            matches!(err, Err::MeshError(crate::core::mesh::Err::PointIndexOutOfRange(_, p, n)) if p == num_points && n == num_points),
+            "{}", err
+        );
+    }
+
+    #[test]
+    fn test_sequential_connectivity() {
+        use crate::core::shared::{NdVector, Vector};
+        let mesh = crate::io::obj::load_obj("tests/data/sphere.obj").unwrap();
+        let mut cfg = Config::default();
+        cfg.encoder_method = shared::header::EncoderMethod::Sequential;
+        let mut buffer = Vec::new();
+        encode(mesh.clone(), &mut buffer, cfg).unwrap();
+        assert_eq!(buffer[8], 0);
+        let decoded = crate::decode::decode(&mut crate::prelude::SliceReader::new(&buffer), crate::decode::Config::default()).unwrap();
+
+        // the faces are written as they are, so the points keep their indices.
+        assert_eq!(decoded.get_faces(), mesh.get_faces());
+        for (att, decoded_att) in mesh.get_attributes().iter().zip(decoded.get_attributes()) {
+            assert_eq!(decoded_att.get_attribute_type(), att.get_attribute_type());
+            for p in (0..mesh.get_attributes()[0].len()).map(PointIdx::from) {
+                let (v, w) = (att.get::<NdVector<3, f32>, 3>(p), decoded_att.get::<NdVector<3, f32>, 3>(p));
+                assert!((0..3).all(|i| (v.get(i) - w.get(i)).abs() < 1e-2), "{:?}: {:?} != {:?}", att.get_attribute_type(), v, w);
+            }
+        }
+    }
+
+    #[test]
+    fn test_mismatched_num_components() {
+        use crate::core::attribute::{Attribute, AttributeDomain};
+        let mut mesh = crate::io::obj::load_obj("tests/data/tetrahedron.obj").unwrap();
+        let bytes = (0..mesh.get_attributes()[0].len() * 5).flat_map(|i| (i as f32).to_le_bytes()).collect::<Vec<_>>();
+        let id = AttributeId::new(mesh.get_attributes().len());
+        mesh.attributes.push(Attribute::from_bytes(id, bytes, ComponentDataType::F32, 5, AttributeType::Normal, AttributeDomain::Position));
+
+        // nothing is written for the normal of five components.
+        let mut buffer = Vec::new();
+        let err = encode(mesh.clone(), &mut buffer, Config::default()).unwrap_err();
+        assert!(matches!(err, Err::MismatchedNumComponents { num_components: 5, ty: AttributeType::Normal, .. }), "{:?}", err);
+        assert!(err.to_string().contains("3..=3"), "{}", err);
+        assert!(buffer.is_empty());
+
+        // the rejection wins over the raw bits of the unsupported components.
+        let cfg = Config { unsupported_components: UnsupportedComponents::RawBits, ..Config::default() };
+        let err = encode(mesh.clone(), &mut buffer, cfg).unwrap_err();
+        assert!(matches!(err, Err::MismatchedNumComponents { .. }), "{:?}", err);
+
+        // once accepted, the normal is subject to the unsupported components.
+        let cfg = Config {
+            mismatched_components: MismatchedComponents::Accept,
+            unsupported_components: UnsupportedComponents::Reject,
+            ..Config::default()
+        };
+        let err = encode(mesh.clone(), &mut Vec::new(), cfg).unwrap_err();
+        assert!(matches!(err, Err::AttributeError(attribute::Err::UnsupportedNumComponents(_))), "{:?}", err);
+
+        // it is accepted as the raw bits of five components if asked, and decoded exactly.
+        let cfg = Config { mismatched_components: MismatchedComponents::Accept, ..Config::default() };
+        encode(mesh.clone(), &mut buffer, cfg).unwrap();
+        let decoded = crate::decode::decode(&mut crate::prelude::SliceReader::new(&buffer), crate::decode::Config::default()).unwrap();
+        let normals_of = |mesh: &Mesh| {
+            let att = mesh.get_attributes().iter()
+                .find(|att| att.get_attribute_type() == AttributeType::Normal && att.get_num_components() == 5)
+                .unwrap();
+            let mut values = (0..att.num_unique_values()).map(|i| att.get_as_bytes(i).to_vec()).collect::<Vec<_>>();
+            values.sort();
+            values
+        };
+        assert_eq!(normals_of(&decoded), normals_of(&mesh));
+    }
+
+    #[test]
+    fn test_output_alignment() {
+        let mesh = crate::io::obj::load_obj("tests/data/sphere.obj").unwrap();
+        let mut unpadded = Vec::new();
+        encode(mesh.clone(), &mut unpadded, Config::default()).unwrap();
+        for alignment in [4, 16, 1000] {
+            let cfg = Config { output_alignment: alignment, ..Config::default() };
+            let mut buffer = Vec::new();
+            encode(mesh.clone(), &mut buffer, cfg).unwrap();
+            assert_eq!(buffer.len() % alignment, 0);
+            assert!(buffer.len() - unpadded.len() < alignment);
+            assert_eq!(&buffer[..unpadded.len()], unpadded.as_slice());
+
+            let decoded = crate::decode::decode(&mut crate::prelude::SliceReader::new(&buffer), crate::decode::Config::default()).unwrap();
+            let expected = crate::decode::decode(&mut crate::prelude::SliceReader::new(&unpadded), crate::decode::Config::default()).unwrap();
+            assert_eq!(decoded.get_faces(), expected.get_faces());
+        }
+    }
+
+    #[test]
+    fn test_encode_without_faces() {
+        use crate::core::attribute::{Attribute, AttributeDomain};
+        use crate::core::shared::{NdVector, Vector};
+        let mut seed = 1234_u32;
+        let mut next = || {
+            seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+            (seed >> 8) as f32 / (1 << 24) as f32
+        };
+        let positions = (0..1000).map(|_| NdVector::from([next(), next(), next()])).collect::<Vec<_>>();
+        let colors = positions.iter().map(|p| NdVector::from([0, 1, 2].map(|i| (*p.get(i) * 255.0) as u8))).collect::<Vec<_>>();
+        let mut mesh = Mesh::new();
+        mesh.attributes.push(Attribute::from_without_removing_duplicates(
+            AttributeId::new(0), positions, AttributeType::Position, AttributeDomain::Position, Vec::new()
+        ));
+        mesh.attributes.push(Attribute::from_without_removing_duplicates(
+            AttributeId::new(1), colors, AttributeType::Color, AttributeDomain::Position, Vec::new()
+        ));
+
+        let mut buffer = Vec::new();
+        encode(mesh, &mut buffer, Config::default()).unwrap();
+        let raw_size = 1000 * (3 * 4 + 3);
+        assert!(!buffer.is_empty() && buffer.len() < raw_size, "{} bytes", buffer.len());
+        // the header tells the point cloud from a mesh.
+        assert_eq!(buffer[7], header::EncodedGeometryType::PointCloud.get_id());
+        let flags = u16::from_le_bytes([buffer[9], buffer[10]]);
+        assert_ne!(flags & header::POINT_SEQUENCE_FLAG_MASK, 0);
+    }
+
+    #[test]
+    fn test_encode_to_budget() {
+        let mesh = crate::io::obj::load_obj("tests/data/torus.obj").unwrap();
+        let size_with = |bits: u8| {
+            let mut cfg = Config::default();
+            cfg.quantization_bits = Some(bits);
+            let mut buffer = Vec::new();
+            encode(mesh.clone(), &mut buffer, cfg).unwrap();
+            buffer.len()
+        };
+        let budget = (size_with(9) + size_with(10)) / 2;
+        assert!(size_with(9) <= budget && budget < size_with(10));
+
+        let mut buffer = Vec::new();
+        let cfg = encode_to_budget(mesh.clone(), &mut buffer, budget, Config::default()).unwrap();
+        assert!(buffer.len() <= budget);
+        assert_eq!(cfg.quantization_bits, Some(9));
+        let mut expected = Vec::new();
+        encode(mesh.clone(), &mut expected, cfg).unwrap();
+        assert_eq!(buffer, expected);
+
+        // the overrides of the quantization bits are lowered as well.
+        let mut overridden = Config::default();
+        overridden.attribute_quantization.insert(AttributeType::Position, 20);
+        overridden.normal_quantization_bits = Some(20);
+        let mut buffer = Vec::new();
+        let cfg = encode_to_budget(mesh.clone(), &mut buffer, budget, overridden).unwrap();
+        assert!(buffer.len() <= budget);
+        assert_eq!(cfg.attribute_quantization[&AttributeType::Position], 9);
+        assert_eq!(cfg.normal_quantization_bits, Some(9));
+        assert_eq!(buffer, expected);
+
+        // the connectivity alone does not fit in a few bytes.
+        let mut buffer = Vec::new();
+        let err = encode_to_budget(mesh.clone(), &mut buffer, 16, Config::default()).unwrap_err();
+        assert!(matches!(err, Err::BudgetExceeded { budget: 16, .. }), "{}", err);
+        assert!(buffer.is_empty());
+
+        // the bits out of the range are rejected before anything is written.
+        for bits in [0, 31] {
+            let mut cfg = Config::default();
+            cfg.quantization_bits = Some(bits);
+            let mut buffer = Vec::new();
+            let err = encode(mesh.clone(), &mut buffer, cfg.clone()).unwrap_err();
+            assert!(matches!(err, Err::InvalidQuantizationBits { option: "quantization_bits", bits: b, .. } if b == bits), "{}", err);
+            assert!(buffer.is_empty());
+            assert!(encode_to_budget(mesh.clone(), &mut buffer, usize::MAX, cfg).is_err());
+        }
+    }
+
+    #[test]
+    fn test_attribute_quantization() {
+        use crate::core::shared::{NdVector, PointIdx, Vector};
+        let mesh = crate::io::obj::load_obj("tests/data/sphere.obj").unwrap();
+        let positions_of = |mesh: &Mesh| {
+            let pos = mesh.get_attributes().iter().find(|att| att.get_attribute_type() == AttributeType::Position).unwrap();
+            (0..pos.len()).map(|p| pos.get::<NdVector<3, f32>, 3>(PointIdx::from(p))).collect::<Vec<_>>()
+        };
+        let positions = positions_of(&mesh);
+        let range = (0..3)
+            .map(|i| {
+                let (min, max) = positions.iter().fold((f32::MAX, f32::MIN), |(min, max), p| (min.min(*p.get(i)), max.max(*p.get(i))));
+                max - min
+            })
+            .fold(0.0_f32, f32::max);
+        // the largest distance from a decoded position to the nearest position of the input.
+        let max_error = |bits| {
+            let mut cfg = Config::default();
+            cfg.attribute_quantization.insert(AttributeType::Position, bits);
+            let mut buffer = Vec::new();
+            encode(mesh.clone(), &mut buffer, cfg).unwrap();
+            let decoded = crate::decode::decode(
+                &mut crate::prelude::SliceReader::new(&buffer), crate::decode::Config::default()
+            ).unwrap();
+            positions_of(&decoded).iter()
+                .map(|d| positions.iter().map(|p| (0..3).map(|i| (d.get(i) - p.get(i)).abs()).fold(0.0_f32, f32::max)).fold(f32::MAX, f32::min))
+                .fold(0.0_f32, f32::max)
+        };
+        // the decoded positions are on the grid of 16 bits, which is much finer than that of 8 bits.
+        let step = range / ((1 << 16) - 1) as f32;
+        assert!(max_error(16) <= step, "{} > {}", max_error(16), step);
+        assert!(max_error(8) > 10.0 * step);
+
+        let mut cfg = Config::default();
+        cfg.attribute_quantization.insert(AttributeType::TextureCoordinate, 31);
+        let err = encode(mesh, &mut Vec::new(), cfg).unwrap_err();
+        assert!(matches!(err, Err::AttributeError(attribute::Err::InvalidQuantizationBits(AttributeType::TextureCoordinate, 31))), "{:?}", err);
+    }
+
+    #[test]
+    fn test_attribute_quantization_stream_size() {
+        let mesh = crate::io::obj::load_obj("tests/data/sphere.obj").unwrap();
+        let size = |bits| {
+            let mut cfg = Config::default();
+            cfg.attribute_quantization.insert(AttributeType::Position, bits);
+            let mut buffer = Vec::new();
+            encode(mesh.clone(), &mut buffer, cfg).unwrap();
+            buffer.len()
+        };
+        let sizes = (11..=30).map(size).collect::<Vec<_>>();
+        // the stream grows with the bits of the values rather than with the range of the values, i.e.
+        // by about a bit for each coordinate of the 114 vertices.
+        for (bits, window) in (11..).zip(sizes.windows(2)) {
+            assert!(window[1] <= window[0] + 100, "{} bits: {:?}", bits, sizes);
+        }
+    }
+
+    #[test]
+    fn test_normal_transforms() {
+        use crate::core::shared::{Dot, NdVector};
+        let mesh = crate::io::obj::load_obj("tests/data/sphere.obj").unwrap();
+        let points_of = |mesh: &Mesh| {
+            let att_of = |ty| mesh.get_attributes().iter().find(|att| att.get_attribute_type() == ty).unwrap();
+            let (pos, normal) = (att_of(AttributeType::Position), att_of(AttributeType::Normal));
+            (0..pos.len())
+                .map(PointIdx::from)
+                .map(|p| (pos.get::<NdVector<3, f32>, 3>(p), normal.get::<NdVector<3, f32>, 3>(p)))
+                .collect::<Vec<_>>()
+        };
+        let points = points_of(&mesh)
+            .into_iter()
+            .map(|(p, n)| (p, n / n.norm()))
+            .collect::<Vec<_>>();
+        let mut max_angles = Vec::new();
+        let mut decoded_points = Vec::new();
+        let mut buffers = Vec::new();
+        for transform in [NormalTransform::Octahedral, NormalTransform::Reflection] {
+            let cfg = Config { normal_transform: transform, ..Config::default() };
+            let mut buffer = Vec::new();
+            encode(mesh.clone(), &mut buffer, cfg).unwrap();
+            let decoded = crate::decode::decode(
+                &mut crate::prelude::SliceReader::new(&buffer), crate::decode::Config::default()
+            ).unwrap();
+
+            let mut max_angle = 0.0_f32;
+            for (pos, normal) in points_of(&decoded) {
+                // the angle to the nearest normal of the input at the position.
+                let angle = points.iter()
+                    .filter(|(p, _)| (*p - pos).norm() < 1e-2)
+                    .map(|(_, n)| n.dot(normal / normal.norm()).clamp(-1.0, 1.0).acos())
+                    .fold(f32::INFINITY, f32::min);
+                max_angle = max_angle.max(angle);
+            }
+            max_angles.push(max_angle);
+            decoded_points.push(points_of(&decoded));
+            buffers.push(buffer);
+        }
+        // both transforms are lossless on the quantized normals, so they only differ in the stream.
+        assert_ne!(buffers[0], buffers[1]);
+        assert_eq!(decoded_points[0], decoded_points[1]);
+        assert!(max_angles.iter().all(|&a| a < 8.0 / 256.0), "{:?}", max_angles);
+    }
+
+    #[test]
+    fn test_encode_with_topology() {
+        use crate::core::corner_table::NUM_CORNER_TABLE_BUILDS;
+
+        let mesh = crate::io::obj::load_obj("tests/data/sphere.obj").unwrap();
+        let mut expected = Vec::new();
+        encode(mesh.clone(), &mut expected, Config::default()).unwrap();
+
+        let topology = Topology::new(&mesh).unwrap();
+        let num_builds = NUM_CORNER_TABLE_BUILDS.with(|n| n.get());
+        let mut buffer = Vec::new();
+        encode_with_topology(&mesh, &topology, &mut buffer, Config::default()).unwrap();
+        assert_eq!(buffer, expected);
+        assert_eq!(NUM_CORNER_TABLE_BUILDS.with(|n| n.get()), num_builds, "the corner table is rebuilt");
+
+        // the points that no face refers to are split from the mesh as by [encode].
+        let mut cfg = Config::default();
+        cfg.loose_points = true;
+        let positions = [[0.0f32, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [2.0, 2.0, 2.0], [-1.0, 3.0, 0.5]];
+        let loose = Mesh::new()
+            .with_attribute(Attribute::from_without_removing_duplicates(
+                AttributeId::new(0), positions.map(crate::core::shared::NdVector::from).to_vec(), AttributeType::Position,
+                crate::core::attribute::AttributeDomain::Position, Vec::new()
+            )).unwrap()
+            .with_faces(vec![[0, 1, 2]]).unwrap();
+        let topology_of_loose = Topology::new(&loose).unwrap();
+        let mut expected = Vec::new();
+        encode(loose.clone(), &mut expected, cfg.clone()).unwrap();
+        let mut buffer = Vec::new();
+        encode_with_topology(&loose, &topology_of_loose, &mut buffer, cfg.clone()).unwrap();
+        assert_eq!(buffer, expected);
+        assert_eq!(split_loose_points(loose, &cfg).unwrap().1.unwrap().len(), 2);
+
+        // the topology of another mesh is rejected.
+        let mut other = mesh.clone();
+        other.faces.swap(0, 1);
+        let err = encode_with_topology(&other, &topology, &mut Vec::new(), Config::default()).unwrap_err();
+        assert!(matches!(err, Err::TopologyMismatch(_)), "{}", err);
+    }
+
+    #[test]
+    #[cfg(feature = "parallel")]
+    fn test_parallel_connectivity_encoding() {
+        use crate::core::attribute::AttributeDomain;
+        use crate::core::shared::{NdVector, Vector};
+        let sphere = crate::io::obj::load_obj("tests/data/sphere.obj").unwrap();
+        let pos = &sphere.get_attributes()[0];
+        let values = (0..pos.len())
+            .map(|p| pos.get::<NdVector<3, f32>, 3>(PointIdx::from(p)))
+            .collect::<Vec<_>>();
+        // eight copies of the sphere side by side, every other one with a hole.
+        let mut positions = Vec::new();
+        let mut faces = Vec::new();
+        for k in 0..8 {
+            let offset = positions.len();
+            positions.extend(values.iter().map(|v| *v + NdVector::from([10.0 * k as f32, 0.0, 0.0])));
+            faces.extend(sphere.get_faces().iter().skip(k % 2).map(|f| f.map(|p| usize::from(p) + offset)));
+        }
+        let mut builder = crate::prelude::MeshBuilder::new();
+        builder.set_connectivity_attribute(faces);
+        builder.add_attribute(positions, AttributeType::Position, AttributeDomain::Position, vec![]);
+        let mesh = builder.build().unwrap();
+
+        // a pool of one thread encodes the components one after another.
+        let encode_on = |num_threads: usize| {
+            let pool = rayon::ThreadPoolBuilder::new().num_threads(num_threads).build().unwrap();
+            let mut buffer = Vec::new();
+            pool.install(|| encode(mesh.clone(), &mut buffer, Config::default()).unwrap());
+            buffer
+        };
+        let serial = encode_on(1);
+        let parallel = encode_on(4);
+        assert_eq!(parallel, serial);
+
+        let decoded = crate::decode::decode(&mut crate::prelude::SliceReader::new(&parallel), crate::decode::Config::default()).unwrap();
+        // the positions span eighty units, so that the step of the quantization is about 0.04.
+        let faces_of = |mesh: &Mesh| {
+            let pos = &mesh.get_attributes()[0];
+            mesh.get_faces().iter()
+                .map(|f| f.map(|p| pos.get::<NdVector<3, f32>, 3>(p)))
+                .collect::<Vec<_>>()
+        };
+        let is_near = |a: &[NdVector<3, f32>; 3], b: &[NdVector<3, f32>; 3]| (0..3)
+            .any(|r| (0..3).all(|j| (0..3).all(|i| (a[j].get(i) - b[(j + r) % 3].get(i)).abs() < 5e-2)));
+        let (faces, decoded_faces) = (faces_of(&mesh), faces_of(&decoded));
+        assert_eq!(decoded_faces.len(), faces.len());
+        for f in &decoded_faces {
+            assert!(faces.iter().any(|g| is_near(f, g)), "{:?} is not a face of the input", f);
+        }
+    }
+
+    #[test]
+    fn test_edgebreaker_start_face() {
+        let mesh = crate::io::obj::load_obj("tests/data/punctured_sphere.obj").unwrap();
+        let num_faces = mesh.get_faces().len();
+        let mut sizes = Vec::new();
+        for start_face in [None, Some(0), Some(num_faces / 3), Some(num_faces - 1)] {
+            let mut cfg = Config::default();
+            cfg.edgebreaker_start_face = start_face;
+            let mut buffer = Vec::new();
+            encode(mesh.clone(), &mut buffer, cfg).unwrap();
+            sizes.push(buffer.len());
+
+            let decoded = crate::decode::decode(
+                &mut crate::prelude::SliceReader::new(&buffer), crate::decode::Config::default()
+            ).unwrap();
+            assert_eq!(decoded.get_faces().len(), num_faces);
+            assert!(mesh.diff_l2_norm(&decoded) < 1e-2, "start face {:?}", start_face);
+        }
+        // the traversal starts from the first face by default, and the size depends on the start face.
+        assert_eq!(sizes[0], sizes[1], "{:?}", sizes);
+        assert!(sizes.iter().any(|&size| size != sizes[0]), "{:?}", sizes);
+
+        let mut cfg = Config::default();
+        cfg.edgebreaker_start_face = Some(num_faces);
+        let err = encode(mesh, &mut Vec::new(), cfg).unwrap_err();
+        assert!(err.to_string().contains("out of range"), "{}", err);
+    }
+
+    #[test]
+    fn test_average_position_prediction() {
+        use crate::shared::attribute::prediction_scheme::PredictionSchemeType;
+        let mesh = crate::io::obj::load_obj("tests/data/sphere.obj").unwrap();
+        let mut cfg = Config::default();
+        cfg.average_position_prediction = true;
+        let mut buffer = Vec::new();
+        let stats = encode_with_stats(mesh.clone(), &mut buffer, cfg).unwrap();
+        let position = stats.attributes.iter().find(|att| att.attribute_type == AttributeType::Position).unwrap();
+        assert_eq!(position.prediction_scheme, PredictionSchemeType::AveragePrediction);
+
+        let decoded = crate::decode::decode(
+            &mut crate::prelude::SliceReader::new(&buffer), crate::decode::Config::default()
+        ).unwrap();
+        assert_eq!(decoded.get_faces().len(), mesh.get_faces().len());
+        assert!(mesh.diff_l2_norm(&decoded) < 1e-2);
+    }
+
+    #[test]
+    fn test_average_position_prediction_of_point_cloud() {
+        use crate::core::attribute::{Attribute, AttributeDomain};
+        use crate::core::shared::{NdVector, Vector};
+        use crate::shared::attribute::prediction_scheme::PredictionSchemeType;
+        // a dense scan along a spiral, where the noise of the points is larger than the step between them.
+        let mut seed = 4321_u32;
+        let mut noise = || {
+            seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+            ((seed >> 8) as f32 / (1 << 24) as f32 - 0.5) * 0.01
+        };
+        let positions = (0..20_000)
+            .map(|i| i as f32 * 2e-4)
+            .map(|t| NdVector::from([t.cos() * 0.5 + noise(), t.sin() * 0.5 + noise(), t * 0.1 + noise()]))
+            .collect::<Vec<_>>();
+        let mut mesh = Mesh::new();
+        mesh.attributes.push(Attribute::from_without_removing_duplicates(
+            AttributeId::new(0), positions.clone(), AttributeType::Position, AttributeDomain::Position, Vec::new()
+        ));
+
+        let encode_with = |average_position_prediction| {
+            let cfg = Config { average_position_prediction, ..Config::default() };
+            let mut buffer = Vec::new();
+            let stats = encode_with_stats(mesh.clone(), &mut buffer, cfg).unwrap();
+            (buffer, stats.attributes[0].prediction_scheme.clone())
+        };
+        let (delta, delta_scheme) = encode_with(false);
+        let (average, average_scheme) = encode_with(true);
+        assert_eq!(delta_scheme, PredictionSchemeType::DeltaPrediction);
+        assert_eq!(average_scheme, PredictionSchemeType::AveragePrediction);
+        assert!(average.len() < delta.len(), "average: {} bytes, delta: {} bytes", average.len(), delta.len());
+
+        // the points keep their order.
+        let decoded = crate::decode::decode(&mut crate::prelude::SliceReader::new(&average), crate::decode::Config::default()).unwrap();
+        let pos = &decoded.get_attributes()[0];
+        assert_eq!(pos.len(), positions.len());
+        for (p, v) in positions.iter().enumerate() {
+            let w = pos.get::<NdVector<3, f32>, 3>(PointIdx::from(p));
+            assert!((0..3).all(|i| (v.get(i) - w.get(i)).abs() < 1e-3), "{:?} != {:?}", v, w);
+        }
+    }
+
+    #[test]
+    fn test_position_prediction() {
+        let mesh = crate::io::obj::load_obj("tests/data/sphere.obj").unwrap();
+        for prediction in [PositionPrediction::Delta, PositionPrediction::Parallelogram, PositionPrediction::MultiParallelogram] {
+            let mut cfg = Config::default();
+            cfg.position_prediction = prediction;
+            let mut buffer = Vec::new();
+            encode(mesh.clone(), &mut buffer, cfg).unwrap();
+
+            let decoded = crate::decode::decode(
+                &mut crate::prelude::SliceReader::new(&buffer), crate::decode::Config::default()
+            ).unwrap();
+            assert!(mesh.diff_l2_norm(&decoded) < 1e-2, "{:?}", prediction);
+        }
+    }
+
+    #[test]
+    fn test_normal_prediction() {
+        use crate::core::shared::NdVector;
+        let mesh = crate::io::obj::load_obj("tests/data/sphere.obj").unwrap();
+        // the pairs of the position and the normal of each point, as the decoder renumbers the points.
+        let points_of = |mesh: &Mesh| {
+            let att_of = |ty| mesh.get_attributes().iter().find(|att| att.get_attribute_type() == ty).unwrap();
+            let (pos, normal) = (att_of(AttributeType::Position), att_of(AttributeType::Normal));
+            (0..pos.len())
+                .map(PointIdx::from)
+                .map(|p| (pos.get::<NdVector<3, f32>, 3>(p), normal.get::<NdVector<3, f32>, 3>(p)))
+                .collect::<Vec<_>>()
+        };
+        let points = points_of(&mesh);
+        let mut sizes = Vec::new();
+        for prediction in [NormalPrediction::Delta, NormalPrediction::Geometric] {
+            let mut cfg = Config::default();
+            cfg.normal_prediction = prediction;
+            let mut buffer = Vec::new();
+            encode(mesh.clone(), &mut buffer, cfg).unwrap();
+            sizes.push(buffer.len());
+
+            let decoded = crate::decode::decode(
+                &mut crate::prelude::SliceReader::new(&buffer), crate::decode::Config::default()
+            ).unwrap();
+            // the normals are quantized on the octahedron with 8 bits.
+            for (pos, normal) in points_of(&decoded) {
+                assert!(
+                    points.iter().any(|&(p, n)| (p - pos).norm() < 1e-2 && (n - normal).norm() < 5e-2),
+                    "{:?}: the normal {:?} at {:?} is not in the input", prediction, normal, pos
+                );
+            }
+        }
+        // the sphere is smooth, so the normals follow its faces.
+        assert!(sizes[1] < sizes[0], "geometric: {} bytes, delta: {} bytes", sizes[1], sizes[0]);
+    }
+
+    #[test]
+    fn test_scalar_attribute() {
+        use crate::core::attribute::AttributeDomain;
+        use crate::core::shared::{NdVector, Vector};
+        let size = 100;
+        let p = |x: usize, y: usize| y * size + x;
+        let mut faces = Vec::new();
+        for y in 0..size-1 {
+            for x in 0..size-1 {
+                faces.push([p(x, y), p(x+1, y), p(x+1, y+1)]);
+                faces.push([p(x, y), p(x+1, y+1), p(x, y+1)]);
+            }
+        }
+        let positions = (0..size*size)
+            .map(|i| NdVector::from([(i % size) as f32, (i / size) as f32, 0.0]))
+            .collect::<Vec<_>>();
+        // a weight that varies smoothly over the grid, and an integer channel.
+        let weights = (0..size*size)
+            .map(|i| NdVector::from([((i % size) as f32 * 0.05).sin() * ((i / size) as f32 * 0.03).cos()]))
+            .collect::<Vec<_>>();
+        let frames = (0..size*size)
+            .map(|i| NdVector::from([(i % size + i / size) as u16 * 7]))
+            .collect::<Vec<_>>();
+        let mut builder = crate::prelude::MeshBuilder::new();
+        builder.set_connectivity_attribute(faces);
+        builder.add_attribute(positions, AttributeType::Position, AttributeDomain::Position, vec![]);
+        builder.add_attribute(weights.clone(), AttributeType::Custom, AttributeDomain::Position, vec![]);
+        builder.add_attribute(frames.clone(), AttributeType::Custom, AttributeDomain::Position, vec![]);
+        let mesh = builder.build().unwrap();
+
+        for scalar_channels in [false, true] {
+            let mut cfg = Config::default();
+            cfg.scalar_channels = scalar_channels;
+            let mut buffer = Vec::new();
+            encode(mesh.clone(), &mut buffer, cfg).unwrap();
+            let decoded = crate::decode::decode(&mut crate::prelude::SliceReader::new(&buffer), crate::decode::Config::default()).unwrap();
+            let [pos, weight, frame] = [0, 1, 2].map(|i| &decoded.get_attributes()[i]);
+            assert_eq!(weight.get_num_components(), 1);
+            assert_eq!(frame.get_component_type(), ComponentDataType::U16);
+            // the decoder renumbers the points, so they are matched by their positions.
+            for q in (0..pos.len()).map(PointIdx::from) {
+                let v: NdVector<3, f32> = pos.get(q);
+                let i = p(v.get(0).round() as usize, v.get(1).round() as usize);
+                let w: NdVector<1, f32> = weight.get(q);
+                if scalar_channels {
+                    // the weights in [-1, 1] are quantized with 11 bits.
+                    assert!((w.get(0) - weights[i].get(0)).abs() < 2.0 / 2047.0, "{:?} != {:?}", w, weights[i]);
+                } else {
+                    // the weights are kept as they are by default.
+                    assert_eq!(w, weights[i]);
+                }
+                assert_eq!(frame.get::<NdVector<1, u16>, 1>(q), frames[i]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_normal_quantization_bits() {
+        use crate::core::shared::{Dot, NdVector};
+        let mesh = crate::io::obj::load_obj("tests/data/sphere.obj").unwrap();
+        let points_of = |mesh: &Mesh| {
+            let att_of = |ty| mesh.get_attributes().iter().find(|att| att.get_attribute_type() == ty).unwrap();
+            let (pos, normal) = (att_of(AttributeType::Position), att_of(AttributeType::Normal));
+            (0..pos.len())
+                .map(PointIdx::from)
+                .map(|p| (pos.get::<NdVector<3, f32>, 3>(p), normal.get::<NdVector<3, f32>, 3>(p)))
+                .collect::<Vec<_>>()
+        };
+        let points = points_of(&mesh)
+            .into_iter()
+            .map(|(p, n)| (p, n / n.norm()))
+            .collect::<Vec<_>>();
+        let mut max_angles = Vec::new();
+        for bits in [8, 10, 12] {
+            let mut cfg = Config::default();
+            cfg.normal_quantization_bits = Some(bits);
+            let mut buffer = Vec::new();
+            encode(mesh.clone(), &mut buffer, cfg).unwrap();
+            let decoded = crate::decode::decode(
+                &mut crate::prelude::SliceReader::new(&buffer), crate::decode::Config::default()
+            ).unwrap();
+
+            let mut max_angle = 0.0_f32;
+            for (pos, normal) in points_of(&decoded) {
+                assert!((normal.norm() - 1.0).abs() < 1e-5, "{} bits: {:?} is not of unit length", bits, normal);
+                // the angle to the nearest normal of the input at the position.
+                let angle = points.iter()
+                    .filter(|(p, _)| (*p - pos).norm() < 1e-2)
+                    .map(|(_, n)| n.dot(normal).clamp(-1.0, 1.0).acos())
+                    .fold(f32::INFINITY, f32::min);
+                max_angle = max_angle.max(angle);
+            }
+            max_angles.push(max_angle);
+        }
+        // the step of the octahedral coordinates is '2 / (2^bits - 2)'.
+        for (bits, max_angle) in [8, 10, 12].into_iter().zip(&max_angles) {
+            assert!(*max_angle < 4.0 / (1 << bits) as f32, "{} bits: {} radians", bits, max_angle);
+        }
+        assert!(max_angles.windows(2).all(|w| w[1] < w[0]), "{:?}", max_angles);
+
+        // the bits out of the range are rejected before anything is written.
+        for bits in [0, 1, 31, 40] {
+            let mut cfg = Config::default();
+            cfg.normal_quantization_bits = Some(bits);
+            let mut buffer = Vec::new();
+            let err = encode(mesh.clone(), &mut buffer, cfg).unwrap_err();
+            assert!(matches!(err, Err::InvalidQuantizationBits { option: "normal_quantization_bits", bits: b, .. } if b == bits), "{}", err);
+            assert!(buffer.is_empty());
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use crate::core::bit_coder::BitWriter;
+use crate::prelude::ByteWriter;
+
+/// Encodes the quantized points, each component of 'bits' bits, by recursively halving the cell
+/// that contains them. At each cell the axis with the most remaining bits is split, and the number
+/// of the points in the lower half is written with just enough bits to represent the number of the
+/// points in the cell. A cell with a single point writes the remaining bits of the point directly.
+/// The points are reordered in place into the order in which the decoder restores them.
+pub(crate) fn encode_kd_tree<W>(points: &mut [[u32; 3]], bits: u8, writer: &mut W)
+    where W: ByteWriter
+{
+    let mut bit_writer: BitWriter<_> = BitWriter::spown_from(writer);
+    encode_cell(points, [bits; 3], &mut bit_writer);
+}
+
+fn encode_cell<W>(points: &mut [[u32; 3]], bits: [u8; 3], writer: &mut BitWriter<'_, W>)
+    where W: ByteWriter
+{
+    if points.is_empty() || bits == [0; 3] {
+        // all the points of a cell of no size are the same.
+        return;
+    }
+    if points.len() == 1 {
+        for (i, &b) in bits.iter().enumerate() {
+            if b > 0 {
+                writer.write_bits((b, (points[0][i] & ((1 << b) - 1)) as u64));
+            }
+        }
+        return;
+    }
+
+    let axis = split_axis(bits);
+    let bit = bits[axis] - 1;
+    let mut num_lower = 0;
+    for i in 0..points.len() {
+        if points[i][axis] & (1 << bit) == 0 {
+            points.swap(i, num_lower);
+            num_lower += 1;
+        }
+    }
+    writer.write_bits((count_bits(points.len()), num_lower as u64));
+
+    let mut child_bits = bits;
+    child_bits[axis] -= 1;
+    let (lower, upper) = points.split_at_mut(num_lower);
+    encode_cell(lower, child_bits, writer);
+    encode_cell(upper, child_bits, writer);
+}
+
+/// The axis with the most remaining bits, the first one on a tie.
+pub(crate) fn split_axis(bits: [u8; 3]) -> usize {
+    (0..3).rev().max_by_key(|&i| bits[i]).unwrap()
+}
+
+/// Number of bits to write a number between 0 and 'n'.
+pub(crate) fn count_bits(n: usize) -> u8 {
+    (usize::BITS - n.leading_zeros()) as u8
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decode::point_cloud::kd_tree::decode_kd_tree;
+    use crate::prelude::SliceReader;
+
+    #[test]
+    fn test_kd_tree_round_trip() {
+        let mut seed = 7_u32;
+        let mut next = || {
+            seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+            (seed >> 8) & 1023
+        };
+        let mut points = (0..1000).map(|_| [next(), next(), next()]).collect::<Vec<_>>();
+        // duplicates and a point on the boundary.
+        points.extend_from_slice(&[[5, 5, 5], [5, 5, 5], [1023, 0, 1023]]);
+        let mut sorted = points.clone();
+        sorted.sort();
+
+        let mut buffer = Vec::new();
+        encode_kd_tree(&mut points, 10, &mut buffer);
+        let decoded = decode_kd_tree(&mut SliceReader::new(&buffer), points.len(), 10).unwrap();
+        assert_eq!(decoded, points);
+        points.sort();
+        assert_eq!(points, sorted);
+    }
+}
@@ -0,0 +1,161 @@
+pub(crate) mod kd_tree;
+pub(crate) mod sequential;
+pub(crate) mod tiles;
+
+use crate::core::attribute::{Attribute, ComponentDataType};
+use crate::core::bit_coder::ReaderErr;
+use crate::core::shared::{NdVector, PointIdx, Vector};
+use crate::encode::attribute::portabilization::{self, PortabilizationImpl};
+use crate::encode::attribute::portabilization::quantization_coordinate_wise::QuantizationCoordinateWise;
+use crate::encode::entropy::symbol_coding;
+use crate::prelude::{AttributeType, ByteWriter};
+use crate::shared::attribute::portabilization::quantization_metadata;
+use crate::utils::bit_coder::leb128_write;
+
+/// How the positions of a point cloud are coded by [crate::encode::encode_point_cloud].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PointCloudMethod {
+    /// The points are coded in their input order, each predicted by the previous one.
+    #[default]
+    Sequential,
+    /// The points are coded by recursively halving their bounding box, in the spirit of Draco's KD-tree
+    /// encoder. This is much smaller for unordered points, but the order of the points is not kept.
+    /// The layout is draco-oxide's own, so the header marks the stream as such and Draco cannot decode it.
+    KdTree,
+}
+
+impl PointCloudMethod {
+    pub(crate) fn get_id(&self) -> u8 {
+        match self {
+            PointCloudMethod::Sequential => 0,
+            PointCloudMethod::KdTree => 1,
+        }
+    }
+
+    /// Inverse of [PointCloudMethod::get_id], or 'None' if the id is unknown.
+    pub(crate) fn from_id(id: u8) -> Option<Self> {
+        match id {
+            0 => Some(PointCloudMethod::Sequential),
+            1 => Some(PointCloudMethod::KdTree),
+            _ => None,
+        }
+    }
+}
+
+/// Encodes the points of the position attribute: the number of points, the quantization metadata,
+/// and the quantized points coded by 'method'.
+pub(crate) fn encode_point_cloud<W>(attributes: Vec<Attribute>, method: PointCloudMethod, writer: &mut W) -> Result<(), Err>
+    where W: ByteWriter
+{
+    let [att] = <[Attribute; 1]>::try_from(attributes)
+        .map_err(|atts| Err::UnsupportedAttribute(format!("{} attributes, but only a position attribute is supported", atts.len())))?;
+    if att.get_attribute_type() != AttributeType::Position || att.get_num_components() != 3 {
+        return Err(Err::UnsupportedAttribute(format!("{:?} attribute of {} components", att.get_attribute_type(), att.get_num_components())));
+    }
+
+    let num_points = att.len();
+    leb128_write(num_points as u64, writer);
+    if num_points == 0 {
+        return Ok(());
+    }
+
+    let cfg = portabilization::Config::default_for(AttributeType::Position);
+    let port_att = match att.get_component_type() {
+        ComponentDataType::F32 => QuantizationCoordinateWise::<NdVector<3, f32>, 3>::new(att, cfg, writer).portabilize(),
+        ComponentDataType::F64 => QuantizationCoordinateWise::<NdVector<3, f64>, 3>::new(att, cfg, writer).portabilize(),
+        ty => return Err(Err::UnsupportedAttribute(format!("position attribute of {:?}", ty))),
+    };
+    let mut points = (0..num_points)
+        .map(|p| {
+            let v = port_att.get::<NdVector<3, i32>, 3>(PointIdx::from(p));
+            [0, 1, 2].map(|i| *v.get(i) as u32)
+        })
+        .collect::<Vec<_>>();
+
+    match method {
+        PointCloudMethod::Sequential => sequential::encode_sequential(&points, writer)?,
+        PointCloudMethod::KdTree => kd_tree::encode_kd_tree(&mut points, cfg.quantization_bits, writer),
+    }
+    Ok(())
+}
+
+#[remain::sorted]
+#[derive(thiserror::Error, Debug)]
+pub enum Err {
+    #[error("The grid of the tiles has no cells along an axis: {0:?}")]
+    InvalidGrid([usize; 3]),
+    #[error("Invalid point cloud header")]
+    InvalidHeader,
+    #[error("{0}")]
+    LimitExceeded(#[from] crate::decode::LimitExceeded),
+    #[error("Quantization error: {0}")]
+    QuantizationError(#[from] quantization_metadata::Err),
+    #[error("Reader Error: {0}")]
+    ReaderError(#[from] ReaderErr),
+    #[error("Symbol encoding error: {0}")]
+    SymbolEncodingError(#[from] symbol_coding::Err),
+    #[error("Unsupported attribute for point clouds: {0}")]
+    UnsupportedAttribute(String),
+    #[error("Unsupported point cloud method: {0}")]
+    UnsupportedMethod(u8),
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::attribute::AttributeDomain;
+    use crate::core::mesh::Mesh;
+    use crate::core::shared::ConfigType;
+    use crate::decode::point_cloud::decode_point_cloud;
+    use crate::prelude::SliceReader;
+    use crate::shared::attribute::portabilization::quantization_metadata::QuantizationMetadata;
+
+    #[test]
+    fn test_kd_tree_against_sequential() {
+        let mut seed = 2024_u32;
+        let mut next = || {
+            seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+            (seed >> 8) as f32 / (1 << 24) as f32 * 2.0 - 1.0
+        };
+        let points = (0..50_000).map(|_| NdVector::from([next(), next(), next()])).collect::<Vec<_>>();
+        let mut mesh = Mesh::new();
+        mesh.attributes.push(Attribute::from_without_removing_duplicates(
+            crate::core::attribute::AttributeId::new(0), points.clone(), AttributeType::Position, AttributeDomain::Position, Vec::new()
+        ));
+
+        let encode = |method| {
+            let mut cfg = crate::encode::Config::default();
+            cfg.point_cloud_method = method;
+            let mut buffer = Vec::new();
+            crate::encode::encode_point_cloud(mesh.clone(), &mut buffer, cfg).unwrap();
+            buffer
+        };
+        let sequential = encode(PointCloudMethod::Sequential);
+        let kd_tree = encode(PointCloudMethod::KdTree);
+        assert!(kd_tree.len() * 10 < sequential.len() * 8, "kd-tree: {} bytes, sequential: {} bytes", kd_tree.len(), sequential.len());
+
+        // both methods code the same quantized points, so the accuracy is that of the quantization.
+        let mut decoded = decode_point_cloud(&mut SliceReader::new(&kd_tree)).unwrap();
+        assert_eq!(decoded.len(), points.len());
+        let mut metadata = Vec::new();
+        let quantized = QuantizationCoordinateWise::<NdVector<3, f32>, 3>::new(
+            mesh.attributes[0].clone(), portabilization::Config::default_for(AttributeType::Position), &mut metadata
+        ).portabilize();
+        let metadata = QuantizationMetadata::read_from(&mut SliceReader::new(&metadata), 3, None).unwrap();
+        let step = metadata.range / ((1 << metadata.quantization_bits) - 1) as f32;
+        let mut expected = Vec::new();
+        for (p, v) in points.iter().enumerate() {
+            let q = quantized.get::<NdVector<3, i32>, 3>(PointIdx::from(p));
+            let restored = [0, 1, 2].map(|i| metadata.dequantize(i, *q.get(i)));
+            for (i, r) in restored.iter().enumerate() {
+                assert!((r - *v.get(i)).abs() <= step);
+            }
+            expected.push(restored);
+        }
+        let cmp = |a: &[f32; 3], b: &[f32; 3]| a.iter().zip(b).map(|(x, y)| x.total_cmp(y)).find(|o| o.is_ne()).unwrap_or(std::cmp::Ordering::Equal);
+        decoded.sort_by(cmp);
+        expected.sort_by(cmp);
+        assert_eq!(decoded, expected);
+    }
+}
@@ -0,0 +1,21 @@
+use crate::encode::entropy::symbol_coding::{encode_symbols, Err};
+use crate::prelude::ByteWriter;
+use crate::shared::entropy::SymbolEncodingMethod;
+
+/// Encodes the quantized points in their input order. Each point is predicted by the previous one,
+/// and the differences are entropy coded.
+pub(crate) fn encode_sequential<W>(points: &[[u32; 3]], writer: &mut W) -> Result<(), Err>
+    where W: ByteWriter
+{
+    let mut prev = [0_u32; 3];
+    let mut symbols = Vec::with_capacity(points.len() * 3);
+    for point in points {
+        for i in 0..3 {
+            let diff = point[i] as i64 - prev[i] as i64;
+            // zigzag
+            symbols.push(((diff << 1) ^ (diff >> 63)) as u64);
+        }
+        prev = *point;
+    }
+    encode_symbols(symbols, 3, SymbolEncodingMethod::LengthCoded, writer)
+}
@@ -0,0 +1,124 @@
+//! The point cloud split into the tiles of a grid, so that a consumer can fetch and decode only the tiles
+//! intersecting a query box. The stream starts with the spatial index: the number of tiles (u32), and for each
+//! tile its box (six f32, the minimum and then the maximum), and the offset and the length of its Draco stream
+//! in bytes from the start of the stream (two u64). The Draco streams of the tiles follow the index.
+
+use crate::core::attribute::AttributeType;
+use crate::core::mesh::diff::unique_value_as_f64;
+use crate::core::shared::PointIdx;
+use crate::prelude::{ByteWriter, Mesh};
+use super::{Err, PointCloudMethod};
+
+/// The size of the index entry of a tile in bytes.
+const TILE_ENTRY_SIZE: usize = 6 * 4 + 2 * 8;
+
+/// The method the tiles are coded with, as it is the one the decoder supports.
+pub(crate) const TILE_METHOD: PointCloudMethod = PointCloudMethod::KdTree;
+
+/// A tile of the spatial index written by [crate::encode::encode_point_cloud_tiles].
+#[derive(Clone, Debug, PartialEq)]
+pub struct PointCloudTile {
+    /// The minimum corner of the cell of the grid that the tile covers.
+    pub min: [f32; 3],
+    /// The maximum corner of the cell of the grid that the tile covers.
+    pub max: [f32; 3],
+    /// The offset of the Draco stream of the tile in bytes from the start of the tiled stream.
+    pub offset: usize,
+    /// The length of the Draco stream of the tile in bytes.
+    pub len: usize,
+}
+
+impl PointCloudTile {
+    /// Whether the cell of the tile intersects the box from 'min' to 'max', boundaries included.
+    pub fn intersects(&self, min: [f32; 3], max: [f32; 3]) -> bool {
+        (0..3).all(|i| self.min[i] <= max[i] && min[i] <= self.max[i])
+    }
+
+    /// The range of the Draco stream of the tile in the tiled stream, e.g. for an HTTP range request.
+    pub fn byte_range(&self) -> std::ops::Range<usize> {
+        self.offset..self.offset + self.len
+    }
+}
+
+/// Splits the points of the position attribute into the cells of the grid of 'grid' cells along x, y and z
+/// over their bounding box, and writes the index followed by the points of each non-empty cell, encoded by
+/// 'encode_tile' as an independent point cloud. Only the positions are encoded, so the mesh of other attributes
+/// is rejected, and so is the grid of no cells along an axis.
+pub(crate) fn encode_tiles<W, F>(mesh: &Mesh, grid: [usize; 3], writer: &mut W, mut encode_tile: F) -> Result<(), crate::encode::Err>
+    where
+        W: ByteWriter,
+        F: FnMut(Mesh, &mut Vec<u8>) -> Result<(), crate::encode::Err>,
+{
+    if grid.contains(&0) {
+        return Err(Err::InvalidGrid(grid).into());
+    }
+    if let Some(att) = mesh.get_attributes().iter().find(|att| att.get_attribute_type() != AttributeType::Position) {
+        return Err(Err::UnsupportedAttribute(format!("the {:?} attribute {:?} of a tiled point cloud", att.get_attribute_type(), att.get_id())).into());
+    }
+    let pos = mesh.get_attributes().iter()
+        .find(|att| att.get_attribute_type() == AttributeType::Position)
+        .ok_or_else(|| Err::UnsupportedAttribute("a point cloud without positions".to_string()))?;
+
+    let points = (0..pos.len())
+        .map(|p| unique_value_as_f64(pos, usize::from(pos.get_unique_val_idx(PointIdx::from(p)))))
+        .collect::<Vec<_>>();
+    let mut min = [f64::INFINITY; 3];
+    let mut max = [f64::NEG_INFINITY; 3];
+    for v in &points {
+        for i in 0..3 {
+            min[i] = min[i].min(v[i]);
+            max[i] = max[i].max(v[i]);
+        }
+    }
+    let cell_size = [0, 1, 2].map(|i| (max[i] - min[i]) / grid[i] as f64);
+
+    // the points of each cell, where the cells are numbered along x first.
+    let mut cells = vec![Vec::new(); grid.iter().product()];
+    for (p, v) in points.iter().enumerate() {
+        let cell = [0, 1, 2].map(|i| {
+            let c = if cell_size[i] > 0.0 { ((v[i] - min[i]) / cell_size[i]) as usize } else { 0 };
+            c.min(grid[i] - 1)
+        });
+        cells[cell[0] + grid[0] * (cell[1] + grid[1] * cell[2])].push(PointIdx::from(p));
+    }
+
+    let mut tiles = Vec::new();
+    let mut streams = Vec::new();
+    for (idx, cell_points) in cells.iter().enumerate() {
+        if cell_points.is_empty() {
+            continue;
+        }
+        let cell = [idx % grid[0], idx / grid[0] % grid[1], idx / (grid[0] * grid[1])];
+        let mut tile_mesh = Mesh::new();
+        tile_mesh.attributes = vec![pos.select_points(cell_points)];
+        let mut stream = Vec::new();
+        encode_tile(tile_mesh, &mut stream)?;
+        tiles.push(PointCloudTile {
+            min: [0, 1, 2].map(|i| (min[i] + cell_size[i] * cell[i] as f64) as f32),
+            max: [0, 1, 2].map(|i| if cell[i] + 1 == grid[i] { max[i] as f32 } else { (min[i] + cell_size[i] * (cell[i] + 1) as f64) as f32 }),
+            offset: 0,
+            len: stream.len(),
+        });
+        streams.push(stream);
+    }
+
+    let mut offset = 4 + tiles.len() * TILE_ENTRY_SIZE;
+    for tile in &mut tiles {
+        tile.offset = offset;
+        offset += tile.len;
+    }
+    writer.write_u32(tiles.len() as u32);
+    for tile in &tiles {
+        for c in tile.min.iter().chain(&tile.max) {
+            writer.write_u32(c.to_bits());
+        }
+        writer.write_u64(tile.offset as u64);
+        writer.write_u64(tile.len as u64);
+    }
+    for stream in streams {
+        for byte in stream {
+            writer.write_u8(byte);
+        }
+    }
+    Ok(())
+}
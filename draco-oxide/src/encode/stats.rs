@@ -0,0 +1,68 @@
+use crate::core::attribute::{AttributeId, AttributeType};
+use crate::shared::attribute::prediction_scheme::PredictionSchemeType;
+
+/// The number of bytes spent on each part of the stream written by [crate::encode::encode_with_stats].
+/// The parts add up to the length of the stream, see [EncodeStats::total].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EncodeStats {
+    /// The header and the metadata.
+    pub header: usize,
+    /// The connectivity, i.e. the faces.
+    pub connectivity: usize,
+    /// The bytes of the attribute section that belong to no single attribute, i.e. the number of the attributes,
+    /// their descriptions and the vertex order if any, and the debug markers.
+    pub attribute_header: usize,
+    /// The attributes in the order they are encoded.
+    pub attributes: Vec<AttributeStats>,
+    /// The points that no face refers to, see [crate::encode::Config::loose_points].
+    pub loose_points: usize,
+    /// The zeros appended to the stream, see [crate::encode::Config::output_alignment].
+    pub padding: usize,
+}
+
+impl EncodeStats {
+    /// The length of the stream in bytes.
+    pub fn total(&self) -> usize {
+        self.header + self.connectivity + self.attribute_header + self.loose_points + self.padding
+            + self.attributes.iter().map(|att| att.bytes).sum::<usize>()
+    }
+}
+
+/// The bytes spent on an attribute, see [EncodeStats].
+#[derive(Clone, Debug, PartialEq)]
+pub struct AttributeStats {
+    pub id: AttributeId,
+    pub attribute_type: AttributeType,
+    /// The prediction scheme chosen by the encoder for the attribute.
+    pub prediction_scheme: PredictionSchemeType,
+    /// The values of the attribute, i.e. their prediction metadata and corrections.
+    pub bytes: usize,
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::core::shared::ConfigType;
+    use crate::encode::{self, encode_with_stats};
+    use crate::io::obj::load_obj;
+    use crate::prelude::AttributeType;
+    use crate::shared::attribute::prediction_scheme::PredictionSchemeType;
+
+    #[test]
+    fn test_encode_with_stats() {
+        let mesh = load_obj("tests/data/sphere.obj").unwrap();
+
+        let mut buffer = Vec::new();
+        let stats = encode_with_stats(mesh.clone(), &mut buffer, encode::Config::default()).unwrap();
+        assert_eq!(stats.total(), buffer.len());
+        assert!(stats.header > 0 && stats.connectivity > 0);
+        assert_eq!(stats.attributes.len(), mesh.get_attributes().len());
+        let pos = stats.attributes.iter().find(|att| att.attribute_type == AttributeType::Position).unwrap();
+        assert_eq!(pos.prediction_scheme, PredictionSchemeType::MeshParallelogramPrediction);
+
+        // the stream is the same as that of 'encode'.
+        let mut expected = Vec::new();
+        encode::encode(mesh, &mut expected, encode::Config::default()).unwrap();
+        assert_eq!(buffer, expected);
+    }
+}
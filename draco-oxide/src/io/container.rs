@@ -0,0 +1,82 @@
+//! A simple framed container that bundles several Draco streams, e.g. the meshes of a tile, into one buffer.
+//! The container starts with the number of streams, followed by each stream prefixed with its length in bytes.
+//! Both numbers are written in LEB128.
+
+use crate::core::bit_coder::ReaderErr;
+use crate::prelude::{ByteReader, ByteWriter};
+use crate::utils::bit_coder::{leb128_read, leb128_write};
+
+#[derive(Debug, thiserror::Error, Clone)]
+pub enum Err {
+    #[error("Reader Error: {0}")]
+    ReaderError(#[from] ReaderErr),
+}
+
+/// Writes the streams into a container.
+pub fn write_container<W, S>(streams: &[S], writer: &mut W)
+    where 
+        W: ByteWriter,
+        S: AsRef<[u8]>,
+{
+    leb128_write(streams.len() as u64, writer);
+    for stream in streams {
+        let stream = stream.as_ref();
+        leb128_write(stream.len() as u64, writer);
+        for &byte in stream {
+            writer.write_u8(byte);
+        }
+    }
+}
+
+/// Reads the streams of a container written by [write_container].
+pub fn read_container<R>(reader: &mut R) -> Result<Vec<Vec<u8>>, Err>
+    where R: ByteReader,
+{
+    let num_streams = leb128_read(reader)? as usize;
+    let mut out = Vec::new();
+    for _ in 0..num_streams {
+        let len = leb128_read(reader)? as usize;
+        let stream = (0..len)
+            .map(|_| reader.read_u8())
+            .collect::<Result<Vec<_>, _>>()?;
+        out.push(stream);
+    }
+    Ok(out)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::SliceReader;
+
+    #[test]
+    fn test_container_round_trip() {
+        let streams = vec![vec![1_u8, 2, 3], Vec::new(), vec![7; 300]];
+        let mut buffer = Vec::new();
+        write_container(&streams, &mut buffer);
+        assert_eq!(read_container(&mut SliceReader::new(&buffer)).unwrap(), streams);
+
+        // a truncated container is an error.
+        buffer.pop();
+        assert!(read_container(&mut SliceReader::new(&buffer)).is_err());
+    }
+
+    #[test]
+    fn test_encode_many() {
+        use crate::core::shared::ConfigType;
+        let meshes = ["sphere.obj", "tetrahedron.obj", "torus.obj"]
+            .map(|name| crate::io::obj::load_obj(format!("tests/data/{}", name)).unwrap());
+
+        let mut buffer = Vec::new();
+        crate::encode::encode_many(&meshes, &mut buffer, crate::encode::Config::default()).unwrap();
+
+        let streams = read_container(&mut SliceReader::new(&buffer)).unwrap();
+        assert_eq!(streams.len(), 3);
+        for (mesh, stream) in meshes.into_iter().zip(streams) {
+            let mut expected = Vec::new();
+            crate::encode::encode(mesh, &mut expected, crate::encode::Config::default()).unwrap();
+            assert_eq!(stream, expected);
+        }
+    }
+}
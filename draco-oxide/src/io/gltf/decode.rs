@@ -14,7 +14,7 @@ use crate::core::scene::TrsMatrix;
 use crate::core::texture::TextureMap;
 use crate::prelude::AttributeType;
 use crate::prelude::MeshBuilder;
-use crate::core::shared::NdVector;
+use crate::core::shared::{ConfigType, NdVector};
 
 /// Holds extension attributes that the gltf crate doesn't recognize
 #[derive(Debug, Clone)]
@@ -537,7 +537,7 @@ impl GltfDecoder {
                                 if attr_name.starts_with("_") {
                                     // This is a custom attribute, extract its accessor info
                                     if let Some(accessor_idx) = accessor_index.as_u64() {
-                                        if let Some(accessor_info) = self.extract_accessor_info(&json, accessor_idx as usize)? {
+                                        if let Some(accessor_info) = Self::extract_accessor_info(&json, accessor_idx as usize)? {
                                             ext_attrs.attributes.insert(attr_name.clone(), accessor_info);
                                         }
                                     }
@@ -564,7 +564,7 @@ impl GltfDecoder {
     }
 
     /// Extract accessor information from JSON for a given accessor index
-    fn extract_accessor_info(&self, json: &serde_json::Value, accessor_index: usize) -> Result<Option<AccessorInfo>, Err> {
+    fn extract_accessor_info(json: &serde_json::Value, accessor_index: usize) -> Result<Option<AccessorInfo>, Err> {
         // Get accessors array
         let accessors = json.get("accessors")
             .and_then(|a| a.as_array())
@@ -605,7 +605,7 @@ impl GltfDecoder {
             .to_string();
         
         // Extract buffer view info
-        let buffer_view_info = self.extract_buffer_view_info(json, buffer_view_index)?;
+        let buffer_view_info = Self::extract_buffer_view_info(json, buffer_view_index)?;
         
         Ok(Some(AccessorInfo {
             buffer_view_index,
@@ -618,7 +618,7 @@ impl GltfDecoder {
     }
 
     /// Extract buffer view information from JSON
-    fn extract_buffer_view_info(&self, json: &serde_json::Value, buffer_view_index: usize) -> Result<BufferViewInfo, Err> {
+    fn extract_buffer_view_info(json: &serde_json::Value, buffer_view_index: usize) -> Result<BufferViewInfo, Err> {
         let buffer_views = json.get("bufferViews")
             .and_then(|bv| bv.as_array())
             .ok_or_else(|| Err::LoadError("No bufferViews found in glTF".to_string()))?;
@@ -1660,7 +1660,7 @@ impl GltfDecoder {
         self.copy_textures_to_scene(scene)?;
 
         // Process all scene nodes to build the scene graph
-        self.decode_scene_nodes(scene)?;
+        let gltf_node_to_scene_node = self.decode_scene_nodes(scene)?;
 
         // Skip animations as per instructions
         // self.add_animations_to_scene(scene)?;
@@ -1668,8 +1668,8 @@ impl GltfDecoder {
         // Add materials to the scene
         self.add_materials_to_scene(scene)?;
 
-        // Skip skins as per instructions
-        // self.add_skins_to_scene(scene)?;
+        // Add skins to the scene
+        self.add_skins_to_scene(scene, &gltf_node_to_scene_node)?;
 
         // Move non-material textures from material texture library to non-material texture library
         Self::move_non_material_textures_from_scene(scene);
@@ -2088,7 +2088,8 @@ impl GltfDecoder {
     }
 
     /// Process all scene nodes to build the scene graph.
-    fn decode_scene_nodes(&mut self, scene: &mut Scene) -> Result<(), Err> {
+    /// Returns the map from the glTF node indices to the scene node indices.
+    fn decode_scene_nodes(&mut self, scene: &mut Scene) -> Result<HashMap<usize, usize>, Err> {
         // Collect node information first to avoid borrow checker issues
         let mut root_node_indices = Vec::new();
         
@@ -2104,6 +2105,7 @@ impl GltfDecoder {
         
         // Clone extension attributes to avoid borrow checker issues
         let extension_attributes = self.extension_attributes.clone();
+        let mut gltf_node_to_scene_node = HashMap::new();
         
         // Process each root node. We'll temporarily take ownership of gltf_model and buffers to avoid borrow conflicts
         let gltf_model = self.gltf_model.take();
@@ -2112,7 +2114,7 @@ impl GltfDecoder {
             for node_index in root_node_indices {
                 if let Some(node) = gltf_model.nodes().nth(node_index) {
                     let parent_index = usize::MAX; // Use MAX to indicate this is a root node
-                    Self::decode_node_for_scene_with_gltf_node_static(&node, parent_index, scene, &buffers, &extension_attributes, &gltf_model)?;
+                    Self::decode_node_for_scene_with_gltf_node_static(&node, parent_index, scene, &buffers, &extension_attributes, &gltf_model, &mut gltf_node_to_scene_node)?;
                 }
             }
             // Restore the gltf_model and buffers
@@ -2120,6 +2122,42 @@ impl GltfDecoder {
             self.buffers = Some(buffers);
         }
         
+        Ok(gltf_node_to_scene_node)
+    }
+
+    /// Decodes the glTF skins into the scene. The joints refer to the scene nodes, and the inverse bind
+    /// matrices, which are column-major in glTF, default to the identity as in the glTF specification.
+    fn add_skins_to_scene(&mut self, scene: &mut Scene, gltf_node_to_scene_node: &HashMap<usize, usize>) -> Result<(), Err> {
+        let (Some(gltf_model), Some(buffers)) = (&self.gltf_model, &self.buffers) else {
+            return Ok(());
+        };
+        for gltf_skin in gltf_model.skins() {
+            let joints = gltf_skin.joints()
+                .map(|joint| gltf_node_to_scene_node.get(&joint.index()).copied().ok_or_else(|| 
+                    Err::InvalidInput(format!("The joint node {} of the skin {} is not in any scene.", joint.index(), gltf_skin.index()))
+                ))
+                .collect::<Result<Vec<_>, Err>>()?;
+            let inverse_bind_matrices = match gltf_skin.reader(|buffer| buffers.get(buffer.index()).map(|data| &data.0[..])).read_inverse_bind_matrices() {
+                Some(matrices) => matrices
+                    .map(|m| {
+                        let mut data = [[0.0f64; 4]; 4];
+                        for col in 0..4 {
+                            for row in 0..4 {
+                                data[row][col] = m[col][row] as f64;
+                            }
+                        }
+                        Matrix4d::new(data)
+                    })
+                    .collect::<Vec<_>>(),
+                None => vec![Matrix4d::identity(); joints.len()],
+            };
+            if inverse_bind_matrices.len() != joints.len() {
+                return Err(Err::InvalidInput(format!(
+                    "The skin {} has {} joints, but {} inverse bind matrices.", gltf_skin.index(), joints.len(), inverse_bind_matrices.len()
+                )));
+            }
+            scene.add_skin(crate::core::scene::Skin { joints, inverse_bind_matrices });
+        }
         Ok(())
     }
 
@@ -2130,7 +2168,8 @@ impl GltfDecoder {
         scene: &mut Scene, 
         buffers: &[gltf::buffer::Data], 
         extension_attributes: &[Vec<ExtensionAttributes>],
-        gltf_model: &gltf::Document
+        gltf_model: &gltf::Document,
+        gltf_node_to_scene_node: &mut HashMap<usize, usize>,
     ) -> Result<(), Err> {
         // Get node transformation
         let trsm = Self::get_node_transformation_matrix(node);
@@ -2138,6 +2177,8 @@ impl GltfDecoder {
         // Create a scene node with the transformation
         let mut scene_node = crate::core::scene::SceneNode::new();
         scene_node.set_trs_matrix(trsm);
+        // The skins are added in the order of the glTF skins, so the index is kept.
+        scene_node.set_skin_index(node.skin().map(|skin| skin.index()));
         
         // Set parent if this is not a root node
         if parent_index != usize::MAX && parent_index < scene.num_nodes() {
@@ -2146,6 +2187,7 @@ impl GltfDecoder {
         
         // Add the node to the scene
         let actual_node_index = scene.add_node(scene_node);
+        gltf_node_to_scene_node.entry(node.index()).or_insert(actual_node_index);
         
         // If this is a root node, add to root indices
         if parent_index == usize::MAX {
@@ -2220,7 +2262,7 @@ impl GltfDecoder {
         
         // Process child nodes recursively
         for child in node.children() {
-            Self::decode_node_for_scene_with_gltf_node_static(&child, actual_node_index, scene, buffers, extension_attributes, gltf_model)?;
+            Self::decode_node_for_scene_with_gltf_node_static(&child, actual_node_index, scene, buffers, extension_attributes, gltf_model, gltf_node_to_scene_node)?;
         }
         
         Ok(())
@@ -2324,12 +2366,32 @@ impl GltfDecoder {
         Ok(result)
     }
 
+    /// Decodes the Draco stream of a primitive compressed with `KHR_draco_mesh_compression`, which is stored in
+    /// the buffer view of the extension.
+    fn decode_draco_primitive(
+        extension: &serde_json::Value,
+        buffers: &[gltf::buffer::Data],
+        gltf_model: &gltf::Document
+    ) -> Result<Mesh, Err> {
+        let view = extension.get("bufferView")
+            .and_then(|view| view.as_u64())
+            .and_then(|view| gltf_model.views().nth(view as usize))
+            .ok_or_else(|| Err::InvalidInput("The KHR_draco_mesh_compression extension has no valid bufferView.".to_string()))?;
+        let bytes = buffers[view.buffer().index()]
+            .get(view.offset()..view.offset() + view.length())
+            .ok_or_else(|| Err::InvalidInput(format!("The buffer view {} is out of its buffer.", view.index())))?;
+        let mut mesh = crate::decode::decode(&mut crate::prelude::SliceReader::new(bytes), crate::decode::Config::default())
+            .map_err(|e| Err::LoadError(format!("Failed to decode the Draco stream: {}", e)))?;
+        apply_draco_attribute_ids(&mut mesh.attributes, extension)?;
+        Ok(mesh)
+    }
+
     /// Create a Draco Mesh from a GLTF primitive with extension attributes support
     fn create_mesh_from_primitive_with_buffers_with_extensions(
         primitive: &gltf::Primitive, 
         buffers: &[gltf::buffer::Data], 
         extension_attributes: Option<&ExtensionAttributes>,
-        _gltf_model: &gltf::Document
+        gltf_model: &gltf::Document
     ) -> Result<Mesh, Err> {
         use gltf::mesh::Mode;
         use gltf::Semantic;
@@ -2341,6 +2403,11 @@ impl GltfDecoder {
             ));
         }
 
+        // The accessors of a Draco-compressed primitive hold no data, so the whole mesh comes from the Draco stream.
+        if let Some(draco) = extension_attributes.and_then(|ext_attrs| ext_attrs.extensions.get("KHR_draco_mesh_compression")) {
+            return Self::decode_draco_primitive(draco, buffers, gltf_model);
+        }
+
         // Create a mesh builder
         let mut mesh_builder = MeshBuilder::new();
         
@@ -2472,14 +2539,6 @@ impl GltfDecoder {
         
         // Process extension attributes if provided
         if let Some(ext_attrs) = extension_attributes {
-            // Check if this primitive uses Draco compression
-            if ext_attrs.extensions.contains_key("KHR_draco_mesh_compression") {
-                return Err(Err::LoadError(
-                    "KHR_draco_mesh_compression is not yet supported. \
-                    This file contains Draco-compressed mesh data that needs to be decoded first.".to_string()
-                ));
-            }
-            
             // Process EXT_mesh_features extension
             // Note: The actual mesh features JSON is stored in scene metadata by the caller
             if let Some(_mesh_features_ext) = ext_attrs.extensions.get("EXT_mesh_features") {
@@ -2858,3 +2917,108 @@ fn copy_data_as_uint32(accessor: &gltf::Accessor, buffers: &[gltf::buffer::Data]
 
     Ok(out)
 }
+
+
+/// Reads the values of the accessor `accessor_index` of the GLB `glb_bytes` into an attribute, slicing them out
+/// of the binary chunk through the buffer view of the accessor without parsing the rest of the asset.
+/// The type of the attribute follows the first primitive attribute referring to the accessor, and it is
+/// [AttributeType::Custom] if there is none. The accessor must be stored in the binary chunk of the GLB.
+pub fn read_accessor(glb_bytes: &[u8], accessor_index: usize) -> Result<crate::core::attribute::Attribute, Err> {
+    let glb = gltf::Glb::from_slice(glb_bytes)
+        .map_err(|e| Err::LoadError(format!("Failed to read GLB: {}", e)))?;
+    let json: serde_json::Value = serde_json::from_slice(&glb.json)
+        .map_err(|e| Err::LoadError(format!("Failed to parse glTF JSON: {}", e)))?;
+    let info = GltfDecoder::extract_accessor_info(&json, accessor_index)?
+        .ok_or_else(|| Err::InvalidInput(format!("Accessor {} has no buffer view", accessor_index)))?;
+    if info.buffer_view_info.buffer != 0 {
+        return Err(Err::InvalidInput(format!("Accessor {} is not stored in the binary chunk", accessor_index)));
+    }
+    let bin = glb.bin.ok_or_else(|| Err::LoadError("GLB has no binary chunk".to_string()))?;
+
+    let component_type = match info.component_type {
+        5120 => ComponentDataType::I8,
+        5121 => ComponentDataType::U8,
+        5122 => ComponentDataType::I16,
+        5123 => ComponentDataType::U16,
+        5125 => ComponentDataType::U32,
+        5126 => ComponentDataType::F32,
+        ty => return Err(Err::InvalidInput(format!("Unsupported component type: {}", ty))),
+    };
+    let num_components = match info.data_type.as_str() {
+        "SCALAR" => 1,
+        "VEC2" => 2,
+        "VEC3" => 3,
+        "VEC4" => 4,
+        "MAT4" => 16,
+        ty => return Err(Err::InvalidInput(format!("Unsupported accessor type: {}", ty))),
+    };
+
+    let value_size = component_type.size() * num_components;
+    let stride = info.buffer_view_info.byte_stride.unwrap_or(value_size);
+    let overrun = || Err::InvalidInput(format!("Accessor {} overruns its buffer view", accessor_index));
+    // The offsets and the count are not trusted, so that a huge one is an error rather than a wrapped sum.
+    let start = info.buffer_view_info.byte_offset.checked_add(info.byte_offset).ok_or_else(overrun)?;
+    let view_end = info.buffer_view_info.byte_offset.checked_add(info.buffer_view_info.byte_length).ok_or_else(overrun)?;
+    if info.count > 0 {
+        let end = stride.checked_mul(info.count - 1)
+            .and_then(|n| n.checked_add(start))
+            .and_then(|n| n.checked_add(value_size))
+            .ok_or_else(overrun)?;
+        if end > view_end.min(bin.len()) {
+            return Err(overrun());
+        }
+    }
+    let bytes = (0..info.count)
+        .flat_map(|i| &bin[start + i * stride..start + i * stride + value_size])
+        .copied()
+        .collect::<Vec<_>>();
+
+    let semantic = json["meshes"].as_array().into_iter().flatten()
+        .flat_map(|mesh| mesh["primitives"].as_array().into_iter().flatten())
+        .flat_map(|primitive| primitive["attributes"].as_object().into_iter().flatten())
+        .find(|(_, idx)| idx.as_u64() == Some(accessor_index as u64))
+        .map(|(name, _)| name.clone());
+    let att_type = semantic.as_deref().map_or(AttributeType::Custom, gltf_attribute_to_draco_attribute);
+
+    let mut att = crate::core::attribute::Attribute::from_bytes(
+        AttributeId::new(0), bytes, component_type, num_components, att_type, AttributeDomain::Position
+    );
+    if let Some(name) = semantic {
+        att.set_name(name);
+    }
+    Ok(att)
+}
+
+/// Returns the type of the attribute of the glTF attribute semantic `name`, e.g. `TEXCOORD_1`.
+fn gltf_attribute_to_draco_attribute(name: &str) -> AttributeType {
+    match name {
+        "POSITION" => AttributeType::Position,
+        "NORMAL" => AttributeType::Normal,
+        "TANGENT" => AttributeType::Tangent,
+        _ if name.starts_with("TEXCOORD_") => AttributeType::TextureCoordinate,
+        _ if name.starts_with("COLOR_") => AttributeType::Color,
+        _ if name.starts_with("JOINTS_") => AttributeType::Joint,
+        _ if name.starts_with("WEIGHTS_") => AttributeType::Weight,
+        _ => AttributeType::Custom,
+    }
+}
+
+/// Sets the type and the name of the attributes decoded from the Draco stream of a primitive, following the
+/// `attributes` map of its `KHR_draco_mesh_compression` extension, which maps the glTF attribute semantics
+/// to the unique ids of the attributes in the stream. The attributes are matched by their unique ids, as the
+/// ids need not follow the order of the semantics.
+pub(crate) fn apply_draco_attribute_ids(attributes: &mut [crate::core::attribute::Attribute], extension: &serde_json::Value) -> Result<(), Err> {
+    let ids = extension.get("attributes")
+        .and_then(|atts| atts.as_object())
+        .ok_or_else(|| Err::InvalidInput("The KHR_draco_mesh_compression extension has no attributes map.".to_string()))?;
+    for (name, id) in ids {
+        let id = id.as_u64()
+            .ok_or_else(|| Err::InvalidInput(format!("The unique id of {} is not an unsigned integer: {}", name, id)))?;
+        let att = attributes.iter_mut()
+            .find(|att| att.get_id().as_usize() as u64 == id)
+            .ok_or_else(|| Err::InvalidInput(format!("{} is mapped to the unique id {}, which is not in the Draco stream.", name, id)))?;
+        att.set_attribute_type(gltf_attribute_to_draco_attribute(name));
+        att.set_name(name.clone());
+    }
+    Ok(())
+}
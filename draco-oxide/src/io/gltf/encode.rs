@@ -63,6 +63,7 @@ impl Default for JsonOutputMode {
 pub struct GltfEncoder {
     output_type: OutputType,
     copyright: String,
+    draco_config: crate::encode::Config,
 }
 
 impl Default for GltfEncoder {
@@ -83,6 +84,7 @@ impl GltfEncoder {
         Self {
             output_type: OutputType::default(),
             copyright: String::new(),
+            draco_config: crate::encode::Config::default(),
         }
     }
 
@@ -209,6 +211,7 @@ impl GltfEncoder {
         let mut gltf_asset = GltfAsset::default();
         gltf_asset.set_output_type(self.output_type);
         gltf_asset.set_copyright(&self.copyright);
+        gltf_asset.set_draco_config(self.draco_config.clone());
         
         // Configure based on output format
         match ext_lower.as_str() {
@@ -253,6 +256,7 @@ impl GltfEncoder {
         gltf_asset.set_buffer_name(String::new());
         gltf_asset.set_add_images_to_buffer(true);
         gltf_asset.set_copyright(&self.copyright);
+        gltf_asset.set_draco_config(self.draco_config.clone());
 
         // Encode scene into the glTF asset
         self.encode_scene_to_buffer_internal(scene, &mut gltf_asset)?;
@@ -281,6 +285,11 @@ impl GltfEncoder {
         &self.copyright
     }
 
+    /// Sets the configuration used to compress the meshes with Draco.
+    pub fn set_draco_config(&mut self, cfg: crate::encode::Config) {
+        self.draco_config = cfg;
+    }
+
     /// Encodes the scene into a buffer.
     fn encode_scene_to_buffer_internal(
         &self,
@@ -314,6 +323,7 @@ impl GltfEncoder {
         gltf_asset.set_buffer_name(String::new());
         gltf_asset.set_add_images_to_buffer(true);
         gltf_asset.set_copyright(&self.copyright);
+        gltf_asset.set_draco_config(self.draco_config.clone());
 
         // Encode scene into the glTF asset
         self.encode_scene_to_buffer_internal(scene, &mut gltf_asset)?;
@@ -703,6 +713,7 @@ pub(crate) struct GltfAsset {
     scenes: Vec<GltfScene>,
     scene_index: i32,
     nodes: Vec<GltfNode>,
+    skins: Vec<EncoderSkin>,
     accessors: Vec<GltfAccessor>,
     buffer_views: Vec<GltfBufferView>,
     meshes: Vec<GltfMesh>,
@@ -721,6 +732,7 @@ pub(crate) struct GltfAsset {
     extensions_required: std::collections::BTreeSet<String>,
     texture_samplers: Vec<TextureSampler>,
     output_type: OutputType,
+    draco_config: crate::encode::Config,
 }
 
 /// glTF value types and values.
@@ -791,6 +803,7 @@ impl GltfAsset {
             scenes: Vec::new(),
             scene_index: -1,
             nodes: Vec::new(),
+            skins: Vec::new(),
             accessors: Vec::new(),
             buffer_views: Vec::new(),
             meshes: Vec::new(),
@@ -809,6 +822,7 @@ impl GltfAsset {
             extensions_required: std::collections::BTreeSet::new(),
             texture_samplers: Vec::new(),
             output_type: OutputType::default(),
+            draco_config: crate::encode::Config::default(),
         }
     }
 
@@ -910,6 +924,10 @@ impl GltfAsset {
         self.buffer_name = name;
     }
 
+    pub fn set_draco_config(&mut self, cfg: crate::encode::Config) {
+        self.draco_config = cfg;
+    }
+
     pub fn buffer(&self) -> &[u8] {
         &self.buffer
     }
@@ -938,7 +956,7 @@ impl GltfAsset {
         // Compress the mesh with Draco
         let mut draco_buffer = Vec::new();
         {
-            let config = crate::encode::Config::default();
+            let config = self.draco_config.clone();
             
             // Use the provided mesh
             let mesh_copy = mesh.clone();
@@ -1417,8 +1435,8 @@ impl GltfAsset {
         // Add animations (skip as per instructions)
         // self.add_animations(scene)?;
         
-        // Add skins (skip as per instructions)
-        // self.add_skins(scene)?;
+        // Add skins
+        self.add_skins(scene)?;
         
         // Add materials variants names
         self.add_materials_variants_names(scene)?;
@@ -1493,6 +1511,12 @@ impl GltfAsset {
             write!(buf_out, ",")?;
             self.encode_meshes_property(buf_out)?;
         }
+
+        // Write skins if any
+        if !self.skins.is_empty() {
+            write!(buf_out, ",")?;
+            self.encode_skins_property(buf_out)?;
+        }
         
         // Write materials if any
         if self.material_library.num_materials() > 0 {
@@ -1811,6 +1835,9 @@ impl GltfAsset {
             
             // Set transformation matrix from scene node
             gltf_node.trs_matrix = scene_node.get_trs_matrix().clone();
+
+            // The skins are added in the order of the scene skins.
+            gltf_node.skin_index = scene_node.get_skin_index().map_or(-1, |idx| idx as i32);
             
             // Add mesh reference if the node has a mesh group
             if let Some(mesh_group_index) = scene_node.get_mesh_group_index() {
@@ -1866,6 +1893,55 @@ impl GltfAsset {
         Ok(())
     }
 
+    /// Adds the skins of |scene| to the asset. The joints keep their indices, as the glTF nodes are added
+    /// in the order of the scene nodes, and the inverse bind matrices are stored column-major in the buffer.
+    fn add_skins(&mut self, scene: &Scene) -> Result<(), Err> {
+        for skin_index in 0..scene.num_skins() {
+            let skin = scene.get_skin(skin_index)
+                .ok_or_else(|| Err::InvalidInput(format!("Skin {} not found", skin_index)))?;
+            if skin.inverse_bind_matrices.len() != skin.joints.len() {
+                return Err(Err::InvalidInput(format!(
+                    "The skin {} has {} joints, but {} inverse bind matrices", skin_index, skin.joints.len(), skin.inverse_bind_matrices.len()
+                )));
+            }
+            let mut encoder_skin = EncoderSkin {
+                joints: skin.joints.iter().map(|&joint| joint as i32).collect(),
+                ..EncoderSkin::default()
+            };
+
+            if !skin.inverse_bind_matrices.is_empty() {
+                let buffer_start_offset = self.buffer.len();
+                for matrix in &skin.inverse_bind_matrices {
+                    for col in 0..4 {
+                        for row in 0..4 {
+                            self.buffer.extend_from_slice(&(matrix.data[row][col] as f32).to_le_bytes());
+                        }
+                    }
+                }
+                let buffer_view = GltfBufferView {
+                    buffer_byte_offset: buffer_start_offset as i64,
+                    byte_length: (self.buffer.len() - buffer_start_offset) as i64,
+                    byte_stride: 0,
+                    target: 0, // Set to 0 for no specific target
+                };
+                self.pad_buffer();
+                self.buffer_views.push(buffer_view);
+
+                let accessor = GltfAccessor {
+                    buffer_view_index: (self.buffer_views.len() - 1) as i32,
+                    component_type: ComponentType::Float as i32,
+                    count: skin.inverse_bind_matrices.len() as i64,
+                    accessor_type: "MAT4".to_string(),
+                    ..GltfAccessor::default()
+                };
+                encoder_skin.inverse_bind_matrices_index = self.accessors.len() as i32;
+                self.accessors.push(accessor);
+            }
+            self.skins.push(encoder_skin);
+        }
+        Ok(())
+    }
+
     /// Iterate through the materials that are associated with |scene| and add them
     /// to the asset.
     fn add_materials_from_scene(&mut self, scene: &Scene) {
@@ -2496,10 +2572,18 @@ impl GltfAsset {
                 }
                 write!(buf_out, "\"mesh\":{}", node.mesh_index)?;
             }
+
+            // Write skin reference if any
+            if node.skin_index >= 0 {
+                if !node.name.is_empty() || !node.children_indices.is_empty() || node.mesh_index >= 0 {
+                    write!(buf_out, ",")?;
+                }
+                write!(buf_out, "\"skin\":{}", node.skin_index)?;
+            }
             
             // Write transformation components if any transformation is set
             if node.trs_matrix.transform_set() {
-                let mut needs_comma = !node.name.is_empty() || !node.children_indices.is_empty() || node.mesh_index >= 0;
+                let mut needs_comma = !node.name.is_empty() || !node.children_indices.is_empty() || node.mesh_index >= 0 || node.skin_index >= 0;
                 
                 // Prefer translation/rotation/scale over matrix when available
                 if node.trs_matrix.translation_set() {
@@ -2558,6 +2642,34 @@ impl GltfAsset {
         Ok(())
     }
 
+    fn encode_skins_property(&self, buf_out: &mut Vec<u8>) -> Result<(), Err> {
+        use std::io::Write;
+
+        write!(buf_out, "\"skins\":[")?;
+        for (i, skin) in self.skins.iter().enumerate() {
+            if i > 0 {
+                write!(buf_out, ",")?;
+            }
+            write!(buf_out, "{{")?;
+            if skin.inverse_bind_matrices_index >= 0 {
+                write!(buf_out, "\"inverseBindMatrices\":{},", skin.inverse_bind_matrices_index)?;
+            }
+            if skin.skeleton_index >= 0 {
+                write!(buf_out, "\"skeleton\":{},", skin.skeleton_index)?;
+            }
+            write!(buf_out, "\"joints\":[")?;
+            for (j, joint) in skin.joints.iter().enumerate() {
+                if j > 0 {
+                    write!(buf_out, ",")?;
+                }
+                write!(buf_out, "{}", joint)?;
+            }
+            write!(buf_out, "]}}")?;
+        }
+        write!(buf_out, "]")?;
+        Ok(())
+    }
+
     fn encode_meshes_property(&mut self, buf_out: &mut Vec<u8>) -> Result<(), Err> {
         use std::io::Write;
         
@@ -3393,6 +3505,11 @@ impl GltfAsset {
             }
             
             write!(buf_out, "{{")?;
+
+            // Buffer view, if the data is not compressed by Draco
+            if accessor.buffer_view_index >= 0 {
+                write!(buf_out, "\"bufferView\":{},\"byteOffset\":0,", accessor.buffer_view_index)?;
+            }
             
             // Component type
             write!(buf_out, "\"componentType\":{}", accessor.component_type)?;
@@ -3,3 +3,243 @@ pub mod decode;
 pub mod transcoder;
 pub mod scene_io;
 
+pub use decode::read_accessor;
+
+use std::path::Path;
+
+use crate::core::mesh::Mesh;
+use crate::core::scene::{MeshInstance, Scene, SceneNode};
+
+/// Writes the mesh to a glTF-Binary (GLB) file at `path`. 
+/// The resulting file contains a single node with one primitive, whose geometry is compressed 
+/// with Draco using `cfg` and stored via the `KHR_draco_mesh_compression` extension.
+/// The materials of the mesh are written as well, and the first one is bound to the primitive.
+pub fn write_glb<P>(mesh: &Mesh, path: P, cfg: crate::encode::Config) -> Result<(), encode::Err> 
+    where P: AsRef<Path>
+{
+    let mut scene = Scene::new();
+    let mesh_idx = scene.add_mesh(mesh.clone());
+    let mesh_group_idx = scene.add_mesh_group();
+    // the first material of the mesh is bound to the primitive.
+    let material_idx = if mesh.get_material_library().num_materials() > 0 {
+        *scene.material_library_mut() = mesh.get_material_library().clone();
+        0
+    } else {
+        -1
+    };
+    scene.get_mesh_group_mut(mesh_group_idx)
+        .unwrap() // the mesh group is added right above
+        .add_mesh_instance(MeshInstance::new(mesh_idx, material_idx));
+
+    let mut node = SceneNode::new();
+    node.set_mesh_group_index(Some(mesh_group_idx));
+    let node_idx = scene.add_node(node);
+    scene.add_root_node_index(node_idx);
+
+    let mut encoder = encode::GltfEncoder::new();
+    encoder.set_draco_config(cfg);
+    let mut buffer = Vec::new();
+    encoder.encode_scene_to_buffer(&scene, &mut buffer)?;
+    std::fs::write(path, buffer)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::shared::{ConfigType, NdVector, PointIdx};
+    use crate::io::obj::load_obj;
+
+    #[test]
+    fn test_write_glb() {
+        let mesh = load_obj("tests/data/tetrahedron.obj").unwrap();
+        let path = std::env::temp_dir().join(format!("draco_oxide_test_write_glb_{}.glb", std::process::id()));
+        write_glb(&mesh, &path, crate::encode::Config::default()).unwrap();
+
+        let glb = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let glb = gltf::Glb::from_slice(&glb).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&glb.json).unwrap();
+        assert!(json["extensionsRequired"].as_array().unwrap().iter().any(|e| e == "KHR_draco_mesh_compression"));
+
+        let primitives = json["meshes"][0]["primitives"].as_array().unwrap();
+        assert_eq!(primitives.len(), 1);
+        let draco_ext = &primitives[0]["extensions"]["KHR_draco_mesh_compression"];
+        let position_accessor = primitives[0]["attributes"]["POSITION"].as_u64().unwrap() as usize;
+        let num_points = json["accessors"][position_accessor]["count"].as_u64().unwrap() as usize;
+        assert_eq!(num_points, mesh.get_attributes()[0].len());
+        let indices_accessor = primitives[0]["indices"].as_u64().unwrap() as usize;
+        let num_indices = json["accessors"][indices_accessor]["count"].as_u64().unwrap() as usize;
+        assert_eq!(num_indices, mesh.get_faces().len() * 3);
+
+        // The buffer view referenced by the extension must hold the Draco bitstream.
+        let view = &json["bufferViews"][draco_ext["bufferView"].as_u64().unwrap() as usize];
+        let offset = view["byteOffset"].as_u64().unwrap_or(0) as usize;
+        let length = view["byteLength"].as_u64().unwrap() as usize;
+        let bin = glb.bin.unwrap();
+        assert!(length > 0);
+        assert_eq!(&bin[offset..offset+5], b"DRACO");
+
+        // The bitstream decodes to the geometry of the mesh.
+        #[cfg(not(feature = "evaluation"))]
+        {
+            let decoded = crate::decode::decode(
+                &mut crate::prelude::SliceReader::new(&bin[offset..offset+length]), crate::decode::Config::default()
+            ).unwrap();
+            assert_eq!(decoded.get_faces().len(), mesh.get_faces().len());
+            assert!(mesh.diff_l2_norm(&decoded) < 1e-3, "{}", mesh.diff_l2_norm(&decoded));
+        }
+    }
+
+    #[test]
+    fn test_write_glb_with_material() {
+        use crate::core::material::Material;
+        use crate::core::shared::NdVector;
+
+        let mut mesh = load_obj("tests/data/tetrahedron.obj").unwrap();
+        let mut material = Material::new();
+        material.set_name("atlas".to_string());
+        material.set_color_factor(NdVector::from([1.0, 0.5, 0.25, 1.0]));
+        assert_eq!(mesh.add_material(material), 0);
+
+        let path = std::env::temp_dir().join(format!("draco_oxide_test_write_glb_with_material_{}.glb", std::process::id()));
+        write_glb(&mesh, &path, crate::encode::Config::default()).unwrap();
+        let glb = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let glb = gltf::Glb::from_slice(&glb).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&glb.json).unwrap();
+
+        let materials = json["materials"].as_array().unwrap();
+        assert_eq!(materials.len(), 1);
+        assert_eq!(materials[0]["name"], "atlas");
+        let color = materials[0]["pbrMetallicRoughness"]["baseColorFactor"].as_array().unwrap()
+            .iter()
+            .map(|c| c.as_f64().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(color, vec![1.0, 0.5, 0.25, 1.0]);
+        assert_eq!(json["meshes"][0]["primitives"][0]["material"], 0);
+    }
+
+    #[test]
+    #[cfg(not(feature = "evaluation"))]
+    fn test_draco_attribute_ids() {
+        use crate::core::attribute::{AttributeDomain, AttributeId, AttributeType};
+        use crate::core::mesh::builder::MeshBuilder;
+
+        // The normals come first, so the unique ids do not follow the order of the glTF semantics.
+        let tetrahedron = load_obj("tests/data/tetrahedron.obj").unwrap();
+        let values = |ty: AttributeType| tetrahedron.get_attributes().iter().find(|att| att.get_attribute_type() == ty).unwrap().clone();
+        let mut builder = MeshBuilder::new();
+        builder.add_attribute(values(AttributeType::Normal).into_vec::<NdVector<3, f32>, 3>().unwrap(), AttributeType::Normal, AttributeDomain::Position, vec![AttributeId::new(1)]);
+        builder.add_attribute(values(AttributeType::Position).into_vec::<NdVector<3, f32>, 3>().unwrap(), AttributeType::Position, AttributeDomain::Position, Vec::new());
+        builder.add_attribute(values(AttributeType::TextureCoordinate).into_vec::<NdVector<2, f32>, 2>().unwrap(), AttributeType::TextureCoordinate, AttributeDomain::Position, vec![AttributeId::new(1)]);
+        builder.set_connectivity_attribute(tetrahedron.get_faces().iter().map(|f| f.map(usize::from)).collect());
+        let mesh = builder.build().unwrap();
+
+        let path = std::env::temp_dir().join(format!("draco_oxide_test_draco_attribute_ids_{}.glb", std::process::id()));
+        write_glb(&mesh, &path, crate::encode::Config::default()).unwrap();
+        let glb = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&gltf::Glb::from_slice(&glb).unwrap().json).unwrap();
+        let extension = &json["meshes"][0]["primitives"][0]["extensions"]["KHR_draco_mesh_compression"];
+        assert_eq!(extension["attributes"]["POSITION"], 1);
+        assert_eq!(extension["attributes"]["NORMAL"], 0);
+
+        // The attributes of the loaded primitive are typed and named after the ids of the extension.
+        let scene = decode::GltfDecoder::new().decode_from_buffer_to_scene(&glb).unwrap();
+        let mut decoded = scene.meshes()[0].get_attributes().to_vec();
+        for att in &decoded {
+            let original = mesh.get_attributes().iter().find(|original| original.get_id() == att.get_id()).unwrap();
+            assert_eq!(att.get_attribute_type(), original.get_attribute_type(), "{:?}", att.get_name());
+            assert_eq!(att.len(), original.len(), "{:?}", att.get_name());
+        }
+        let position = decoded.iter().find(|att| att.get_id() == AttributeId::new(1)).unwrap();
+        assert_eq!(position.get_name().map(|name| name.as_str()), Some("POSITION"));
+
+        // An id that is not in the stream is an error.
+        let mut extension = extension.clone();
+        extension["attributes"]["TEXCOORD_1"] = 7.into();
+        assert!(decode::apply_draco_attribute_ids(&mut decoded, &extension).is_err());
+    }
+
+    #[test]
+    fn test_skin_round_trip() {
+        use crate::core::scene::{Matrix4d, Skin, Vector3d};
+
+        let mesh = load_obj("tests/data/tetrahedron.obj").unwrap();
+        let mut scene = Scene::new();
+        let mesh_idx = scene.add_mesh(mesh);
+        let mesh_group_idx = scene.add_mesh_group();
+        scene.get_mesh_group_mut(mesh_group_idx).unwrap().add_mesh_instance(MeshInstance::new(mesh_idx, -1));
+
+        // the skinned mesh, and a skeleton of three joints.
+        let mut skinned = SceneNode::new();
+        skinned.set_mesh_group_index(Some(mesh_group_idx));
+        skinned.set_skin_index(Some(0));
+        let skinned_idx = scene.add_node(skinned);
+        scene.add_root_node_index(skinned_idx);
+        let root_joint = scene.add_node(SceneNode::new());
+        scene.add_root_node_index(root_joint);
+        let joints = [root_joint, scene.add_node(SceneNode::new()), scene.add_node(SceneNode::new())];
+        for &joint in &joints[1..] {
+            scene.get_node_mut(joint).unwrap().add_parent_index(root_joint);
+            scene.get_node_mut(root_joint).unwrap().add_child_index(joint);
+        }
+        let inverse_bind_matrices = (0..3)
+            .map(|i| Matrix4d::from_translation(Vector3d::new(-(i as f64), 0.5 * i as f64, 2.0)))
+            .collect::<Vec<_>>();
+        scene.add_skin(Skin { joints: joints.to_vec(), inverse_bind_matrices: inverse_bind_matrices.clone() });
+
+        let mut buffer = Vec::new();
+        encode::GltfEncoder::new().encode_scene_to_buffer(&scene, &mut buffer).unwrap();
+        let decoded = decode::GltfDecoder::new().decode_from_buffer_to_scene(&buffer).unwrap();
+
+        assert_eq!(decoded.num_skins(), 1);
+        let skin = decoded.get_skin(0).unwrap();
+        assert_eq!(skin.joints, joints);
+        assert_eq!(skin.inverse_bind_matrices, inverse_bind_matrices);
+        assert_eq!(decoded.get_node(skinned_idx).unwrap().get_skin_index(), Some(0));
+        assert_eq!(decoded.get_node(root_joint).unwrap().get_skin_index(), None);
+    }
+
+    #[test]
+    fn test_read_accessor() {
+        // the triangle of 'Triangle.gltf' packed into a GLB with its buffer as the binary chunk.
+        let mut json: serde_json::Value = serde_json::from_slice(&std::fs::read("tests/data/Triangle.gltf").unwrap()).unwrap();
+        json["buffers"][0].as_object_mut().unwrap().remove("uri");
+        let mut bin = std::fs::read("tests/data/simpleTriangle.bin").unwrap();
+        bin.resize(bin.len().next_multiple_of(4), 0);
+        let pack = |json: &serde_json::Value| {
+            let mut json = serde_json::to_vec(json).unwrap();
+            json.resize(json.len().next_multiple_of(4), b' ');
+            let mut glb = Vec::new();
+            glb.extend_from_slice(b"glTF");
+            glb.extend_from_slice(&2_u32.to_le_bytes());
+            glb.extend_from_slice(&((12 + 8 + json.len() + 8 + bin.len()) as u32).to_le_bytes());
+            glb.extend_from_slice(&(json.len() as u32).to_le_bytes());
+            glb.extend_from_slice(b"JSON");
+            glb.extend_from_slice(&json);
+            glb.extend_from_slice(&(bin.len() as u32).to_le_bytes());
+            glb.extend_from_slice(b"BIN\0");
+            glb.extend_from_slice(&bin);
+            glb
+        };
+        let glb = pack(&json);
+
+        let att = read_accessor(&glb, 1).unwrap();
+        assert_eq!(att.get_attribute_type(), crate::prelude::AttributeType::Position);
+        assert_eq!(att.len(), 3);
+        let values = (0..att.len())
+            .map(|p| att.get::<NdVector<3, f32>, 3>(PointIdx::from(p)))
+            .collect::<Vec<_>>();
+        let expected = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]].map(NdVector::from);
+        assert_eq!(values, expected);
+
+        // an accessor that does not exist is rejected.
+        assert!(read_accessor(&glb, 2).is_err());
+
+        // so is a count whose end overflows rather than wrapping past the end of the buffer view.
+        json["accessors"][1]["count"] = 1537228672809129301_u64.into();
+        assert!(read_accessor(&pack(&json), 1).is_err());
+    }
+}
@@ -1,3 +1,6 @@
+pub mod container;
 pub mod obj;
+pub mod ply;
+pub mod stl;
 pub mod gltf;
 pub mod texture_io;
\ No newline at end of file
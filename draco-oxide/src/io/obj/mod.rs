@@ -1,16 +1,23 @@
-use crate::core::attribute::AttributeDomain;
+use crate::core::attribute::{Attribute, AttributeDomain};
+use crate::core::mesh::diff::unique_value_as_f64;
+use crate::core::shared::PointIdx;
 // use tobj to load the obj file and convert it to our internal mesh representation
 use crate::prelude::{AttributeType, MeshBuilder, NdVector};
 use crate::prelude::Mesh;
 use std::fmt::Debug;
+use std::io::Write;
 use std::path::Path;
 
 #[derive(Debug, thiserror::Error, Clone)]
 pub enum Err {
+    #[error("IO Error: {0}")]
+    IoError(String),
     #[error("Mesh Builder Error: {0}")]
     MeshBuilderError(#[from] crate::core::mesh::builder::Err),
 }
 
+/// Loads the first object of the OBJ file as a triangulated mesh. The face indices may be negative, i.e. relative
+/// to the vertices defined before the face, as the OBJ format allows.
 pub fn load_obj<P: AsRef<Path> + Debug>(path: P) -> Result<Mesh, Err> {
     let op = tobj::LoadOptions {
         triangulate: true,
@@ -63,6 +70,52 @@ fn load_tex_coords(mesh: &tobj::Mesh) -> (Vec<NdVector<2, f32>>, AttributeDomain
     (tex_coords, AttributeDomain::Corner)
 }
 
+/// Writes the mesh to the OBJ file at 'path' as the 'v', 'vt' and 'vn' records of the unique values of its first
+/// position, texture coordinate and normal attributes, and an 'f' record of each face whose corners refer to the
+/// values of their points with 1-based indices, e.g. 'f 1/1/1 2/2/2 3/3/3'. Since the values are referred to
+/// per corner, the values shared by several points, e.g. the normals of the corner domain, are written once.
+/// The other attributes are not written.
+pub fn write_obj<P: AsRef<Path>>(mesh: &Mesh, path: P) -> Result<(), Err> {
+    let file = std::fs::File::create(path).map_err(|e| Err::IoError(e.to_string()))?;
+    let mut writer = std::io::BufWriter::new(file);
+    write_obj_records(mesh, &mut writer).map_err(|e| Err::IoError(e.to_string()))
+}
+
+fn write_obj_records<W: Write>(mesh: &Mesh, writer: &mut W) -> std::io::Result<()> {
+    let att_of = |ty| mesh.get_attributes().iter().find(|att| att.get_attribute_type() == ty);
+    let pos = att_of(AttributeType::Position);
+    let tex_coord = att_of(AttributeType::TextureCoordinate);
+    let normal = att_of(AttributeType::Normal);
+
+    for (att, record) in [(pos, "v"), (tex_coord, "vt"), (normal, "vn")] {
+        let Some(att) = att else { continue };
+        for idx in 0..att.num_unique_values() {
+            write!(writer, "{}", record)?;
+            for c in unique_value_as_f64(att, idx) {
+                write!(writer, " {}", c)?;
+            }
+            writeln!(writer)?;
+        }
+    }
+
+    // the 1-based index of the value of the point in the records of the attribute.
+    let idx_of = |att: &Attribute, p: PointIdx| usize::from(att.get_unique_val_idx(p)) + 1;
+    for face in mesh.get_faces() {
+        write!(writer, "f")?;
+        for &p in face {
+            let v = pos.map_or(usize::from(p) + 1, |pos| idx_of(pos, p));
+            match (tex_coord, normal) {
+                (None, None) => write!(writer, " {}", v)?,
+                (Some(vt), None) => write!(writer, " {}/{}", v, idx_of(vt, p))?,
+                (None, Some(vn)) => write!(writer, " {}//{}", v, idx_of(vn, p))?,
+                (Some(vt), Some(vn)) => write!(writer, " {}/{}/{}", v, idx_of(vt, p), idx_of(vn, p))?,
+            }
+        }
+        writeln!(writer)?;
+    }
+    writer.flush()
+}
+
 #[cfg(test)]
 mod tests {
     use crate::core::shared::PointIdx;
@@ -87,4 +140,65 @@ mod tests {
         assert_eq!(mesh.attributes[0].num_unique_values(), 4);
         assert_eq!(mesh.attributes[0].len(), 6);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn write_then_load() {
+        for file in ["tetrahedron", "sphere"] {
+            let mesh = load_obj(format!("tests/data/{}.obj", file)).unwrap();
+            let path = std::env::temp_dir().join(format!("draco_oxide_test_write_obj_{}_{}.obj", file, std::process::id()));
+            write_obj(&mesh, &path).unwrap();
+            let reloaded = load_obj(&path).unwrap();
+            std::fs::remove_file(&path).unwrap();
+
+            assert_eq!(reloaded.get_faces().len(), mesh.get_faces().len());
+            assert_eq!(reloaded.attributes.len(), mesh.attributes.len());
+            // the values of the corners are kept, including the normals of the corner domain.
+            for (att, reloaded_att) in mesh.attributes.iter().zip(&reloaded.attributes) {
+                assert_eq!(att.get_attribute_type(), reloaded_att.get_attribute_type());
+                for (face, reloaded_face) in mesh.get_faces().iter().zip(reloaded.get_faces()) {
+                    for (&p, &q) in face.iter().zip(reloaded_face) {
+                        let value = unique_value_as_f64(att, usize::from(att.get_unique_val_idx(p)));
+                        let reloaded_value = unique_value_as_f64(reloaded_att, usize::from(reloaded_att.get_unique_val_idx(q)));
+                        assert_eq!(value, reloaded_value);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn write_positions_only() {
+        let mut builder = MeshBuilder::new();
+        builder.set_connectivity_attribute(vec![[0, 1, 2], [0, 2, 3]]);
+        let pos = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [1.0, 1.0, 0.0], [0.0, 1.0, 0.0]].map(NdVector::<3, f32>::from);
+        builder.add_attribute(pos.to_vec(), AttributeType::Position, AttributeDomain::Position, vec![]);
+        let mesh = builder.build().unwrap();
+
+        let mut buffer = Vec::new();
+        write_obj_records(&mesh, &mut buffer).unwrap();
+        let text = String::from_utf8(buffer).unwrap();
+        assert_eq!(text.lines().filter(|l| l.starts_with("v ")).count(), 4);
+        assert_eq!(text.lines().filter(|l| l.starts_with("f ")).collect::<Vec<_>>(), vec!["f 1 2 3", "f 1 3 4"]);
+    }
+
+    #[test]
+    fn relative_indices() {
+        // the negative indices refer to the vertices defined most recently before the face.
+        let mesh = load_obj("tests/data/relative_indices.obj").unwrap();
+        let types = mesh.attributes.iter().map(|att| att.get_attribute_type()).collect::<Vec<_>>();
+        assert_eq!(types, vec![AttributeType::Position, AttributeType::Normal, AttributeType::TextureCoordinate]);
+
+        let expected = [
+            [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0]],
+            [[0.0, 0.0], [1.0, 1.0], [0.0, 1.0]],
+        ];
+        assert_eq!(mesh.get_faces().len(), expected.len());
+        for (face, expected) in mesh.get_faces().iter().zip(expected) {
+            for (&p, [x, y]) in face.iter().zip(expected) {
+                assert_eq!(mesh.attributes[0].get::<NdVector<3, f32>, 3>(p), NdVector::from([x, y, 0.0]));
+                assert_eq!(mesh.attributes[1].get::<NdVector<3, f32>, 3>(p), NdVector::from([0.0, 0.0, 1.0]));
+                assert_eq!(mesh.attributes[2].get::<NdVector<2, f32>, 2>(p), NdVector::from([x, y]));
+            }
+        }
+    }
+}
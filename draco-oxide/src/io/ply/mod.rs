@@ -0,0 +1,396 @@
+use crate::core::attribute::{AttributeDomain, AttributeId};
+use crate::prelude::{AttributeType, Mesh, MeshBuilder, NdVector};
+use std::fmt::Debug;
+use std::path::Path;
+
+#[remain::sorted]
+#[derive(Debug, thiserror::Error)]
+pub enum Err {
+    #[error("Invalid PLY header: {0}")]
+    InvalidHeader(String),
+    #[error("Invalid value in the PLY body: {0}")]
+    InvalidValue(String),
+    #[error("IO Error: {0}")]
+    IoError(#[from] std::io::Error),
+    #[error("Mesh Builder Error: {0}")]
+    MeshBuilderError(#[from] crate::core::mesh::builder::Err),
+    #[error("The vertices have no 'x', 'y', and 'z' properties.")]
+    NoPositions,
+    #[error("The PLY body ends before all the elements are read.")]
+    UnexpectedEnd,
+}
+
+/// Loads an ASCII, little-endian, or big-endian binary PLY file.
+/// The 'x', 'y', 'z' properties of the vertices are read as the positions, 'nx', 'ny', 'nz' as the normals,
+/// and 'red', 'green', 'blue' (and 'alpha' if present) as the colors. Any other scalar property of the vertices
+/// is read as a custom attribute of one f32 component named after the property.
+/// The faces are fan-triangulated, and a file without faces is loaded as a point cloud, i.e. a mesh without faces.
+pub fn load_ply<P: AsRef<Path> + Debug>(path: P) -> Result<Mesh, Err> {
+    let data = std::fs::read(path)?;
+    parse_ply(&data)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Format {
+    Ascii,
+    BinaryLittleEndian,
+    BinaryBigEndian,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ScalarType {
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+    F32,
+    F64,
+}
+
+impl ScalarType {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "char" | "int8" => Some(Self::I8),
+            "uchar" | "uint8" => Some(Self::U8),
+            "short" | "int16" => Some(Self::I16),
+            "ushort" | "uint16" => Some(Self::U16),
+            "int" | "int32" => Some(Self::I32),
+            "uint" | "uint32" => Some(Self::U32),
+            "float" | "float32" => Some(Self::F32),
+            "double" | "float64" => Some(Self::F64),
+            _ => None,
+        }
+    }
+
+    fn size(self) -> usize {
+        match self {
+            Self::I8 | Self::U8 => 1,
+            Self::I16 | Self::U16 => 2,
+            Self::I32 | Self::U32 | Self::F32 => 4,
+            Self::F64 => 8,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Property {
+    Scalar(String, ScalarType),
+    List {
+        name: String,
+        count_ty: ScalarType,
+        item_ty: ScalarType,
+    },
+}
+
+#[derive(Clone, Debug)]
+struct Element {
+    name: String,
+    count: usize,
+    properties: Vec<Property>,
+}
+
+/// Reads the values of the body one by one, as the text tokens or the binary scalars.
+struct BodyReader<'a> {
+    format: Format,
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl BodyReader<'_> {
+    fn read(&mut self, ty: ScalarType) -> Result<f64, Err> {
+        if self.format == Format::Ascii {
+            let start = self.data[self.pos..].iter()
+                .position(|b| !b.is_ascii_whitespace())
+                .map(|i| self.pos + i)
+                .ok_or(Err::UnexpectedEnd)?;
+            let end = self.data[start..].iter()
+                .position(|b| b.is_ascii_whitespace())
+                .map_or(self.data.len(), |i| start + i);
+            self.pos = end;
+            let token = std::str::from_utf8(&self.data[start..end])
+                .map_err(|_| Err::InvalidValue("the token is not valid UTF-8".to_string()))?;
+            return token.parse::<f64>()
+                .map_err(|_| Err::InvalidValue(format!("'{}' is not a number", token)));
+        }
+
+        let size = ty.size();
+        let bytes = self.data.get(self.pos..self.pos + size).ok_or(Err::UnexpectedEnd)?;
+        self.pos += size;
+        let mut buf = [0u8; 8];
+        buf[..size].copy_from_slice(bytes);
+        if self.format == Format::BinaryBigEndian {
+            buf[..size].reverse();
+        }
+        Ok(match ty {
+            ScalarType::I8 => i8::from_le_bytes([buf[0]]) as f64,
+            ScalarType::U8 => buf[0] as f64,
+            ScalarType::I16 => i16::from_le_bytes([buf[0], buf[1]]) as f64,
+            ScalarType::U16 => u16::from_le_bytes([buf[0], buf[1]]) as f64,
+            ScalarType::I32 => i32::from_le_bytes([buf[0], buf[1], buf[2], buf[3]]) as f64,
+            ScalarType::U32 => u32::from_le_bytes([buf[0], buf[1], buf[2], buf[3]]) as f64,
+            ScalarType::F32 => f32::from_le_bytes([buf[0], buf[1], buf[2], buf[3]]) as f64,
+            ScalarType::F64 => f64::from_le_bytes(buf),
+        })
+    }
+}
+
+fn parse_ply(data: &[u8]) -> Result<Mesh, Err> {
+    let (format, elements, body_start) = parse_header(data)?;
+    let mut reader = BodyReader { format, data, pos: body_start };
+
+    // The values of the scalar properties of the vertices, in the order of the header.
+    let mut vertex_properties: Vec<(String, ScalarType, Vec<f64>)> = Vec::new();
+    let mut polygons = Vec::new();
+    for element in &elements {
+        let is_vertex = element.name == "vertex";
+        let is_face = element.name == "face";
+        if is_vertex {
+            vertex_properties = element.properties.iter()
+                .filter_map(|p| match p {
+                    // The count of the header is not trusted for the allocation, as the file may be corrupted.
+                    Property::Scalar(name, ty) => Some((name.clone(), *ty, Vec::new())),
+                    Property::List { .. } => None,
+                })
+                .collect();
+        }
+        for _ in 0..element.count {
+            let mut scalar_idx = 0;
+            for property in &element.properties {
+                match property {
+                    Property::Scalar(_, ty) => {
+                        let value = reader.read(*ty)?;
+                        if is_vertex {
+                            vertex_properties[scalar_idx].2.push(value);
+                        }
+                        scalar_idx += 1;
+                    },
+                    Property::List { name, count_ty, item_ty } => {
+                        let count = reader.read(*count_ty)? as usize;
+                        let items = (0..count)
+                            .map(|_| reader.read(*item_ty))
+                            .collect::<Result<Vec<_>, _>>()?;
+                        if is_face && (name == "vertex_indices" || name == "vertex_index") {
+                            if let Some(i) = items.iter().find(|&&i| i < 0.0) {
+                                return Err(Err::InvalidValue(format!("the face {:?} refers to the negative vertex {}", items, i)));
+                            }
+                            polygons.push(items.into_iter().map(|i| i as usize).collect::<Vec<_>>());
+                        }
+                    },
+                }
+            }
+        }
+    }
+
+    let num_vertices = vertex_properties.first().map_or(0, |(_, _, values)| values.len());
+    if let Some(polygon) = polygons.iter().find(|polygon| polygon.iter().any(|&i| i >= num_vertices)) {
+        return Err(Err::InvalidValue(format!("the face {:?} refers to a vertex out of the {} vertices", polygon, num_vertices)));
+    }
+
+    let take = |vertex_properties: &mut Vec<(String, ScalarType, Vec<f64>)>, name: &str| {
+        let idx = vertex_properties.iter().position(|(n, _, _)| n == name)?;
+        Some(vertex_properties.remove(idx))
+    };
+    let [x, y, z] = ["x", "y", "z"].map(|name| take(&mut vertex_properties, name));
+    let (Some(x), Some(y), Some(z)) = (x, y, z) else {
+        return Err(Err::NoPositions);
+    };
+    let mut builder = MeshBuilder::new();
+    builder.set_polygon_connectivity(polygons);
+    builder.set_triangulate(true);
+    let pos = (0..num_vertices)
+        .map(|i| NdVector::from([x.2[i] as f32, y.2[i] as f32, z.2[i] as f32]))
+        .collect::<Vec<_>>();
+    let pos_att_id = builder.add_attribute(pos, AttributeType::Position, AttributeDomain::Position, vec![]);
+
+    if let [Some(nx), Some(ny), Some(nz)] = ["nx", "ny", "nz"].map(|name| take(&mut vertex_properties, name)) {
+        let normals = (0..num_vertices)
+            .map(|i| NdVector::from([nx.2[i] as f32, ny.2[i] as f32, nz.2[i] as f32]))
+            .collect::<Vec<_>>();
+        builder.add_attribute(normals, AttributeType::Normal, AttributeDomain::Position, vec![pos_att_id]);
+    }
+
+    if let [Some(r), Some(g), Some(b)] = ["red", "green", "blue"].map(|name| take(&mut vertex_properties, name)) {
+        let mut channels = vec![r, g, b];
+        channels.extend(take(&mut vertex_properties, "alpha"));
+        let is_u8 = channels.iter().all(|(_, ty, _)| *ty == ScalarType::U8);
+        match (channels.len(), is_u8) {
+            (3, true) => builder.add_attribute(color_values::<u8, 3>(&channels, num_vertices), AttributeType::Color, AttributeDomain::Position, vec![pos_att_id]),
+            (3, false) => builder.add_attribute(color_values::<f32, 3>(&channels, num_vertices), AttributeType::Color, AttributeDomain::Position, vec![pos_att_id]),
+            (_, true) => builder.add_attribute(color_values::<u8, 4>(&channels, num_vertices), AttributeType::Color, AttributeDomain::Position, vec![pos_att_id]),
+            (_, false) => builder.add_attribute(color_values::<f32, 4>(&channels, num_vertices), AttributeType::Color, AttributeDomain::Position, vec![pos_att_id]),
+        };
+    }
+
+    let mut custom_names: Vec<(AttributeId, String)> = Vec::new();
+    for (name, _, values) in vertex_properties {
+        let values = values.into_iter()
+            .map(|v| NdVector::from([v as f32]))
+            .collect::<Vec<_>>();
+        let id = builder.add_attribute(values, AttributeType::Custom, AttributeDomain::Position, vec![]);
+        custom_names.push((id, name));
+    }
+
+    let mut mesh = builder.build()?;
+    for att in mesh.get_attributes_mut() {
+        if let Some((_, name)) = custom_names.iter().find(|(id, _)| *id == att.get_id()) {
+            att.set_name(name.clone());
+        }
+    }
+    Ok(mesh)
+}
+
+fn color_values<T, const N: usize>(channels: &[(String, ScalarType, Vec<f64>)], num_vertices: usize) -> Vec<NdVector<N, T>>
+    where T: crate::core::shared::DataValue,
+{
+    (0..num_vertices)
+        .map(|i| {
+            let mut out = [T::zero(); N];
+            for (o, (_, _, values)) in out.iter_mut().zip(channels) {
+                *o = T::from_f64(values[i]);
+            }
+            NdVector::from(out)
+        })
+        .collect()
+}
+
+/// Parses the header, returning the format, the elements, and the offset of the body.
+fn parse_header(data: &[u8]) -> Result<(Format, Vec<Element>, usize), Err> {
+    let mut pos = 0;
+    let mut next_line = || -> Result<&str, Err> {
+        let len = data[pos..].iter()
+            .position(|&b| b == b'\n')
+            .ok_or(Err::InvalidHeader("the header has no 'end_header'".to_string()))?;
+        let line = std::str::from_utf8(&data[pos..pos + len])
+            .map_err(|_| Err::InvalidHeader("the header is not valid UTF-8".to_string()))?;
+        pos += len + 1;
+        Ok(line.trim_end_matches('\r'))
+    };
+
+    if next_line()?.trim() != "ply" {
+        return Err(Err::InvalidHeader("the file does not begin with 'ply'".to_string()));
+    }
+    let mut format = None;
+    let mut elements: Vec<Element> = Vec::new();
+    loop {
+        let line = next_line()?;
+        let tokens = line.split_whitespace().collect::<Vec<_>>();
+        match tokens.as_slice() {
+            ["end_header"] => break,
+            ["format", f, _version] => {
+                format = Some(match *f {
+                    "ascii" => Format::Ascii,
+                    "binary_little_endian" => Format::BinaryLittleEndian,
+                    "binary_big_endian" => Format::BinaryBigEndian,
+                    _ => return Err(Err::InvalidHeader(format!("unknown format '{}'", f))),
+                });
+            },
+            ["element", name, count] => {
+                let count = count.parse::<usize>()
+                    .map_err(|_| Err::InvalidHeader(format!("invalid element count '{}'", count)))?;
+                elements.push(Element { name: name.to_string(), count, properties: Vec::new() });
+            },
+            ["property", "list", count_ty, item_ty, name] => {
+                let element = elements.last_mut()
+                    .ok_or(Err::InvalidHeader("a property precedes the elements".to_string()))?;
+                let [count_ty, item_ty] = [count_ty, item_ty].map(|ty| ScalarType::from_name(ty)
+                    .ok_or(Err::InvalidHeader(format!("unknown type '{}'", ty))));
+                element.properties.push(Property::List { name: name.to_string(), count_ty: count_ty?, item_ty: item_ty? });
+            },
+            ["property", ty, name] => {
+                let element = elements.last_mut()
+                    .ok_or(Err::InvalidHeader("a property precedes the elements".to_string()))?;
+                let ty = ScalarType::from_name(ty)
+                    .ok_or(Err::InvalidHeader(format!("unknown type '{}'", ty)))?;
+                element.properties.push(Property::Scalar(name.to_string(), ty));
+            },
+            ["comment", ..] | ["obj_info", ..] | [] => {},
+            _ => return Err(Err::InvalidHeader(format!("unexpected line '{}'", line))),
+        }
+    }
+    let format = format.ok_or(Err::InvalidHeader("the header has no format".to_string()))?;
+    Ok((format, elements, pos))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ascii_cube() {
+        let mesh = load_ply("tests/data/cube.ply").unwrap();
+        // six quads, each split into two triangles.
+        assert_eq!(mesh.get_faces().len(), 12);
+        let types = mesh.get_attributes().iter()
+            .map(|att| att.get_attribute_type())
+            .collect::<Vec<_>>();
+        assert_eq!(types, vec![AttributeType::Position, AttributeType::Normal, AttributeType::Color, AttributeType::Custom]);
+        let pos = &mesh.get_attributes()[0];
+        assert_eq!(pos.num_unique_values(), 8);
+        let color = &mesh.get_attributes()[2];
+        assert_eq!(color.get_component_type(), crate::core::attribute::ComponentDataType::U8);
+        let custom = &mesh.get_attributes()[3];
+        assert_eq!(custom.get_name().map(String::as_str), Some("quality"));
+    }
+
+    /// Encodes the vertices and the triangles as a binary PLY of the given format.
+    fn binary_ply(format: &str, vertices: &[[f32; 3]], faces: &[[u32; 3]], big_endian: bool) -> Vec<u8> {
+        let mut out = format!(
+            "ply\nformat {} 1.0\nelement vertex {}\nproperty float x\nproperty float y\nproperty float z\n\
+            element face {}\nproperty list uchar int vertex_indices\nend_header\n",
+            format, vertices.len(), faces.len()
+        ).into_bytes();
+        for v in vertices.iter().flatten() {
+            out.extend_from_slice(&if big_endian { v.to_be_bytes() } else { v.to_le_bytes() });
+        }
+        for f in faces {
+            out.push(3);
+            for i in f {
+                out.extend_from_slice(&if big_endian { i.to_be_bytes() } else { i.to_le_bytes() });
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn test_binary_endianness() {
+        let vertices = [[0.0f32, 0.0, 0.0], [1.5, 0.0, 0.0], [0.0, -2.25, 0.0], [0.0, 0.0, 1e3]];
+        let faces = [[0u32, 1, 2], [0, 3, 1]];
+        let little = parse_ply(&binary_ply("binary_little_endian", &vertices, &faces, false)).unwrap();
+        let big = parse_ply(&binary_ply("binary_big_endian", &vertices, &faces, true)).unwrap();
+        for mesh in [&little, &big] {
+            assert_eq!(mesh.get_faces().len(), 2);
+            let pos = &mesh.get_attributes()[0];
+            for (p, v) in vertices.iter().enumerate() {
+                assert_eq!(pos.get::<NdVector<3, f32>, 3>(p.into()), NdVector::from(*v));
+            }
+        }
+        assert_eq!(little.get_faces(), big.get_faces());
+    }
+
+    #[test]
+    fn test_point_cloud() {
+        let data = b"ply\nformat ascii 1.0\nelement vertex 3\nproperty double x\nproperty double y\nproperty double z\nend_header\n\
+            0 0 0\n1 2 3\n-1 0.5 4\n";
+        let mesh = parse_ply(data).unwrap();
+        assert!(mesh.get_faces().is_empty());
+        assert_eq!(mesh.get_attributes().len(), 1);
+        assert_eq!(mesh.get_attributes()[0].len(), 3);
+        assert_eq!(mesh.get_attributes()[0].get::<NdVector<3, f32>, 3>(2.into()), NdVector::from([-1.0f32, 0.5, 4.0]));
+    }
+
+    #[test]
+    fn test_corrupted_files() {
+        // a huge count in the header is not allocated up front.
+        let data = b"ply\nformat ascii 1.0\nelement vertex 9999999999999999999\nproperty float x\nproperty float y\nproperty float z\nend_header\n\
+            0 0 0\n";
+        assert!(matches!(parse_ply(data), Err(Err::UnexpectedEnd)));
+
+        // a negative index is an error rather than the first vertex.
+        let data = b"ply\nformat ascii 1.0\nelement vertex 3\nproperty float x\nproperty float y\nproperty float z\n\
+            element face 1\nproperty list uchar int vertex_indices\nend_header\n\
+            0 0 0\n1 0 0\n0 1 0\n3 0 1 -2\n";
+        assert!(matches!(parse_ply(data), Err(Err::InvalidValue(_))));
+    }
+}
@@ -0,0 +1,204 @@
+use crate::core::attribute::AttributeDomain;
+use crate::core::shared::Cross;
+use crate::prelude::{AttributeType, Mesh, MeshBuilder, NdVector};
+use std::fmt::Debug;
+use std::path::Path;
+
+#[remain::sorted]
+#[derive(Debug, thiserror::Error)]
+pub enum Err {
+    #[error("Invalid ASCII STL at line {0}: {1}")]
+    InvalidAscii(usize, String),
+    #[error("IO Error: {0}")]
+    IoError(#[from] std::io::Error),
+    #[error("Mesh Builder Error: {0}")]
+    MeshBuilderError(#[from] crate::core::mesh::builder::Err),
+    #[error("The binary STL is too short; it has {0} bytes.")]
+    TooShort(usize),
+}
+
+/// The size of the header and the triangle count of a binary STL.
+const BINARY_HEADER_SIZE: usize = 84;
+/// The size of a triangle of a binary STL, i.e. the normal, the three vertices, and the attribute byte count.
+const BINARY_TRIANGLE_SIZE: usize = 50;
+
+/// A triangle as it is stored in STL, i.e. the normal and the positions of its three vertices.
+type Triangle = ([f32; 3], [[f32; 3]; 3]);
+
+/// Loads an ASCII or binary STL file. STL stores each triangle with its own copies of the positions,
+/// so the vertices shared by the triangles are merged by their positions. The normals of the triangles,
+/// unless they are all zero, are added as a normal attribute on the corners.
+/// Triangles of zero area are dropped.
+pub fn load_stl<P: AsRef<Path> + Debug>(path: P) -> Result<Mesh, Err> {
+    let data = std::fs::read(path)?;
+    parse_stl(&data)
+}
+
+fn parse_stl(data: &[u8]) -> Result<Mesh, Err> {
+    let triangles = if is_binary(data) {
+        parse_binary(data)?
+    } else {
+        parse_ascii(data)?
+    };
+
+    let triangles = triangles.into_iter()
+        .filter(|(_, [a, b, c])| {
+            let [a, b, c] = [a, b, c].map(|&v| NdVector::from(v));
+            (b - a).cross(c - a).norm() > 0.0
+        })
+        .collect::<Vec<_>>();
+
+    let faces = (0..triangles.len())
+        .map(|i| [3 * i, 3 * i + 1, 3 * i + 2])
+        .collect::<Vec<_>>();
+    let pos = triangles.iter()
+        .flat_map(|(_, vertices)| vertices.iter().map(|&v| NdVector::from(v)))
+        .collect::<Vec<_>>();
+    let mut builder = MeshBuilder::new();
+    builder.set_connectivity_attribute(faces);
+    let pos_att_id = builder.add_attribute(pos, AttributeType::Position, AttributeDomain::Position, vec![]);
+    if triangles.iter().any(|(n, _)| *n != [0.0; 3]) {
+        let normals = triangles.iter()
+            .flat_map(|&(n, _)| [NdVector::from(n); 3])
+            .collect::<Vec<_>>();
+        builder.add_attribute(normals, AttributeType::Normal, AttributeDomain::Corner, vec![pos_att_id]);
+    }
+
+    Ok(builder.build()?)
+}
+
+/// An ASCII STL begins with 'solid', but so do some binary ones, so a file whose size agrees with
+/// the triangle count of the binary header is taken as binary.
+fn is_binary(data: &[u8]) -> bool {
+    if data.len() >= BINARY_HEADER_SIZE {
+        let count = u32::from_le_bytes([data[80], data[81], data[82], data[83]]) as usize;
+        if count.checked_mul(BINARY_TRIANGLE_SIZE).and_then(|n| n.checked_add(BINARY_HEADER_SIZE)) == Some(data.len()) {
+            return true;
+        }
+    }
+    let start = data.iter().position(|b| !b.is_ascii_whitespace()).unwrap_or(data.len());
+    !data[start..].starts_with(b"solid")
+}
+
+/// Reads the triangles of a binary STL. The triangle count of the header is not trusted, as some writers
+/// leave it zero or wrong; the triangles are read as far as the data goes.
+fn parse_binary(data: &[u8]) -> Result<Vec<Triangle>, Err> {
+    if data.len() < BINARY_HEADER_SIZE {
+        return Err(Err::TooShort(data.len()));
+    }
+    let read_vec = |bytes: &[u8]| {
+        let mut out = [0.0f32; 3];
+        for (o, b) in out.iter_mut().zip(bytes.chunks_exact(4)) {
+            *o = f32::from_le_bytes([b[0], b[1], b[2], b[3]]);
+        }
+        out
+    };
+    Ok(data[BINARY_HEADER_SIZE..].chunks_exact(BINARY_TRIANGLE_SIZE)
+        .map(|t| (read_vec(&t[0..12]), [read_vec(&t[12..24]), read_vec(&t[24..36]), read_vec(&t[36..48])]))
+        .collect())
+}
+
+fn parse_ascii(data: &[u8]) -> Result<Vec<Triangle>, Err> {
+    let text = String::from_utf8_lossy(data);
+    let mut out = Vec::new();
+    let mut normal = [0.0; 3];
+    let mut vertices = Vec::with_capacity(3);
+    for (i, line) in text.lines().enumerate() {
+        let line_number = i + 1;
+        let mut tokens = line.split_whitespace();
+        match tokens.next() {
+            Some("facet") => {
+                if tokens.next() != Some("normal") {
+                    return Err(Err::InvalidAscii(line_number, "'facet' must be followed by 'normal'".to_string()));
+                }
+                normal = parse_vec(tokens, line_number)?;
+                vertices.clear();
+            },
+            Some("vertex") => {
+                vertices.push(parse_vec(tokens, line_number)?);
+            },
+            Some("endfacet") => {
+                if vertices.len() != 3 {
+                    return Err(Err::InvalidAscii(line_number, format!("a facet has {} vertices", vertices.len())));
+                }
+                out.push((normal, [vertices[0], vertices[1], vertices[2]]));
+                vertices.clear();
+            },
+            _ => {},
+        }
+    }
+    Ok(out)
+}
+
+fn parse_vec<'a>(mut tokens: impl Iterator<Item = &'a str>, line_number: usize) -> Result<[f32; 3], Err> {
+    let mut out = [0.0; 3];
+    for o in out.iter_mut() {
+        *o = tokens.next()
+            .and_then(|t| t.parse::<f32>().ok())
+            .ok_or_else(|| Err::InvalidAscii(line_number, "expected three numbers".to_string()))?;
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_binary_cube() {
+        let mesh = load_stl("tests/data/cube.stl").unwrap();
+        assert_eq!(mesh.get_faces().len(), 12);
+        let pos = mesh.get_attributes().iter().find(|att| att.get_attribute_type() == AttributeType::Position).unwrap();
+        assert_eq!(pos.num_unique_values(), 8);
+        let normal = mesh.get_attributes().iter().find(|att| att.get_attribute_type() == AttributeType::Normal).unwrap();
+        assert_eq!(normal.get_domain(), AttributeDomain::Corner);
+        assert_eq!(normal.num_unique_values(), 6);
+    }
+
+    #[test]
+    fn test_binary_with_bogus_count_and_degenerate_triangle() {
+        let mut data = std::fs::read("tests/data/cube.stl").unwrap();
+        // a zero-area triangle whose vertices are collinear.
+        data.extend_from_slice(&[0u8; 12]);
+        for v in [[0.0f32, 0.0, 0.0], [0.5, 0.0, 0.0], [1.0, 0.0, 0.0]] {
+            v.iter().for_each(|c| data.extend_from_slice(&c.to_le_bytes()));
+        }
+        data.extend_from_slice(&[0u8; 2]);
+        // the count is left as the original 12, which disagrees with the size of the file.
+        assert!(!data.starts_with(b"solid"));
+        let mesh = parse_stl(&data).unwrap();
+        assert_eq!(mesh.get_faces().len(), 12);
+
+        data[80..84].copy_from_slice(&1000u32.to_le_bytes());
+        let mesh = parse_stl(&data).unwrap();
+        assert_eq!(mesh.get_faces().len(), 12);
+    }
+
+    #[test]
+    fn test_ascii() {
+        let data = b"solid square
+  facet normal 0 0 1
+    outer loop
+      vertex 0 0 0
+      vertex 1 0 0
+      vertex 1 1 0
+    endloop
+  endfacet
+  facet normal 0 0 1
+    outer loop
+      vertex 0 0 0
+      vertex 1 1 0
+      vertex 0 1 0
+    endloop
+  endfacet
+endsolid square
+";
+        let mesh = parse_stl(data).unwrap();
+        assert_eq!(mesh.get_faces().len(), 2);
+        let pos = mesh.get_attributes().iter().find(|att| att.get_attribute_type() == AttributeType::Position).unwrap();
+        assert_eq!(pos.num_unique_values(), 4);
+        assert_eq!(mesh.get_faces()[0][0], mesh.get_faces()[1][0]);
+
+        assert!(matches!(parse_stl(b"solid broken\nfacet normal 0 0\nendsolid\n"), Err(Err::InvalidAscii(2, _))));
+    }
+}
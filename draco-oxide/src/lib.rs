@@ -10,36 +10,42 @@ pub(crate) mod shared;
 /// Defines the mesh encoder.
 pub mod encode;
 
-// /// Defines the decoders.
-// pub mod decode;
+/// Defines the decoders.
+pub mod decode;
 
 /// Contains the shared definitions, native objects, and the buffer.
 pub(crate) mod core;
 
-/// Contains the macros used by the encoder and the decoder.
-pub(crate) mod utils;
+/// Contains the macros used by the encoder and the decoder. Only [utils::winding_number], the inside-outside test
+/// of a closed mesh, and [utils::ParseEnumError] are public.
+pub mod utils;
 
 
 /// Contains the most commonly used traits, types, and objects.
 pub mod prelude {
-    pub use crate::core::attribute::{Attribute, AttributeType};
-    pub use crate::core::mesh::{Mesh, builder::MeshBuilder};
-    pub use crate::core::shared::{NdVector, Vector, DataValue};
+    pub use crate::core::attribute::{Attribute, AttributeDomain, AttributeLayout, AttributeType};
+    pub use crate::core::mesh::{Mesh, builder::MeshBuilder, manifold::ManifoldReport, topology::Topology};
+    pub use crate::core::material::{Material, MaterialLibrary};
+    pub use crate::core::shared::{NdVector, Vector, DataValue, VertexIdx};
     pub use crate::core::shared::ConfigType;
     pub use crate::core::bit_coder::{
         ByteReader, 
         ByteWriter, 
         FunctionalByteReader, 
-        FunctionalByteWriter
+        FunctionalByteWriter,
+        IoByteReader,
+        IoByteWriter,
+        SliceReader,
     };
-    pub use crate::encode::{self, encode};
-    // pub use crate::decode::{self, decode};
+    pub use crate::encode::{self, encode, encode_to_vec};
+    pub use crate::decode::{self, decode};
 }
 
 
 /// Evaluation module contains the evaluation functions for the encoder and the decoder.
 /// When enabled, draco-oxide encoder will spit out the evaluation data mixed with encoded data,
 /// and then the `EvalWriter` is used to filter out the evaluation data. This functionality is
-/// most often used in the development and testing phase.
+/// most often used in the development and testing phase. The stream is then not a Draco stream, so the tests
+/// that decode it or measure it are gated by `not(feature = "evaluation")`.
 #[cfg(feature = "evaluation")]
 pub mod eval;
\ No newline at end of file
@@ -0,0 +1,198 @@
+//! Tangents are 4-dimensional vectors whose 'w' component is either 1 or -1 and
+//! describes the handedness of the tangent space. When the 'xyz' part is encoded with
+//! a lossy transform such as the octahedral quantization, the handedness is separated
+//! from the 'xyz' part so that it is stored bit-exact.
+
+use crate::core::attribute::{Attribute, ComponentDataType};
+use crate::core::shared::{NdVector, Vector};
+
+/// The tolerance of the length of the 'xyz' part of a tangent from 1. The octahedral quantization keeps only
+/// the direction of the 'xyz' part, so a tangent of another length would not be restored.
+pub(crate) const UNIT_LENGTH_TOLERANCE: f32 = 1e-3;
+
+/// The first tangent of the attribute (4 components of f32) whose 'xyz' part is not of unit length within
+/// [UNIT_LENGTH_TOLERANCE], or 'None' if all of them are.
+pub(crate) fn find_non_unit_tangent(att: &Attribute) -> Option<[f32; 4]> {
+    att.unique_vals_as_slice::<NdVector<4, f32>, 4>()
+        .iter()
+        .find(|v| {
+            let len = (0..3).map(|i| v.get(i) * v.get(i)).sum::<f32>().sqrt();
+            // a length of 'NaN' is not of unit length either.
+            (len - 1.0).abs().partial_cmp(&UNIT_LENGTH_TOLERANCE) != Some(std::cmp::Ordering::Less)
+        })
+        .map(|v| [0, 1, 2, 3].map(|i| *v.get(i)))
+}
+
+/// Splits the tangent attribute (4 components of f32) into the attribute of its 'xyz' part and
+/// the handedness of each unique value, where 'true' means that 'w' is negative.
+/// The returned attribute has the same id, type, domain, parents, and point-to-value mapping as the input.
+pub(crate) fn split_handedness(att: Attribute) -> (Attribute, Vec<bool>) {
+    assert_eq!(att.get_num_components(), 4, "Tangent attribute must have 4 components.");
+    assert_eq!(att.get_component_type(), ComponentDataType::F32, "Tangent attribute must be of f32.");
+
+    let (values, point_to_att_val_map, att) = att.into_parts::<NdVector<4, f32>, 4>();
+    let handedness = values.iter()
+        .map(|v| *v.get(3) < 0.0)
+        .collect::<Vec<_>>();
+    let xyz = values.into_iter()
+        .map(|v| NdVector::from([*v.get(0), *v.get(1), *v.get(2)]))
+        .collect::<Vec<_>>();
+
+    let mut xyz_att = Attribute::from_without_removing_duplicates(
+        att.get_id(),
+        xyz,
+        att.get_attribute_type(),
+        att.get_domain(),
+        att.get_parents().clone(),
+    );
+    xyz_att.set_point_to_att_val_map(point_to_att_val_map);
+    (xyz_att, handedness)
+}
+
+/// Inverse of [split_handedness]. Joins the attribute of the 'xyz' part (3 components of f32)
+/// and the handedness of each unique value into a tangent attribute with 4 components.
+pub(crate) fn join_handedness(xyz_att: Attribute, handedness: &[bool]) -> Attribute {
+    assert_eq!(xyz_att.get_num_components(), 3, "The 'xyz' part of the tangent must have 3 components.");
+    assert_eq!(xyz_att.num_unique_values(), handedness.len(), "Each value must have its handedness.");
+
+    let (values, point_to_att_val_map, att) = xyz_att.into_parts::<NdVector<3, f32>, 3>();
+    let values = values.into_iter()
+        .zip(handedness)
+        .map(|(v, &is_negative)| NdVector::from([*v.get(0), *v.get(1), *v.get(2), if is_negative { -1.0 } else { 1.0 }]))
+        .collect::<Vec<_>>();
+
+    let mut out = Attribute::from_without_removing_duplicates(
+        att.get_id(),
+        values,
+        att.get_attribute_type(),
+        att.get_domain(),
+        att.get_parents().clone(),
+    );
+    out.set_point_to_att_val_map(point_to_att_val_map);
+    out
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::attribute::{AttributeDomain, AttributeId};
+    use crate::core::shared::PointIdx;
+    use crate::prelude::AttributeType;
+
+    #[test]
+    fn test_handedness_is_exact() {
+        // tangents with mixed handedness
+        let tangents = (0..64)
+            .map(|i| {
+                let t = i as f32 * 0.1;
+                let w = if i % 3 == 0 { -1.0 } else { 1.0 };
+                NdVector::from([t.cos(), t.sin(), 0.0, w])
+            })
+            .collect::<Vec<_>>();
+        let att = Attribute::from_without_removing_duplicates(
+            AttributeId::new(1),
+            tangents.clone(),
+            AttributeType::Tangent,
+            AttributeDomain::Position,
+            Vec::new(),
+        );
+
+        let (xyz_att, handedness) = split_handedness(att);
+        assert_eq!(xyz_att.get_num_components(), 3);
+
+        // Distort the 'xyz' part as a lossy transform would do.
+        let (values, map, xyz_att) = xyz_att.into_parts::<NdVector<3, f32>, 3>();
+        let mut xyz_att = xyz_att;
+        xyz_att.set_values::<NdVector<3, f32>, 3>(
+            values.into_iter()
+                .map(|v| NdVector::from([(*v.get(0) * 64.0).round() / 64.0, (*v.get(1) * 64.0).round() / 64.0, *v.get(2)]))
+                .collect()
+        );
+        xyz_att.set_point_to_att_val_map(map);
+
+        let joined = join_handedness(xyz_att, &handedness);
+        for (i, t) in tangents.into_iter().enumerate() {
+            let joined: NdVector<4, f32> = joined.get(PointIdx::from(i));
+            assert_eq!(joined.get(3), t.get(3), "handedness of the {}th tangent is not exact", i);
+        }
+    }
+
+    #[test]
+    fn test_non_unit_tangents_are_rejected() {
+        use crate::core::shared::ConfigType;
+        let mut mesh = crate::io::obj::load_obj("tests/data/sphere.obj").unwrap();
+        let num_points = mesh.get_attributes()[0].len();
+        // the tangents are of unit length but one, which is twice as long.
+        let tangents = (0..num_points)
+            .map(|i| {
+                let t = i as f32 * 0.37;
+                let s = if i == num_points / 2 { 2.0 } else { 1.0 };
+                NdVector::from([s * t.cos(), s * t.sin(), 0.0, 1.0])
+            })
+            .collect::<Vec<_>>();
+        let att = Attribute::from(AttributeId::new(mesh.get_attributes().len()), tangents, AttributeType::Tangent, AttributeDomain::Position, Vec::new());
+        assert!(find_non_unit_tangent(&att).is_some_and(|v| (v[0].hypot(v[1]) - 2.0).abs() < 1e-6));
+        mesh.attributes.push(att);
+
+        let mut cfg = crate::encode::Config::default();
+        cfg.separate_tangent_handedness = true;
+        let err = crate::encode::encode(mesh.clone(), &mut Vec::new(), cfg).unwrap_err();
+        assert!(matches!(err, crate::encode::Err::AttributeError(crate::encode::attribute::Err::NonUnitTangent(..))), "{:?}", err);
+
+        // the tangents are kept as they are without the octahedral quantization.
+        crate::encode::encode(mesh, &mut Vec::new(), crate::encode::Config::default()).unwrap();
+
+        // a zero tangent has no direction to keep either.
+        let zero = Attribute::from(AttributeId::new(0), vec![NdVector::from([0.0_f32; 4])], AttributeType::Tangent, AttributeDomain::Position, Vec::new());
+        assert!(find_non_unit_tangent(&zero).is_some());
+    }
+
+    #[test]
+    fn test_encode_mesh_with_mixed_handedness() {
+        use crate::core::shared::ConfigType;
+        let mut mesh = crate::io::obj::load_obj("tests/data/sphere.obj").unwrap();
+        let num_points = mesh.get_attributes()[0].len();
+        let tangents = (0..num_points)
+            .map(|i| {
+                let t = i as f32 * 0.37;
+                let w = if i % 2 == 0 { -1.0 } else { 1.0 };
+                NdVector::from([t.cos(), t.sin(), 0.0, w])
+            })
+            .collect::<Vec<_>>();
+        let id = AttributeId::new(mesh.get_attributes().len());
+        mesh.attributes.push(
+            Attribute::from(id, tangents, AttributeType::Tangent, AttributeDomain::Position, Vec::new())
+        );
+
+        let mut cfg = crate::encode::Config::default();
+        cfg.separate_tangent_handedness = true;
+        let mut split = Vec::new();
+        crate::encode::encode(mesh.clone(), &mut split, cfg).unwrap();
+        let mut not_split = Vec::new();
+        crate::encode::encode(mesh.clone(), &mut not_split, crate::encode::Config::default()).unwrap();
+        assert_ne!(split, not_split);
+
+        #[cfg(not(feature = "evaluation"))]
+        {
+            let decoded = crate::decode::decode(&mut crate::prelude::SliceReader::new(&split), crate::decode::Config::default()).unwrap();
+            let find = |mesh: &crate::core::mesh::Mesh, ty: AttributeType| mesh.get_attributes().iter().find(|att| att.get_attribute_type() == ty).unwrap().clone();
+            let (pos, tangent) = (find(&mesh, AttributeType::Position), find(&mesh, AttributeType::Tangent));
+            let (decoded_pos, decoded_tangent) = (find(&decoded, AttributeType::Position), find(&decoded, AttributeType::Tangent));
+            assert_eq!(decoded_tangent.len(), num_points);
+            // the points are matched by their positions and the 'xyz' parts of their tangents, which are lossy.
+            let distance = |p: PointIdx, q: PointIdx| {
+                let (a, b) = (pos.get::<NdVector<3, f32>, 3>(p), decoded_pos.get::<NdVector<3, f32>, 3>(q));
+                let (s, t) = (tangent.get::<NdVector<4, f32>, 4>(p), decoded_tangent.get::<NdVector<4, f32>, 4>(q));
+                (0..3).map(|i| (a.get(i) - b.get(i)).abs() + (s.get(i) - t.get(i)).abs()).sum::<f32>()
+            };
+            for q in (0..num_points).map(PointIdx::from) {
+                let p = (0..num_points).map(PointIdx::from)
+                    .min_by(|&p, &r| distance(p, q).total_cmp(&distance(r, q)))
+                    .unwrap();
+                let w = *decoded_tangent.get::<NdVector<4, f32>, 4>(q).get(3);
+                assert_eq!(w, *tangent.get::<NdVector<4, f32>, 4>(p).get(3), "the handedness of the point {:?}", q);
+            }
+        }
+    }
+}
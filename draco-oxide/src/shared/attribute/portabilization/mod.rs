@@ -0,0 +1 @@
+pub mod quantization_metadata;
@@ -0,0 +1,157 @@
+use crate::core::bit_coder::ReaderErr;
+use crate::prelude::ByteReader;
+use crate::shared::attribute::Portable;
+
+/// What the decoder does when the quantization metadata implies values beyond [SanityBounds].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutOfBounds {
+    /// The stream is rejected with [Err::OutOfSanityBounds].
+    Reject,
+    /// The dequantized values are clamped to the bounds.
+    Clamp,
+}
+
+/// Bound on the magnitude of the dequantized values, used to guard the decoder against untrusted streams
+/// whose quantization metadata would produce absurd floats. Non-finite metadata is rejected either way.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SanityBounds {
+    /// The largest absolute value a dequantized component may take.
+    pub max_abs_value: f64,
+    pub on_violation: OutOfBounds,
+}
+
+impl SanityBounds {
+    pub fn reject(max_abs_value: f64) -> Self {
+        Self { max_abs_value, on_violation: OutOfBounds::Reject }
+    }
+
+    pub fn clamp(max_abs_value: f64) -> Self {
+        Self { max_abs_value, on_violation: OutOfBounds::Clamp }
+    }
+}
+
+/// Metadata of the coordinate-wise quantization, written by the encoder before the quantized values:
+/// the minimum of each component, the largest range over the components, and the number of bits.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct QuantizationMetadata {
+    pub(crate) min_values: Vec<f32>,
+    pub(crate) range: f32,
+    pub(crate) quantization_bits: u8,
+    /// Bound the dequantized values are clamped to, if any.
+    clamp_to: Option<f64>,
+}
+
+impl QuantizationMetadata {
+    /// Reads the metadata of an attribute of 'num_components' components. If 'sanity_bounds' is given,
+    /// the metadata is checked against it before any value is dequantized.
+    pub(crate) fn read_from<R>(reader: &mut R, num_components: usize, sanity_bounds: Option<SanityBounds>) -> Result<Self, Err>
+        where R: ByteReader
+    {
+        let min_values = (0..num_components)
+            .map(|_| f32::read_from(reader))
+            .collect::<Result<Vec<_>, _>>()?;
+        let range = f32::read_from(reader)?;
+        let quantization_bits = reader.read_u8()?;
+        if quantization_bits == 0 || quantization_bits > 30 {
+            return Err(Err::InvalidQuantizationBits(quantization_bits));
+        }
+
+        let mut out = Self { min_values, range, quantization_bits, clamp_to: None };
+        if let Some(bounds) = sanity_bounds {
+            out.check(bounds)?;
+        }
+        Ok(out)
+    }
+
+    fn check(&mut self, bounds: SanityBounds) -> Result<(), Err> {
+        if !self.range.is_finite() || self.range < 0.0 || self.min_values.iter().any(|m| !m.is_finite()) {
+            return Err(Err::NonFiniteMetadata);
+        }
+        // the dequantized values of each component lie in [min, min + range].
+        let extent = self.min_values.iter()
+            .flat_map(|&m| [m as f64, m as f64 + self.range as f64])
+            .map(f64::abs)
+            .fold(0.0, f64::max);
+        if extent > bounds.max_abs_value {
+            match bounds.on_violation {
+                OutOfBounds::Reject => return Err(Err::OutOfSanityBounds(extent, bounds.max_abs_value)),
+                OutOfBounds::Clamp => self.clamp_to = Some(bounds.max_abs_value),
+            }
+        }
+        Ok(())
+    }
+
+    /// Restores the 'component'th component from its quantized value.
+    pub(crate) fn dequantize(&self, component: usize, quantized: i32) -> f32 {
+        let max_quantized = ((1_u32 << self.quantization_bits) - 1) as f32;
+        let value = self.min_values[component] + quantized as f32 * (self.range / max_quantized);
+        match self.clamp_to {
+            Some(bound) => value.clamp(-bound as f32, bound as f32),
+            None => value,
+        }
+    }
+}
+
+#[remain::sorted]
+#[derive(thiserror::Error, Debug)]
+pub enum Err {
+    #[error("Invalid number of quantization bits: {0}")]
+    InvalidQuantizationBits(u8),
+    #[error("The quantization metadata is not finite")]
+    NonFiniteMetadata,
+    #[error("The quantization metadata implies values up to {0}, beyond the sanity bound {1}")]
+    OutOfSanityBounds(f64, f64),
+    #[error("Reader Error: {0}")]
+    ReaderError(#[from] ReaderErr),
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::attribute::AttributeDomain;
+    use crate::core::shared::{ConfigType, NdVector, PointIdx, Vector};
+    use crate::encode::attribute::portabilization::{Config, PortabilizationImpl};
+    use crate::encode::attribute::portabilization::quantization_coordinate_wise::QuantizationCoordinateWise;
+    use crate::prelude::{Attribute, AttributeType, SliceReader};
+
+    #[test]
+    fn test_poisoned_metadata_is_rejected() {
+        let values = (0..50)
+            .map(|i| NdVector::from([i as f32 * 0.1, -(i as f32), 2.0]))
+            .collect::<Vec<_>>();
+        let att = Attribute::new(values.clone(), AttributeType::Position, AttributeDomain::Position, Vec::new());
+        let mut metadata = Vec::new();
+        let port_att = QuantizationCoordinateWise::<NdVector<3, f32>, 3>::new(att, Config::default(), &mut metadata)
+            .portabilize();
+
+        // the metadata written by the encoder passes and restores the values.
+        let bounds = SanityBounds::reject(1e3);
+        let read = QuantizationMetadata::read_from(&mut SliceReader::new(&metadata), 3, Some(bounds)).unwrap();
+        let step = read.range / ((1 << read.quantization_bits) - 1) as f32;
+        for (p, v) in values.iter().enumerate() {
+            let q = port_att.get::<NdVector<3, i32>, 3>(PointIdx::from(p));
+            for i in 0..3 {
+                assert!((read.dequantize(i, *q.get(i)) - *v.get(i)).abs() <= step);
+            }
+        }
+
+        // 'delta_max' follows the three minimums.
+        let mut poisoned = metadata.clone();
+        poisoned[12..16].copy_from_slice(&1e30_f32.to_le_bytes());
+        let err = QuantizationMetadata::read_from(&mut SliceReader::new(&poisoned), 3, Some(bounds)).unwrap_err();
+        assert!(matches!(err, Err::OutOfSanityBounds(..)), "{:?}", err);
+        // without the bounds, the stream is read as it is.
+        assert!(QuantizationMetadata::read_from(&mut SliceReader::new(&poisoned), 3, None).is_ok());
+
+        // clamping keeps the values within the bounds.
+        let clamped = QuantizationMetadata::read_from(&mut SliceReader::new(&poisoned), 3, Some(SanityBounds::clamp(1e3))).unwrap();
+        assert_eq!(clamped.dequantize(0, 2047), 1e3);
+
+        // non-finite metadata is rejected even when clamping.
+        let mut poisoned = metadata;
+        poisoned[0..4].copy_from_slice(&f32::NAN.to_le_bytes());
+        let err = QuantizationMetadata::read_from(&mut SliceReader::new(&poisoned), 3, Some(SanityBounds::clamp(1e3))).unwrap_err();
+        assert!(matches!(err, Err::NonFiniteMetadata), "{:?}", err);
+    }
+}
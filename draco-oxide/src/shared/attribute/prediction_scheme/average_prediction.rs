@@ -0,0 +1,273 @@
+use crate::core::attribute::Attribute;
+use crate::core::corner_table::GenericCornerTable;
+use crate::core::shared::{CornerIdx, VertexIdx};
+use crate::prelude::{NdVector, Vector};
+use super::PredictionSchemeImpl;
+
+/// Predicts the value of a vertex by the average of the values of its neighboring vertices
+/// that have already been processed. When none of the neighbors is processed yet, the value
+/// of the most recently processed vertex is used instead, i.e. it falls back to the delta prediction.
+/// This works well for densely and uniformly sampled geometry, where the average of the
+/// neighbors is closer to the actual value than any single neighbor.
+/// The points of a point cloud have no faces, so they are predicted by the running average of the
+/// [POINT_CLOUD_WINDOW] points processed last instead.
+pub struct AveragePrediction<'parents, C, const N: usize> {
+	corner_table: &'parents C,
+
+	/// Whether the corner table is that of a point cloud, i.e. has no faces.
+	is_point_cloud: bool,
+
+	/// 'is_processed[v]' is true if the vertex 'v' has already been processed.
+	is_processed: Vec<bool>,
+
+	/// Number of the processed vertices that are already recorded in 'is_processed'.
+	num_recorded: usize,
+}
+
+/// The number of the points processed last whose average predicts the next point of a point cloud.
+pub(crate) const POINT_CLOUD_WINDOW: usize = 8;
+
+impl<'parents, C, const N: usize> AveragePrediction<'parents, C, N>
+	where C: GenericCornerTable,
+	      NdVector<N, i32>: Vector<N, Component = i32>,
+{
+	/// Updates 'is_processed' with the vertices that are processed since the last call.
+	fn record_processed(&mut self, vertices_processed_up_till_now: &[VertexIdx]) {
+		for &v in &vertices_processed_up_till_now[self.num_recorded..] {
+			self.is_processed[usize::from(v)] = true;
+		}
+		self.num_recorded = vertices_processed_up_till_now.len();
+	}
+
+	/// Returns the corners of the faces incident to the vertex of 'c'.
+	fn corners_around(&self, c: CornerIdx) -> Vec<CornerIdx> {
+		let mut out = vec![c];
+		let mut curr = c;
+		while let Some(next) = self.corner_table.swing_right(curr) {
+			if next == c {
+				// we went around the vertex; no need to swing left.
+				return out;
+			}
+			out.push(next);
+			curr = next;
+		}
+		curr = c;
+		while let Some(next) = self.corner_table.swing_left(curr) {
+			out.push(next);
+			curr = next;
+		}
+		out
+	}
+
+	/// Returns the average of the values of the vertices processed last, see [POINT_CLOUD_WINDOW].
+	fn running_average(&self, vertices_processed_up_till_now: &[VertexIdx], attribute: &Attribute) -> NdVector<N, i32> {
+		let window = &vertices_processed_up_till_now[vertices_processed_up_till_now.len().saturating_sub(POINT_CLOUD_WINDOW)..];
+		if window.is_empty() {
+			return NdVector::zero();
+		}
+		self.average(window, attribute)
+	}
+
+	/// Returns the average of the values of the vertices. The values are summed up in 'i64', as the sum of
+	/// a few large values overflows 'i32', and the average truncated toward zero always fits back in 'i32'.
+	fn average(&self, vertices: &[VertexIdx], attribute: &Attribute) -> NdVector<N, i32> {
+		let mut sum = [0_i64; N];
+		for &v in vertices {
+			let value: NdVector<N, i32> = attribute.get(self.corner_table.point_idx(self.corner_table.left_most_corner(v)));
+			for (i, s) in sum.iter_mut().enumerate() {
+				*s += *value.get(i) as i64;
+			}
+		}
+		let mut out = NdVector::<N, i32>::zero();
+		for (i, s) in sum.into_iter().enumerate() {
+			*out.get_mut(i) = (s / vertices.len() as i64) as i32;
+		}
+		out
+	}
+}
+
+impl<'parents, C, const N: usize> PredictionSchemeImpl<'parents, C, N> for AveragePrediction<'parents, C, N>
+	where C: GenericCornerTable,
+	      NdVector<N, i32>: Vector<N, Component = i32>,
+{
+	type AdditionalDataForMetadata = ();
+
+	fn new(_parents: &[&'parents Attribute], corner_table: &'parents C) -> Self {
+		Self {
+			corner_table,
+			is_point_cloud: corner_table.num_faces() == 0,
+			is_processed: vec![false; corner_table.num_vertices()],
+			num_recorded: 0,
+		}
+	}
+
+	/// The prediction falls back to the most recent value, so every value is predicted.
+	fn get_values_impossible_to_predict(&mut self, _value_indices: &mut Vec<std::ops::Range<usize>>)
+		-> Vec<std::ops::Range<usize>>
+	{
+		Vec::new()
+	}
+
+	fn predict(
+		&mut self,
+		c: CornerIdx,
+		vertices_processed_up_till_now: &[VertexIdx],
+		attribute: &Attribute,
+	) -> NdVector<N, i32> {
+		if self.is_point_cloud {
+			return self.running_average(vertices_processed_up_till_now, attribute);
+		}
+		self.record_processed(vertices_processed_up_till_now);
+
+		let mut neighbors = Vec::new();
+		for corner in self.corners_around(c) {
+			for n in [self.corner_table.next(corner), self.corner_table.previous(corner)] {
+				let v = self.corner_table.vertex_idx(n);
+				if self.is_processed[usize::from(v)] && !neighbors.contains(&v) {
+					neighbors.push(v);
+				}
+			}
+		}
+
+		if neighbors.is_empty() {
+			// No neighbor is available. Use the most recent value instead.
+			return if let Some(&last_v) = vertices_processed_up_till_now.last() {
+				attribute.get(self.corner_table.point_idx(self.corner_table.left_most_corner(last_v)))
+			} else {
+				NdVector::zero()
+			};
+		}
+
+		self.average(&neighbors, attribute)
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::core::attribute::{AttributeDomain, AttributeId};
+	use crate::core::corner_table::CornerTable;
+	use crate::core::shared::PointIdx;
+	use crate::encode::entropy::symbol_coding::encode_symbols;
+	use crate::prelude::AttributeType;
+	use crate::shared::attribute::prediction_scheme::delta_prediction::DeltaPrediction;
+	use crate::shared::entropy::SymbolEncodingMethod;
+	use crate::utils::to_positive_i32;
+
+	/// Dense and uniformly sampled (jittered) grid on a smooth surface, in quantized coordinates.
+	fn dense_grid(size: usize) -> (Vec<[PointIdx; 3]>, Attribute) {
+		let mut seed = 12345_u32;
+		let mut jitter = || {
+			seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+			((seed >> 16) % 9) as i32 - 4
+		};
+		let mut points = Vec::new();
+		for y in 0..size {
+			for x in 0..size {
+				let (fx, fy) = (x as f32 / size as f32, y as f32 / size as f32);
+				let z = ((fx * 3.0).sin() * (fy * 2.0).cos() * 1000.0) as i32;
+				points.push(NdVector::from([(x * 64) as i32 + jitter(), (y * 64) as i32 + jitter(), z + jitter()]));
+			}
+		}
+		let mut faces = Vec::new();
+		for y in 0..size-1 {
+			for x in 0..size-1 {
+				let p = |x: usize, y: usize| PointIdx::from(y * size + x);
+				faces.push([p(x, y), p(x+1, y), p(x, y+1)]);
+				faces.push([p(x+1, y), p(x+1, y+1), p(x, y+1)]);
+			}
+		}
+		let att = Attribute::new(points, AttributeType::Position, AttributeDomain::Position, Vec::new());
+		(faces, att)
+	}
+
+	/// Corners in the order the vertices first appear in the faces.
+	fn sequence(ct: &CornerTable) -> Vec<CornerIdx> {
+		let mut visited = vec![false; ct.num_vertices()];
+		let mut out = Vec::new();
+		for c in 0..ct.num_corners() {
+			let c = CornerIdx::from(c);
+			let v = usize::from(ct.vertex_idx(c));
+			if !visited[v] {
+				visited[v] = true;
+				out.push(c);
+			}
+		}
+		out
+	}
+
+	fn residuals<'a, P>(mut prediction: P, ct: &CornerTable, seq: &[CornerIdx], att: &Attribute) -> Vec<NdVector<3, i32>>
+		where P: PredictionSchemeImpl<'a, CornerTable<'a>, 3>
+	{
+		let mut processed = Vec::new();
+		let mut out = Vec::new();
+		for &c in seq {
+			let pred = prediction.predict(c, &processed, att);
+			out.push(att.get::<NdVector<3,i32>,3>(ct.point_idx(c)) - pred);
+			processed.push(ct.vertex_idx(c));
+		}
+		out
+	}
+
+	fn encoded_size(residuals: &[NdVector<3, i32>]) -> usize {
+		let symbols = residuals.iter()
+			.flat_map(|r| (0..3).map(|i| to_positive_i32(*r.get(i)) as u64))
+			.collect::<Vec<_>>();
+		let mut buffer = Vec::new();
+		encode_symbols(symbols, 3, SymbolEncodingMethod::DirectCoded, &mut buffer).unwrap();
+		buffer.len()
+	}
+
+	#[test]
+	fn test_smaller_than_delta() {
+		let (faces, att) = dense_grid(40);
+		let ct = CornerTable::new(&faces, &att);
+		let seq = sequence(&ct);
+
+		let average = residuals(AveragePrediction::<_, 3>::new(&[], &ct), &ct, &seq, &att);
+		let delta = residuals(DeltaPrediction::<_, 3>::new(&[], &ct), &ct, &seq, &att);
+		let average_size = encoded_size(&average);
+		let delta_size = encoded_size(&delta);
+		assert!(average_size < delta_size, "average: {} bytes, delta: {} bytes", average_size, delta_size);
+	}
+
+	#[test]
+	fn test_round_trip() {
+		let (faces, att) = dense_grid(20);
+		round_trip(&faces, &att);
+
+		// the sum of the neighbors of the values near the maximum overflows 'i32'.
+		let large = att.unique_vals_as_slice::<NdVector<3, i32>, 3>().iter()
+			.map(|&v| v + NdVector::from([i32::MAX - 4096; 3]))
+			.collect::<Vec<_>>();
+		round_trip(&faces, &Attribute::new(large, AttributeType::Position, AttributeDomain::Position, Vec::new()));
+	}
+
+	fn round_trip(faces: &[[PointIdx; 3]], att: &Attribute) {
+		let ct = CornerTable::new(faces, att);
+		let seq = sequence(&ct);
+		let residuals = residuals(AveragePrediction::<_, 3>::new(&[], &ct), &ct, &seq, att);
+
+		// Decode the values in the same order only using the values decoded so far.
+		let mut decoded = Attribute::from_without_removing_duplicates(
+			AttributeId::new(0),
+			vec![NdVector::<3, i32>::zero(); att.len()],
+			AttributeType::Position,
+			AttributeDomain::Position,
+			Vec::new()
+		);
+		let mut prediction = AveragePrediction::<_, 3>::new(&[], &ct);
+		let mut processed = Vec::new();
+		for (&c, &r) in seq.iter().zip(residuals.iter()) {
+			let pred = prediction.predict(c, &processed, &decoded);
+			let p = usize::from(ct.point_idx(c));
+			decoded.unique_vals_as_slice_mut::<NdVector<3, i32>, 3>()[p] = pred + r;
+			processed.push(ct.vertex_idx(c));
+		}
+		for p in 0..att.len() {
+			let p = PointIdx::from(p);
+			assert_eq!(decoded.get::<NdVector<3, i32>, 3>(p), att.get::<NdVector<3, i32>, 3>(p));
+		}
+	}
+}
@@ -14,8 +14,6 @@ impl<'parents, C, const N: usize> PredictionSchemeImpl<'parents, C, N> for Delta
 	where C: GenericCornerTable,
 	      NdVector<N, i32>: Vector<N, Component = i32>,
 {
-	const ID: u32 = 1;
-	
 	type AdditionalDataForMetadata = ();
 	
 	fn new(_parents: &[&'parents Attribute], corner_table: &'parents C) -> Self {
@@ -113,8 +113,6 @@ impl<'parents, C, const N: usize> PredictionSchemeImpl<'parents, C, N> for Deriv
         C: GenericCornerTable,
         NdVector<N, i32>: Vector<N, Component = i32>,
 {
-	const ID: u32 = 4;
-	
 	type AdditionalDataForMetadata = ();
 	
     /// We need two parents: faces and points.
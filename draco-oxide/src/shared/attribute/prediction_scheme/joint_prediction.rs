@@ -0,0 +1,63 @@
+use crate::core::attribute::Attribute;
+use crate::core::corner_table::GenericCornerTable;
+use crate::core::shared::{CornerIdx, VertexIdx};
+use crate::prelude::{ByteWriter, NdVector, Vector};
+use crate::utils::bit_coder::leb128_write;
+use super::mesh_parallelogram_prediction::MeshParallelogramPrediction;
+use super::PredictionSchemeImpl;
+
+/// Predicts the value of an attribute jointly with a correlated attribute that is already encoded,
+/// e.g. a displacement that follows the position. Both attributes are predicted by the parallelogram
+/// prediction, and the correction of the parent at the same point, which the decoder already knows,
+/// is added to the prediction. The correction stream of the attribute is then the difference of the
+/// corrections of the two attributes, which is small when they are correlated on comparable scales.
+pub struct JointPrediction<'parents, C, const N: usize> {
+	parallelogram: MeshParallelogramPrediction<'parents, C, N>,
+	corner_table: &'parents C,
+	parent: &'parents Attribute,
+}
+
+impl<'parents, C, const N: usize> PredictionSchemeImpl<'parents, C, N> for JointPrediction<'parents, C, N>
+	where C: GenericCornerTable,
+	      NdVector<N, i32>: Vector<N, Component = i32>,
+{
+	type AdditionalDataForMetadata = ();
+
+	/// The encoder and the decoder check the parent before, so that it is an 'i32' attribute of 'N' components.
+	fn new(parents: &[&'parents Attribute], corner_table: &'parents C) -> Self {
+		debug_assert!(parents.len() == 1, "JointPrediction requires exactly one parent attribute, but it has {} parents.", parents.len());
+		debug_assert_eq!(parents[0].get_num_components(), N, "JointPrediction requires the parent attribute to have the same number of components.");
+		Self {
+			parallelogram: MeshParallelogramPrediction::new(&[], corner_table),
+			corner_table,
+			parent: parents[0],
+		}
+	}
+
+	/// The parallelogram prediction falls back to the delta prediction, so every value is predicted.
+	fn get_values_impossible_to_predict(&mut self, _value_indices: &mut Vec<std::ops::Range<usize>>)
+		-> Vec<std::ops::Range<usize>>
+	{
+		Vec::new()
+	}
+
+	fn predict(
+		&mut self,
+		c: CornerIdx,
+		vertices_processed_up_till_now: &[VertexIdx],
+		attribute: &Attribute,
+	) -> NdVector<N, i32> {
+		let prediction = self.parallelogram.predict(c, vertices_processed_up_till_now, attribute);
+		let parent_prediction = self.parallelogram.predict(c, vertices_processed_up_till_now, self.parent);
+		let parent_value = self.parent.get::<NdVector<N, i32>, N>(self.corner_table.point_idx(c));
+		prediction + parent_value - parent_prediction
+	}
+
+	/// The decoder finds the parent by its unique id.
+	fn encode_prediction_metadtata<W>(&self, writer: &mut W) -> Result<(), super::Err>
+		where W: ByteWriter
+	{
+		leb128_write(self.parent.get_id().as_usize() as u64, writer);
+		Ok(())
+	}
+}
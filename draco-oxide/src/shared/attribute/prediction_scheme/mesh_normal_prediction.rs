@@ -1,7 +1,8 @@
-use crate::core::shared::{CornerIdx, Cross, Dot, VertexIdx};
-use crate::encode::attribute::prediction_transform::geom::{into_faithful_oct_quantization, octahedral_transform};
+use crate::core::shared::{CornerIdx, Cross, VertexIdx};
+use crate::encode::attribute::prediction_transform::geom::{canonicalize_integer_vector, integer_vector_to_oct_quantization};
+use crate::decode::entropy::rans::RabsDecoder;
 use crate::encode::entropy::rans::RabsCoder;
-use crate::utils::bit_coder::leb128_write;
+use crate::utils::bit_coder::{leb128_read, leb128_write};
 
 use super::PredictionSchemeImpl;
 use crate::core::corner_table::GenericCornerTable;
@@ -12,6 +13,10 @@ pub(crate) struct MeshNormalPrediction<'parents, C, const N: usize> {
     corner_table: &'parents C,
     pos: &'parents Attribute,
     flips: Vec<bool>,
+    /// The flips read from the metadata in the decoder mode, in the order of the predictions.
+    decoded_flips: Option<std::vec::IntoIter<bool>>,
+    /// The largest quantized value of the octahedral coordinates, i.e. '2^quantization_bits - 1'.
+    max_quantized: i32,
 }
 
 impl<'parents, C, const N: usize> MeshNormalPrediction<'parents, C, N> 
@@ -27,20 +32,44 @@ impl<'parents, C, const N: usize> MeshNormalPrediction<'parents, C, N>
         let pos_next = self.pos.get::<NdVector<3,i32>, 3>(self.corner_table.point_idx(c_next));
         let pos_prev = self.pos.get::<NdVector<3,i32>, 3>(self.corner_table.point_idx(c_prev));
 
-        // Compute the difference to next and prev.
-        let delta_next = pos_next - pos_c;
-        let delta_prev = pos_prev - pos_c;
+        // Compute the difference to next and prev in 64 bits as Draco does, as the cross product of the
+        // positions of high quantization bits overflows 32 bits.
+        let widen = |v: NdVector<3, i32>| NdVector::from([0, 1, 2].map(|i| *v.get(i) as i64));
+        let delta_next = widen(pos_next) - widen(pos_c);
+        let delta_prev = widen(pos_prev) - widen(pos_c);
 
         // Take the cross product
-        let cross = {
-            let cross_i32 = delta_next.cross(delta_prev);
-            let mut cross = NdVector::<3, i64>::zero();
-            *cross.get_mut(0) = *cross_i32.get(0) as i64;
-            *cross.get_mut(1) = *cross_i32.get(1) as i64;
-            *cross.get_mut(2) = *cross_i32.get(2) as i64;
-            cross
-        };
-        cross
+        delta_next.cross(delta_prev)
+    }
+
+    /// Sets the largest quantized value of the octahedral coordinates to predict, which is 255 (8 bits) by default.
+    pub(crate) fn set_max_quantized(&mut self, max_quantized: i32) {
+        self.max_quantized = max_quantized;
+    }
+
+    /// The octahedral coordinates of the vector on the octahedron.
+    fn to_oct(&self, v: [i32; 3]) -> NdVector<N, i32> {
+        let oct = integer_vector_to_oct_quantization(v, self.max_quantized - 1);
+        let mut out = NdVector::<N, i32>::zero();
+        *out.get_mut(0) = *oct.get(0);
+        *out.get_mut(1) = *oct.get(1);
+        out
+    }
+
+    /// The size of the correction of the octahedral coordinates, where the coordinates wrap around as in
+    /// the octahedral orthogonal transform.
+    fn correction_size(&self, actual: NdVector<N, i32>, pred: NdVector<N, i32>) -> i32 {
+        let center = self.max_quantized / 2;
+        (0..2).map(|i| {
+            let d = *actual.get(i) - *pred.get(i);
+            if d > center {
+                d - self.max_quantized
+            } else if d < -center {
+                d + self.max_quantized
+            } else {
+                d
+            }.abs()
+        }).sum()
     }
 }
 
@@ -49,8 +78,6 @@ impl<'parents, C, const N: usize> PredictionSchemeImpl<'parents, C, N> for MeshN
         C: GenericCornerTable,
         NdVector<N, i32>: Vector<N, Component = i32>,
 {
-    const ID: u32 = 2;
-    
     type AdditionalDataForMetadata = ();
 	
 	fn new(parents: &[&'parents Attribute], corner_table: &'parents C ) -> Self {
@@ -62,7 +89,9 @@ impl<'parents, C, const N: usize> PredictionSchemeImpl<'parents, C, N> for MeshN
         Self {
             corner_table,
             pos: parents[0], // we made sure that the first parent is the position attribute
-            flips: Vec::new()
+            flips: Vec::new(),
+            decoded_flips: None,
+            max_quantized: 255,
         }
     }
 
@@ -103,44 +132,23 @@ impl<'parents, C, const N: usize> PredictionSchemeImpl<'parents, C, N> for MeshN
             let quotient = abs_sum / upper_bound;
             sum /= quotient;
         }
-        let mut out = {
-            let mut out = NdVector::<3, i32>::zero();
-            *out.get_mut(0) = *sum.get(0) as i32;
-            *out.get_mut(1) = *sum.get(1) as i32;
-            *out.get_mut(2) = *sum.get(2) as i32;
-            
-            // Check if the normal is zero and handle gracefully
-            if out == NdVector::<3, i32>::zero() {
-                // Return a default normal pointing up (0, 0, 1) in octahedral space
-                let mut default_out = NdVector::<N, i32>::zero();
-                *default_out.get_mut(0) = 0;
-                *default_out.get_mut(1) = 0;
-                default_out
-            } else {
-                let val_oct = octahedral_transform(out) + NdVector::<2, f32>::from([1.0,1.0]);
-                let quantized = val_oct * ((1<<8-1)-1) as f32; // TODO: Stop hardcoding the quantization bits.
-                let mut out = NdVector::<2, i32>::zero();
-                for i in 0..2 {
-                    *out.get_mut(i) = (*quantized.get(i)) as i32;
-                }
-                let quant_out = into_faithful_oct_quantization(out);
-                let mut out = NdVector::<N, i32>::zero();
-                *out.get_mut(0) = *quant_out.get(0);
-                *out.get_mut(1) = *quant_out.get(1);
-                out
-            }
+        // Scale the normal onto the octahedron with the integer arithmetic of Draco, so that the prediction agrees with it.
+        let normal = canonicalize_integer_vector([0, 1, 2].map(|i| *sum.get(i) as i32), (self.max_quantized - 1) / 2);
+        let flip = if let Some(flips) = &mut self.decoded_flips {
+            flips.next().unwrap_or(false)
+        } else {
+            // flip the normal if its opposite is closer to the actual value.
+            let actual_val = attribute.get::<NdVector<N,i32>,N>(self.corner_table.point_idx(c));
+            let pos = self.to_oct(normal);
+            let neg = self.to_oct(normal.map(|x| -x));
+            self.correction_size(actual_val, neg) < self.correction_size(actual_val, pos)
         };
-        let actual_val = attribute.get::<NdVector<N,i32>,N>(self.corner_table.point_idx(c));
-        let diff1 = out - actual_val;
-        let diff2 = out * -1 - actual_val;
-        if diff1.dot(diff1) > diff2.dot(diff2) {
-            // if -out is closer to the actual value, we flip the sign.
-            self.flips.push(true);
-            out = out * -1;
+        self.flips.push(flip);
+        if flip {
+            self.to_oct(normal.map(|x| -x))
         } else {
-            self.flips.push(false);
+            self.to_oct(normal)
         }
-        out
     }
 
 
@@ -162,4 +170,19 @@ impl<'parents, C, const N: usize> PredictionSchemeImpl<'parents, C, N> for MeshN
         }
         Ok(())
     }
+
+    fn decode_prediction_metadata<R>(&mut self, num_values: usize, reader: &mut R) -> Result<(), super::Err>
+        where R: crate::prelude::ByteReader
+    {
+        let zero_prob = reader.read_u8()?;
+        let len = leb128_read(reader)? as usize;
+        let mut rabs_decoder: RabsDecoder<_> = RabsDecoder::new(reader, len, zero_prob as usize, None)?;
+        // The bits are read in the reverse order of the encoder.
+        let mut flips = (0..num_values)
+            .map(|_| rabs_decoder.read().map(|b| b == 1))
+            .collect::<Result<Vec<_>, _>>()?;
+        flips.reverse();
+        self.decoded_flips = Some(flips.into_iter());
+        Ok(())
+    }
 }
\ No newline at end of file
@@ -13,8 +13,6 @@ impl<'parents, C, const N: usize> PredictionSchemeImpl<'parents, C, N> for MeshP
         C: GenericCornerTable,
         NdVector<N, i32>: Vector<N, Component = i32>,
 {
-    const ID: u32 = 2;
-    
     type AdditionalDataForMetadata = ();
 	
 	fn new(_parents: &[&'parents Attribute], corner_table: &'parents C ) -> Self {
@@ -189,51 +187,74 @@ impl<'parents, C, const N: usize> PredictionSchemeImpl<'parents, C, N> for MeshP
 		vertices_up_till_now: &[VertexIdx],
         attribute: &Attribute,
 	) -> NdVector<N, i32> {
-        // Find the the most recent opposite corner.
-        // 'diagonal' is the vertex opposite to 'i', and 'a' and 'b' are the other points
-        // so that 'a', 'i', 'b', and 'diagonal' form a parallelogram.
-        let [a,b,diagonal] = {
-            if let Some(opp) = self.corner_table.opposite(c) {
-                let opp_v = self.corner_table.vertex_idx(opp);
-                let next_v = self.corner_table.vertex_idx(self.corner_table.next(c));
-                let prev_v = self.corner_table.vertex_idx(self.corner_table.previous(c));
-                if vertices_up_till_now.contains(&opp_v) 
-                    && vertices_up_till_now.contains(&next_v)
-                    && vertices_up_till_now.contains(&prev_v)
-                {
-                    // we found the opposite corner
-                    [self.corner_table.next(c), self.corner_table.previous(c), opp]
-                } else {
-                    // If there is no opposite corner, then we cannot do the parallelogram prediction.
-                    // return the most recent value instead.
-                    return if let Some(&last_v) = vertices_up_till_now.last() {
-                        attribute.get(self.corner_table.point_idx(self.corner_table.left_most_corner(last_v)))
-                    } else {
-                        // If there are no vertices or corners up till now, return a zero vector.
-                        NdVector::<N, i32>::zero()
-                    };
-                }
-            } else {
-                // If there is no opposite corner, then we cannot do the parallelogram prediction.
-                // return the most recent value instead.
-                return if let Some(&last_v) = vertices_up_till_now.last() {
-                    attribute.get(self.corner_table.point_idx(self.corner_table.left_most_corner(last_v)))
-                } else {
-                    // If there are no vertices or corners up till now, return a zero vector.
-                    NdVector::<N, i32>::zero()
-                };
-            }
+        let ct = self.corner_table;
+        // The parallelogram across the opposite corner needs the three vertices of the opposite face.
+        // On the boundary, where the corner has no opposite, or when they are not processed yet, the value
+        // of the previously processed vertex is used instead as Draco does, so nothing is written for the choice.
+        let opp = ct.opposite(c).filter(|&opp| 
+            [opp, ct.next(c), ct.previous(c)].iter().all(|&c| vertices_up_till_now.contains(&ct.vertex_idx(c)))
+        );
+        let Some(opp) = opp else {
+            return self.delta_prediction(vertices_up_till_now, attribute);
         };
         
-        let diagonal = self.corner_table.point_idx(diagonal);
-        let a = self.corner_table.point_idx(a);
-        let b = self.corner_table.point_idx(b);
-
-        let a_coord = attribute.get::<NdVector<N,i32>, N>(a).clone();
-        let b_coord = attribute.get::<NdVector<N,i32>, N>(b).clone();
-        let diagonal_coord = attribute.get::<NdVector<N,i32>, N>(diagonal).clone();
-        let out = a_coord + b_coord - diagonal_coord;
-        out
+        // 'a', 'c', 'b', and 'opp' form a parallelogram.
+        let value_at = |c: CornerIdx| attribute.get::<NdVector<N,i32>, N>(ct.point_idx(c));
+        let (a, b) = (ct.next(c), ct.previous(c));
+        value_at(a) + value_at(b) - value_at(opp)
+    }
+}
+
+impl<'parents, C, const N: usize> MeshParallelogramPrediction<'parents, C, N> 
+    where 
+        C: GenericCornerTable,
+        NdVector<N, i32>: Vector<N, Component = i32>,
+{
+    /// The value of the previously processed vertex, or zero for the first vertex.
+    fn delta_prediction(&self, vertices_up_till_now: &[VertexIdx], attribute: &Attribute) -> NdVector<N, i32> {
+        if let Some(&last_v) = vertices_up_till_now.last() {
+            attribute.get(self.corner_table.point_idx(self.corner_table.left_most_corner(last_v)))
+        } else {
+            NdVector::<N, i32>::zero()
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::attribute::{AttributeDomain, AttributeId, AttributeType};
+    use crate::core::corner_table::CornerTable;
+    use crate::core::shared::PointIdx;
+
+    #[test]
+    fn test_predict_on_boundary() {
+        // a quad split into two triangles, whose diagonal is the only interior edge.
+        let points = [[0, 0, 0], [10, 0, 1], [10, 10, 2], [0, 10, 3]].map(NdVector::<3, i32>::from);
+        let att = Attribute::from(AttributeId::new(0), points.to_vec(), AttributeType::Position, AttributeDomain::Position, Vec::new());
+        let faces = [[0, 1, 2], [0, 2, 3]].map(|f| f.map(PointIdx::from));
+        let ct = CornerTable::new(&faces, &att);
+        let mut prediction = <MeshParallelogramPrediction<_, 3> as PredictionSchemeImpl<_, 3>>::new(&[], &ct);
+
+        // the vertices of the first face, then the last vertex from the corner of the second face.
+        let corners = [0, 1, 2, 5].map(CornerIdx::from);
+        let expected = [
+            // nothing is processed.
+            NdVector::zero(),
+            // the opposite vertex 3 is not processed yet.
+            points[0],
+            // the edge (0, 1) is on the boundary.
+            points[1],
+            // the parallelogram across the diagonal.
+            points[0] + points[2] - points[1],
+        ];
+        let mut processed = Vec::new();
+        for (c, expected) in corners.into_iter().zip(expected) {
+            assert_eq!(prediction.predict(c, &processed, &att), expected, "the prediction at the corner {:?}", c);
+            processed.push(ct.vertex_idx(c));
+        }
+        assert_eq!(ct.opposite(corners[2]), None);
     }
 }
 
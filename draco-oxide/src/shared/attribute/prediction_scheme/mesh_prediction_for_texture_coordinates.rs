@@ -2,14 +2,17 @@ use super::PredictionSchemeImpl;
 use crate::core::corner_table::GenericCornerTable;
 use crate::core::shared::{CornerIdx, PointIdx, VertexIdx};
 use crate::core::{attribute::Attribute, shared::{Vector, Dot}};
+use crate::decode::entropy::rans::RabsDecoder;
 use crate::encode::entropy::rans::RabsCoder;
-use crate::prelude::{ByteWriter, NdVector};
-use crate::utils::bit_coder::leb128_write;
+use crate::prelude::{ByteReader, ByteWriter, NdVector};
+use crate::utils::bit_coder::{leb128_read, leb128_write};
 
 pub(crate) struct MeshPredictionForTextureCoordinates<'parents, C, const N: usize> {
     corner_table: &'parents C,
     pos_att: &'parents Attribute,
     orientation: Vec<bool>, // Stores orientation for encoder
+    /// The orientations read from the metadata in the decoder mode, in the order of the predictions.
+    decoded_orientation: Option<std::vec::IntoIter<bool>>,
 }
 
 impl<'parents, C, const N: usize> MeshPredictionForTextureCoordinates<'parents, C, N>
@@ -86,8 +89,6 @@ impl<'parents, C, const N: usize> PredictionSchemeImpl<'parents, C, N> for MeshP
         C: GenericCornerTable,
         NdVector<N, i32>: Vector<N, Component = i32>,
 {
-    const ID: u32 = 2;
-    
     type AdditionalDataForMetadata = ();
 	
 	fn new(parents: &[&'parents Attribute], corner_table: &'parents C ) -> Self {
@@ -95,6 +96,7 @@ impl<'parents, C, const N: usize> PredictionSchemeImpl<'parents, C, N> for MeshP
             corner_table,
             pos_att: parents[0],
             orientation: Vec::new(), // Initialize orientation vector
+            decoded_orientation: None,
         }
     }
 
@@ -128,8 +130,6 @@ impl<'parents, C, const N: usize> PredictionSchemeImpl<'parents, C, N> for MeshP
         // Check if both neighboring vertices have already been processed
         if vertices_up_till_now.contains(&next_vertex) && vertices_up_till_now.contains(&prev_vertex) {
             // Get texture coordinates for next and previous vertices
-            let curr_uv: NdVector<N, i32> = attribute.get(curr_pt);
-            let curr_uv = NdVector::<2, i64>::from([*curr_uv.get(0) as i64, *curr_uv.get(1) as i64]);
             let next_uv: NdVector<N, i32> = attribute.get(next_pt);
             let next_uv = NdVector::<2, i64>::from([*next_uv.get(0) as i64, *next_uv.get(1) as i64]);
             let prev_uv: NdVector<N, i32> = attribute.get(prev_pt);
@@ -198,12 +198,20 @@ impl<'parents, C, const N: usize> PredictionSchemeImpl<'parents, C, N> for MeshP
                 let predicted_uv_0 = (x_uv + cx_uv) / (pn_norm2_squared as i64);
                 let predicted_uv_1 = (x_uv - cx_uv) / (pn_norm2_squared as i64);
                 
-                // In encoder mode, we would choose the orientation that gives better prediction
-                let predicted_uv = if (curr_uv-predicted_uv_0).dot(curr_uv-predicted_uv_0) < (curr_uv-predicted_uv_1).dot(curr_uv-predicted_uv_1) {
-                    self.orientation.push(true);
+                // In encoder mode, we would choose the orientation that gives better prediction.
+                // In decoder mode, the orientation is given by the metadata.
+                let orientation = if let Some(orientation) = &mut self.decoded_orientation {
+                    orientation.next().unwrap_or(true)
+                } else {
+                    let curr_uv: NdVector<N, i32> = attribute.get(curr_pt);
+                    let curr_uv = NdVector::<2, i64>::from([*curr_uv.get(0) as i64, *curr_uv.get(1) as i64]);
+                    let orientation = (curr_uv-predicted_uv_0).dot(curr_uv-predicted_uv_0) < (curr_uv-predicted_uv_1).dot(curr_uv-predicted_uv_1);
+                    self.orientation.push(orientation);
+                    orientation
+                };
+                let predicted_uv = if orientation {
                     predicted_uv_0
                 } else {
-                    self.orientation.push(false);
                     predicted_uv_1
                 };
 
@@ -258,4 +266,24 @@ impl<'parents, C, const N: usize> PredictionSchemeImpl<'parents, C, N> for MeshP
         }
         Ok(())
     }
+
+    fn decode_prediction_metadata<R>(&mut self, _num_values: usize, reader: &mut R) -> Result<(), super::Err>
+        where R: ByteReader
+    {
+        let num_orientations = reader.read_u32()? as usize;
+        let zero_prob = reader.read_u8()?;
+        let len = leb128_read(reader)? as usize;
+        let mut rabs_decoder: RabsDecoder<_> = RabsDecoder::new(reader, len, zero_prob as usize, None)?;
+        // The bits are read from the last orientation, each telling whether it equals the one after it.
+        let mut orientation = vec![true; num_orientations];
+        let mut last_orientation = true;
+        for o in orientation.iter_mut().rev() {
+            if rabs_decoder.read()? == 0 {
+                last_orientation = !last_orientation;
+            }
+            *o = last_orientation;
+        }
+        self.decoded_orientation = Some(orientation.into_iter());
+        Ok(())
+    }
 }
@@ -1,4 +1,6 @@
+pub mod average_prediction;
 pub mod delta_prediction;
+pub mod joint_prediction;
 pub mod mesh_parallelogram_prediction;
 pub mod mesh_multi_parallelogram_prediction;
 pub mod derivative_prediction;
@@ -16,11 +18,6 @@ pub(crate) trait PredictionSchemeImpl<'parents, C, const N: usize>
 	where C: GenericCornerTable,
 	      NdVector<N,i32>: Vector<N, Component = i32>
 {
-	/// Id of the prediction method. This value is encoded to buffer in order
-	/// for the decoder to identify the prediction method.
-	#[allow(dead_code)]
-	const ID: u32 = 0;
-	
 	type AdditionalDataForMetadata;
 
 	/// Creates the prediction.
@@ -55,34 +52,105 @@ pub(crate) trait PredictionSchemeImpl<'parents, C, const N: usize>
 	{
 		Ok(())
 	}
+
+	/// Reads the metadata written by [PredictionSchemeImpl::encode_prediction_metadtata] for the
+	/// 'num_values' values of the attribute, and switches the prediction to the decoder mode, where
+	/// the choices of the encoder are taken from the metadata instead of the attribute values.
+	/// The implementation of this function is optional.
+	fn decode_prediction_metadata<R>(&mut self, _num_values: usize, _reader: &mut R) -> Result<(), Err>
+		where R: ByteReader
+	{
+		Ok(())
+	}
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
-pub enum PredictionSchemeType
-{
-	DerivativePrediction,
-	MeshMultiParallelogramPrediction,
-	MeshParallelogramPrediction,
-	MeshNormalPrediction,
-	MeshPredictionForTextureCoordinates,
-	DeltaPrediction,
-	NoPrediction,
-	Invalid, 
+/// Defines [PredictionSchemeType] and [PredictionScheme] from the registry of the prediction schemes, which
+/// gives for each scheme its id in the stream, its names, and the prediction implementing it, so that the id,
+/// the names and the constructor of a scheme are kept in one entry. The 'Invalid' type, whose id is 0xFF
+/// (-1 in i8), is added to the registered ones and cannot be constructed.
+macro_rules! prediction_scheme_registry {
+	($( $(#[$meta:meta])* $variant:ident => $id:literal, [$($name:literal),+], $scheme:ty; )+) => {
+		#[derive(Clone, Debug, PartialEq, Eq)]
+		pub enum PredictionSchemeType
+		{
+			$( $(#[$meta])* $variant, )+
+			Invalid,
+		}
+
+		crate::utils::enum_str::impl_enum_str!(PredictionSchemeType, "prediction scheme", {
+			$( $variant => [$($name),+], )+
+			Invalid => ["invalid"],
+		});
+
+		impl PredictionSchemeType {
+			/// The registered prediction schemes, i.e. all the types but 'Invalid'.
+			pub const REGISTERED: &'static [PredictionSchemeType] = &[ $( PredictionSchemeType::$variant, )+ ];
+
+			pub(crate) fn get_id(&self) -> u8 {
+				match self {
+					$( PredictionSchemeType::$variant => $id, )+
+					PredictionSchemeType::Invalid => 0xFF,
+				}
+			}
+
+			/// Returns the type of the id, or 'None' if the id is not registered.
+			pub(crate) fn from_id(id: u8) -> Option<Self> {
+				match id {
+					$( $id => Some(PredictionSchemeType::$variant), )+
+					0xFF => Some(PredictionSchemeType::Invalid),
+					_ => None,
+				}
+			}
+		}
+
+		pub(crate) enum PredictionScheme<'parents, C, const N: usize>
+		{
+			$( $variant($scheme), )+
+		}
+
+		impl<'parents, C, const N: usize> PredictionScheme<'parents, C, N>
+			where 
+				C: GenericCornerTable,
+				NdVector<N,i32>: Vector<N, Component = i32>,
+		{
+			pub(crate) fn new(ty: PredictionSchemeType, parents: &[&'parents Attribute], corner_table: &'parents C) -> Self {
+				match ty {
+					$(
+						PredictionSchemeType::$variant => PredictionScheme::$variant(
+							<$scheme as PredictionSchemeImpl<'parents, C, N>>::new(parents, corner_table)
+						),
+					)+
+					PredictionSchemeType::Invalid => panic!("Invalid prediction scheme type"),
+				}
+			}
+
+			pub(crate) fn get_type(&self) -> PredictionSchemeType {
+				match self {
+					$( PredictionScheme::$variant(_) => PredictionSchemeType::$variant, )+
+				}
+			}
+		}
+	};
+}
+
+prediction_scheme_registry! {
+	AveragePrediction => 8, ["average"], average_prediction::AveragePrediction<'parents, C, N>;
+	DerivativePrediction => 7, ["derivative"], derivative_prediction::DerivativePredictionForTextureCoordinates<'parents, C, N>;
+	MeshMultiParallelogramPrediction => 2, ["multi_parallelogram"], mesh_multi_parallelogram_prediction::MeshMultiParallelogramPrediction<'parents, C, N>;
+	MeshParallelogramPrediction => 1, ["parallelogram"], mesh_parallelogram_prediction::MeshParallelogramPrediction<'parents, C, N>;
+	MeshNormalPrediction => 6, ["geometric_normal"], mesh_normal_prediction::MeshNormalPrediction<'parents, C, N>;
+	MeshPredictionForTextureCoordinates => 5, ["texcoord"], mesh_prediction_for_texture_coordinates::MeshPredictionForTextureCoordinates<'parents, C, N>;
+	DeltaPrediction => 0, ["delta", "difference"], delta_prediction::DeltaPrediction<'parents, C, N>;
+	/// Predicts the attribute jointly with a correlated parent attribute.
+	JointPrediction => 9, ["joint"], joint_prediction::JointPrediction<'parents, C, N>;
+	NoPrediction => 0xFE, ["none"], NoPrediction; // -2 in i8
 }
 
 impl PredictionSchemeType {
-	pub(crate) fn get_id(&self) -> u8 {
-		match self {
-			PredictionSchemeType::DeltaPrediction => 0,
-			PredictionSchemeType::MeshParallelogramPrediction => 1,
-			PredictionSchemeType::MeshMultiParallelogramPrediction => 2,
-			PredictionSchemeType::MeshPredictionForTextureCoordinates => 5,
-			PredictionSchemeType::MeshNormalPrediction => 6,
-			PredictionSchemeType::DerivativePrediction => 7,
-
-			PredictionSchemeType::NoPrediction => 0xFE, // -2 in i8
-			PredictionSchemeType::Invalid => 0xFF, // -1 in i8
-		}
+	/// Parses the name of a prediction scheme, e.g. from a config file. Any of the names of the scheme
+	/// and the name of the variant are accepted, ignoring the case, '_', '-', and spaces.
+	pub fn parse(name: &str) -> Result<Self, crate::utils::enum_str::ParseEnumError> {
+		name.parse()
 	}
 
 	pub(crate) fn write_to<W>(&self, writer: &mut W) 
@@ -92,57 +160,24 @@ impl PredictionSchemeType {
 		writer.write_u8(id);
 	}
 
-	#[allow(unused)]
-	pub(crate) fn read_from<R>(reader: &mut R) -> Result<Self, usize> 
+	pub(crate) fn read_from<R>(reader: &mut R) -> Result<Self, Err> 
 		where R: ByteReader
 	{
-		let id = reader.read_u8().unwrap() as usize; // ToDo: handle error.
-		let out = match id {
-			0 => PredictionSchemeType::DeltaPrediction,
-			1 => PredictionSchemeType::MeshParallelogramPrediction,
-			2 => PredictionSchemeType::MeshMultiParallelogramPrediction,
-			5 => PredictionSchemeType::DerivativePrediction,
-			6 => PredictionSchemeType::MeshNormalPrediction,
-			7 => PredictionSchemeType::MeshPredictionForTextureCoordinates,
-			0xFE => PredictionSchemeType::NoPrediction, // -2 in i8
-			0xFF => PredictionSchemeType::Invalid, // -1 in i8
-			// If the id is not recognized, return an error.
-			_ => return Err(id as usize),
-		};
-		Ok(out)
-	}
-
-	#[allow(unused)]
-	pub fn to_string(&self) -> String {
-		match self {
-			PredictionSchemeType::DeltaPrediction => "DeltaPrediction".to_string(),
-			PredictionSchemeType::DerivativePrediction => "DerivativePrediction".to_string(),
-			PredictionSchemeType::MeshMultiParallelogramPrediction => "MeshMultiParallelogramPrediction".to_string(),
-			PredictionSchemeType::MeshParallelogramPrediction => "MeshParallelogramPrediction".to_string(),
-			PredictionSchemeType::NoPrediction => "NoPrediction".to_string(),
-			PredictionSchemeType::MeshNormalPrediction => "MeshNormalPrediction".to_string(),
-			PredictionSchemeType::MeshPredictionForTextureCoordinates => "MeshPredictionForTextureCoordinates".to_string(),
-			// Invalid is used when the prediction scheme type is not recognized.
-			PredictionSchemeType::Invalid => "Invalid".to_string(),
-		}
+		let id = reader.read_u8()?;
+		Self::from_id(id).ok_or(Err::InvalidPredictionSchemeId(id))
 	}
 }
 
 #[derive(thiserror::Error, Clone, Debug)]
 pub enum Err {
+	#[error("Invalid prediction scheme id: {0}")]
+	InvalidPredictionSchemeId(u8),
+	#[error("rabs decoder error: {0}")]
+	RabsDecoderError(#[from] crate::decode::entropy::rans::Err),
 	#[error("ranscoder error: {0}")]
 	RanscoderError(#[from] crate::encode::entropy::rans::Err),
-}
-
-pub(crate) enum PredictionScheme<'parents, C, const N: usize>
-{
-	DeltaPrediction(delta_prediction::DeltaPrediction<'parents, C, N>),
-	DerivativePrediction(derivative_prediction::DerivativePredictionForTextureCoordinates<'parents, C, N>),
-	MeshMultiParallelogramPrediction(mesh_multi_parallelogram_prediction::MeshMultiParallelogramPrediction<'parents, C, N>),
-	MeshParallelogramPrediction(mesh_parallelogram_prediction::MeshParallelogramPrediction<'parents, C, N>),
-	MeshNormalPrediction(mesh_normal_prediction::MeshNormalPrediction<'parents, C, N>),
-	MeshPredictionForTextureCoordinates(mesh_prediction_for_texture_coordinates::MeshPredictionForTextureCoordinates<'parents, C, N>),
-	NoPrediction(NoPrediction),
+	#[error("Reader error: {0}")]
+	ReaderError(#[from] crate::core::bit_coder::ReaderErr),
 }
 
 impl<'parents, C, const N: usize> PredictionScheme<'parents, C, N>
@@ -150,54 +185,8 @@ impl<'parents, C, const N: usize> PredictionScheme<'parents, C, N>
 		C: GenericCornerTable,
 		NdVector<N,i32>: Vector<N, Component = i32>,
 {
-	pub(crate) fn new(ty: PredictionSchemeType, parents: &[&'parents Attribute], corner_table: &'parents C) -> Self {
-		match ty {
-			PredictionSchemeType::DeltaPrediction => {
-				let prediction = delta_prediction::DeltaPrediction::new(parents, corner_table);
-				PredictionScheme::DeltaPrediction(prediction)
-			}
-			PredictionSchemeType::DerivativePrediction => {
-				let prediction = derivative_prediction::DerivativePredictionForTextureCoordinates::new(
-					parents, corner_table
-				);
-				PredictionScheme::DerivativePrediction(prediction)
-			}
-			PredictionSchemeType::MeshMultiParallelogramPrediction => {
-				let prediction = mesh_multi_parallelogram_prediction::MeshMultiParallelogramPrediction::new(
-					parents, corner_table
-				);
-				PredictionScheme::MeshMultiParallelogramPrediction(prediction)
-			}
-			PredictionSchemeType::MeshParallelogramPrediction => {
-				let prediction = mesh_parallelogram_prediction::MeshParallelogramPrediction::new(
-					parents, corner_table
-				);
-				PredictionScheme::MeshParallelogramPrediction(prediction)
-			}
-			PredictionSchemeType::MeshNormalPrediction => {
-				let prediction = mesh_normal_prediction::MeshNormalPrediction::new(
-					parents, corner_table
-				);
-				PredictionScheme::MeshNormalPrediction(prediction)
-			}
-			PredictionSchemeType::MeshPredictionForTextureCoordinates => {
-				let prediction = mesh_prediction_for_texture_coordinates::MeshPredictionForTextureCoordinates::new(
-					parents, corner_table
-				);
-				PredictionScheme::MeshPredictionForTextureCoordinates(prediction)
-			}
-			PredictionSchemeType::NoPrediction => {
-				let prediction = NoPrediction::new();
-				PredictionScheme::NoPrediction(prediction)
-			}
-			PredictionSchemeType::Invalid => {
-				panic!("Invalid prediction scheme type");
-			}
-		}
-	}
-
-	#[allow(unused)] // TODO: Remove this function when the decoder is complete
-	pub(crate) fn read_from<R>(reader: &mut R, parents: &[&'parents Attribute], conn_att: &'parents C ) -> Result<Self, usize> 
+	#[allow(unused)] // The decoder reads the type before it knows the parents.
+	pub(crate) fn read_from<R>(reader: &mut R, parents: &[&'parents Attribute], conn_att: &'parents C ) -> Result<Self, Err> 
 		where R: ByteReader
 	{
 		let ty = PredictionSchemeType::read_from(reader)?;
@@ -209,12 +198,18 @@ impl<'parents, C, const N: usize> PredictionScheme<'parents, C, N>
 		-> Vec<std::ops::Range<usize>>
 	{
 		match self {
+			PredictionScheme::AveragePrediction(prediction) => {
+				prediction.get_values_impossible_to_predict(value_indices)
+			}
 			PredictionScheme::DeltaPrediction(prediction) => {
 				prediction.get_values_impossible_to_predict(value_indices)
 			}
 			PredictionScheme::DerivativePrediction(prediction) => {
 				prediction.get_values_impossible_to_predict(value_indices)
 			}
+			PredictionScheme::JointPrediction(prediction) => {
+				prediction.get_values_impossible_to_predict(value_indices)
+			}
 			PredictionScheme::MeshMultiParallelogramPrediction(prediction) => {
 				prediction.get_values_impossible_to_predict(value_indices)
 			}
@@ -248,12 +243,18 @@ impl<'parents, C, const N: usize> PredictionScheme<'parents, C, N>
 		attribute: &Attribute,
 	) -> NdVector<N,i32> {
 		match self {
+			PredictionScheme::AveragePrediction(prediction) => {
+				prediction.predict(i, vertices_processed_up_till_now, attribute)
+			}
 			PredictionScheme::DeltaPrediction(prediction)=> {
 				prediction.predict(i, vertices_processed_up_till_now, attribute)
 			}
 			PredictionScheme::DerivativePrediction(prediction) => {
 				prediction.predict(i, vertices_processed_up_till_now, attribute)
 			}
+			PredictionScheme::JointPrediction(prediction) => {
+				prediction.predict(i, vertices_processed_up_till_now, attribute)
+			}
 			PredictionScheme::MeshMultiParallelogramPrediction(prediction) => {
 				prediction.predict(i, vertices_processed_up_till_now, attribute)
 			}
@@ -277,12 +278,18 @@ impl<'parents, C, const N: usize> PredictionScheme<'parents, C, N>
 		where W: ByteWriter
 	{
 		match self {
+			PredictionScheme::AveragePrediction(prediction) => {
+				prediction.encode_prediction_metadtata(writer)
+			}
 			PredictionScheme::DeltaPrediction(prediction) => {
 				prediction.encode_prediction_metadtata(writer)
 			}
 			PredictionScheme::DerivativePrediction(prediction) => {
 				prediction.encode_prediction_metadtata(writer)
 			}
+			PredictionScheme::JointPrediction(prediction) => {
+				prediction.encode_prediction_metadtata(writer)
+			}
 			PredictionScheme::MeshMultiParallelogramPrediction(prediction) => {
 				prediction.encode_prediction_metadtata(writer)
 			}
@@ -302,15 +309,27 @@ impl<'parents, C, const N: usize> PredictionScheme<'parents, C, N>
 		}
 	}
 
-	pub(crate) fn get_type(&self) -> PredictionSchemeType {
+	/// Reads the prediction metadata and switches the prediction to the decoder mode.
+	pub(crate) fn decode_prediction_metadata<R>(&mut self, num_values: usize, reader: &mut R) -> Result<(), Err>
+		where R: ByteReader
+	{
 		match self {
-			PredictionScheme::DeltaPrediction(_) => PredictionSchemeType::DeltaPrediction,
-			PredictionScheme::DerivativePrediction(_) => PredictionSchemeType::DerivativePrediction,
-			PredictionScheme::MeshMultiParallelogramPrediction(_) => PredictionSchemeType::MeshMultiParallelogramPrediction,
-			PredictionScheme::MeshParallelogramPrediction(_) => PredictionSchemeType::MeshParallelogramPrediction,
-			PredictionScheme::MeshNormalPrediction(_) => PredictionSchemeType::MeshNormalPrediction,
-			PredictionScheme::MeshPredictionForTextureCoordinates(_) => PredictionSchemeType::MeshPredictionForTextureCoordinates,
-			PredictionScheme::NoPrediction(_) => PredictionSchemeType::NoPrediction,
+			PredictionScheme::MeshNormalPrediction(prediction) => {
+				prediction.decode_prediction_metadata(num_values, reader)
+			}
+			PredictionScheme::MeshPredictionForTextureCoordinates(prediction) => {
+				prediction.decode_prediction_metadata(num_values, reader)
+			}
+			// The others have no metadata, or their metadata is read before they are created.
+			_ => Ok(()),
+		}
+	}
+
+	/// Sets the largest quantized value of the octahedral coordinates, i.e. '2^quantization_bits - 1',
+	/// that the normal prediction predicts. The other predictions ignore it.
+	pub(crate) fn set_octahedral_max_quantized(&mut self, max_quantized: i32) {
+		if let PredictionScheme::MeshNormalPrediction(prediction) = self {
+			prediction.set_max_quantized(max_quantized);
 		}
 	}
 }
@@ -331,20 +350,13 @@ impl ConfigType for Config {
 
 pub struct NoPrediction {}
 
-impl NoPrediction {
-	pub fn new() -> Self {
-		Self{}
-	}
-}
-
 impl<'a, C, const N: usize> PredictionSchemeImpl<'a, C, N> for NoPrediction 
 	where C: GenericCornerTable,
 	      NdVector<N,i32>: Vector<N, Component = i32>,
 {
-	const ID: u32 = 0;
 	type AdditionalDataForMetadata = ();
 	fn new(_parents: &[&'a Attribute], _conn_att: &'a C) -> Self {
-		unreachable!()
+		Self{}
 	}
 
 	fn get_values_impossible_to_predict(&mut self, _value_indices: &mut Vec<std::ops::Range<usize>>) 
@@ -361,3 +373,43 @@ impl<'a, C, const N: usize> PredictionSchemeImpl<'a, C, N> for NoPrediction
 		unreachable!()
 	}
 }
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::core::attribute::{AttributeDomain, AttributeId, AttributeType};
+	use crate::core::corner_table::CornerTable;
+	use crate::core::shared::PointIdx;
+	use crate::prelude::SliceReader;
+
+	#[test]
+	fn test_registry() {
+		// a tetrahedron.
+		let positions = vec![
+			NdVector::from([0.0_f32, 0.0, 0.0]),
+			NdVector::from([1.0, 0.0, 0.0]),
+			NdVector::from([0.0, 1.0, 0.0]),
+			NdVector::from([0.0, 0.0, 1.0]),
+		];
+		let pos = Attribute::from(AttributeId::new(0), positions, AttributeType::Position, AttributeDomain::Position, Vec::new());
+		let faces = [[0, 1, 2], [0, 3, 1], [1, 3, 2], [2, 3, 0]].map(|f| f.map(PointIdx::from));
+		let corner_table = CornerTable::new(&faces, &pos);
+
+		for ty in PredictionSchemeType::REGISTERED {
+			let mut buffer = Vec::new();
+			ty.write_to(&mut buffer);
+			let read = PredictionSchemeType::read_from(&mut SliceReader::new(&buffer)).unwrap();
+			assert_eq!(&read, ty);
+			assert_eq!(&PredictionSchemeType::parse(&read.to_string()).unwrap(), ty);
+
+			// the derivative prediction takes the positions twice, and the others once.
+			let parents = if *ty == PredictionSchemeType::DerivativePrediction { vec![&pos, &pos] } else { vec![&pos] };
+			let scheme = PredictionScheme::<_, 3>::new(ty.clone(), &parents, &corner_table);
+			assert_eq!(&scheme.get_type(), ty);
+		}
+
+		assert!(PredictionSchemeType::read_from(&mut SliceReader::new(&[3])).is_err());
+		assert!(PredictionSchemeType::parse("unknown").is_err());
+	}
+}
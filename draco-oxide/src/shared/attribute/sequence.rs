@@ -152,6 +152,35 @@ impl<'ct, T> Traverser<'ct, T>
 }
 
 
+/// Reorders 'sequence', a traversal of a corner table that shares the corners with 'universal', so that it visits
+/// the vertices in the given order of the vertices of 'universal'. The corner table may be an attribute corner table,
+/// whose vertices may be split along the seams of the attribute; the vertices split from a vertex of 'universal'
+/// are visited in a row, in the order of 'sequence', so that the encoder and the decoder agree on them.
+pub(crate) fn sequence_in_vertex_order<U>(universal: &U, mut sequence: Vec<CornerIdx>, order: &[VertexIdx]) -> Vec<CornerIdx> 
+    where U: GenericCornerTable
+{
+    let mut position = vec![0; universal.num_vertices()];
+    for (i, &v) in order.iter().enumerate() {
+        position[usize::from(v)] = i;
+    }
+    // the sort is stable, so the split vertices keep the order of 'sequence'.
+    sequence.sort_by_key(|&c| position[usize::from(universal.vertex_idx(c))]);
+    sequence
+}
+
+/// Returns the index of each vertex of 'order' in 'sequence', the default traversal of 'universal', which the
+/// decoder computes on its own numbering of the vertices. This is how a custom order of the vertices is written.
+pub(crate) fn ranks_in_sequence<U>(universal: &U, sequence: &[CornerIdx], order: &[VertexIdx]) -> Vec<usize> 
+    where U: GenericCornerTable
+{
+    let mut rank = vec![0; universal.num_vertices()];
+    for (i, &c) in sequence.iter().enumerate() {
+        rank[usize::from(universal.vertex_idx(c))] = i;
+    }
+    order.iter().map(|&v| rank[usize::from(v)]).collect()
+}
+
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -164,6 +193,7 @@ mod tests {
         let mut mesh = load_obj("tests/data/tetrahedron.obj").unwrap();
         let out: crate::encode::connectivity::ConnectivityEncoderOutput<'_> = encode_connectivity(
             &mesh.faces, 
+            None,
             &mut mesh.attributes, 
             &mut Vec::new(), 
             &crate::encode::Config::default()
@@ -205,4 +235,41 @@ mod tests {
             vec![3,1,0,2,5,4]
         );
     }
+
+    #[test]
+    fn test_sequence_in_vertex_order() {
+        let mut mesh = load_obj("tests/data/tetrahedron.obj").unwrap();
+        let out = encode_connectivity(
+            &mesh.faces, 
+            None,
+            &mut mesh.attributes, 
+            &mut Vec::new(), 
+            &crate::encode::Config::default()
+        ).unwrap();
+        let (ct, corners) = if let ConnectivityEncoderOutput::Edgebreaker(edgebreaker_out) = out {
+            (edgebreaker_out.corner_table, edgebreaker_out.corners_of_edgebreaker)
+        } else {
+            panic!("Expected Edgebreaker Output");
+        };
+
+        let ct_pos = ct.universal_corner_table();
+        let order = [2, 0, 3, 1].map(VertexIdx::from);
+        let default = Traverser::new(ct_pos, corners.clone()).compute_seqeunce();
+        let sequence = sequence_in_vertex_order(ct_pos, default.clone(), &order);
+        assert_eq!(
+            sequence.iter().map(|&c| ct_pos.vertex_idx(c)).collect::<Vec<_>>(),
+            order.to_vec()
+        );
+        // the order is restored from its ranks in the default traversal.
+        let ranks = ranks_in_sequence(ct_pos, &default, &order);
+        assert_eq!(ranks.iter().map(|&i| ct_pos.vertex_idx(default[i])).collect::<Vec<_>>(), order.to_vec());
+
+        // The texture coordinates are split along the seams, so some vertices are visited more than once.
+        let ct_tex = &ct.attribute_corner_table(2).unwrap();
+        let sequence = sequence_in_vertex_order(ct_pos, Traverser::new(ct_tex, corners).compute_seqeunce(), &order);
+        assert_eq!(sequence.len(), ct_tex.num_vertices());
+        let mut pos_vertices = sequence.iter().map(|&c| ct_pos.vertex_idx(c)).collect::<Vec<_>>();
+        pos_vertices.dedup();
+        assert_eq!(pos_vertices, order.to_vec());
+    }
 }
@@ -26,7 +26,6 @@ pub(crate) enum EdgebreakerKind {
 }
 
 impl EdgebreakerKind {
-    #[allow(unused)] // TODO: Remove this function when the decoder is complete
     pub(crate) fn read_from<R>(reader: &mut R) -> Result<Self, Err> 
         where R: ByteReader
     {
@@ -64,7 +63,6 @@ pub(crate) enum TraversalType {
 }
 
 impl TraversalType {
-    #[allow(unused)] // TODO: Remove this function when the decoder is complete
     pub(crate) fn read_from<R>(reader: &mut R) -> Result<Self, Err> 
         where R: ByteReader
     {
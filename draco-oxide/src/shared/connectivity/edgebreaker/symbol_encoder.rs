@@ -1,4 +1,5 @@
-use crate::core::bit_coder::BitReader;
+use crate::core::bit_coder::{BitReader, ReaderErr};
+use crate::core::buffer::LsbFirst;
 use crate::encode::connectivity::edgebreaker::Err;
 use crate::prelude::ByteReader;
 
@@ -36,13 +37,24 @@ impl Symbol {
             Symbol::E => 4,
         }
     }
+
+    /// Inverse of [Symbol::get_id].
+    pub(crate) fn from_id(id: usize) -> Option<Self> {
+        match id {
+            0 => Some(Symbol::C),
+            1 => Some(Symbol::S),
+            2 => Some(Symbol::L),
+            3 => Some(Symbol::R),
+            4 => Some(Symbol::E),
+            _ => None,
+        }
+    }
 }
 
 pub(crate) trait SymbolEncoder {
     fn encode_symbol(symbol: Symbol) -> Result<(u8, u64), Err>;
 
-    #[allow(dead_code)] // TODO: remove this after completing the decoder.
-    fn decode_symbol<R>(reader: &mut BitReader<R>) -> Symbol where R: ByteReader;
+    fn decode_symbol<R>(reader: &mut BitReader<R, LsbFirst>) -> Result<Symbol, ReaderErr> where R: ByteReader;
 }
 
 pub(crate) struct CrLight;
@@ -57,24 +69,112 @@ impl SymbolEncoder for CrLight {
         }
     }
 
-    fn decode_symbol<R>(reader: &mut BitReader<R>) -> Symbol 
+    fn decode_symbol<R>(reader: &mut BitReader<R, LsbFirst>) -> Result<Symbol, ReaderErr> 
         where R: ByteReader
     {
-        if reader.read_bits(1).unwrap() == 0 {
-            return Symbol::C;
+        if reader.read_bits(1)? == 0 {
+            return Ok(Symbol::C);
         }
 
-        if reader.read_bits(1).unwrap() == 0 {
-            return Symbol::R;
-        }
+        // The bits are read in the order they are written, i.e. the least significant bit of the code first.
+        let symbol = match reader.read_bits(2)? {
+            0b00 => Symbol::S,
+            0b01 => Symbol::L,
+            0b10 => Symbol::R,
+            _ => Symbol::E,
+        };
+        Ok(symbol)
+    }
+}
 
-        return match reader.read_bits(2).unwrap() {
-            0b00 => Symbol::L,
-            0b01 => Symbol::E,
-            0b10 => Symbol::S,
-            _ => panic!("Internal Error: Invalid symbol encoding"),
-        }
+
+
+
+/// How the CLERS symbols of the standard Edgebreaker are coded.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ClersCoding {
+    /// Each symbol is written with a fixed prefix code ('C' with 1 bit and the others with 3 bits).
+    /// This is what the Draco decoder expects.
+    #[default]
+    Flat,
+    /// Each symbol is entropy coded with the statistics of the symbols that follow the same symbol,
+    /// i.e. with a context model of order one.
+    Context,
+}
+
+/// How the CLERS symbols coded with [ClersCoding::Flat] and the symbol indices of the topology splits are stored.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SymbolPacking {
+    /// The symbols are packed with their variable-length codes into a bit stream, and the indices of
+    /// the topology splits are written with the variable-length LEB128. This is what the Draco decoder expects.
+    #[default]
+    BitPacked,
+    /// Each symbol is stored in a byte of its own, and the indices of the topology splits as 32-bit integers,
+    /// so that the streams can be read without a bit reader.
+    ByteAligned,
+}
+
+/// Number of the contexts of [ClersCoding::Context], one for each previous symbol.
+pub(crate) const NUM_CLERS_CONTEXTS: usize = 5;
+
+/// Distributes the symbols to their contexts, i.e. the previous symbol. The first symbol is put in the context of 'C'.
+pub(crate) fn split_by_context<I>(symbols: I) -> Vec<Vec<Symbol>> 
+    where I: IntoIterator<Item = Symbol>
+{
+    let mut out = vec![Vec::new(); NUM_CLERS_CONTEXTS];
+    let mut prev = Symbol::C;
+    for s in symbols {
+        out[prev.get_id()].push(s);
+        prev = s;
+    }
+    out
+}
+
+/// Inverse of [split_by_context]. Restores the 'num_symbols' symbols from the symbols of each context.
+/// Returns 'None' if the contexts do not hold that many symbols, as 'num_symbols' may be read from a corrupted stream.
+pub(crate) fn join_by_context(contexts: Vec<Vec<Symbol>>, num_symbols: usize) -> Option<Vec<Symbol>> {
+    if contexts.iter().map(Vec::len).sum::<usize>() < num_symbols {
+        return None;
     }
+    let mut contexts = contexts.into_iter().map(|c| c.into_iter()).collect::<Vec<_>>();
+    let mut out = Vec::with_capacity(num_symbols);
+    let mut prev = Symbol::C;
+    for _ in 0..num_symbols {
+        let s = contexts.get_mut(prev.get_id())?.next()?;
+        out.push(s);
+        prev = s;
+    }
+    Some(out)
 }
 
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_context_split_and_join() {
+        let mut seed = 7_u32;
+        let symbols = (0..1000)
+            .map(|_| {
+                seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+                // mostly 'C' and 'R' as in a typical mesh.
+                match (seed >> 16) % 10 {
+                    0..=4 => Symbol::C,
+                    5..=7 => Symbol::R,
+                    8 => Symbol::S,
+                    _ => [Symbol::L, Symbol::E][(seed >> 8) as usize % 2],
+                }
+            })
+            .collect::<Vec<_>>();
+        let contexts = split_by_context(symbols.iter().copied());
+        assert_eq!(contexts.iter().map(|c| c.len()).sum::<usize>(), symbols.len());
+        assert_eq!(join_by_context(contexts.clone(), symbols.len()).unwrap(), symbols);
+        // a corrupted number of the symbols is rejected before anything is allocated for them.
+        assert!(join_by_context(contexts, usize::MAX).is_none());
+
+        for s in [Symbol::C, Symbol::S, Symbol::L, Symbol::R, Symbol::E] {
+            assert_eq!(Symbol::from_id(s.get_id()), Some(s));
+        }
+    }
+}
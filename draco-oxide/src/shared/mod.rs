@@ -5,25 +5,22 @@ pub mod attribute;
 pub mod entropy;
 
 pub mod header {
-    use crate::{core::bit_coder::ReaderErr, prelude::{ByteReader, ByteWriter}};
+    use crate::prelude::ByteWriter;
 
     #[derive(Debug, Clone, Copy, PartialEq)]
     pub(crate) enum EncoderMethod {
         Edgebreaker,
-        #[allow(unused)]
         Sequential,
     }
 
     impl EncoderMethod {
+        /// Returns the method of the given id, or 'None' if the id is unknown.
         #[inline]
-        #[allow(unused)]
-        pub fn read_from<R>(reader: &mut R) -> Result<Self, ReaderErr> 
-            where R: ByteReader
-        {
-            match reader.read_u8()? {
-                0 => Ok(EncoderMethod::Sequential),
-                1 => Ok(EncoderMethod::Edgebreaker),
-                _ => panic!("Unknown encoder method ID"),
+        pub fn from_id(id: u8) -> Option<Self> {
+            match id {
+                0 => Some(EncoderMethod::Sequential),
+                1 => Some(EncoderMethod::Edgebreaker),
+                _ => None,
             }
         }
 
@@ -1,8 +1,9 @@
+/// Writes the marker 'msg' to the stream if 'enabled', i.e. if [crate::encode::Config::debug_markers] is set.
+/// The flag is stored in the header, so that the decoder expects the markers exactly when they are written.
 #[macro_export]
 macro_rules! debug_write {
-    ($msg:literal, $writer:expr) => {
-        #[cfg(feature = "debug_format")]
-        {
+    ($msg:literal, $writer:expr, $enabled:expr) => {
+        if $enabled {
             for byte in $msg.as_bytes() {
                 $writer.write_u8(*byte);
             }
@@ -10,18 +11,16 @@ macro_rules! debug_write {
     };
 }
 
+/// Reads the marker 'msg' from the stream if 'enabled', i.e. if the header has the flag of the debug markers.
+/// Evaluates to `Err(Err::DebugMarkerMismatch(msg))` of the `Err` in scope if the stream does not have the marker,
+/// so that the caller propagates it with `?`.
 #[macro_export]
 macro_rules! debug_expect {
-    ($msg:literal, $reader:expr) => {
-        #[cfg(feature = "debug_format")]
-        {
-            for byte in $msg.as_bytes() {
-                assert!(
-                    *byte == $reader.read_u8().unwrap(),
-                    "Expected {:?}, but did not match.",
-                    $msg
-                );
-            }
+    ($msg:literal, $reader:expr, $enabled:expr) => {
+        if $enabled && !$msg.as_bytes().iter().all(|byte| $reader.read_u8().is_ok_and(|b| b == *byte)) {
+            Err(Err::DebugMarkerMismatch($msg))
+        } else {
+            Ok(())
         }
     };
-}
\ No newline at end of file
+}
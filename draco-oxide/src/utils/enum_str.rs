@@ -0,0 +1,147 @@
+/// Error returned when a string does not name any variant of an enum.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[error("Unknown {type_name}: '{input}'")]
+pub struct ParseEnumError {
+    type_name: &'static str,
+    input: String,
+}
+
+impl ParseEnumError {
+    pub(crate) fn new(type_name: &'static str, input: &str) -> Self {
+        Self { type_name, input: input.to_owned() }
+    }
+}
+
+/// Normalizes the name of a variant so that the comparison ignores the case, '_', '-', and spaces.
+pub(crate) fn normalize(s: &str) -> String {
+    s.chars()
+        .filter(|c| !matches!(c, '_' | '-' | ' '))
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+/// Implements `Display`, `FromStr`, and `serde::Deserialize` for a fieldless enum.
+/// `Display` writes the first name of each variant. `FromStr` accepts any of the names
+/// and the name of the variant itself, ignoring the case, '_', '-', and spaces.
+macro_rules! impl_enum_str {
+    ($ty:ident, $type_name:literal, { $($variant:ident => [$($name:literal),+]),+ $(,)? }) => {
+        impl std::fmt::Display for $ty {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                let names: &[&str] = match self {
+                    $( $ty::$variant => &[$($name),+], )+
+                };
+                f.write_str(names[0])
+            }
+        }
+
+        impl std::str::FromStr for $ty {
+            type Err = $crate::utils::enum_str::ParseEnumError;
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let s_normalized = $crate::utils::enum_str::normalize(s);
+                $(
+                    if [$($name,)+ stringify!($variant)].iter().any(|name| $crate::utils::enum_str::normalize(name) == s_normalized) {
+                        return Ok($ty::$variant);
+                    }
+                )+
+                Err($crate::utils::enum_str::ParseEnumError::new($type_name, s))
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $ty {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                where D: serde::Deserializer<'de>
+            {
+                let s = String::deserialize(deserializer)?;
+                s.parse().map_err(serde::de::Error::custom)
+            }
+        }
+    };
+}
+pub(crate) use impl_enum_str;
+
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use std::fmt::{Debug, Display};
+    use crate::core::attribute::AttributeType;
+    use crate::encode::attribute::portabilization::PortabilizationType;
+    use crate::encode::attribute::prediction_transform::PredictionTransformType;
+    use crate::shared::attribute::prediction_scheme::PredictionSchemeType;
+
+    fn assert_round_trip<T>(variants: &[T])
+        where T: FromStr + Display + Debug + PartialEq + for<'de> serde::Deserialize<'de>,
+              T::Err: Debug,
+    {
+        for v in variants {
+            let s = v.to_string();
+            assert_eq!(&s.parse::<T>().unwrap(), v, "'{}' does not parse back", s);
+            assert_eq!(&s.to_uppercase().parse::<T>().unwrap(), v);
+            assert_eq!(&serde_json::from_str::<T>(&format!("\"{}\"", s)).unwrap(), v);
+            // the name of the variant itself is accepted as well.
+            assert_eq!(&format!("{:?}", v).parse::<T>().unwrap(), v);
+        }
+        assert!("not a variant".parse::<T>().is_err());
+    }
+
+    #[test]
+    fn test_attribute_type() {
+        assert_round_trip(&[
+            AttributeType::Position,
+            AttributeType::Normal,
+            AttributeType::Color,
+            AttributeType::TextureCoordinate,
+            AttributeType::Custom,
+            AttributeType::Tangent,
+            AttributeType::Material,
+            AttributeType::Joint,
+            AttributeType::Weight,
+            AttributeType::Invalid,
+        ]);
+        assert_eq!("texcoord".parse::<AttributeType>().unwrap(), AttributeType::TextureCoordinate);
+        assert_eq!("tex-coord".parse::<AttributeType>().unwrap(), AttributeType::TextureCoordinate);
+        // the serialized form is accepted as well.
+        let json = serde_json::to_string(&AttributeType::TextureCoordinate).unwrap();
+        assert_eq!(serde_json::from_str::<AttributeType>(&json).unwrap(), AttributeType::TextureCoordinate);
+    }
+
+    #[test]
+    fn test_prediction_scheme_type() {
+        assert_round_trip(&[
+            PredictionSchemeType::AveragePrediction,
+            PredictionSchemeType::DerivativePrediction,
+            PredictionSchemeType::MeshMultiParallelogramPrediction,
+            PredictionSchemeType::MeshParallelogramPrediction,
+            PredictionSchemeType::MeshNormalPrediction,
+            PredictionSchemeType::MeshPredictionForTextureCoordinates,
+            PredictionSchemeType::DeltaPrediction,
+            PredictionSchemeType::JointPrediction,
+            PredictionSchemeType::NoPrediction,
+            PredictionSchemeType::Invalid,
+        ]);
+    }
+
+    #[test]
+    fn test_prediction_transform_type() {
+        assert_round_trip(&[
+            PredictionTransformType::NoTransform,
+            PredictionTransformType::Difference,
+            PredictionTransformType::WrappedDifference,
+            PredictionTransformType::OctahedralOrthogonal,
+            PredictionTransformType::OctahedralReflection,
+            PredictionTransformType::Orthogonal,
+        ]);
+    }
+
+    #[test]
+    fn test_portabilization_type() {
+        assert_round_trip(&[
+            PortabilizationType::QuantizationCoordinateWise,
+            PortabilizationType::OctahedralQuantization,
+            PortabilizationType::Integer,
+            PortabilizationType::ToBits,
+            PortabilizationType::WrappedQuantization,
+            PortabilizationType::Palette,
+        ]);
+    }
+}
@@ -6,38 +6,94 @@ use crate::core::shared::{
 };
 
 /// Calculates the distance from a point to a triangle in 3D space.
+/// If the point does not project into the triangle, the distance is the one to the closest edge.
 pub fn point_to_face_distance_3d<F: Float>(p: NdVector<3, F>, face: [NdVector<3,F>; 3]) -> F {
-    let x = face[1] - face[0];
-    let y = face[2] - face[0];
-    let n = x.cross(y).normalize();
-    let distance_to_plane = n.dot(p-face[0]).abs();
-
-    let p_onto_plane = p - n * distance_to_plane;
-    let p_onto_plane_inside_face = 
-        (p_onto_plane - face[0]).dot(face[1] - face[0]) * (face[2] - face[0]).dot(face[1] - face[0]) > F::zero() &&
-        (p_onto_plane - face[1]).dot(face[2] - face[1]) * (face[0] - face[1]).dot(face[2] - face[1]) > F::zero() &&
-        (p_onto_plane - face[2]).dot(face[0] - face[2]) * (face[1] - face[2]).dot(face[0] - face[2]) > F::zero();
- 
+    let n = (face[1] - face[0]).cross(face[2] - face[0]);
+    let signed_distance_to_plane = n.normalize().dot(p - face[0]);
+
+    // The projection is inside if it is on the inner side of every edge. A degenerate triangle,
+    // whose normal is zero or not a number, has no inside.
+    let p_onto_plane = p - n.normalize() * signed_distance_to_plane;
+    let p_onto_plane_inside_face = (0..3).all(|i| {
+        let (a, b) = (face[i], face[(i + 1) % 3]);
+        (b - a).cross(p_onto_plane - a).dot(n) >= F::zero()
+    });
+
     if p_onto_plane_inside_face {
-        distance_to_plane
+        signed_distance_to_plane.abs()
     } else {
-        [
-            point_to_line_distance_3d(p, [face[0], face[1]]),
-            point_to_line_distance_3d(p, [face[1], face[2]]),
-            point_to_line_distance_3d(p, [face[2], face[0]]),
-            (face[1]-face[0]).norm(),
-            (face[2]-face[1]).norm(),
-            (face[0]-face[2]).norm()
-        ].into_iter().min_by(|a,b| a.partial_cmp(b).unwrap()).unwrap()
+        (0..3)
+            .map(|i| point_to_segment_distance_3d(p, [face[i], face[(i + 1) % 3]]))
+            .fold(F::MAX_VALUE, |a, b| if b < a { b } else { a })
     }
 }
 
-/// Calculates the distance from a point to a line in 3D space.
-/// The line should be expressed as two points in 3D space.
-pub fn point_to_line_distance_3d<F: Float>(p: NdVector<3, F>, line: [NdVector<3,F>; 2]) -> F {
-    let dir = (line[1] - line[0]).normalize();
-    let p_line0 = p - line[0];
-    let n = (p_line0 - dir * p_line0.dot(dir)).normalize();
-    debug_assert!(n.dot(dir).abs() < F::from_f64(1e-6));
-    n.dot(p_line0).abs()
-}
\ No newline at end of file
+/// Calculates the distance from a point to a line segment in 3D space.
+fn point_to_segment_distance_3d<F: Float>(p: NdVector<3, F>, segment: [NdVector<3,F>; 2]) -> F {
+    let dir = segment[1] - segment[0];
+    let squared_length = dir.dot(dir);
+    let t = if squared_length > F::zero() {
+        let t = (p - segment[0]).dot(dir) / squared_length;
+        if t < F::zero() { F::zero() } else if t > F::one() { F::one() } else { t }
+    } else {
+        F::zero()
+    };
+    (p - (segment[0] + dir * t)).norm()
+}
+
+/// The generalized winding number of the triangles around the point, i.e. the sum of the solid angles
+/// of the triangles seen from the point divided by '4π'. For a closed mesh, it is '±1' inside, with the sign
+/// of the orientation of the faces, and '0' outside. Unlike casting a ray, it does not depend on a direction
+/// that may hit an edge or a vertex.
+pub fn winding_number(p: NdVector<3, f64>, triangles: &[[NdVector<3, f64>; 3]]) -> f64 {
+    let solid_angle_sum = triangles.iter()
+        .map(|t| {
+            // Van Oosterom and Strackee's formula of the solid angle of a triangle.
+            let [a, b, c] = t.map(|v| v - p);
+            let [la, lb, lc] = [a.norm(), b.norm(), c.norm()];
+            let numerator = a.dot(b.cross(c));
+            let denominator = la * lb * lc + a.dot(b) * lc + b.dot(c) * la + c.dot(a) * lb;
+            2.0 * numerator.atan2(denominator)
+        })
+        .sum::<f64>();
+    solid_angle_sum / (4.0 * std::f64::consts::PI)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_point_to_face_distance() {
+        let face = [NdVector::from([0.0_f64, 0.0, 0.0]), NdVector::from([1.0, 0.0, 0.0]), NdVector::from([0.0, 1.0, 0.0])];
+        // above and below the inside of the face.
+        assert!((point_to_face_distance_3d(NdVector::from([0.2, 0.2, 2.0]), face) - 2.0).abs() < 1e-12);
+        assert!((point_to_face_distance_3d(NdVector::from([0.2, 0.2, -2.0]), face) - 2.0).abs() < 1e-12);
+        // beyond the hypotenuse, and beyond a vertex.
+        assert!((point_to_face_distance_3d(NdVector::from([1.0, 1.0, 0.0]), face) - 0.5_f64.sqrt()).abs() < 1e-12);
+        assert!((point_to_face_distance_3d(NdVector::from([-3.0, -4.0, 0.0]), face) - 5.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_inside_outside_of_cube() {
+        use crate::core::mesh::diff::unique_value_as_f64;
+        use crate::prelude::AttributeType;
+
+        let cube = crate::io::stl::load_stl("tests/data/cube.stl").unwrap();
+        let pos = cube.get_attributes().iter().find(|att| att.get_attribute_type() == AttributeType::Position).unwrap();
+        let triangles = cube.get_faces().iter()
+            .map(|f| f.map(|p| {
+                let v = unique_value_as_f64(pos, usize::from(pos.get_unique_val_idx(p)));
+                NdVector::from([v[0], v[1], v[2]])
+            }))
+            .collect::<Vec<_>>();
+        let is_inside = |p: [f64; 3]| winding_number(NdVector::from(p), &triangles).abs() > 0.5;
+
+        assert!((winding_number(NdVector::from([0.5, 0.5, 0.5]), &triangles).abs() - 1.0).abs() < 1e-9);
+        assert!(winding_number(NdVector::from([10.0, -3.0, 7.0]), &triangles).abs() < 1e-9);
+        // just outside of a face, and just inside of a corner.
+        assert!(!is_inside([1.01, 0.5, 0.5]));
+        assert!(is_inside([0.01, 0.01, 0.01]));
+    }
+}
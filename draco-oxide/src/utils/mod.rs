@@ -1,8 +1,12 @@
 use crate::prelude::{NdVector, Vector};
 
-pub mod geom;
+pub(crate) mod geom;
 pub(crate) mod debug;
 pub(crate) mod bit_coder;
+pub(crate) mod enum_str;
+
+pub use enum_str::ParseEnumError;
+pub use geom::winding_number;
 
 #[allow(dead_code)] // Remove this when attribute encoder supports multiple groups.
 pub(crate) fn splice_disjoint_indices(set_of_subseqs: Vec<Vec<std::ops::Range<usize>>>) -> Vec<std::ops::Range<usize>> {
@@ -165,4 +169,23 @@ pub(crate) fn to_positive_i32_vec<const N: usize>(mut vec: NdVector<N, i32>) ->
         *vec.get_mut(i) = to_positive_i32(*vec.get(i));
     }
     vec
+}
+
+/// Inverse of [to_positive_i32].
+pub(crate) fn from_positive_i32(val: i32) -> i32 {
+    if val & 1 == 0 {
+        val >> 1
+    } else {
+        -((val >> 1) + 1)
+    }
+}
+
+pub(crate) fn from_positive_i32_vec<const N: usize>(mut vec: NdVector<N, i32>) -> NdVector<N, i32> 
+    where 
+        NdVector<N, i32>: Vector<N, Component = i32>,
+{
+    for i in 0..N {
+        *vec.get_mut(i) = from_positive_i32(*vec.get(i));
+    }
+    vec
 }
\ No newline at end of file
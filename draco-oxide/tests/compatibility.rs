@@ -14,4 +14,31 @@ fn en() {
     let mut file = std::fs::File::create(&format!("tests/outputs/{}.drc", FILE_NAME)).unwrap();
 
     file.write_all(&writer).unwrap();
-}
\ No newline at end of file
+}
+
+/// Decodes our output with the C++ decoder of Draco, which must be on the path as 'draco_decoder'.
+#[test]
+#[ignore]
+#[cfg(not(feature = "evaluation"))]
+fn cpp_draco_decodes_our_output() {
+    let mesh = load_obj("tests/data/tetrahedron.obj").unwrap();
+    let mut writer = Vec::new();
+    encode(mesh.clone(), &mut writer, encode::Config::default()).unwrap();
+
+    let dir = std::env::temp_dir();
+    let drc_path = dir.join("draco_oxide_compatibility.drc");
+    let obj_path = dir.join("draco_oxide_compatibility.obj");
+    std::fs::write(&drc_path, &writer).unwrap();
+
+    let output = std::process::Command::new("draco_decoder")
+        .arg("-i")
+        .arg(&drc_path)
+        .arg("-o")
+        .arg(&obj_path)
+        .output()
+        .expect("draco_decoder not found. Please install Google's Draco C++ tools.");
+    assert!(output.status.success(), "draco_decoder failed: {}", String::from_utf8_lossy(&output.stderr));
+
+    let decoded = load_obj(&obj_path).unwrap();
+    assert_eq!(decoded.get_faces().len(), mesh.get_faces().len());
+}
@@ -0,0 +1,107 @@
+//! Decodes the committed golden '.drc' files and checks the geometry against the expected one, so that
+//! a change of the bitstream or of the decoder does not go unnoticed.
+//!
+//! The golden files are written by the ignored test 'generate', which encodes the source meshes and
+//! records what the decoder restores from them:
+//!
+//! ```sh
+//! cargo test -p draco-oxide --test golden -- --ignored generate
+//! ```
+//!
+//! Regenerate them only when the bitstream is changed on purpose.
+
+use draco_oxide::prelude::{decode, AttributeType, ConfigType, Mesh, NdVector, SliceReader, Vector};
+use serde::{Deserialize, Serialize};
+
+const GOLDEN_DIR: &str = "tests/data/golden";
+
+/// The names of the golden cases, each with a '.drc' file and a '.json' file of the expected geometry.
+const CASES: [&str; 3] = ["triangle", "sphere", "seamed_cube"];
+
+/// The tolerance of the decoded coordinates, which covers the rounding of the JSON.
+const TOLERANCE: f32 = 1e-6;
+
+/// The decoded geometry as it is stored in the '.json' files.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Geometry {
+    positions: Vec<[f32; 3]>,
+    faces: Vec<[usize; 3]>,
+}
+
+impl Geometry {
+    fn from_mesh(mesh: &Mesh) -> Self {
+        let pos = mesh.get_attributes().iter()
+            .find(|att| att.get_attribute_type() == AttributeType::Position)
+            .expect("the mesh has no positions");
+        let positions = (0..pos.len())
+            .map(|p| {
+                let v = pos.get::<NdVector<3, f32>, 3>(p.into());
+                [0, 1, 2].map(|i| *v.get(i))
+            })
+            .collect();
+        let faces = mesh.get_faces().iter()
+            .map(|f| f.map(usize::from))
+            .collect();
+        Self { positions, faces }
+    }
+}
+
+fn decode_file(path: &str) -> Mesh {
+    let data = std::fs::read(path).unwrap_or_else(|e| panic!("failed to read {}: {}", path, e));
+    decode(&mut SliceReader::new(&data), decode::Config::default())
+        .unwrap_or_else(|e| panic!("failed to decode {}: {}", path, e))
+}
+
+#[test]
+fn golden() {
+    for name in CASES {
+        let mesh = decode_file(&format!("{}/{}.drc", GOLDEN_DIR, name));
+        let actual = Geometry::from_mesh(&mesh);
+        let json = std::fs::read_to_string(format!("{}/{}.json", GOLDEN_DIR, name)).unwrap();
+        let expected: Geometry = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(actual.faces, expected.faces, "{}: the faces differ", name);
+        assert_eq!(actual.positions.len(), expected.positions.len(), "{}: the number of points differs", name);
+        for (p, (a, e)) in actual.positions.iter().zip(&expected.positions).enumerate() {
+            let err = a.iter().zip(e).map(|(a, e)| (a - e).abs()).fold(0.0f32, f32::max);
+            assert!(err <= TOLERANCE, "{}: the point {} is {:?}, expected {:?}", name, p, a, e);
+        }
+    }
+}
+
+/// The seams of the normals and the texture coordinates split the corners of the cube into more points.
+#[test]
+fn golden_seamed_cube_has_seams() {
+    let mesh = decode_file(&format!("{}/seamed_cube.drc", GOLDEN_DIR));
+    assert!(mesh.get_attributes().iter().any(|att| att.get_attribute_type() == AttributeType::Normal));
+    assert!(Geometry::from_mesh(&mesh).positions.len() > 8);
+}
+
+/// The mesh from which the golden file of the case is encoded.
+#[cfg(not(feature = "evaluation"))]
+fn source_mesh(name: &str) -> Mesh {
+    let path = match name {
+        "triangle" => "tests/data/triangle.obj",
+        "sphere" => "tests/data/sphere.obj",
+        "seamed_cube" => "tests/data/cube_quads.obj",
+        _ => unreachable!("unknown golden case: {}", name),
+    };
+    draco_oxide::io::obj::load_obj(path).unwrap()
+}
+
+/// Writes the golden files.
+#[test]
+#[ignore]
+#[cfg(not(feature = "evaluation"))]
+fn generate() {
+    std::fs::create_dir_all(GOLDEN_DIR).unwrap();
+    for name in CASES {
+        let mut encoded = Vec::new();
+        draco_oxide::encode::encode(source_mesh(name), &mut encoded, draco_oxide::encode::Config::default()).unwrap();
+        std::fs::write(format!("{}/{}.drc", GOLDEN_DIR, name), &encoded).unwrap();
+
+        let decoded = decode(&mut SliceReader::new(&encoded), decode::Config::default()).unwrap();
+        let json = serde_json::to_string(&Geometry::from_mesh(&decoded)).unwrap();
+        std::fs::write(format!("{}/{}.json", GOLDEN_DIR, name), json + "\n").unwrap();
+    }
+}
@@ -0,0 +1,31 @@
+//! Parses the names of the enums through the public paths, as a config file or a CLI flag would.
+
+use draco_oxide::encode::{PortabilizationType, PredictionSchemeType, PredictionTransformType};
+use draco_oxide::prelude::AttributeType;
+use draco_oxide::utils::ParseEnumError;
+
+#[derive(Debug, PartialEq, serde::Deserialize)]
+struct AttributeConfig {
+    attribute: AttributeType,
+    prediction: PredictionSchemeType,
+    transform: PredictionTransformType,
+    portabilization: PortabilizationType,
+}
+
+#[test]
+fn parse_names() {
+    assert_eq!("texcoord".parse::<AttributeType>().unwrap(), AttributeType::TextureCoordinate);
+    assert_eq!(PredictionSchemeType::DeltaPrediction.to_string().parse::<PredictionSchemeType>().unwrap(), PredictionSchemeType::DeltaPrediction);
+
+    let err: ParseEnumError = "sideways".parse::<PredictionTransformType>().unwrap_err();
+    assert!(err.to_string().contains("sideways"), "{}", err);
+
+    let json = r#"{ "attribute": "position", "prediction": "delta", "transform": "difference", "portabilization": "quantization" }"#;
+    let cfg = serde_json::from_str::<AttributeConfig>(json).unwrap();
+    assert_eq!(cfg, AttributeConfig {
+        attribute: AttributeType::Position,
+        prediction: PredictionSchemeType::DeltaPrediction,
+        transform: PredictionTransformType::Difference,
+        portabilization: PortabilizationType::QuantizationCoordinateWise,
+    });
+}